@@ -109,10 +109,24 @@ export interface ImportDependency {
   media: string | null,
   /** The `supports()` query for the `@import` rule. */
   supports: string | null,
+  /** The cascade layer requested by the `@import` rule, if any. */
+  layer: ImportLayer | null,
   /** The source location where the `@import` rule was found. */
   loc: SourceLocation
 }
 
+export type ImportLayer = AnonymousImportLayer | NamedImportLayer;
+
+export interface AnonymousImportLayer {
+  type: 'anonymous'
+}
+
+export interface NamedImportLayer {
+  type: 'named',
+  /** The name of the layer. */
+  name: string
+}
+
 export interface UrlDependency {
   type: 'url',
   /** The url of the dependency. */
@@ -49,16 +49,6 @@ use napi::{CallContext, JsObject, JsUnknown};
 #[cfg(not(target_arch = "wasm32"))]
 use napi_derive::{js_function, module_exports};
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-struct SourceMapJson<'a> {
-  version: u8,
-  mappings: String,
-  sources: &'a Vec<String>,
-  sources_content: &'a Vec<String>,
-  names: &'a Vec<String>,
-}
-
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct TransformResult {
@@ -239,17 +229,18 @@ fn compile<'i>(code: &'i str, config: &Config) -> Result<TransformResult, Compil
       custom_media: matches!(drafts, Some(d) if d.custom_media),
       css_modules: config.css_modules.unwrap_or(false),
       source_index: 0,
+      ..ParserOptions::default()
     },
   )?;
   stylesheet.minify(MinifyOptions {
     targets: config.targets,
     unused_symbols: config.unused_symbols.clone().unwrap_or_default(),
+    ..MinifyOptions::default()
   })?;
 
   let mut source_map = if config.source_map.unwrap_or(false) {
     let mut sm = SourceMap::new("/");
     sm.add_source(&config.filename);
-    sm.set_source_content(0, code)?;
     Some(sm)
   } else {
     None
@@ -258,16 +249,15 @@ fn compile<'i>(code: &'i str, config: &Config) -> Result<TransformResult, Compil
   let res = stylesheet.to_css(PrinterOptions {
     minify: config.minify.unwrap_or(false),
     source_map: source_map.as_mut(),
+    encode_source_map: true,
+    include_sources_content: true,
     targets: config.targets,
     analyze_dependencies: config.analyze_dependencies.unwrap_or(false),
     pseudo_classes: config.pseudo_classes.as_ref().map(|p| p.into()),
+    ..PrinterOptions::default()
   })?;
 
-  let map = if let Some(mut source_map) = source_map {
-    Some(source_map_to_json(&mut source_map)?)
-  } else {
-    None
-  };
+  let map = res.source_map.as_ref().map(|map| serde_json::to_vec(map).unwrap());
 
   Ok(TransformResult {
     code: res.code.into_bytes(),
@@ -298,21 +288,21 @@ fn compile_bundle<'i>(fs: &'i FileProvider, config: &BundleConfig) -> Result<Tra
   stylesheet.minify(MinifyOptions {
     targets: config.targets,
     unused_symbols: config.unused_symbols.clone().unwrap_or_default(),
+    ..MinifyOptions::default()
   })?;
 
   let res = stylesheet.to_css(PrinterOptions {
     minify: config.minify.unwrap_or(false),
     source_map: source_map.as_mut(),
+    encode_source_map: true,
+    include_sources_content: true,
     targets: config.targets,
     analyze_dependencies: config.analyze_dependencies.unwrap_or(false),
     pseudo_classes: config.pseudo_classes.as_ref().map(|p| p.into()),
+    ..PrinterOptions::default()
   })?;
 
-  let map = if let Some(source_map) = &mut source_map {
-    Some(source_map_to_json(source_map)?)
-  } else {
-    None
-  };
+  let map = res.source_map.as_ref().map(|map| serde_json::to_vec(map).unwrap());
 
   Ok(TransformResult {
     code: res.code.into_bytes(),
@@ -322,22 +312,6 @@ fn compile_bundle<'i>(fs: &'i FileProvider, config: &BundleConfig) -> Result<Tra
   })
 }
 
-#[inline]
-fn source_map_to_json<'i>(source_map: &mut SourceMap) -> Result<Vec<u8>, CompileError<'i>> {
-  let mut vlq_output: Vec<u8> = Vec::new();
-  source_map.write_vlq(&mut vlq_output)?;
-
-  let sm = SourceMapJson {
-    version: 3,
-    mappings: unsafe { String::from_utf8_unchecked(vlq_output) },
-    sources: source_map.get_sources(),
-    sources_content: source_map.get_sources_content(),
-    names: source_map.get_names(),
-  };
-
-  Ok(serde_json::to_vec(&sm).unwrap())
-}
-
 #[derive(Serialize, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AttrConfig {
@@ -381,6 +355,7 @@ fn compile_attr<'i>(code: &'i str, config: &AttrConfig) -> Result<AttrResult, Co
     targets: config.targets,
     analyze_dependencies: config.analyze_dependencies.unwrap_or(false),
     pseudo_classes: None,
+    ..PrinterOptions::default()
   })?;
   Ok(AttrResult {
     code: res.code.into_bytes(),
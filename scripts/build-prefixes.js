@@ -168,7 +168,8 @@ let cssFeatures = [
   'css-rrggbbaa',
   'css-nesting',
   'css-not-sel-list',
-  'css-has'
+  'css-has',
+  'css-nth-child-of'
 ];
 
 let compat = new Map();
@@ -211,18 +212,36 @@ let mdnFeatures = {
   overflowShorthand: mdn.css.properties['overflow'].multiple_keywords.__compat.support,
   mediaRangeSyntax: mdn.css['at-rules'].media.range_syntax.__compat.support,
   mediaIntervalSyntax: {}, // currently no browsers
+  mediaQueries: mdn.css['at-rules'].media.__compat.support,
   logicalBorders: mdn.css.properties['border-inline-start'].__compat.support,
   logicalBorderRadius: mdn.css.properties['border-start-start-radius'].__compat.support,
   logicalMargin: mdn.css.properties['margin-inline-start'].__compat.support,
   logicalPadding: mdn.css.properties['padding-inline-start'].__compat.support,
   logicalInset: mdn.css.properties['inset-inline-start'].__compat.support,
   logicalSize: mdn.css.properties['inline-size'].__compat.support,
+  logicalOverscrollBehavior: mdn.css.properties['overscroll-behavior-inline'].__compat.support,
   logicalTextAlign: mdn.css.properties['text-align']['flow_relative_values_start_and_end'].__compat.support,
   labColors: mdn.css.types.color.lab.__compat.support,
   oklabColors: {},
   colorFunction: mdn.css.types.color.color.__compat.support,
   textDecorationThicknessPercent: mdn.css.properties['text-decoration-thickness'].percentage.__compat.support,
   textDecorationThicknessShorthand: mdn.css.properties['text-decoration']['text-decoration-thickness'].__compat.support,
+  revert: mdn.css.types.global_keywords.revert.__compat.support,
+  revertLayer: mdn.css.types.global_keywords['revert-layer'].__compat.support,
+  offsetShorthand: mdn.css.properties.offset.__compat.support,
+  flexGap: mdn.css.properties.gap.flex_context.__compat.support,
+  dppxResolution: mdn.css.types.resolution.dppx.__compat.support,
+  lineClamp: {}, // currently only supported with the -webkit- prefix
+  zoom: mdn.css.properties.zoom.__compat.support,
+  colorScheme: mdn.css.properties['color-scheme'].__compat.support,
+  accentColor: mdn.css.properties['accent-color'].__compat.support,
+  individualTransforms: mdn.css.properties.translate.__compat.support,
+  overflowWrap: mdn.css.properties['overflow-wrap'].__compat.support,
+  overflowWrapAnywhere: mdn.css.properties['overflow-wrap'].anywhere.__compat.support,
+  wordBreakBreakWord: mdn.css.properties['word-break']['break-word'].__compat.support,
+  whiteSpaceCollapse: mdn.css.properties['white-space-collapse'].__compat.support,
+  textWrap: mdn.css.properties['text-wrap'].__compat.support,
+  atProperty: mdn.css['at-rules'].property.__compat.support,
   anyPseudo: Object.fromEntries(
     Object.entries(mdn.css.selectors.is.__compat.support)
       .map(([key, value]) => {
@@ -273,6 +292,17 @@ for (let feature in mdnFeatures) {
   addValue(compat, browserMap, feature);
 }
 
+// Features that are the support data of an entire property, used to look up the feature by property name.
+let propertyFeatures = [];
+for (let feature in mdnFeatures) {
+  for (let property in mdn.css.properties) {
+    if (mdn.css.properties[property].__compat?.support === mdnFeatures[feature]) {
+      propertyFeatures.push([property, feature]);
+    }
+  }
+}
+propertyFeatures.sort(([a], [b]) => a < b ? -1 : 1);
+
 addValue(compat, {
   safari: parseVersion('10.1'),
   ios_saf: parseVersion('10.3')
@@ -348,6 +378,13 @@ impl Feature {
     }
     prefixes
   }
+
+  pub fn from_name(name: &str) -> Option<Feature> {
+    Some(match name {
+      ${[...p.keys()].flat().filter(name => /^[a-z-]+$/.test(name)).sort().map(name => `"${name}" => Feature::${enumify(name)},`).join('\n      ')}
+      _ => return None,
+    })
+  }
 }
 
 pub fn is_flex_2009(browsers: Browsers) -> bool {
@@ -414,6 +451,13 @@ impl Feature {
     }\n`).join('    ')}
     false
   }
+
+  pub fn from_property(name: &str) -> Option<Feature> {
+    Some(match name {
+      ${propertyFeatures.map(([property, feature]) => `"${property}" => Feature::${enumify(feature)},`).join('\n      ')}
+      _ => return None,
+    })
+  }
 }
 `;
 
@@ -325,6 +325,20 @@ where
         }
         *specificity += Specificity::from(max);
       }
+      Component::NthChildOf(_, _, ref list) | Component::NthLastChildOf(_, _, ref list) => {
+        // https://drafts.csswg.org/selectors/#specificity-rules:
+        //
+        //     The specificity of an :nth-child() or :nth-last-child() selector
+        //     is the specificity of the pseudo class itself (counting as one
+        //     pseudo-class selector) plus the specificity of the most specific
+        //     complex selector in its selector list argument (if any).
+        specificity.class_like_selectors += 1;
+        let mut max = 0;
+        for selector in &**list {
+          max = std::cmp::max(selector.specificity(), max);
+        }
+        *specificity += Specificity::from(max);
+      }
       Component::Where(..)
       | Component::Has(..)
       | Component::ExplicitUniversalType
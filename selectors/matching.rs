@@ -372,6 +372,8 @@ fn matches_hover_and_active_quirk<'i, Impl: SelectorImpl<'i>>(
     | Component::Empty
     | Component::NthChild(_, _)
     | Component::NthLastChild(_, _)
+    | Component::NthChildOf(..)
+    | Component::NthLastChildOf(..)
     | Component::NthOfType(_, _)
     | Component::NthLastOfType(_, _)
     | Component::FirstOfType
@@ -813,7 +815,9 @@ where
       }
       true
     }),
-    Component::Nesting | Component::Has(..) => unreachable!(),
+    Component::Nesting | Component::Has(..) | Component::NthChildOf(..) | Component::NthLastChildOf(..) => {
+      unreachable!()
+    }
   }
 }
 
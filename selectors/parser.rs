@@ -333,6 +333,25 @@ pub trait Parser<'i> {
   fn is_nesting_allowed(&self) -> bool {
     false
   }
+
+  /// Parses the contents of a nested block that may contain further selectors, e.g. the arguments
+  /// of a functional pseudo-class. This can be overridden to limit how deeply selectors are nested.
+  fn parse_nested_block<'t, R, F>(
+    &self,
+    input: &mut CssParser<'i, 't>,
+    parse: F,
+  ) -> Result<R, ParseError<'i, Self::Error>>
+  where
+    F: for<'tt> FnOnce(&mut CssParser<'i, 'tt>) -> Result<R, ParseError<'i, Self::Error>>,
+  {
+    input.parse_nested_block(parse)
+  }
+
+  /// Whether an invalid selector may be ignored in a list that uses
+  /// [IgnoreInvalidSelector](ParseErrorRecovery::IgnoreInvalidSelector) error recovery.
+  fn is_recoverable_error(&self, _error: &ParseError<'i, Self::Error>) -> bool {
+    true
+  }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -405,8 +424,8 @@ impl<'i, Impl: SelectorImpl<'i>> SelectorList<'i, Impl> {
       match selector {
         Ok(selector) => values.push(selector),
         Err(err) => match recovery {
-          ParseErrorRecovery::DiscardList => return Err(err),
-          ParseErrorRecovery::IgnoreInvalidSelector => {}
+          ParseErrorRecovery::IgnoreInvalidSelector if parser.is_recoverable_error(&err) => {}
+          _ => return Err(err),
         },
       }
 
@@ -448,8 +467,8 @@ impl<'i, Impl: SelectorImpl<'i>> SelectorList<'i, Impl> {
       match selector {
         Ok(selector) => values.push(selector),
         Err(err) => match recovery {
-          ParseErrorRecovery::DiscardList => return Err(err),
-          ParseErrorRecovery::IgnoreInvalidSelector => {}
+          ParseErrorRecovery::IgnoreInvalidSelector if parser.is_recoverable_error(&err) => {}
+          _ => return Err(err),
         },
       }
 
@@ -815,6 +834,9 @@ impl<'i, Impl: SelectorImpl<'i>> Selector<'i, Impl> {
   }
 
   pub fn from_vec2(vec: Vec<Component<'i, Impl>>) -> Self {
+    let parsed_pseudo = vec.iter().any(|c| matches!(c, Component::PseudoElement(..)));
+    let parsed_slotted = vec.iter().any(|c| matches!(c, Component::Slotted(..)));
+    let parsed_part = vec.iter().any(|c| matches!(c, Component::Part(..)));
     let mut builder = SelectorBuilder::default();
     for component in vec.into_iter() {
       if let Some(combinator) = component.as_combinator() {
@@ -823,7 +845,7 @@ impl<'i, Impl: SelectorImpl<'i>> Selector<'i, Impl> {
         builder.push_simple_selector(component);
       }
     }
-    let (spec, components) = builder.build(false, false, false);
+    let (spec, components) = builder.build(parsed_pseudo, parsed_slotted, parsed_part);
     Selector(spec, components)
   }
 
@@ -1109,6 +1131,12 @@ pub enum Component<'i, Impl: SelectorImpl<'i>> {
   NthLastChild(i32, i32),
   NthOfType(i32, i32),
   NthLastOfType(i32, i32),
+  /// The `:nth-child(An+B of S)` pseudo-class.
+  ///
+  /// https://drafts.csswg.org/selectors/#the-nth-child-pseudo
+  NthChildOf(i32, i32, Box<[Selector<'i, Impl>]>),
+  /// The `:nth-last-child(An+B of S)` pseudo-class.
+  NthLastChildOf(i32, i32, Box<[Selector<'i, Impl>]>),
   FirstOfType,
   LastOfType,
   OnlyOfType,
@@ -1290,7 +1318,11 @@ impl<'i, Impl: SelectorImpl<'i>> Component<'i, Impl> {
         }
       }
 
-      Negation(ref list) | Is(ref list) | Where(ref list) => {
+      Negation(ref list)
+      | Is(ref list)
+      | Where(ref list)
+      | NthChildOf(_, _, ref list)
+      | NthLastChildOf(_, _, ref list) => {
         if !visitor.visit_selector_list(&list) {
           return false;
         }
@@ -1615,6 +1647,17 @@ impl<'i, Impl: SelectorImpl<'i>> ToCss for Component<'i, Impl> {
         write_affine(dest, a, b)?;
         dest.write_char(')')
       }
+      NthChildOf(a, b, ref list) | NthLastChildOf(a, b, ref list) => {
+        match *self {
+          NthChildOf(..) => dest.write_str(":nth-child(")?,
+          NthLastChildOf(..) => dest.write_str(":nth-last-child(")?,
+          _ => unreachable!(),
+        }
+        write_affine(dest, a, b)?;
+        dest.write_str(" of ")?;
+        serialize_selector_list(list.iter(), dest)?;
+        dest.write_char(')')
+      }
       Is(ref list) | Where(ref list) | Negation(ref list) | Has(ref list) | Any(_, ref list) => {
         match *self {
           Where(..) => dest.write_str(":where(")?,
@@ -2380,9 +2423,9 @@ where
   Impl: SelectorImpl<'i>,
 {
   match_ignore_ascii_case! { &name,
-      "nth-child" => return parse_nth_pseudo_class(parser, input, *state, Component::NthChild),
+      "nth-child" => return parse_nth_child_pseudo_class(parser, input, state, Component::NthChild, Component::NthChildOf),
       "nth-of-type" => return parse_nth_pseudo_class(parser, input, *state, Component::NthOfType),
-      "nth-last-child" => return parse_nth_pseudo_class(parser, input, *state, Component::NthLastChild),
+      "nth-last-child" => return parse_nth_child_pseudo_class(parser, input, state, Component::NthLastChild, Component::NthLastChildOf),
       "nth-last-of-type" => return parse_nth_pseudo_class(parser, input, *state, Component::NthLastOfType),
       "is" if parser.parse_is_and_where() => return parse_is_or_where(parser, input, state, Component::Is),
       "where" if parser.parse_is_and_where() => return parse_is_or_where(parser, input, state, Component::Where),
@@ -2428,6 +2471,44 @@ where
   Ok(selector(a, b))
 }
 
+/// Parses the argument of `:nth-child()` or `:nth-last-child()`, including an optional
+/// `of <selector-list>` clause.
+fn parse_nth_child_pseudo_class<'i, 't, P, Impl, F, G>(
+  parser: &P,
+  input: &mut CssParser<'i, 't>,
+  state: &mut SelectorParsingState,
+  selector: F,
+  selector_of: G,
+) -> Result<Component<'i, Impl>, ParseError<'i, P::Error>>
+where
+  P: Parser<'i, Impl = Impl>,
+  Impl: SelectorImpl<'i>,
+  F: FnOnce(i32, i32) -> Component<'i, Impl>,
+  G: FnOnce(i32, i32, Box<[Selector<'i, Impl>]>) -> Component<'i, Impl>,
+{
+  if !state.allows_tree_structural_pseudo_classes() {
+    return Err(input.new_custom_error(SelectorParseErrorKind::InvalidState));
+  }
+  let (a, b) = parse_nth(input)?;
+  if input.try_parse(|i| i.expect_ident_matching("of")).is_err() {
+    return Ok(selector(a, b));
+  }
+
+  let mut child_state =
+    *state | SelectorParsingState::SKIP_DEFAULT_NAMESPACE | SelectorParsingState::DISALLOW_PSEUDOS;
+  let inner = SelectorList::parse_with_state(
+    parser,
+    input,
+    &mut child_state,
+    ParseErrorRecovery::DiscardList,
+    NestingRequirement::None,
+  )?;
+  if child_state.contains(SelectorParsingState::AFTER_NESTING) {
+    state.insert(SelectorParsingState::AFTER_NESTING)
+  }
+  Ok(selector_of(a, b, inner.0.into_vec().into_boxed_slice()))
+}
+
 /// Returns whether the name corresponds to a CSS2 pseudo-element that
 /// can be specified with the single colon syntax (in addition to the
 /// double-colon syntax, which can be used for all pseudo-elements).
@@ -2531,10 +2612,10 @@ where
               return Err(input.new_custom_error(SelectorParseErrorKind::InvalidState));
             }
             let selector =
-              input.parse_nested_block(|input| parse_inner_compound_selector(parser, input, state))?;
+              parser.parse_nested_block(input, |input| parse_inner_compound_selector(parser, input, state))?;
             return Ok(Some(SimpleSelectorParseResult::SlottedPseudo(selector)));
           }
-          input.parse_nested_block(|input| P::parse_functional_pseudo_element(parser, name, input))?
+          parser.parse_nested_block(input, |input| P::parse_functional_pseudo_element(parser, name, input))?
         } else {
           P::parse_pseudo_element(parser, location, name)?
         };
@@ -2545,7 +2626,7 @@ where
         SimpleSelectorParseResult::PseudoElement(pseudo_element)
       } else {
         let pseudo_class = if is_functional {
-          input.parse_nested_block(|input| parse_functional_pseudo_class(parser, input, name, state))?
+          parser.parse_nested_block(input, |input| parse_functional_pseudo_class(parser, input, name, state))?
         } else {
           parse_simple_pseudo_class(parser, location, name, *state)?
         };
@@ -18,6 +18,7 @@ use crate::{
 };
 use dashmap::DashMap;
 use parcel_sourcemap::SourceMap;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use serde::Serialize;
 use std::{
@@ -136,7 +137,7 @@ impl<'a, 's, P: SourceProvider> Bundler<'a, 's, P> {
   }
 
   pub fn bundle<'e>(&mut self, entry: &'e Path) -> Result<StyleSheet<'a>, Error<BundleErrorKind<'a>>> {
-    // Phase 1: load and parse all files. This is done in parallel.
+    // Phase 1: load and parse all files. This is done in parallel when the `parallel` feature is enabled.
     self.load_file(
       &entry,
       ImportRule {
@@ -148,6 +149,7 @@ impl<'a, 's, P: SourceProvider> Bundler<'a, 's, P> {
           source_index: 0,
           line: 0,
           column: 1,
+          generated: false,
         },
       },
     )?;
@@ -159,15 +161,19 @@ impl<'a, 's, P: SourceProvider> Bundler<'a, 's, P> {
     let mut rules: Vec<CssRule<'a>> = Vec::new();
     self.inline(&mut rules);
 
-    let sources = self
-      .stylesheets
-      .get_mut()
-      .unwrap()
+    let stylesheets = self.stylesheets.get_mut().unwrap();
+    let sources = stylesheets
       .iter()
       .flat_map(|s| s.stylesheet.as_ref().unwrap().sources.iter().cloned())
       .collect();
+    let sources_content = stylesheets
+      .iter()
+      .flat_map(|s| s.stylesheet.as_ref().unwrap().sources_content.iter().cloned())
+      .collect();
 
-    Ok(StyleSheet::new(sources, CssRuleList(rules), self.options.clone()))
+    let mut stylesheet = StyleSheet::new(sources, CssRuleList(rules), self.options.clone());
+    stylesheet.sources_content = sources_content;
+    Ok(stylesheet)
   }
 
   fn find_filename(&self, source_index: u32) -> String {
@@ -265,11 +271,12 @@ impl<'a, 's, P: SourceProvider> Bundler<'a, 's, P> {
 
     let mut stylesheet = StyleSheet::parse(filename.into(), code, opts)?;
 
-    // Collect and load dependencies for this stylesheet in parallel.
-    let dependencies: Result<Vec<u32>, _> = stylesheet
-      .rules
-      .0
-      .par_iter_mut()
+    // Collect and load dependencies for this stylesheet, in parallel when enabled.
+    #[cfg(feature = "parallel")]
+    let rules = stylesheet.rules.0.par_iter_mut();
+    #[cfg(not(feature = "parallel"))]
+    let rules = stylesheet.rules.0.iter_mut();
+    let dependencies: Result<Vec<u32>, _> = rules
       .filter_map(|r| {
         // Prepend parent layer name to @layer statements.
         if let CssRule::LayerStatement(layer) = r {
@@ -4,9 +4,12 @@ use crate::targets::Browsers;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Feature {
+  AccentColor,
   AnyPseudo,
+  AtProperty,
   Clamp,
   ColorFunction,
+  ColorScheme,
   CssAnyLink,
   CssAutofill,
   CssCaseInsensitive,
@@ -25,6 +28,7 @@ pub enum Feature {
   CssNamespaces,
   CssNesting,
   CssNotSelList,
+  CssNthChildOf,
   CssOptionalPseudo,
   CssPlaceholder,
   CssPlaceholderShown,
@@ -36,28 +40,43 @@ pub enum Feature {
   CustomMediaQueries,
   Dialog,
   DoublePositionGradients,
+  DppxResolution,
+  FlexGap,
   FormValidation,
   Fullscreen,
+  IndividualTransforms,
   LabColors,
   LangList,
+  LineClamp,
   LogicalBorderRadius,
   LogicalBorders,
   LogicalInset,
   LogicalMargin,
+  LogicalOverscrollBehavior,
   LogicalPadding,
   LogicalSize,
   LogicalTextAlign,
   MediaIntervalSyntax,
+  MediaQueries,
   MediaRangeSyntax,
+  OffsetShorthand,
   OklabColors,
   OverflowShorthand,
+  OverflowWrap,
+  OverflowWrapAnywhere,
   P3Colors,
   PlaceContent,
   PlaceItems,
   PlaceSelf,
+  Revert,
+  RevertLayer,
   Shadowdomv1,
   TextDecorationThicknessPercent,
   TextDecorationThicknessShorthand,
+  TextWrap,
+  WhiteSpaceCollapse,
+  WordBreakBreakWord,
+  Zoom,
 }
 
 impl Feature {
@@ -1199,7 +1218,52 @@ impl Feature {
           return false;
         }
       }
-      Feature::CssNesting | Feature::CustomMediaQueries | Feature::MediaIntervalSyntax | Feature::OklabColors => {
+      Feature::CssNesting => {
+        if let Some(version) = browsers.chrome {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7667712 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6946816 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1114624 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1114624 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1638400 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7864320 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::CustomMediaQueries | Feature::LineClamp | Feature::MediaIntervalSyntax | Feature::OklabColors => {
         return false
       }
       Feature::CssNotSelList => {
@@ -1248,6 +1312,26 @@ impl Feature {
         }
       }
       Feature::CssHas => {
+        if let Some(version) = browsers.chrome {
+          if version < 6881280 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 6881280 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7929856 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 5963776 {
+            return false;
+          }
+        }
         if let Some(version) = browsers.safari {
           if version < 984064 {
             return false;
@@ -1258,6 +1342,31 @@ impl Feature {
             return false;
           }
         }
+        if let Some(version) = browsers.samsung {
+          if version < 1310720 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 6881280 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::CssNthChildOf => {
+        if let Some(version) = browsers.safari {
+          if version < 589824 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 589824 {
+            return false;
+          }
+        }
         if browsers.android.is_some()
           || browsers.chrome.is_some()
           || browsers.edge.is_some()
@@ -1637,6 +1746,51 @@ impl Feature {
           return false;
         }
       }
+      Feature::LogicalOverscrollBehavior => {
+        if let Some(version) = browsers.chrome {
+          if version < 5046272 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5177344 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4784128 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4194304 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 786432 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5046272 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
       Feature::LogicalSize => {
         if let Some(version) = browsers.chrome {
           if version < 3735552 {
@@ -1727,119 +1881,134 @@ impl Feature {
           return false;
         }
       }
-      Feature::LabColors | Feature::ColorFunction => {
+      Feature::AccentColor => {
+        if let Some(version) = browsers.chrome {
+          if version < 6094848 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 6094848 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 6029312 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 5177344 {
+            return false;
+          }
+        }
         if let Some(version) = browsers.safari {
-          if version < 983040 {
+          if version < 984064 {
             return false;
           }
         }
         if let Some(version) = browsers.ios_saf {
-          if version < 983040 {
+          if version < 984064 {
             return false;
           }
         }
-        if browsers.android.is_some()
-          || browsers.chrome.is_some()
-          || browsers.edge.is_some()
-          || browsers.firefox.is_some()
-          || browsers.ie.is_some()
-          || browsers.opera.is_some()
-          || browsers.samsung.is_some()
-        {
-          return false;
+        if let Some(version) = browsers.samsung {
+          if version < 1114112 {
+            return false;
+          }
         }
-      }
-      Feature::TextDecorationThicknessPercent => {
-        if let Some(version) = browsers.firefox {
-          if version < 4849664 {
+        if let Some(version) = browsers.android {
+          if version < 6094848 {
             return false;
           }
         }
-        if browsers.android.is_some()
-          || browsers.chrome.is_some()
-          || browsers.edge.is_some()
-          || browsers.ie.is_some()
-          || browsers.ios_saf.is_some()
-          || browsers.opera.is_some()
-          || browsers.safari.is_some()
-          || browsers.samsung.is_some()
-        {
+        if browsers.ie.is_some() {
           return false;
         }
       }
-      Feature::TextDecorationThicknessShorthand => {
+      Feature::IndividualTransforms => {
         if let Some(version) = browsers.chrome {
-          if version < 5701632 {
+          if version < 6815744 {
             return false;
           }
         }
         if let Some(version) = browsers.edge {
-          if version < 5701632 {
+          if version < 6815744 {
             return false;
           }
         }
         if let Some(version) = browsers.firefox {
-          if version < 4587520 {
+          if version < 4718592 {
             return false;
           }
         }
         if let Some(version) = browsers.opera {
-          if version < 4063232 {
+          if version < 5898240 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 917760 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 918784 {
             return false;
           }
         }
         if let Some(version) = browsers.samsung {
-          if version < 917504 {
+          if version < 1310720 {
             return false;
           }
         }
         if let Some(version) = browsers.android {
-          if version < 5701632 {
+          if version < 6815744 {
             return false;
           }
         }
-        if browsers.ie.is_some() || browsers.ios_saf.is_some() || browsers.safari.is_some() {
+        if browsers.ie.is_some() {
           return false;
         }
       }
-      Feature::AnyPseudo => {
+      Feature::OverflowWrap => {
         if let Some(version) = browsers.chrome {
-          if version < 1179648 {
+          if version < 1507328 {
             return false;
           }
         }
         if let Some(version) = browsers.edge {
-          if version < 5177344 {
+          if version < 1179648 {
             return false;
           }
         }
         if let Some(version) = browsers.firefox {
-          if version < 262144 {
+          if version < 3211264 {
             return false;
           }
         }
         if let Some(version) = browsers.opera {
-          if version < 917504 {
+          if version < 786688 {
             return false;
           }
         }
         if let Some(version) = browsers.safari {
-          if version < 327680 {
+          if version < 458752 {
             return false;
           }
         }
         if let Some(version) = browsers.ios_saf {
-          if version < 327680 {
+          if version < 458752 {
             return false;
           }
         }
         if let Some(version) = browsers.samsung {
-          if version < 65536 {
+          if version < 66816 {
             return false;
           }
         }
         if let Some(version) = browsers.android {
-          if version < 2424832 {
+          if version < 263168 {
             return false;
           }
         }
@@ -1847,46 +2016,675 @@ impl Feature {
           return false;
         }
       }
-      Feature::P3Colors | Feature::LangList => {
+      Feature::OverflowWrapAnywhere => {
+        if let Some(version) = browsers.chrome {
+          if version < 5242880 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5242880 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4259840 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4390912 {
+            return false;
+          }
+        }
         if let Some(version) = browsers.safari {
-          if version < 655616 {
+          if version < 984064 {
             return false;
           }
         }
         if let Some(version) = browsers.ios_saf {
-          if version < 656128 {
+          if version < 984064 {
             return false;
           }
         }
-        if browsers.android.is_some()
-          || browsers.chrome.is_some()
-          || browsers.edge.is_some()
-          || browsers.firefox.is_some()
-          || browsers.ie.is_some()
-          || browsers.opera.is_some()
-          || browsers.samsung.is_some()
-        {
+        if let Some(version) = browsers.samsung {
+          if version < 851968 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5242880 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
           return false;
         }
       }
-    }
-    true
-  }
-
-  pub fn is_partially_compatible(&self, targets: Browsers) -> bool {
-    let mut browsers = Browsers::default();
-    if targets.android.is_some() {
-      browsers.android = targets.android;
-      if self.is_compatible(browsers) {
-        return true;
-      }
-      browsers.android = None;
-    }
-    if targets.chrome.is_some() {
-      browsers.chrome = targets.chrome;
-      if self.is_compatible(browsers) {
-        return true;
-      }
+      Feature::WhiteSpaceCollapse => {
+        if let Some(version) = browsers.chrome {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 8126464 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6553600 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1507328 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::WordBreakBreakWord => {
+        if let Some(version) = browsers.chrome {
+          if version < 65536 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5177344 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4390912 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 983040 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 196608 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 196608 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 65536 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 65536 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::TextWrap => {
+        if let Some(version) = browsers.chrome {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 7929856 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 6553600 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1115136 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1507328 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 7471104 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::AtProperty => {
+        if let Some(version) = browsers.chrome {
+          if version < 5570560 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5570560 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 8388608 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4653056 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1049600 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1049600 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 917504 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5570560 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::ColorScheme => {
+        if let Some(version) = browsers.chrome {
+          if version < 5308416 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5308416 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 6291456 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4456448 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 786688 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 786944 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 851968 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5308416 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::LabColors | Feature::ColorFunction => {
+        if let Some(version) = browsers.safari {
+          if version < 983040 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 983040 {
+            return false;
+          }
+        }
+        if browsers.android.is_some()
+          || browsers.chrome.is_some()
+          || browsers.edge.is_some()
+          || browsers.firefox.is_some()
+          || browsers.ie.is_some()
+          || browsers.opera.is_some()
+          || browsers.samsung.is_some()
+        {
+          return false;
+        }
+      }
+      Feature::TextDecorationThicknessPercent => {
+        if let Some(version) = browsers.firefox {
+          if version < 4849664 {
+            return false;
+          }
+        }
+        if browsers.android.is_some()
+          || browsers.chrome.is_some()
+          || browsers.edge.is_some()
+          || browsers.ie.is_some()
+          || browsers.ios_saf.is_some()
+          || browsers.opera.is_some()
+          || browsers.safari.is_some()
+          || browsers.samsung.is_some()
+        {
+          return false;
+        }
+      }
+      Feature::TextDecorationThicknessShorthand => {
+        if let Some(version) = browsers.chrome {
+          if version < 5701632 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5701632 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4587520 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4063232 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 917504 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5701632 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() || browsers.ios_saf.is_some() || browsers.safari.is_some() {
+          return false;
+        }
+      }
+      Feature::AnyPseudo => {
+        if let Some(version) = browsers.chrome {
+          if version < 1179648 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5177344 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 262144 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 917504 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 327680 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 327680 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 65536 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 2424832 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::Revert => {
+        if let Some(version) = browsers.chrome {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4390912 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4587520 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 590080 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 590592 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 917504 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::RevertLayer => {
+        if let Some(version) = browsers.chrome {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 6356992 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 5636096 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 984064 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 1179648 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 6488064 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::OffsetShorthand => {
+        if let Some(version) = browsers.chrome {
+          if version < 3604480 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5177344 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4718592 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 2752512 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 393216 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 3604480 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::FlexGap => {
+        if let Some(version) = browsers.chrome {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 4128768 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 4587520 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 917760 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 918784 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 917504 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 5505024 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::DppxResolution => {
+        if let Some(version) = browsers.chrome {
+          if version < 1900544 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.edge {
+          if version < 786432 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.firefox {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.opera {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.safari {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 1048576 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.samsung {
+          if version < 131072 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.android {
+          if version < 263168 {
+            return false;
+          }
+        }
+        if browsers.ie.is_some() {
+          return false;
+        }
+      }
+      Feature::MediaQueries => {
+        if let Some(version) = browsers.ie {
+          if version < 589824 {
+            return false;
+          }
+        }
+      }
+      Feature::Zoom => {
+        if let Some(version) = browsers.firefox {
+          if version < 8257536 {
+            return false;
+          }
+        }
+      }
+      Feature::P3Colors | Feature::LangList => {
+        if let Some(version) = browsers.safari {
+          if version < 655616 {
+            return false;
+          }
+        }
+        if let Some(version) = browsers.ios_saf {
+          if version < 656128 {
+            return false;
+          }
+        }
+        if browsers.android.is_some()
+          || browsers.chrome.is_some()
+          || browsers.edge.is_some()
+          || browsers.firefox.is_some()
+          || browsers.ie.is_some()
+          || browsers.opera.is_some()
+          || browsers.samsung.is_some()
+        {
+          return false;
+        }
+      }
+    }
+    true
+  }
+
+  pub fn is_partially_compatible(&self, targets: Browsers) -> bool {
+    let mut browsers = Browsers::default();
+    if targets.android.is_some() {
+      browsers.android = targets.android;
+      if self.is_compatible(browsers) {
+        return true;
+      }
+      browsers.android = None;
+    }
+    if targets.chrome.is_some() {
+      browsers.chrome = targets.chrome;
+      if self.is_compatible(browsers) {
+        return true;
+      }
       browsers.chrome = None;
     }
     if targets.edge.is_some() {
@@ -1941,4 +2739,28 @@ impl Feature {
 
     false
   }
+
+  pub fn from_property(name: &str) -> Option<Feature> {
+    Some(match name {
+      "accent-color" => Feature::AccentColor,
+      "border-inline-start" => Feature::LogicalBorders,
+      "border-start-start-radius" => Feature::LogicalBorderRadius,
+      "color-scheme" => Feature::ColorScheme,
+      "inline-size" => Feature::LogicalSize,
+      "inset-inline-start" => Feature::LogicalInset,
+      "margin-inline-start" => Feature::LogicalMargin,
+      "offset" => Feature::OffsetShorthand,
+      "overflow-wrap" => Feature::OverflowWrap,
+      "overscroll-behavior-inline" => Feature::LogicalOverscrollBehavior,
+      "padding-inline-start" => Feature::LogicalPadding,
+      "place-content" => Feature::PlaceContent,
+      "place-items" => Feature::PlaceItems,
+      "place-self" => Feature::PlaceSelf,
+      "text-wrap" => Feature::TextWrap,
+      "translate" => Feature::IndividualTransforms,
+      "white-space-collapse" => Feature::WhiteSpaceCollapse,
+      "zoom" => Feature::Zoom,
+      _ => return None,
+    })
+  }
 }
@@ -1,13 +1,28 @@
 use crate::compat::Feature;
 use crate::declaration::DeclarationBlock;
+use crate::error::MinifyWarningKind;
+use crate::media_query::{
+  DiscreteMediaFeature, DiscreteMediaFeatureValue, MediaCondition, MediaFeature, MediaList, MediaQuery, MediaType,
+};
+use crate::properties::animation::{
+  Animation, AnimationDirection, AnimationFillMode, AnimationIterationCount, AnimationName, AnimationPlayState,
+};
 use crate::properties::custom::UnparsedProperty;
-use crate::properties::Property;
+use crate::properties::{Property, PropertyId};
+use crate::rules::media::MediaRule;
 use crate::rules::supports::{SupportsCondition, SupportsRule};
 use crate::rules::{style::StyleRule, CssRule, CssRuleList};
 use crate::selector::{Direction, PseudoClass};
+use crate::stylesheet::{LogicalFallback, MinifyOptions, Optimization, ReducedMotion};
 use crate::targets::Browsers;
+use crate::values::easing::EasingFunction;
+use crate::values::syntax::SyntaxString;
+use crate::values::time::Time;
 use crate::vendor_prefix::VendorPrefix;
 use parcel_selectors::parser::Component;
+use smallvec::smallvec;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(crate) struct SupportsEntry<'i> {
@@ -32,6 +47,16 @@ pub(crate) struct PropertyHandlerContext<'i> {
   ltr: Vec<Property<'i>>,
   rtl: Vec<Property<'i>>,
   pub context: DeclarationContext,
+  pub warnings: Vec<MinifyWarningKind>,
+  pub convert_zoom: bool,
+  pub remove_initial_values: bool,
+  pub remove_unneeded_prefixes: bool,
+  pub wrap_unsupported_features: bool,
+  pub warn_optimizations: bool,
+  pub disabled_optimizations: Arc<HashSet<Optimization>>,
+  pub registered_properties: Arc<HashMap<String, SyntaxString>>,
+  pub declared_properties: Arc<HashMap<PropertyId<'i>, usize>>,
+  pub logical_fallback: LogicalFallback,
 }
 
 impl<'i> PropertyHandlerContext<'i> {
@@ -43,6 +68,48 @@ impl<'i> PropertyHandlerContext<'i> {
       ltr: Vec::new(),
       rtl: Vec::new(),
       context: DeclarationContext::None,
+      warnings: Vec::new(),
+      convert_zoom: false,
+      remove_initial_values: false,
+      remove_unneeded_prefixes: false,
+      wrap_unsupported_features: false,
+      warn_optimizations: false,
+      disabled_optimizations: Arc::new(HashSet::new()),
+      registered_properties: Arc::new(HashMap::new()),
+      declared_properties: Arc::new(HashMap::new()),
+      logical_fallback: LogicalFallback::default(),
+    }
+  }
+
+  /// Creates a context with the declaration options from the given minify options.
+  pub fn from_options(options: &MinifyOptions) -> Self {
+    PropertyHandlerContext {
+      convert_zoom: options.convert_zoom,
+      remove_initial_values: options.remove_initial_values,
+      remove_unneeded_prefixes: options.remove_unneeded_prefixes,
+      wrap_unsupported_features: options.wrap_unsupported_features,
+      warn_optimizations: options.warn_optimizations,
+      disabled_optimizations: Arc::new(options.disabled_optimizations.clone()),
+      registered_properties: Arc::new(options.registered_properties.clone()),
+      logical_fallback: options.logical_fallback,
+      ..PropertyHandlerContext::new(options.targets)
+    }
+  }
+
+  /// Creates a context with the same options as this one, but none of its state, e.g. to minify
+  /// rules in parallel.
+  pub fn fork(&self) -> Self {
+    PropertyHandlerContext {
+      convert_zoom: self.convert_zoom,
+      remove_initial_values: self.remove_initial_values,
+      remove_unneeded_prefixes: self.remove_unneeded_prefixes,
+      wrap_unsupported_features: self.wrap_unsupported_features,
+      warn_optimizations: self.warn_optimizations,
+      disabled_optimizations: self.disabled_optimizations.clone(),
+      registered_properties: self.registered_properties.clone(),
+      declared_properties: self.declared_properties.clone(),
+      logical_fallback: self.logical_fallback,
+      ..PropertyHandlerContext::new(self.targets)
     }
   }
 
@@ -60,6 +127,19 @@ impl<'i> PropertyHandlerContext<'i> {
     }
   }
 
+  /// Returns whether the optimization is enabled, and emits a warning for it if requested. This should
+  /// only be called once all other conditions for applying it are met.
+  pub fn apply_optimization(&mut self, optimization: Optimization) -> bool {
+    if self.disabled_optimizations.contains(&optimization) {
+      return false;
+    }
+
+    if self.warn_optimizations {
+      self.warnings.push(MinifyWarningKind::OptimizationApplied { optimization });
+    }
+    true
+  }
+
   pub fn add_logical_rule(&mut self, ltr: Property<'i>, rtl: Property<'i>) {
     self.ltr.push(ltr);
     self.rtl.push(rtl);
@@ -82,9 +162,10 @@ impl<'i> PropertyHandlerContext<'i> {
           declarations: DeclarationBlock {
             declarations: std::mem::take(&mut self.$decls),
             important_declarations: vec![],
+            apply: vec![],
           },
           rules: CssRuleList(vec![]),
-          loc: style_rule.loc.clone(),
+          loc: style_rule.loc.as_generated(),
         };
 
         dest.push(CssRule::Style(rule));
@@ -164,14 +245,171 @@ impl<'i> PropertyHandlerContext<'i> {
           declarations: DeclarationBlock {
             declarations: entry.declarations,
             important_declarations: entry.important_declarations,
+            apply: vec![],
           },
           rules: CssRuleList(vec![]),
-          loc: style_rule.loc.clone(),
+          loc: style_rule.loc.as_generated(),
         })]),
-        loc: style_rule.loc.clone(),
+        loc: style_rule.loc.as_generated(),
       }));
     }
 
     dest
   }
+
+  /// Returns a rule that makes the animations and transitions declared by the given style rule
+  /// respect the user's motion preference, if it declares any. With [ReducedMotion::Reset], this is
+  /// a `@media (prefers-reduced-motion: reduce)` rule that resets them. With [ReducedMotion::Guard],
+  /// the declarations are moved out of the style rule into a `@media (prefers-reduced-motion: no-preference)` rule.
+  pub fn get_reduced_motion_rule(
+    &self,
+    style_rule: &mut StyleRule<'i>,
+    mode: ReducedMotion,
+  ) -> Option<CssRule<'i>> {
+    // Declarations from unexpanded @apply rules could not be moved along with the rest.
+    if mode == ReducedMotion::Guard && !style_rule.declarations.apply.is_empty() {
+      return None;
+    }
+
+    let (declarations, important_declarations, value) = match mode {
+      ReducedMotion::Reset => (
+        // Mirror the importance of the original declarations so that the reset wins the cascade.
+        get_motion_resets(&style_rule.declarations.declarations),
+        get_motion_resets(&style_rule.declarations.important_declarations),
+        DiscreteMediaFeatureValue::Reduce,
+      ),
+      ReducedMotion::Guard => (
+        take_motion_declarations(&mut style_rule.declarations.declarations),
+        take_motion_declarations(&mut style_rule.declarations.important_declarations),
+        DiscreteMediaFeatureValue::NoPreference,
+      ),
+    };
+
+    if declarations.is_empty() && important_declarations.is_empty() {
+      return None;
+    }
+
+    Some(CssRule::Media(MediaRule {
+      query: MediaList {
+        media_queries: vec![MediaQuery {
+          qualifier: None,
+          media_type: MediaType::All,
+          condition: Some(MediaCondition::Feature(MediaFeature::Discrete {
+            feature: DiscreteMediaFeature::PrefersReducedMotion,
+            value: Some(value),
+          })),
+        }],
+      },
+      rules: CssRuleList(vec![CssRule::Style(StyleRule {
+        selectors: style_rule.selectors.clone(),
+        vendor_prefix: style_rule.vendor_prefix,
+        declarations: DeclarationBlock {
+          declarations,
+          important_declarations,
+          apply: vec![],
+        },
+        rules: CssRuleList(vec![]),
+        loc: style_rule.loc.as_generated(),
+      })]),
+      loc: style_rule.loc.as_generated(),
+    }))
+  }
+}
+
+/// The kind of motion declared by a property.
+#[derive(PartialEq)]
+enum Motion {
+  Animation,
+  Transition,
+}
+
+fn get_motion(property: &Property) -> Option<(Motion, VendorPrefix)> {
+  use PropertyId::*;
+  match property.property_id() {
+    Animation(prefix)
+    | AnimationName(prefix)
+    | AnimationDuration(prefix)
+    | AnimationTimingFunction(prefix)
+    | AnimationIterationCount(prefix)
+    | AnimationDirection(prefix)
+    | AnimationPlayState(prefix)
+    | AnimationDelay(prefix)
+    | AnimationFillMode(prefix) => Some((Motion::Animation, prefix)),
+    Transition(prefix)
+    | TransitionProperty(prefix)
+    | TransitionDuration(prefix)
+    | TransitionDelay(prefix)
+    | TransitionTimingFunction(prefix) => Some((Motion::Transition, prefix)),
+    _ => None,
+  }
+}
+
+/// Returns declarations that reset the motion declared by the given
+/// properties, for each vendor prefix they use.
+fn get_motion_resets<'i>(properties: &[Property<'i>]) -> Vec<Property<'i>> {
+  let mut animation_prefixes = VendorPrefix::empty();
+  let mut transition_prefixes = VendorPrefix::empty();
+  for property in properties {
+    match get_motion(property) {
+      Some((Motion::Animation, prefix)) => animation_prefixes |= prefix,
+      Some((Motion::Transition, prefix)) => transition_prefixes |= prefix,
+      None => {}
+    }
+  }
+
+  let mut resets = Vec::new();
+  for prefix in [
+    VendorPrefix::WebKit,
+    VendorPrefix::Moz,
+    VendorPrefix::Ms,
+    VendorPrefix::O,
+    VendorPrefix::None,
+  ] {
+    if animation_prefixes.contains(prefix) {
+      resets.push(Property::Animation(
+        smallvec![Animation {
+          name: AnimationName::None,
+          duration: Time::Seconds(0.0),
+          timing_function: EasingFunction::Ease,
+          iteration_count: AnimationIterationCount::Number(1.0),
+          direction: AnimationDirection::Normal,
+          play_state: AnimationPlayState::Running,
+          delay: Time::Seconds(0.0),
+          fill_mode: AnimationFillMode::None,
+        }],
+        prefix,
+      ));
+    }
+  }
+
+  for prefix in [
+    VendorPrefix::WebKit,
+    VendorPrefix::Moz,
+    VendorPrefix::Ms,
+    VendorPrefix::O,
+    VendorPrefix::None,
+  ] {
+    if transition_prefixes.contains(prefix) {
+      // Transitions with a zero duration and delay complete immediately.
+      resets.push(Property::TransitionDuration(smallvec![Time::Seconds(0.0)], prefix));
+      resets.push(Property::TransitionDelay(smallvec![Time::Seconds(0.0)], prefix));
+    }
+  }
+
+  resets
+}
+
+/// Removes the `animation` and `transition` declarations from the given list, and returns them.
+fn take_motion_declarations<'i>(properties: &mut Vec<Property<'i>>) -> Vec<Property<'i>> {
+  let mut motion = Vec::new();
+  let mut i = 0;
+  while i < properties.len() {
+    if get_motion(&properties[i]).is_some() {
+      motion.push(properties.remove(i));
+    } else {
+      i += 1;
+    }
+  }
+
+  motion
 }
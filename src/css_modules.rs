@@ -39,16 +39,40 @@ lazy_static! {
 
 pub(crate) struct CssModule<'a> {
   pub hash: &'a str,
+  /// The hash of each source, if names are hashed based on the source of each rule.
+  pub source_hashes: Option<&'a [String]>,
   pub exports: &'a mut CssModuleExports,
 }
 
 impl<'a> CssModule<'a> {
+  pub fn set_source_index(&mut self, source_index: u32) {
+    if let Some(hash) = self.source_hashes.and_then(|hashes| hashes.get(source_index as usize)) {
+      self.hash = hash;
+    }
+  }
+
   pub fn add_local(&mut self, exported: &str, local: &str) {
-    self.exports.entry(exported.into()).or_insert_with(|| CssModuleExport {
-      name: get_hashed_name(self.hash, local),
-      composes: vec![],
-      is_referenced: false,
-    });
+    let name = get_hashed_name(self.hash, local);
+    match self.exports.entry(exported.into()) {
+      std::collections::hash_map::Entry::Occupied(mut entry) => {
+        // When names are hashed per source, the same name may be declared by several sources.
+        // The export then refers to the hashed name from each of them.
+        let export = entry.get_mut();
+        if export.name != name {
+          let reference = CssModuleReference::Local { name };
+          if !export.composes.contains(&reference) {
+            export.composes.push(reference);
+          }
+        }
+      }
+      std::collections::hash_map::Entry::Vacant(entry) => {
+        entry.insert(CssModuleExport {
+          name,
+          composes: vec![],
+          is_referenced: false,
+        });
+      }
+    }
   }
 
   pub fn reference(&mut self, name: &str) {
@@ -1,10 +1,20 @@
-use crate::context::PropertyHandlerContext;
-use crate::error::{ParserError, PrinterError};
-use crate::parser::ParserOptions;
-use crate::printer::Printer;
+use crate::compat::Feature;
+use crate::context::{DeclarationContext, PropertyHandlerContext};
+use crate::error::{
+  MinifyError, MinifyErrorKind, MinifyWarningKind, ParserError, ParserWarning, ParserWarningKind, PrinterError,
+};
+use crate::parser::{with_max_nesting_depth, ParserOptions};
+use crate::printer::{Printer, PrinterOptions};
+use crate::properties::align::{Gap, GapValue};
+use crate::properties::apply::{Apply, ApplyRule};
 use crate::properties::box_shadow::BoxShadowHandler;
-use crate::properties::masking::MaskHandler;
-use crate::properties::Property;
+use crate::properties::contain::ContainIntrinsicSizeHandler;
+use crate::properties::custom::{CSSWideKeyword, CustomProperty, VerbatimProperty};
+use crate::properties::display::{Display, DisplayInside, DisplayPair};
+use crate::properties::effects::FilterList;
+use crate::properties::masking::{ClipPath, MaskHandler};
+use crate::properties::motion::OffsetHandler;
+use crate::properties::transform::Perspective;
 use crate::properties::{
   align::AlignHandler,
   animation::AnimationHandler,
@@ -14,53 +24,133 @@ use crate::properties::{
   flex::FlexHandler,
   font::FontHandler,
   grid::GridHandler,
-  list::ListStyleHandler,
+  list::{CounterHandler, ListStyleHandler},
   margin_padding::*,
   outline::OutlineHandler,
-  overflow::OverflowHandler,
+  overflow::{LineClampHandler, OverflowHandler, OverscrollBehaviorHandler},
   position::PositionHandler,
   prefix_handler::{FallbackHandler, PrefixHandler},
   size::SizeHandler,
-  text::TextDecorationHandler,
+  text::{OverflowWrapHandler, TextDecorationHandler, WhiteSpaceHandler},
   transform::TransformHandler,
   transition::TransitionHandler,
+  ui::{AccentColorHandler, ColorSchemeHandler, UserSelectHandler},
+  writing_modes::WritingModeHandler,
 };
+use crate::properties::{Property, PropertyId};
+use crate::rules::supports::SupportsCondition;
+use crate::rules::Location;
+use crate::stylesheet::Optimization;
 use crate::targets::Browsers;
-use crate::traits::{PropertyHandler, ToCss};
+use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
+use std::collections::HashMap;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct DeclarationBlock<'i> {
   pub important_declarations: Vec<Property<'i>>,
   pub declarations: Vec<Property<'i>>,
+  /// The `@apply` rules within the block, in source order.
+  pub(crate) apply: Vec<ApplyRule<'i>>,
+}
+
+/// An item within a declaration block, in printing order.
+pub(crate) enum DeclarationItem<'a, 'i> {
+  /// A declaration, and whether it is `!important`.
+  Declaration(&'a Property<'i>, bool),
+  /// An `@apply` rule.
+  Apply(&'a ApplyRule<'i>),
+}
+
+impl<'a, 'i> DeclarationItem<'a, 'i> {
+  pub(crate) fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      DeclarationItem::Declaration(decl, important) => decl.to_css(dest, *important),
+      DeclarationItem::Apply(apply) => apply.to_css(dest),
+    }
+  }
 }
 
 impl<'i> DeclarationBlock<'i> {
+  /// Creates a declaration block from lists of normal and `!important` declarations.
+  pub fn new(declarations: Vec<Property<'i>>, important_declarations: Vec<Property<'i>>) -> Self {
+    DeclarationBlock {
+      important_declarations,
+      declarations,
+      apply: Vec::new(),
+    }
+  }
+
+  /// Returns the `@apply` rules within the block that have not been expanded, in source order.
+  pub fn apply_rules(&self) -> &[ApplyRule<'i>] {
+    &self.apply
+  }
+
   pub fn parse<'t>(
     input: &mut Parser<'i, 't>,
     options: &ParserOptions,
   ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     let mut important_declarations = DeclarationList::new();
     let mut declarations = DeclarationList::new();
+    let mut apply = Vec::new();
     let mut parser = DeclarationListParser::new(
       input,
       PropertyDeclarationParser {
         important_declarations: &mut important_declarations,
         declarations: &mut declarations,
+        apply: &mut apply,
         options,
       },
     );
-    while let Some(res) = parser.next() {
-      if let Err((err, _)) = res {
-        return Err(err);
+    with_max_nesting_depth(options.max_nesting_depth, || {
+      while let Some(res) = parser.next() {
+        if let Err((err, _)) = res {
+          return Err(err);
+        }
       }
-    }
+      Ok(())
+    })?;
 
     Ok(DeclarationBlock {
       important_declarations,
       declarations,
+      apply,
     })
   }
+
+  /// Returns the declarations and `@apply` rules of the block in printing order. Normal declarations
+  /// come first, followed by `!important` declarations, and each `@apply` rule is placed at its index
+  /// within the declarations with the same importance.
+  pub(crate) fn items(&self) -> Vec<DeclarationItem<'_, 'i>> {
+    let mut items =
+      Vec::with_capacity(self.declarations.len() + self.important_declarations.len() + self.apply.len());
+    for (decls, important) in [(&self.declarations, false), (&self.important_declarations, true)] {
+      let mut apply = self.apply.iter().filter(|rule| rule.important == important).peekable();
+      for (i, decl) in decls.iter().enumerate() {
+        while let Some(rule) = apply.next_if(|rule| rule.index <= i) {
+          items.push(DeclarationItem::Apply(rule));
+        }
+        items.push(DeclarationItem::Declaration(decl, important));
+      }
+      items.extend(apply.map(DeclarationItem::Apply));
+    }
+    items
+  }
+
+  /// Appends the declarations and `@apply` rules of another block to this one.
+  pub(crate) fn append(&mut self, other: DeclarationBlock<'i>) {
+    let (len, important_len) = (self.declarations.len(), self.important_declarations.len());
+    self.apply.extend(other.apply.into_iter().map(|mut rule| {
+      rule.index += if rule.important { important_len } else { len };
+      rule
+    }));
+    self.declarations.extend(other.declarations);
+    self.important_declarations.extend(other.important_declarations);
+  }
 }
 
 impl<'i> ToCss for DeclarationBlock<'i> {
@@ -72,25 +162,16 @@ impl<'i> ToCss for DeclarationBlock<'i> {
     dest.write_char('{')?;
     dest.indent();
 
-    let mut i = 0;
-    let len = self.declarations.len() + self.important_declarations.len();
-
-    macro_rules! write {
-      ($decls: expr, $important: literal) => {
-        for decl in &$decls {
-          dest.newline()?;
-          decl.to_css(dest, $important)?;
-          if i != len - 1 || !dest.minify {
-            dest.write_char(';')?;
-          }
-          i += 1;
-        }
-      };
+    let items = self.items();
+    let len = items.len();
+    for (i, item) in items.iter().enumerate() {
+      dest.newline()?;
+      item.to_css(dest)?;
+      if i != len - 1 || !dest.minify {
+        dest.write_char(';')?;
+      }
     }
 
-    write!(self.declarations, false);
-    write!(self.important_declarations, true);
-
     dest.dedent();
     dest.newline()?;
     dest.write_char('}')
@@ -104,9 +185,33 @@ impl<'i> DeclarationBlock<'i> {
     important_handler: &mut DeclarationHandler<'i>,
     context: &mut PropertyHandlerContext<'i>,
   ) {
+    // The properties declared by the block before the handlers merge or convert them. These are the ones
+    // the block contributes to the properties declared by the style sheet.
+    let property_ids = if context.remove_initial_values {
+      self.property_ids()
+    } else {
+      Vec::new()
+    };
+
+    if let Some(targets) = context.targets {
+      if !Feature::FlexGap.is_compatible(targets) && self.has_flex_gap() {
+        context.warnings.push(MinifyWarningKind::UnsupportedFlexGap);
+      }
+    }
+
     macro_rules! handle {
       ($decls: expr, $handler: expr, $important: literal) => {
-        for decl in $decls.iter() {
+        // Unexpanded @apply rules may set any property, so the declarations on either side of them are
+        // handled separately, and the rules are moved to the position of the flushed declarations.
+        let mut apply = self.apply.iter_mut().filter(|rule| rule.important == $important).peekable();
+        for (i, decl) in $decls.iter().enumerate() {
+          if apply.peek().map_or(false, |rule| rule.index <= i) {
+            $handler.finalize(context);
+            while let Some(rule) = apply.next_if(|rule| rule.index <= i) {
+              rule.index = $handler.decls.len();
+            }
+          }
+
           context.is_important = $important;
           let handled = $handler.handle_property(decl, context);
 
@@ -114,6 +219,13 @@ impl<'i> DeclarationBlock<'i> {
             $handler.decls.push(decl.clone());
           }
         }
+
+        if apply.peek().is_some() {
+          $handler.finalize(context);
+          for rule in apply {
+            rule.index = $handler.decls.len();
+          }
+        }
       };
     }
 
@@ -124,16 +236,290 @@ impl<'i> DeclarationBlock<'i> {
     important_handler.finalize(context);
     self.important_declarations = std::mem::take(&mut important_handler.decls);
     self.declarations = std::mem::take(&mut handler.decls);
+
+    for decl in self.declarations.iter_mut().chain(self.important_declarations.iter_mut()) {
+      if let Property::Filter(filters, _) | Property::BackdropFilter(filters, _) = decl {
+        if filters.has_trailing_identities() && context.apply_optimization(Optimization::IdentityFilterRemoval) {
+          filters.remove_trailing_identities();
+        }
+      }
+    }
+
+    // The remaining passes remove declarations, which would move unexpanded @apply rules.
+    if !self.apply.is_empty() {
+      return;
+    }
+
+    // Normal declarations of properties that are also set by an !important declaration,
+    // either directly or via a shorthand, never apply.
+    if !self.important_declarations.is_empty() {
+      let important: Vec<PropertyId> = self.important_declarations.iter().map(|d| d.property_id()).collect();
+      self.declarations.retain(|decl| {
+        !important.iter().any(|id| overrides(id, &decl.property_id()))
+          || !context.apply_optimization(Optimization::DeclarationDrop)
+      });
+    }
+
+    if context.remove_initial_values
+      && matches!(
+        context.context,
+        DeclarationContext::StyleRule | DeclarationContext::StyleAttribute
+      )
+    {
+      self.remove_initial_values(&property_ids, context);
+    }
+
+    if context.remove_unneeded_prefixes {
+      if let Some(targets) = context.targets {
+        self.remove_unneeded_prefixes(targets);
+      }
+    }
+
+    if context.wrap_unsupported_features && context.context == DeclarationContext::StyleRule {
+      if let Some(targets) = context.targets {
+        self.wrap_unsupported_features(targets, context);
+      }
+    }
   }
 
   pub fn is_empty(&self) -> bool {
-    return self.declarations.is_empty() && self.important_declarations.is_empty();
+    return self.declarations.is_empty() && self.important_declarations.is_empty() && self.apply.is_empty();
+  }
+
+  /// Returns the ids of the properties declared in the block, without duplicates.
+  /// Custom properties are not included.
+  pub(crate) fn property_ids(&self) -> Vec<PropertyId<'i>> {
+    let mut ids = Vec::new();
+    for decl in self.declarations.iter().chain(self.important_declarations.iter()) {
+      let id = decl.property_id();
+      if !matches!(id, PropertyId::Custom(..)) && !ids.contains(&id) {
+        ids.push(id);
+      }
+    }
+    ids
+  }
+
+  /// Removes normal declarations that set a property to its initial value, unless an earlier
+  /// declaration in the block also sets the property, or another rule in the style sheet declares it.
+  /// `!important` declarations are kept. `property_ids` are the properties the block declared before
+  /// it was minified.
+  fn remove_initial_values(&mut self, property_ids: &[PropertyId<'i>], context: &mut PropertyHandlerContext<'i>) {
+    // None of the removable properties are set by a shorthand, so only their own ids are counted. The
+    // style sheet's counts include this block, so the property is only declared by other rules if it
+    // is declared by more rules than this one.
+    let declared_properties = context.declared_properties.clone();
+    let is_declared_elsewhere = |id: &PropertyId<'i>| {
+      declared_properties.get(id).map_or(0, |count| *count) > usize::from(property_ids.contains(id))
+    };
+
+    let ids: Vec<PropertyId> = self.declarations.iter().map(|decl| decl.property_id()).collect();
+    let mut index = 0;
+    self.declarations.retain(|decl| {
+      let i = index;
+      index += 1;
+      !is_initial_value(decl)
+        || ids[..i].iter().any(|id| overrides(id, &ids[i]))
+        || is_declared_elsewhere(&ids[i])
+        || !context.apply_optimization(Optimization::InitialValueRemoval)
+    });
+  }
+
+  /// Moves declarations using features that are supported by some targets but not all into `@supports` rules
+  /// that test for the declaration itself, and adds fallbacks for them in `@supports not` rules where
+  /// possible. Values with `var()` references are always considered valid by `@supports`, so they
+  /// cannot be tested and a warning is emitted instead.
+  fn wrap_unsupported_features(&mut self, targets: Browsers, context: &mut PropertyHandlerContext<'i>) {
+    let has_transform = self.declarations.iter().chain(self.important_declarations.iter()).any(|decl| {
+      matches!(
+        decl.property_id(),
+        PropertyId::Transform(_) | PropertyId::Translate | PropertyId::Rotate | PropertyId::Scale
+      )
+    });
+    let is_important = context.is_important;
+    for (decls, important) in [
+      (&mut self.declarations, false),
+      (&mut self.important_declarations, true),
+    ] {
+      context.is_important = important;
+      let mut kept = Vec::with_capacity(decls.len());
+      for decl in decls.drain(..) {
+        match supports_feature(&decl.property_id()) {
+          // Wrapping a feature that no target supports only adds an `@supports` test that always fails.
+          Some(feature) if feature.is_partially_compatible(targets) && !feature.is_compatible(targets) => {
+            if matches!(decl, Property::Unparsed(..)) {
+              let warning = MinifyWarningKind::UnsupportedProperty {
+                property: decl.name().into(),
+              };
+              if !context.warnings.contains(&warning) {
+                context.warnings.push(warning);
+              }
+              kept.push(decl);
+            } else if let Ok(s) = decl.to_css_string(false, PrinterOptions::default()) {
+              let condition = SupportsCondition::Declaration(s.into());
+              let fallbacks = unsupported_fallbacks(&decl, has_transform, targets);
+              context.add_conditional_property(condition.clone(), decl);
+              for fallback in fallbacks {
+                context.add_conditional_property(SupportsCondition::Not(Box::new(condition.clone())), fallback);
+              }
+            } else {
+              kept.push(decl);
+            }
+          }
+          _ => kept.push(decl),
+        }
+      }
+      *decls = kept;
+    }
+    context.is_important = is_important;
+  }
+
+  /// Removes declarations whose vendor prefixes are not needed by any of the targets.
+  /// Properties that are only supported with a prefix have no prefix feature, and are kept.
+  fn remove_unneeded_prefixes(&mut self, targets: Browsers) {
+    let is_needed = |decl: &Property| {
+      let id = decl.property_id();
+      let prefix = id.prefix();
+      if prefix.contains(VendorPrefix::None) {
+        return true;
+      }
+
+      match id.prefix_feature() {
+        Some(feature) => feature.prefixes_for(targets).intersects(prefix),
+        None => true,
+      }
+    };
+
+    self.declarations.retain(|decl| is_needed(decl));
+    self.important_declarations.retain(|decl| is_needed(decl));
+  }
+
+  /// Returns whether the block sets a flex display along with a gap. Flex containers
+  /// whose display is set in another rule cannot be detected.
+  fn has_flex_gap(&self) -> bool {
+    let mut is_flex = false;
+    let mut has_gap = false;
+    for decl in self.declarations.iter().chain(self.important_declarations.iter()) {
+      match decl {
+        Property::Display(Display::Pair(DisplayPair {
+          inside: DisplayInside::Flex(..),
+          ..
+        })) => is_flex = true,
+        Property::Display(_) => is_flex = false,
+        Property::Gap(Gap { row, column }) => {
+          has_gap = *row != GapValue::Normal || *column != GapValue::Normal;
+        }
+        Property::RowGap(gap) | Property::ColumnGap(gap) if *gap != GapValue::Normal => has_gap = true,
+        _ => {}
+      }
+    }
+    is_flex && has_gap
   }
+
+  /// Replaces `@apply` rules with the declarations of the referenced utilities, in place.
+  pub(crate) fn apply_utilities(
+    &mut self,
+    utilities: &HashMap<String, DeclarationBlock<'i>>,
+    loc: Location,
+  ) -> Result<(), MinifyError> {
+    if self.apply.is_empty() {
+      return Ok(());
+    }
+
+    let mut stack = Vec::new();
+    let mut declarations = DeclarationList::new();
+    let mut applied_important = DeclarationList::new();
+    let mut important_declarations = DeclarationList::new();
+    for item in self.items() {
+      match item {
+        DeclarationItem::Declaration(decl, false) => declarations.push(decl.clone()),
+        DeclarationItem::Declaration(decl, true) => important_declarations.push(decl.clone()),
+        DeclarationItem::Apply(rule) if rule.important => expand_apply(
+          &rule.apply,
+          utilities,
+          true,
+          &mut DeclarationList::new(),
+          &mut important_declarations,
+          &mut stack,
+          loc,
+        )?,
+        DeclarationItem::Apply(rule) => expand_apply(
+          &rule.apply,
+          utilities,
+          false,
+          &mut declarations,
+          &mut applied_important,
+          &mut stack,
+          loc,
+        )?,
+      }
+    }
+
+    // Important declarations from applied utilities come before the rule's own
+    // important declarations, so that local overrides still win.
+    applied_important.extend(important_declarations);
+    self.declarations = declarations;
+    self.important_declarations = applied_important;
+    self.apply.clear();
+    Ok(())
+  }
+}
+
+fn expand_apply<'i>(
+  apply: &Apply<'i>,
+  utilities: &HashMap<String, DeclarationBlock<'i>>,
+  important: bool,
+  declarations: &mut DeclarationList<'i>,
+  important_declarations: &mut DeclarationList<'i>,
+  stack: &mut Vec<String>,
+  loc: Location,
+) -> Result<(), MinifyError> {
+  for name in &apply.names {
+    let name = name.as_ref();
+    let utility = match utilities.get(name) {
+      Some(utility) => utility,
+      None => {
+        return Err(MinifyError {
+          kind: MinifyErrorKind::UtilityNotDefined { name: name.to_owned() },
+          loc,
+        })
+      }
+    };
+
+    if stack.iter().any(|n| n == name) {
+      return Err(MinifyError {
+        kind: MinifyErrorKind::CircularApply { name: name.to_owned() },
+        loc,
+      });
+    }
+
+    stack.push(name.to_owned());
+    for item in utility.items() {
+      match item {
+        DeclarationItem::Declaration(decl, is_important) if important || is_important => {
+          important_declarations.push(decl.clone())
+        }
+        DeclarationItem::Declaration(decl, _) => declarations.push(decl.clone()),
+        DeclarationItem::Apply(rule) => expand_apply(
+          &rule.apply,
+          utilities,
+          important || rule.important,
+          declarations,
+          important_declarations,
+          stack,
+          loc,
+        )?,
+      }
+    }
+    stack.pop();
+  }
+
+  Ok(())
 }
 
 struct PropertyDeclarationParser<'a, 'i> {
   important_declarations: &'a mut Vec<Property<'i>>,
   declarations: &'a mut Vec<Property<'i>>,
+  apply: &'a mut Vec<ApplyRule<'i>>,
   options: &'a ParserOptions,
 }
 
@@ -157,11 +543,53 @@ impl<'a, 'i> cssparser::DeclarationParser<'i> for PropertyDeclarationParser<'a,
   }
 }
 
-/// Default methods reject all at rules.
+/// Parse an `@apply` rule within a declaration block. All other at rules are rejected.
 impl<'a, 'i> AtRuleParser<'i> for PropertyDeclarationParser<'a, 'i> {
-  type Prelude = ();
+  type Prelude = (Apply<'i>, bool);
   type AtRule = ();
   type Error = ParserError<'i>;
+
+  fn parse_prelude<'t>(
+    &mut self,
+    name: CowRcStr<'i>,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+    parse_apply(name, input)
+  }
+
+  fn rule_without_block(&mut self, prelude: Self::Prelude, _start: &ParserState) -> Result<Self::AtRule, ()> {
+    let (apply, important) = prelude;
+    let index = if important {
+      self.important_declarations.len()
+    } else {
+      self.declarations.len()
+    };
+    self.apply.push(ApplyRule {
+      apply,
+      important,
+      index,
+    });
+    Ok(())
+  }
+}
+
+pub(crate) fn parse_apply<'i, 't>(
+  name: CowRcStr<'i>,
+  input: &mut cssparser::Parser<'i, 't>,
+) -> Result<(Apply<'i>, bool), cssparser::ParseError<'i, ParserError<'i>>> {
+  if !name.eq_ignore_ascii_case("apply") {
+    return Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)));
+  }
+
+  let apply = Apply::parse(input)?;
+  let important = input
+    .try_parse(|input| {
+      input.expect_delim('!')?;
+      input.expect_ident_matching("important")
+    })
+    .is_ok();
+  input.expect_exhausted()?;
+  Ok((apply, important))
 }
 
 pub(crate) fn parse_declaration<'i, 't>(
@@ -171,7 +599,57 @@ pub(crate) fn parse_declaration<'i, 't>(
   important_declarations: &mut DeclarationList<'i>,
   options: &ParserOptions,
 ) -> Result<(), cssparser::ParseError<'i, ParserError<'i>>> {
-  let property = input.parse_until_before(Delimiter::Bang, |input| Property::parse(name, input, options))?;
+  let location = input.current_source_location();
+  let raw_name = if options.preserve_raw_values {
+    Some(name.clone())
+  } else {
+    None
+  };
+  let start = input.position();
+  let mut property = input.parse_until_before(Delimiter::Bang, |input| Property::parse(name, input, options))?;
+  if let Some(warnings) = &options.warnings {
+    let kind = match &property {
+      Property::Custom(custom) if !custom.name.starts_with("--") => Some(ParserWarningKind::UnknownProperty {
+        property: custom.name.to_string(),
+      }),
+      Property::Unparsed(unparsed) if !unparsed.value.has_references() => {
+        let mut value = String::new();
+        let mut printer = Printer::new(&mut value, PrinterOptions::default());
+        let res = unparsed.value.to_css(&mut printer, false);
+        res.ok().map(|_| ParserWarningKind::InvalidPropertyValue {
+          property: unparsed.property_id.name().to_string(),
+          value,
+        })
+      }
+      _ => property
+        .unknown_transition_property()
+        .map(|name| ParserWarningKind::UnknownTransitionProperty {
+          property: property.name().to_string(),
+          name: name.name().to_string(),
+        }),
+    };
+
+    if let (Some(kind), Ok(mut warnings)) = (kind, warnings.write()) {
+      warnings.push(ParserWarning {
+        kind,
+        loc: Location {
+          source_index: options.source_index,
+          line: location.line,
+          column: location.column,
+          generated: false,
+        },
+      });
+    }
+  }
+
+  if let Some(name) = raw_name {
+    property = Property::Verbatim(VerbatimProperty {
+      property: Box::new(property),
+      name: name.into(),
+      value: input.slice_from(start).trim().into(),
+    });
+  }
+
   let important = input
     .try_parse(|input| {
       input.expect_delim('!')?;
@@ -186,6 +664,74 @@ pub(crate) fn parse_declaration<'i, 't>(
   Ok(())
 }
 
+/// Returns whether setting the property `a` also sets `b`. Only shorthands are considered
+/// to set their longhands, and not logical properties their physical equivalents.
+fn overrides(a: &PropertyId, b: &PropertyId) -> bool {
+  if a == b {
+    return true;
+  }
+
+  let (a_name, b_name) = (a.name(), b.name());
+  if b_name.len() <= a_name.len() || !b_name.starts_with(a_name) || b_name.as_bytes()[a_name.len()] != b'-' {
+    return false;
+  }
+
+  // Longhands are listed unprefixed, and are set with the prefix of the shorthand.
+  let (a_prefix, b_prefix) = (a.prefix(), b.prefix());
+  let sets = |id: &PropertyId| a.longhands().iter().any(|l| l.with_prefix(a_prefix) == *id);
+  if sets(b) {
+    return true;
+  }
+
+  let b_longhands = b.longhands();
+  !b_longhands.is_empty() && b_longhands.iter().all(|l| sets(&l.with_prefix(b_prefix)))
+}
+
+/// Returns the feature required by declarations of a property, which may be wrapped in `@supports`
+/// if the handlers did not lower it for targets without support. `gap` is not included, since
+/// browsers that only support it in grid containers also pass `@supports (gap: 1rem)`.
+fn supports_feature(property_id: &PropertyId) -> Option<Feature> {
+  match property_id {
+    PropertyId::Custom(_) => None,
+    _ if property_id.prefix().contains(VendorPrefix::None) => Feature::from_property(property_id.name()),
+    _ => None,
+  }
+}
+
+/// Returns the declarations that browsers without support for a declaration wrapped in `@supports`
+/// fall back to, if it has an approximate equivalent.
+fn unsupported_fallbacks<'i>(
+  property: &Property<'i>,
+  has_transform: bool,
+  targets: Browsers,
+) -> Vec<Property<'i>> {
+  match property {
+    // A scale would override the transform declared by the rule.
+    Property::Zoom(zoom) if !has_transform => {
+      let prefix = crate::prefixes::Feature::Transform.prefixes_for(targets);
+      zoom.to_transform_properties(prefix).map_or(Vec::new(), Vec::from)
+    }
+    _ => Vec::new(),
+  }
+}
+
+/// Returns whether the declaration sets a property to its initial value, so that it is equivalent
+/// to not declaring the property at all. This is limited to properties whose initial value is not
+/// affected by user agent styles. Prefixed declarations are not considered, since they may be
+/// intended for browsers with a different initial value.
+fn is_initial_value(property: &Property) -> bool {
+  match property.unwrap_verbatim() {
+    Property::Opacity(opacity) => opacity.0 == 1.0,
+    Property::Transform(transform, prefix) => *prefix == VendorPrefix::None && transform.0.is_empty(),
+    Property::Perspective(perspective, prefix) => {
+      *prefix == VendorPrefix::None && *perspective == Perspective::None
+    }
+    Property::Filter(filter, prefix) => *prefix == VendorPrefix::None && *filter == FilterList::None,
+    Property::ClipPath(clip, prefix) => *prefix == VendorPrefix::None && *clip == ClipPath::None,
+    _ => false,
+  }
+}
+
 pub(crate) type DeclarationList<'i> = Vec<Property<'i>>;
 
 pub(crate) struct DeclarationHandler<'i> {
@@ -202,15 +748,26 @@ pub(crate) struct DeclarationHandler<'i> {
   scroll_padding: ScrollPaddingHandler<'i>,
   font: FontHandler<'i>,
   text: TextDecorationHandler<'i>,
+  white_space: WhiteSpaceHandler,
+  overflow_wrap: OverflowWrapHandler,
   list: ListStyleHandler<'i>,
+  counter: CounterHandler,
   transition: TransitionHandler<'i>,
   animation: AnimationHandler<'i>,
   display: DisplayHandler<'i>,
   position: PositionHandler,
   inset: InsetHandler<'i>,
   overflow: OverflowHandler,
+  line_clamp: LineClampHandler,
+  overscroll_behavior: OverscrollBehaviorHandler,
+  offset: OffsetHandler<'i>,
+  contain_intrinsic_size: ContainIntrinsicSizeHandler,
   transform: TransformHandler,
   box_shadow: BoxShadowHandler,
+  user_select: UserSelectHandler,
+  writing_mode: WritingModeHandler,
+  color_scheme: ColorSchemeHandler,
+  accent_color: AccentColorHandler,
   mask: MaskHandler<'i>,
   fallback: FallbackHandler,
   prefix: PrefixHandler,
@@ -233,15 +790,26 @@ impl<'i> DeclarationHandler<'i> {
       scroll_padding: ScrollPaddingHandler::default(),
       font: FontHandler::default(),
       text: TextDecorationHandler::new(targets),
+      white_space: WhiteSpaceHandler::new(targets),
+      overflow_wrap: OverflowWrapHandler::new(targets),
       list: ListStyleHandler::new(targets),
+      counter: CounterHandler::default(),
       transition: TransitionHandler::new(targets),
       animation: AnimationHandler::new(targets),
       display: DisplayHandler::new(targets),
       position: PositionHandler::new(targets),
       inset: InsetHandler::default(),
       overflow: OverflowHandler::new(targets),
+      line_clamp: LineClampHandler::new(targets),
+      overscroll_behavior: OverscrollBehaviorHandler::default(),
+      offset: OffsetHandler::new(targets),
+      contain_intrinsic_size: ContainIntrinsicSizeHandler::default(),
       transform: TransformHandler::new(targets),
       box_shadow: BoxShadowHandler::new(targets),
+      user_select: UserSelectHandler::new(targets),
+      writing_mode: WritingModeHandler::new(targets),
+      color_scheme: ColorSchemeHandler::new(targets),
+      accent_color: AccentColorHandler::new(targets),
       mask: MaskHandler::default(),
       fallback: FallbackHandler::new(targets),
       prefix: PrefixHandler::new(targets),
@@ -250,6 +818,51 @@ impl<'i> DeclarationHandler<'i> {
   }
 
   pub fn handle_property(&mut self, property: &Property<'i>, context: &mut PropertyHandlerContext<'i>) -> bool {
+    match property {
+      Property::All(keyword) => {
+        self.handle_wide_keyword(property, *keyword, context);
+        return true;
+      }
+      Property::WideKeyword(wide) => {
+        self.handle_wide_keyword(property, wide.keyword, context);
+        return true;
+      }
+      Property::Verbatim(verbatim) => return self.handle_property(&verbatim.property, context),
+      Property::Custom(custom) => {
+        // Any value is valid for a custom property, so an earlier declaration with the same name and
+        // importance never applies. Unknown properties are kept, since they may be fallbacks.
+        if custom.name.starts_with("--")
+          && self
+            .decls
+            .iter()
+            .any(|decl| matches!(decl, Property::Custom(c) if c.name == custom.name))
+          && context.apply_optimization(Optimization::CustomPropertyDedupe)
+        {
+          self
+            .decls
+            .retain(|decl| !matches!(decl, Property::Custom(c) if c.name == custom.name));
+        }
+
+        // Registered properties are only typed by browsers that support them. Others substitute the value
+        // as written, where e.g. a unitless zero is not a valid length in `calc()`.
+        let syntax = context.registered_properties.get(&*custom.name);
+        if let Some(syntax) = syntax.filter(|_| context.is_supported(Feature::AtProperty)) {
+          if let Some(value) = custom.value.minify_with_syntax(syntax) {
+            let property = Property::Custom(CustomProperty {
+              name: custom.name.clone(),
+              value,
+            });
+            return self.dispatch_property(&property, context);
+          }
+        }
+      }
+      _ => {}
+    }
+
+    self.dispatch_property(property, context)
+  }
+
+  fn dispatch_property(&mut self, property: &Property<'i>, context: &mut PropertyHandlerContext<'i>) -> bool {
     self.background.handle_property(property, &mut self.decls, context)
       || self.border.handle_property(property, &mut self.decls, context)
       || self.outline.handle_property(property, &mut self.decls, context)
@@ -263,15 +876,26 @@ impl<'i> DeclarationHandler<'i> {
       || self.scroll_padding.handle_property(property, &mut self.decls, context)
       || self.font.handle_property(property, &mut self.decls, context)
       || self.text.handle_property(property, &mut self.decls, context)
+      || self.white_space.handle_property(property, &mut self.decls, context)
+      || self.overflow_wrap.handle_property(property, &mut self.decls, context)
       || self.list.handle_property(property, &mut self.decls, context)
+      || self.counter.handle_property(property, &mut self.decls, context)
       || self.transition.handle_property(property, &mut self.decls, context)
       || self.animation.handle_property(property, &mut self.decls, context)
       || self.display.handle_property(property, &mut self.decls, context)
       || self.position.handle_property(property, &mut self.decls, context)
       || self.inset.handle_property(property, &mut self.decls, context)
       || self.overflow.handle_property(property, &mut self.decls, context)
+      || self.line_clamp.handle_property(property, &mut self.decls, context)
+      || self.overscroll_behavior.handle_property(property, &mut self.decls, context)
+      || self.offset.handle_property(property, &mut self.decls, context)
+      || self.contain_intrinsic_size.handle_property(property, &mut self.decls, context)
       || self.transform.handle_property(property, &mut self.decls, context)
       || self.box_shadow.handle_property(property, &mut self.decls, context)
+      || self.user_select.handle_property(property, &mut self.decls, context)
+      || self.writing_mode.handle_property(property, &mut self.decls, context)
+      || self.color_scheme.handle_property(property, &mut self.decls, context)
+      || self.accent_color.handle_property(property, &mut self.decls, context)
       || self.mask.handle_property(property, &mut self.decls, context)
       || self.fallback.handle_property(property, &mut self.decls, context)
       || self.prefix.handle_property(property, &mut self.decls, context)
@@ -291,17 +915,66 @@ impl<'i> DeclarationHandler<'i> {
     self.scroll_padding.finalize(&mut self.decls, context);
     self.font.finalize(&mut self.decls, context);
     self.text.finalize(&mut self.decls, context);
+    self.white_space.finalize(&mut self.decls, context);
+    self.overflow_wrap.finalize(&mut self.decls, context);
     self.list.finalize(&mut self.decls, context);
+    self.counter.finalize(&mut self.decls, context);
     self.transition.finalize(&mut self.decls, context);
     self.animation.finalize(&mut self.decls, context);
     self.display.finalize(&mut self.decls, context);
     self.position.finalize(&mut self.decls, context);
     self.inset.finalize(&mut self.decls, context);
     self.overflow.finalize(&mut self.decls, context);
+    self.line_clamp.finalize(&mut self.decls, context);
+    self.overscroll_behavior.finalize(&mut self.decls, context);
+    self.offset.finalize(&mut self.decls, context);
+    self.contain_intrinsic_size.finalize(&mut self.decls, context);
     self.transform.finalize(&mut self.decls, context);
     self.box_shadow.finalize(&mut self.decls, context);
+    self.user_select.finalize(&mut self.decls, context);
+    self.writing_mode.finalize(&mut self.decls, context);
+    self.color_scheme.finalize(&mut self.decls, context);
+    self.accent_color.finalize(&mut self.decls, context);
     self.mask.finalize(&mut self.decls, context);
     self.fallback.finalize(&mut self.decls, context);
     self.prefix.finalize(&mut self.decls, context);
   }
+
+  fn handle_wide_keyword(
+    &mut self,
+    property: &Property<'i>,
+    keyword: CSSWideKeyword,
+    context: &mut PropertyHandlerContext<'i>,
+  ) {
+    if let (Some(targets), Some(feature)) = (context.targets, keyword.feature()) {
+      if !feature.is_compatible(targets) {
+        if let Ok(value) = keyword.to_css_string(PrinterOptions::default()) {
+          context.warnings.push(MinifyWarningKind::UnsupportedValue {
+            property: property.name().into(),
+            value,
+          });
+        }
+      }
+    }
+
+    // The handlers cannot merge across a CSS-wide keyword, so flush
+    // everything seen so far to preserve the order of declarations.
+    self.finalize(context);
+
+    // The `all` property resets every property except custom properties,
+    // `direction`, and `unicode-bidi`, so earlier declarations with the same
+    // importance have no effect. Later declarations still apply as usual.
+    if let Property::All(_) = property {
+      self.decls.retain(|decl| match decl {
+        Property::Custom(custom) => {
+          custom.name.starts_with("--")
+            || custom.name.eq_ignore_ascii_case("direction")
+            || custom.name.eq_ignore_ascii_case("unicode-bidi")
+        }
+        _ => false,
+      });
+    }
+
+    self.decls.push(property.clone());
+  }
 }
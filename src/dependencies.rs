@@ -18,9 +18,20 @@ pub struct ImportDependency {
   pub url: String,
   pub supports: Option<String>,
   pub media: Option<String>,
+  pub layer: Option<ImportLayer>,
   pub loc: SourceRange,
 }
 
+/// The cascade layer requested by an `@import` rule.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ImportLayer {
+  /// An anonymous layer, e.g. `@import "foo.css" layer`.
+  Anonymous,
+  /// A named layer, e.g. `@import "foo.css" layer(foo)`.
+  Named { name: String },
+}
+
 impl ImportDependency {
   pub fn new(rule: &ImportRule, filename: &str) -> ImportDependency {
     let supports = if let Some(supports) = &rule.supports {
@@ -37,10 +48,19 @@ impl ImportDependency {
       None
     };
 
+    let layer = match &rule.layer {
+      Some(Some(name)) => Some(ImportLayer::Named {
+        name: name.to_css_string(PrinterOptions::default()).unwrap(),
+      }),
+      Some(None) => Some(ImportLayer::Anonymous),
+      None => None,
+    };
+
     ImportDependency {
       url: rule.url.as_ref().to_owned(),
       supports,
       media,
+      layer,
       loc: SourceRange::new(
         filename,
         SourceLocation {
@@ -0,0 +1,370 @@
+//! Structural diffing of style sheets.
+//!
+//! See [StyleSheet::diff](crate::stylesheet::StyleSheet::diff).
+
+use crate::declaration::DeclarationBlock;
+use crate::properties::Property;
+use crate::rules::{style::StyleRule, CssRule, CssRuleList, Location};
+
+/// A structural change between two style sheets.
+///
+/// Rules are matched between the old and new style sheets by their prelude (e.g. selectors, media query,
+/// or name), so a rule whose contents changed is reported as changes to its declarations or child rules
+/// rather than as a removal and an addition. Declarations do not have their own locations, so declaration
+/// changes carry the locations of their parent rules on both sides.
+#[derive(Debug, PartialEq)]
+pub enum Change<'a, 'i> {
+  /// A rule was added in the new style sheet.
+  RuleAdded {
+    /// The added rule.
+    rule: &'a CssRule<'i>,
+    /// The location of the rule in the new style sheet.
+    new_loc: Location,
+  },
+  /// A rule was removed from the old style sheet.
+  RuleRemoved {
+    /// The removed rule.
+    rule: &'a CssRule<'i>,
+    /// The location of the rule in the old style sheet.
+    old_loc: Location,
+  },
+  /// A rule was moved relative to its siblings. Changes to its contents are reported separately.
+  RuleMoved {
+    /// The rule in the new style sheet.
+    rule: &'a CssRule<'i>,
+    /// The location of the rule in the old style sheet.
+    old_loc: Location,
+    /// The location of the rule in the new style sheet.
+    new_loc: Location,
+  },
+  /// The contents of a rule without declarations or child rules changed, e.g. `@font-face` or `@keyframes`.
+  RuleChanged {
+    /// The rule in the old style sheet.
+    old: &'a CssRule<'i>,
+    /// The rule in the new style sheet.
+    new: &'a CssRule<'i>,
+    /// The location of the rule in the old style sheet.
+    old_loc: Location,
+    /// The location of the rule in the new style sheet.
+    new_loc: Location,
+  },
+  /// A declaration was added to a rule.
+  DeclarationAdded {
+    /// The added declaration.
+    property: &'a Property<'i>,
+    /// Whether the declaration is `!important`.
+    important: bool,
+    /// The location of the parent rule in the old style sheet.
+    old_loc: Location,
+    /// The location of the parent rule in the new style sheet.
+    new_loc: Location,
+  },
+  /// A declaration was removed from a rule.
+  DeclarationRemoved {
+    /// The removed declaration.
+    property: &'a Property<'i>,
+    /// Whether the declaration is `!important`.
+    important: bool,
+    /// The location of the parent rule in the old style sheet.
+    old_loc: Location,
+    /// The location of the parent rule in the new style sheet.
+    new_loc: Location,
+  },
+  /// The value of a declaration changed.
+  DeclarationChanged {
+    /// The declaration in the old style sheet.
+    old: &'a Property<'i>,
+    /// The declaration in the new style sheet.
+    new: &'a Property<'i>,
+    /// Whether the declaration is `!important`.
+    important: bool,
+    /// The location of the parent rule in the old style sheet.
+    old_loc: Location,
+    /// The location of the parent rule in the new style sheet.
+    new_loc: Location,
+  },
+}
+
+pub(crate) fn diff_rules<'a, 'i>(
+  old: &'a CssRuleList<'i>,
+  new: &'a CssRuleList<'i>,
+  changes: &mut Vec<Change<'a, 'i>>,
+) {
+  let old: Vec<(&CssRule, Location)> = old.0.iter().filter_map(|rule| rule.loc().map(|loc| (rule, loc))).collect();
+  let new: Vec<(&CssRule, Location)> = new.0.iter().filter_map(|rule| rule.loc().map(|loc| (rule, loc))).collect();
+
+  // Pair identical rules first so that duplicates are matched with their counterparts,
+  // then pair the remaining rules by their prelude.
+  let mut old_matches: Vec<Option<usize>> = vec![None; old.len()];
+  let mut new_matches: Vec<Option<usize>> = vec![None; new.len()];
+  for matches in [rules_equal as fn(&CssRule, &CssRule) -> bool, same_prelude] {
+    for (i, (old_rule, _)) in old.iter().enumerate() {
+      if old_matches[i].is_some() {
+        continue;
+      }
+
+      let j = (0..new.len()).find(|&j| new_matches[j].is_none() && matches(old_rule, new[j].0));
+      if let Some(j) = j {
+        old_matches[i] = Some(j);
+        new_matches[j] = Some(i);
+      }
+    }
+  }
+
+  // Matched rules that are not part of the longest run kept in the same relative order were moved.
+  let matched: Vec<usize> = old_matches.iter().filter_map(|j| *j).collect();
+  let in_order = longest_increasing_subsequence(&matched);
+  let mut moved = vec![false; new.len()];
+  for (j, in_order) in matched.iter().zip(in_order) {
+    moved[*j] = !in_order;
+  }
+
+  for (i, &(rule, old_loc)) in old.iter().enumerate() {
+    if old_matches[i].is_none() {
+      changes.push(Change::RuleRemoved { rule, old_loc });
+    }
+  }
+
+  for (j, &(new_rule, new_loc)) in new.iter().enumerate() {
+    match new_matches[j] {
+      Some(i) => {
+        let (old_rule, old_loc) = old[i];
+        if moved[j] {
+          changes.push(Change::RuleMoved {
+            rule: new_rule,
+            old_loc,
+            new_loc,
+          });
+        }
+        diff_rule(old_rule, new_rule, old_loc, new_loc, changes);
+      }
+      None => changes.push(Change::RuleAdded {
+        rule: new_rule,
+        new_loc,
+      }),
+    }
+  }
+}
+
+fn diff_rule<'a, 'i>(
+  old: &'a CssRule<'i>,
+  new: &'a CssRule<'i>,
+  old_loc: Location,
+  new_loc: Location,
+  changes: &mut Vec<Change<'a, 'i>>,
+) {
+  match (old, new) {
+    (CssRule::Style(a), CssRule::Style(b)) => diff_style(a, b, old_loc, new_loc, changes),
+    (CssRule::Nesting(a), CssRule::Nesting(b)) => diff_style(&a.style, &b.style, old_loc, new_loc, changes),
+    (CssRule::Media(a), CssRule::Media(b)) => diff_rules(&a.rules, &b.rules, changes),
+    (CssRule::Supports(a), CssRule::Supports(b)) => diff_rules(&a.rules, &b.rules, changes),
+    (CssRule::LayerBlock(a), CssRule::LayerBlock(b)) => diff_rules(&a.rules, &b.rules, changes),
+    (CssRule::MozDocument(a), CssRule::MozDocument(b)) => diff_rules(&a.rules, &b.rules, changes),
+    (CssRule::Page(a), CssRule::Page(b)) => {
+      diff_declarations(&a.declarations, &b.declarations, old_loc, new_loc, changes)
+    }
+    (CssRule::Viewport(a), CssRule::Viewport(b)) => {
+      diff_declarations(&a.declarations, &b.declarations, old_loc, new_loc, changes)
+    }
+    (CssRule::CounterStyle(a), CssRule::CounterStyle(b)) => {
+      diff_declarations(&a.declarations, &b.declarations, old_loc, new_loc, changes)
+    }
+    _ => {
+      if !rules_equal(old, new) {
+        changes.push(Change::RuleChanged {
+          old,
+          new,
+          old_loc,
+          new_loc,
+        })
+      }
+    }
+  }
+}
+
+fn diff_style<'a, 'i>(
+  old: &'a StyleRule<'i>,
+  new: &'a StyleRule<'i>,
+  old_loc: Location,
+  new_loc: Location,
+  changes: &mut Vec<Change<'a, 'i>>,
+) {
+  diff_declarations(&old.declarations, &new.declarations, old_loc, new_loc, changes);
+  diff_rules(&old.rules, &new.rules, changes);
+}
+
+fn diff_declarations<'a, 'i>(
+  old: &'a DeclarationBlock<'i>,
+  new: &'a DeclarationBlock<'i>,
+  old_loc: Location,
+  new_loc: Location,
+  changes: &mut Vec<Change<'a, 'i>>,
+) {
+  diff_properties(&old.declarations, &new.declarations, false, old_loc, new_loc, changes);
+  diff_properties(
+    &old.important_declarations,
+    &new.important_declarations,
+    true,
+    old_loc,
+    new_loc,
+    changes,
+  );
+}
+
+fn diff_properties<'a, 'i>(
+  old: &'a [Property<'i>],
+  new: &'a [Property<'i>],
+  important: bool,
+  old_loc: Location,
+  new_loc: Location,
+  changes: &mut Vec<Change<'a, 'i>>,
+) {
+  // As with rules, pair identical declarations first, then declarations of the same property.
+  let mut old_matches: Vec<Option<usize>> = vec![None; old.len()];
+  let mut new_matches: Vec<Option<usize>> = vec![None; new.len()];
+  for matches in [properties_equal as fn(&Property, &Property) -> bool, same_property] {
+    for (i, old_property) in old.iter().enumerate() {
+      if old_matches[i].is_some() {
+        continue;
+      }
+
+      let j = (0..new.len()).find(|&j| new_matches[j].is_none() && matches(old_property, &new[j]));
+      if let Some(j) = j {
+        old_matches[i] = Some(j);
+        new_matches[j] = Some(i);
+      }
+    }
+  }
+
+  for (i, old_property) in old.iter().enumerate() {
+    match old_matches[i] {
+      Some(j) => {
+        if !old_property.same_value(&new[j]) {
+          changes.push(Change::DeclarationChanged {
+            old: old_property,
+            new: &new[j],
+            important,
+            old_loc,
+            new_loc,
+          })
+        }
+      }
+      None => changes.push(Change::DeclarationRemoved {
+        property: old_property,
+        important,
+        old_loc,
+        new_loc,
+      }),
+    }
+  }
+
+  for (j, new_property) in new.iter().enumerate() {
+    if new_matches[j].is_none() {
+      changes.push(Change::DeclarationAdded {
+        property: new_property,
+        important,
+        old_loc,
+        new_loc,
+      })
+    }
+  }
+}
+
+fn properties_equal(a: &Property, b: &Property) -> bool {
+  a.same_value(b)
+}
+
+fn same_property(a: &Property, b: &Property) -> bool {
+  a.name() == b.name()
+}
+
+/// Returns whether two declaration blocks set the same properties to the same values, ignoring formatting.
+fn declarations_equal(a: &DeclarationBlock, b: &DeclarationBlock) -> bool {
+  fn lists_equal(a: &[Property], b: &[Property]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.same_value(b))
+  }
+
+  lists_equal(&a.declarations, &b.declarations)
+    && lists_equal(&a.important_declarations, &b.important_declarations)
+    && a.apply_rules() == b.apply_rules()
+}
+
+/// Returns whether two rules are equal, ignoring source locations.
+fn rules_equal(a: &CssRule, b: &CssRule) -> bool {
+  fn lists_equal(a: &CssRuleList, b: &CssRuleList) -> bool {
+    a.0.len() == b.0.len() && a.0.iter().zip(b.0.iter()).all(|(a, b)| rules_equal(a, b))
+  }
+
+  fn styles_equal(a: &StyleRule, b: &StyleRule) -> bool {
+    a.selectors == b.selectors
+      && a.vendor_prefix == b.vendor_prefix
+      && declarations_equal(&a.declarations, &b.declarations)
+      && lists_equal(&a.rules, &b.rules)
+  }
+
+  match (a, b) {
+    (CssRule::Style(a), CssRule::Style(b)) => styles_equal(a, b),
+    (CssRule::Nesting(a), CssRule::Nesting(b)) => styles_equal(&a.style, &b.style),
+    (CssRule::Media(a), CssRule::Media(b)) => a.query == b.query && lists_equal(&a.rules, &b.rules),
+    (CssRule::Supports(a), CssRule::Supports(b)) => a.condition == b.condition && lists_equal(&a.rules, &b.rules),
+    (CssRule::LayerBlock(a), CssRule::LayerBlock(b)) => a.name == b.name && lists_equal(&a.rules, &b.rules),
+    (CssRule::MozDocument(a), CssRule::MozDocument(b)) => lists_equal(&a.rules, &b.rules),
+    _ => match b.loc() {
+      // All other rules only store a location at the top level.
+      Some(loc) => {
+        let mut a = a.clone();
+        a.set_loc(loc);
+        a == *b
+      }
+      None => a == b,
+    },
+  }
+}
+
+/// Returns whether two rules have the same prelude, and should be compared by their contents.
+fn same_prelude(a: &CssRule, b: &CssRule) -> bool {
+  match (a, b) {
+    (CssRule::Style(a), CssRule::Style(b)) => a.selectors == b.selectors && a.vendor_prefix == b.vendor_prefix,
+    (CssRule::Nesting(a), CssRule::Nesting(b)) => a.style.selectors == b.style.selectors,
+    (CssRule::Media(a), CssRule::Media(b)) => a.query == b.query,
+    (CssRule::Supports(a), CssRule::Supports(b)) => a.condition == b.condition,
+    (CssRule::LayerBlock(a), CssRule::LayerBlock(b)) => a.name == b.name,
+    (CssRule::MozDocument(_), CssRule::MozDocument(_)) => true,
+    (CssRule::Page(a), CssRule::Page(b)) => a.selectors == b.selectors,
+    (CssRule::Viewport(a), CssRule::Viewport(b)) => a.vendor_prefix == b.vendor_prefix,
+    (CssRule::CounterStyle(a), CssRule::CounterStyle(b)) => a.name == b.name,
+    (CssRule::Keyframes(a), CssRule::Keyframes(b)) => a.name == b.name && a.vendor_prefix == b.vendor_prefix,
+    (CssRule::FontPaletteValues(a), CssRule::FontPaletteValues(b)) => a.name == b.name,
+    (CssRule::Namespace(a), CssRule::Namespace(b)) => a.prefix == b.prefix,
+    (CssRule::CustomMedia(a), CssRule::CustomMedia(b)) => a.name == b.name,
+    (CssRule::Custom(a), CssRule::Custom(b)) => a.name == b.name && a.prelude == b.prelude,
+    _ => rules_equal(a, b),
+  }
+}
+
+/// Returns which of the given values are part of a longest strictly increasing subsequence.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<bool> {
+  // Indices of the smallest tail value of an increasing subsequence of each length.
+  let mut tails: Vec<usize> = Vec::new();
+  let mut prev: Vec<Option<usize>> = vec![None; values.len()];
+  for (i, value) in values.iter().enumerate() {
+    let pos = tails.partition_point(|&t| values[t] < *value);
+    if pos > 0 {
+      prev[i] = Some(tails[pos - 1]);
+    }
+
+    if pos == tails.len() {
+      tails.push(i);
+    } else {
+      tails[pos] = i;
+    }
+  }
+
+  let mut result = vec![false; values.len()];
+  let mut cur = tails.last().copied();
+  while let Some(i) = cur {
+    result[i] = true;
+    cur = prev[i];
+  }
+  result
+}
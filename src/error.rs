@@ -1,5 +1,6 @@
 use crate::properties::custom::Token;
 use crate::rules::Location;
+use crate::stylesheet::Optimization;
 use crate::values::string::CowArcStr;
 use cssparser::{BasicParseErrorKind, ParseError, ParseErrorKind};
 use parcel_selectors::parser::SelectorParseErrorKind;
@@ -22,6 +23,7 @@ impl<T: fmt::Display> fmt::Display for Error<T> {
   }
 }
 
+#[cfg(feature = "std")]
 impl<T: fmt::Display + fmt::Debug> std::error::Error for Error<T> {}
 
 #[derive(Debug, PartialEq, Clone)]
@@ -52,12 +54,14 @@ impl fmt::Display for ErrorLocation {
 pub enum ParserError<'i> {
   AtRuleBodyInvalid,
   AtRuleInvalid(CowArcStr<'i>),
+  ComputationallyDependentInitialValue,
   EndOfInput,
   InvalidDeclaration,
   InvalidMediaQuery,
   InvalidNesting,
   InvalidPageSelector,
   InvalidValue,
+  MaximumNestingDepthExceeded,
   QualifiedRuleInvalid,
   SelectorError(SelectorError<'i>),
   UnexpectedImportRule,
@@ -71,12 +75,17 @@ impl<'i> fmt::Display for ParserError<'i> {
     match self {
       AtRuleBodyInvalid => write!(f, "Invalid @ rule body"),
       AtRuleInvalid(name) => write!(f, "Unknown at rule: @{}", name),
+      ComputationallyDependentInitialValue => write!(
+        f,
+        "The initial value of a registered custom property must be computationally independent"
+      ),
       EndOfInput => write!(f, "Unexpected end of input"),
       InvalidDeclaration => write!(f, "Invalid declaration"),
       InvalidMediaQuery => write!(f, "Invalid media query"),
       InvalidNesting => write!(f, "Invalid nesting"),
       InvalidPageSelector => write!(f, "Invalid page selector"),
       InvalidValue => write!(f, "Invalid value"),
+      MaximumNestingDepthExceeded => write!(f, "Maximum nesting depth exceeded"),
       QualifiedRuleInvalid => write!(f, "Invalid qualified rule"),
       SelectorError(s) => s.fmt(f),
       UnexpectedImportRule => write!(
@@ -232,6 +241,7 @@ impl<T: fmt::Display> fmt::Display for ErrorWithLocation<T> {
   }
 }
 
+#[cfg(feature = "std")]
 impl<T: fmt::Display + fmt::Debug> std::error::Error for ErrorWithLocation<T> {}
 
 pub type MinifyError = ErrorWithLocation<MinifyErrorKind>;
@@ -242,6 +252,8 @@ pub enum MinifyErrorKind {
   CircularCustomMedia { name: String },
   CustomMediaNotDefined { name: String },
   UnsupportedCustomMediaBooleanLogic { custom_media_loc: Location },
+  CircularApply { name: String },
+  UtilityNotDefined { name: String },
 }
 
 impl fmt::Display for MinifyErrorKind {
@@ -254,6 +266,8 @@ impl fmt::Display for MinifyErrorKind {
         f,
         "Boolean logic with media types in @custom-media rules is not supported by Parcel CSS"
       ),
+      CircularApply { name } => write!(f, "Circular @apply of utility {} detected", name),
+      UtilityNotDefined { name } => write!(f, "Utility {} referenced by @apply is not defined", name),
     }
   }
 }
@@ -265,6 +279,86 @@ impl MinifyErrorKind {
   }
 }
 
+pub type ParserWarning = ErrorWithLocation<ParserWarningKind>;
+
+/// A notice about a declaration or media query that was preserved as is rather than parsed, reported when
+/// [ParserOptions::warnings](crate::stylesheet::ParserOptions::warnings) is set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum ParserWarningKind {
+  UnknownProperty { property: String },
+  InvalidPropertyValue { property: String, value: String },
+  UnknownTransitionProperty { property: String, name: String },
+  InvalidMediaFeatureValue { feature: String, value: String },
+}
+
+impl fmt::Display for ParserWarningKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use ParserWarningKind::*;
+    match self {
+      UnknownProperty { property } => write!(f, "Unknown property `{}`", property),
+      InvalidPropertyValue { property, value } => {
+        write!(f, "Invalid value `{}` for the `{}` property", value, property)
+      }
+      UnknownTransitionProperty { property, name } => {
+        write!(f, "Unknown property `{}` in the `{}` property", name, property)
+      }
+      InvalidMediaFeatureValue { feature, value } => {
+        write!(f, "Invalid value `{}` for the `{}` media feature", value, feature)
+      }
+    }
+  }
+}
+
+pub type MinifyWarning = ErrorWithLocation<MinifyWarningKind>;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum MinifyWarningKind {
+  UnsupportedSelector { selector: String },
+  UnsupportedValue { property: String, value: String },
+  UnsupportedFlexGap,
+  UnsupportedProperty { property: String },
+  MisplacedImportRule,
+  OptimizationApplied { optimization: Optimization },
+}
+
+impl fmt::Display for MinifyWarningKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use MinifyWarningKind::*;
+    match self {
+      UnsupportedSelector { selector } => {
+        write!(
+          f,
+          "The selector `{}` is not supported by all configured targets",
+          selector
+        )
+      }
+      UnsupportedValue { property, value } => {
+        write!(
+          f,
+          "The value `{}` for the `{}` property is not supported by all configured targets",
+          value, property
+        )
+      }
+      UnsupportedProperty { property } => write!(
+        f,
+        "The `{}` property is not supported by all configured targets",
+        property
+      ),
+      UnsupportedFlexGap => write!(
+        f,
+        "The `gap` property in flex containers is not supported by all configured targets"
+      ),
+      MisplacedImportRule => write!(
+        f,
+        "@import rules must precede all rules aside from @charset and @layer statements, and are otherwise ignored"
+      ),
+      OptimizationApplied { optimization } => write!(f, "{}", optimization),
+    }
+  }
+}
+
 pub type PrinterError = Error<PrinterErrorKind>;
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -274,6 +368,9 @@ pub enum PrinterErrorKind {
   FmtError,
   InvalidComposesNesting,
   InvalidComposesSelector,
+  MaximumOutputSizeExceeded { max: usize },
+  InvalidPxToRemRootFontSize,
+  InvalidPxToRemPrecision { max: u8 },
 }
 
 impl From<fmt::Error> for PrinterError {
@@ -293,6 +390,9 @@ impl fmt::Display for PrinterErrorKind {
       FmtError => write!(f, "Printer error"),
       InvalidComposesNesting => write!(f, "The `composes` property cannot be used within nested rules"),
       InvalidComposesSelector => write!(f, "The `composes` property cannot be used with a simple class selector"),
+      MaximumOutputSizeExceeded { max } => write!(f, "The output exceeded the maximum size of {} bytes", max),
+      InvalidPxToRemRootFontSize => write!(f, "The root font size used to convert px to rem must be a positive number"),
+      InvalidPxToRemPrecision { max } => write!(f, "The precision used to convert px to rem must be at most {}", max),
     }
   }
 }
@@ -303,3 +403,45 @@ impl PrinterErrorKind {
     self.to_string()
   }
 }
+
+/// An error that occurred while parsing, minifying, or printing a style sheet with [transform](crate::transform).
+#[derive(Debug, PartialEq)]
+pub enum TransformError<'i> {
+  /// An error that occurred while parsing.
+  Parser(Error<ParserError<'i>>),
+  /// An error that occurred while minifying.
+  Minify(Error<MinifyErrorKind>),
+  /// An error that occurred while printing.
+  Printer(PrinterError),
+}
+
+impl<'i> fmt::Display for TransformError<'i> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      TransformError::Parser(err) => err.fmt(f),
+      TransformError::Minify(err) => err.fmt(f),
+      TransformError::Printer(err) => err.fmt(f),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<'i> std::error::Error for TransformError<'i> {}
+
+impl<'i> From<Error<ParserError<'i>>> for TransformError<'i> {
+  fn from(err: Error<ParserError<'i>>) -> TransformError<'i> {
+    TransformError::Parser(err)
+  }
+}
+
+impl<'i> From<Error<MinifyErrorKind>> for TransformError<'i> {
+  fn from(err: Error<MinifyErrorKind>) -> TransformError<'i> {
+    TransformError::Minify(err)
+  }
+}
+
+impl<'i> From<PrinterError> for TransformError<'i> {
+  fn from(err: PrinterError) -> TransformError<'i> {
+    TransformError::Printer(err)
+  }
+}
@@ -1,9 +1,11 @@
+#[cfg(feature = "std")]
 pub mod bundler;
 mod compat;
 mod context;
 pub mod css_modules;
 pub mod declaration;
 pub mod dependencies;
+pub mod diff;
 pub mod error;
 mod logical;
 mod macros;
@@ -20,21 +22,52 @@ pub mod traits;
 pub mod values;
 pub mod vendor_prefix;
 
+use error::TransformError;
+use stylesheet::{StyleSheet, ToCssResult, TransformOptions};
+
+/// Parses, minifies, and prints a style sheet in one step.
+///
+/// This is a shortcut for calling [StyleSheet::parse], [StyleSheet::minify], and [StyleSheet::to_css]
+/// in turn. Errors from any of the steps are returned as a [TransformError].
+pub fn transform<'i>(code: &'i str, options: TransformOptions) -> Result<ToCssResult, TransformError<'i>> {
+  let TransformOptions {
+    filename,
+    parser,
+    minify,
+    mut printer,
+  } = options;
+
+  if printer.targets.is_none() {
+    printer.targets = minify.targets;
+  }
+
+  let mut stylesheet = StyleSheet::parse(filename, code, parser)?;
+  stylesheet.minify(minify)?;
+  Ok(stylesheet.to_css(printer)?)
+}
+
 #[cfg(test)]
 mod tests {
   use crate::css_modules::{CssModuleExport, CssModuleExports, CssModuleReference};
-  use crate::dependencies::Dependency;
-  use crate::error::{Error, ErrorLocation, MinifyErrorKind, ParserError, PrinterErrorKind, SelectorError};
-  use crate::properties::custom::Token;
-  use crate::properties::Property;
+  use crate::dependencies::{Dependency, ImportLayer};
+  use crate::error::{
+    Error, ErrorLocation, MinifyErrorKind, MinifyWarningKind, ParserError, ParserWarningKind, PrinterError,
+    PrinterErrorKind, SelectorError,
+  };
+  use crate::printer::Printer;
+  use crate::properties::custom::{CSSWideKeyword, Token, WideKeywordProperty};
+  use crate::properties::{Property, PropertyId};
+  use crate::rules::custom_at_rule::{AtRuleHandler, CustomAtRule};
   use crate::rules::CssRule;
   use crate::rules::Location;
   use crate::stylesheet::*;
   use crate::targets::Browsers;
   use crate::traits::{Parse, ToCss};
   use crate::values::color::CssColor;
+  use crate::vendor_prefix::VendorPrefix;
   use indoc::indoc;
   use std::collections::HashMap;
+  use std::sync::{Arc, RwLock};
 
   fn test(source: &str, expected: &str) {
     let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
@@ -179,6 +212,20 @@ mod tests {
     }
   }
 
+  fn warning_test(source: &str, targets: Browsers, expected: Vec<MinifyWarningKind>) {
+    let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    stylesheet
+      .minify(MinifyOptions {
+        targets: Some(targets),
+        warnings: Some(warnings.clone()),
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let warnings: Vec<_> = warnings.read().unwrap().iter().map(|w| w.kind.clone()).collect();
+    assert_eq!(warnings, expected);
+  }
+
   macro_rules! map(
     { $($key:expr => $name:literal $(referenced: $referenced: literal)? $($value:literal $(global: $global: literal)? $(from $from:literal)?)*),* } => {
       {
@@ -2757,6 +2804,107 @@ mod tests {
 
   #[test]
   fn test_size() {
+    minify_test(".foo { width: fit-content(20px) }", ".foo{width:fit-content(20px)}");
+    minify_test(".foo { width: FIT-CONTENT }", ".foo{width:fit-content}");
+    minify_test(".foo { max-height: fit-content }", ".foo{max-height:fit-content}");
+    minify_test(
+      ".foo { max-height: fit-content(50%) }",
+      ".foo{max-height:fit-content(50%)}",
+    );
+    minify_test(".foo { min-height: stretch }", ".foo{min-height:stretch}");
+    minify_test(
+      ".foo { min-height: -webkit-fill-available }",
+      ".foo{min-height:-webkit-fill-available}",
+    );
+    minify_test(".foo { max-width: -moz-available }", ".foo{max-width:-moz-available}");
+    minify_test(
+      ".foo { height: -webkit-min-content }",
+      ".foo{height:-webkit-min-content}",
+    );
+    minify_test(".foo { height: -moz-fit-content }", ".foo{height:-moz-fit-content}");
+
+    prefix_test(
+      r#"
+      .foo {
+        width: stretch;
+        min-height: fit-content;
+        max-height: fit-content(20px);
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        width: -webkit-fill-available;
+        width: -moz-available;
+        width: stretch;
+        min-height: -moz-fit-content;
+        min-height: fit-content;
+        max-height: fit-content(20px);
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        firefox: Some(60 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        height: -webkit-fill-available;
+        height: -moz-available;
+        height: stretch;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        height: -webkit-fill-available;
+        height: stretch;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        min-block-size: stretch;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        min-height: -webkit-fill-available;
+        min-height: stretch;
+      }
+    "#},
+      Browsers {
+        safari: Some(12 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        width: stretch;
+        max-width: min-content;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        width: stretch;
+        max-width: min-content;
+      }
+    "#},
+      Browsers {
+        chrome: Some(110 << 16),
+        ..Browsers::default()
+      },
+    );
+
     prefix_test(
       r#"
       .foo {
@@ -2825,6 +2973,50 @@ mod tests {
         ..Browsers::default()
       },
     );
+
+    prefix_test(
+      r#"
+      .foo {
+        inline-size: auto;
+        max-inline-size: none;
+        max-block-size: 100px;
+        min-block-size: auto;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        width: auto;
+        max-width: none;
+        max-height: 100px;
+        min-height: auto;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        inline-size: auto;
+        max-inline-size: none;
+        max-block-size: 100px;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        inline-size: auto;
+        max-inline-size: none;
+        max-block-size: 100px;
+      }
+    "#},
+      Browsers {
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+    );
   }
 
   #[test]
@@ -2974,7 +3166,7 @@ mod tests {
     );
     minify_test(
       ".foo { background-position: left 10px center }",
-      ".foo{background-position:10px 50%}",
+      ".foo{background-position:10px}",
     );
     minify_test(
       ".foo { background-position: right 10px center }",
@@ -3000,6 +3192,26 @@ mod tests {
       ".foo { background-position: bottom right }",
       ".foo{background-position:100% 100%}",
     );
+    minify_test(
+      ".foo { background-position: right 10% bottom 20% }",
+      ".foo{background-position:90% 80%}",
+    );
+    minify_test(
+      ".foo { background-position: right 10% center }",
+      ".foo{background-position:90%}",
+    );
+    minify_test(
+      ".foo { background-position: right 0 top 0 }",
+      ".foo{background-position:100% 0}",
+    );
+    minify_test(
+      ".foo { background-position: right 10px bottom 20px }",
+      ".foo{background-position:right 10px bottom 20px}",
+    );
+    minify_test(
+      ".foo { background-position: center bottom 10px }",
+      ".foo{background-position:center bottom 10px}",
+    );
 
     minify_test(
       ".foo { background: url('img-sprite.png') no-repeat bottom right }",
@@ -3127,6 +3339,64 @@ mod tests {
       },
     );
 
+    prefix_test(
+      r#"
+      .foo {
+        background-clip: text;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-background-clip: text;
+        background-clip: text;
+      }
+    "#
+      },
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        background-clip: padding-box;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        background-clip: padding-box;
+      }
+    "#
+      },
+      Browsers {
+        safari: Some(8 << 16),
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -webkit-background-clip: text;
+        background-clip: text;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-background-clip: text;
+        background-clip: text;
+      }
+    "#
+      },
+      Browsers {
+        safari: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+
     minify_test(".foo { background: none center }", ".foo{background:50%}");
     minify_test(".foo { background: none }", ".foo{background:0 0}");
 
@@ -4657,6 +4927,20 @@ mod tests {
     minify_test("a:lang(en) {color:red}", "a:lang(en){color:red}");
     minify_test("a:lang(en, fr) {color:red}", "a:lang(en,fr){color:red}");
     minify_test("a:lang('en') {color:red}", "a:lang(en){color:red}");
+    minify_test("a:lang(en, 'fr') {color:red}", "a:lang(en,fr){color:red}");
+    minify_test("a:lang(de-DE) {color:red}", "a:lang(de-DE){color:red}");
+    minify_test("a:lang(\"*-CH\") {color:red}", "a:lang(\"*-CH\"){color:red}");
+    minify_test("a:lang(\\*-CH) {color:red}", "a:lang(\"*-CH\"){color:red}");
+    minify_test("a:lang(en, \"*-CH\") {color:red}", "a:lang(en,\"*-CH\"){color:red}");
+    minify_test("a:lang(\"\") {color:red}", "a:lang(\"\"){color:red}");
+    test(
+      "a:lang(en, \"*-CH\") {color:red}",
+      indoc! {r#"
+      a:lang(en, "*-CH") {
+        color: red;
+      }
+    "#},
+    );
     minify_test(
       "a:-webkit-any(.foo, .bar) {color:red}",
       "a:-webkit-any(.foo,.bar){color:red}",
@@ -5438,6 +5722,28 @@ mod tests {
     );
 
     minify_test(".foo { width: max(0px, 1vw) }", ".foo{width:max(0px,1vw)}");
+    minify_test(
+      ".foo { width: max(1px, max(2em, 3vw)) }",
+      ".foo{width:max(1px,2em,3vw)}",
+    );
+    minify_test(
+      ".foo { width: min(min(1px, 1em), 2vw) }",
+      ".foo{width:min(1px,1em,2vw)}",
+    );
+    minify_test(".foo { width: max(1px, max(1em, 2px)) }", ".foo{width:max(2px,1em)}");
+    minify_test(
+      ".foo { width: min(1px, max(1em, 2vw)) }",
+      ".foo{width:min(1px,max(1em,2vw))}",
+    );
+    minify_test(".foo { width: max(10px, 10px) }", ".foo{width:10px}");
+    minify_test(
+      ".foo { width: max(1em + 1px, 1em + 1px) }",
+      ".foo{width:calc(1em + 1px)}",
+    );
+    minify_test(
+      ".foo { width: min(1em + 1px, 2vw, min(1em + 1px, 3vw)) }",
+      ".foo{width:min(1em + 1px,2vw)}",
+    );
 
     prefix_test(
       ".foo { border-width: clamp(1em, 2px, 4vh) }",
@@ -5499,6 +5805,22 @@ mod tests {
       ".foo { box-shadow: 12px 12px 0px 0px rgba(0,0,0,0.4) }",
       ".foo{box-shadow:12px 12px #0006}",
     );
+    minify_test(
+      ".foo { box-shadow: 12px 12px 0em 0rem rgba(0,0,0,0.4) }",
+      ".foo{box-shadow:12px 12px #0006}",
+    );
+    minify_test(
+      ".foo { box-shadow: 12px 12px 0vw 2px red }",
+      ".foo{box-shadow:12px 12px 0 2px red}",
+    );
+    minify_test(
+      ".foo { box-shadow: rgba(0,0,0,0.4) 12px 12px 0px 8px inset }",
+      ".foo{box-shadow:inset 12px 12px 0 8px #0006}",
+    );
+    minify_test(
+      ".foo { box-shadow: inset red 12px 12px }",
+      ".foo{box-shadow:inset 12px 12px red}",
+    );
     minify_test(
       ".foo { box-shadow: 64px 64px 12px 40px rgba(0,0,0,0.4), 12px 12px 0px 8px rgba(0,0,0,0.4) inset }",
       ".foo{box-shadow:64px 64px 12px 40px #0006,inset 12px 12px 0 8px #0006}",
@@ -5533,6 +5855,20 @@ mod tests {
       },
     );
 
+    prefix_test(
+      ".foo { box-shadow: 12px 12px 0px 0px yellow }",
+      indoc! { r#"
+        .foo {
+          -webkit-box-shadow: 12px 12px #ff0;
+          box-shadow: 12px 12px #ff0;
+        }
+      "#},
+      Browsers {
+        chrome: Some(4 << 16),
+        ..Browsers::default()
+      },
+    );
+
     prefix_test(
       ".foo { box-shadow: 12px 12px lab(40% 56.6 39), 12px 12px yellow }",
       indoc! { r#"
@@ -7570,8 +7906,14 @@ mod tests {
     minify_test(".foo { scale: 1 0 1 }", ".foo{scale:1 0}");
     minify_test(".foo { scale: 1 0 0 }", ".foo{scale:1 0 0}");
 
-    minify_test(".foo { transform: scale(3); scale: 0.5 }", ".foo{transform:scale(1.5)}");
-    minify_test(".foo { scale: 0.5; transform: scale(3); }", ".foo{transform:scale(3)}");
+    minify_test(
+      ".foo { transform: scale(3); scale: 0.5 }",
+      ".foo{transform:scale(3);scale:.5}",
+    );
+    minify_test(
+      ".foo { scale: 0.5; transform: scale(3); }",
+      ".foo{transform:scale(3);scale:.5}",
+    );
 
     prefix_test(
       r#"
@@ -8774,6 +9116,46 @@ mod tests {
       }
     "#},
     );
+    minify_test(
+      "@supports selector(:has(a)) { .test { color: red } }",
+      "@supports selector(:has(a)){.test{color:red}}",
+    );
+    minify_test(
+      "@supports selector(:has(a)) and (display: grid) { .test { color: red } }",
+      "@supports selector(:has(a)) and (display: grid){.test{color:red}}",
+    );
+    minify_test(
+      "@supports (display: grid) or selector(a > b) { .test { color: red } }",
+      "@supports (display: grid) or selector(a > b){.test{color:red}}",
+    );
+    minify_test(
+      "@supports font-tech(color-COLRv1) { .test { color: red } }",
+      "@supports font-tech(color-COLRv1){.test{color:red}}",
+    );
+    minify_test(
+      "@supports not font-format(woff2) { .test { color: red } }",
+      "@supports not font-format(woff2){.test{color:red}}",
+    );
+    minify_test(
+      "@supports font-tech(variations) and font-format(woff2) { .test { color: red } }",
+      "@supports font-tech(variations) and font-format(woff2){.test{color:red}}",
+    );
+    minify_test(
+      "@supports font-tech(\"invalid\") { .test { color: red } }",
+      "@supports font-tech(\"invalid\"){.test{color:red}}",
+    );
+
+    use crate::rules::supports::SupportsCondition;
+    use cssparser::{Parser, ParserInput};
+    let mut input = ParserInput::new("font-tech(color-COLRv1) or selector(:focus-visible)");
+    let mut parser = Parser::new(&mut input);
+    assert_eq!(
+      SupportsCondition::parse(&mut parser).unwrap(),
+      SupportsCondition::Or(vec![
+        SupportsCondition::FontTechnology("color-COLRv1".into()),
+        SupportsCondition::Selector(":focus-visible".into()),
+      ])
+    );
   }
 
   #[test]
@@ -9285,29 +9667,156 @@ mod tests {
     minify_test(".foo { word-break: keep-all }", ".foo{word-break:keep-all}");
     minify_test(".foo { word-break: break-all }", ".foo{word-break:break-all}");
     minify_test(".foo { word-break: break-word }", ".foo{word-break:break-word}");
-  }
-
-  #[test]
-  fn test_line_break() {
-    minify_test(".foo { line-break: auto }", ".foo{line-break:auto}");
-    minify_test(".foo { line-break: Loose }", ".foo{line-break:loose}");
-    minify_test(".foo { line-break: anywhere }", ".foo{line-break:anywhere}");
-  }
-
-  #[test]
-  fn test_wrap() {
-    minify_test(".foo { overflow-wrap: nOrmal }", ".foo{overflow-wrap:normal}");
-    minify_test(".foo { overflow-wrap: break-Word }", ".foo{overflow-wrap:break-word}");
-    minify_test(".foo { overflow-wrap: Anywhere }", ".foo{overflow-wrap:anywhere}");
-    minify_test(".foo { word-wrap: Normal }", ".foo{word-wrap:normal}");
-    minify_test(".foo { word-wrap: Break-wOrd }", ".foo{word-wrap:break-word}");
-    minify_test(".foo { word-wrap: Anywhere }", ".foo{word-wrap:anywhere}");
-  }
 
-  #[test]
-  fn test_hyphens() {
-    minify_test(".foo { hyphens: manual }", ".foo{hyphens:manual}");
-    minify_test(".foo { hyphens: auto }", ".foo{hyphens:auto}");
+    prefix_test(
+      ".foo { word-break: break-word }",
+      indoc! {r#"
+        .foo {
+          word-break: normal;
+          overflow-wrap: anywhere;
+        }
+      "#},
+      Browsers {
+        firefox: Some(65 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-break: break-word; overflow-wrap: normal }",
+      indoc! {r#"
+        .foo {
+          word-break: normal;
+          overflow-wrap: anywhere;
+        }
+      "#},
+      Browsers {
+        firefox: Some(65 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-break: break-word }",
+      indoc! {r#"
+        .foo {
+          word-break: break-word;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-break: break-word }",
+      indoc! {r#"
+        .foo {
+          word-break: break-word;
+        }
+      "#},
+      Browsers {
+        firefox: Some(60 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_line_break() {
+    minify_test(".foo { line-break: auto }", ".foo{line-break:auto}");
+    minify_test(".foo { line-break: Loose }", ".foo{line-break:loose}");
+    minify_test(".foo { line-break: anywhere }", ".foo{line-break:anywhere}");
+  }
+
+  #[test]
+  fn test_wrap() {
+    minify_test(".foo { overflow-wrap: nOrmal }", ".foo{overflow-wrap:normal}");
+    minify_test(".foo { overflow-wrap: break-Word }", ".foo{overflow-wrap:break-word}");
+    minify_test(".foo { overflow-wrap: Anywhere }", ".foo{overflow-wrap:anywhere}");
+    minify_test(".foo { word-wrap: Normal }", ".foo{word-wrap:normal}");
+    minify_test(".foo { word-wrap: Break-wOrd }", ".foo{word-wrap:break-word}");
+    minify_test(".foo { word-wrap: Anywhere }", ".foo{word-wrap:anywhere}");
+    minify_test(
+      ".foo { word-wrap: break-word; overflow-wrap: break-word }",
+      ".foo{word-wrap:break-word;overflow-wrap:break-word}",
+    );
+    minify_test(
+      ".foo { overflow-wrap: normal; overflow-wrap: anywhere }",
+      ".foo{overflow-wrap:anywhere}",
+    );
+    minify_test(
+      ".foo { word-wrap: break-word; overflow-wrap: anywhere }",
+      ".foo{word-wrap:break-word;overflow-wrap:anywhere}",
+    );
+
+    prefix_test(
+      ".foo { overflow-wrap: break-word }",
+      indoc! {r#"
+        .foo {
+          word-wrap: break-word;
+          overflow-wrap: break-word;
+        }
+      "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-wrap: break-word }",
+      indoc! {r#"
+        .foo {
+          word-wrap: break-word;
+          overflow-wrap: break-word;
+        }
+      "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-wrap: break-word; overflow-wrap: break-word }",
+      indoc! {r#"
+        .foo {
+          overflow-wrap: break-word;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-wrap: break-word }",
+      indoc! {r#"
+        .foo {
+          overflow-wrap: break-word;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { word-wrap: break-word; overflow-wrap: anywhere }",
+      indoc! {r#"
+        .foo {
+          overflow-wrap: break-word;
+          overflow-wrap: anywhere;
+        }
+      "#},
+      Browsers {
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_hyphens() {
+    minify_test(".foo { hyphens: manual }", ".foo{hyphens:manual}");
+    minify_test(".foo { hyphens: auto }", ".foo{hyphens:auto}");
     minify_test(".foo { hyphens: none }", ".foo{hyphens:none}");
     minify_test(".foo { -webkit-hyphens: manual }", ".foo{-webkit-hyphens:manual}");
     minify_test(".foo { -moz-hyphens: manual }", ".foo{-moz-hyphens:manual}");
@@ -10347,6 +10856,14 @@ mod tests {
       ".foo { text-shadow: 1px 1px yellow; }",
       ".foo{text-shadow:1px 1px #ff0}",
     );
+    minify_test(
+      ".foo { text-shadow: 1px 1px 0em 0px yellow; }",
+      ".foo{text-shadow:1px 1px #ff0}",
+    );
+    minify_test(
+      ".foo { text-shadow: yellow 1px 1px 2px; }",
+      ".foo{text-shadow:1px 1px 2px #ff0}",
+    );
     minify_test(
       ".foo { text-shadow: 1px 1px yellow, 2px 3px red; }",
       ".foo{text-shadow:1px 1px #ff0,2px 3px red}",
@@ -10912,6 +11429,61 @@ mod tests {
       },
     );
 
+    prefix_test(
+      r#"
+      .foo {
+        user-select: contain;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -ms-user-select: element;
+        user-select: contain;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        firefox: Some(10 << 16),
+        ie: Some(10 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -ms-user-select: element;
+        user-select: contain;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        user-select: contain;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    minify_test(
+      ".foo { -ms-user-select: element; user-select: contain }",
+      ".foo{-ms-user-select:element;user-select:contain}",
+    );
+    minify_test(".foo { user-select: element }", ".foo{user-select:element}");
+    assert!(matches!(
+      Property::parse_string("user-select", "element", ParserOptions::default()),
+      Ok(Property::Unparsed(_))
+    ));
+    assert!(matches!(
+      Property::parse_string("-ms-user-select", "element", ParserOptions::default()),
+      Ok(Property::UserSelect(
+        crate::properties::ui::UserSelect::Element,
+        VendorPrefix::Ms
+      ))
+    ));
+
     prefix_test(
       r#"
       .foo {
@@ -11027,6 +11599,46 @@ mod tests {
       },
     );
 
+    prefix_test(
+      r#"
+      .foo {
+        appearance: none;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-appearance: none;
+        -moz-appearance: none;
+        appearance: none;
+      }
+    "#},
+      Browsers {
+        safari: Some(14 << 16),
+        firefox: Some(70 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        appearance: none;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-appearance: none;
+        appearance: none;
+      }
+    "#},
+      Browsers {
+        chrome: Some(80 << 16),
+        safari: Some(14 << 16),
+        firefox: Some(85 << 16),
+        ..Browsers::default()
+      },
+    );
+
     prefix_test(
       ".foo { caret-color: lch(50.998% 135.363 338) }",
       indoc! { r#"
@@ -14107,6 +14719,26 @@ mod tests {
     minify_test(".foo { --test: foo  ; }", ".foo{--test:foo}");
     minify_test(".foo { --test:foo; }", ".foo{--test:foo}");
     minify_test(".foo { --test:foo ; }", ".foo{--test:foo}");
+    minify_test(".foo { --test: foo !important }", ".foo{--test:foo!important}");
+    minify_test(".foo { --test: foo!important }", ".foo{--test:foo!important}");
+    test(
+      ".foo { --test: 1 !important }",
+      indoc! {r#"
+      .foo {
+        --test: 1 !important;
+      }
+      "#},
+    );
+    attr_test("--test: 1 !important", "--test:1!important", true, None);
+    minify_test(".foo { --test: 1; --test: 2 }", ".foo{--test:2}");
+    minify_test(".foo { --test: 1; --other: 2; --test: 3 }", ".foo{--other:2;--test:3}");
+    minify_test(
+      ".foo { --test: 1 !important; --test: 2 !important }",
+      ".foo{--test:2!important}",
+    );
+    minify_test(".foo { --test: 1 !important; --test: 2 }", ".foo{--test:1!important}");
+    minify_test(".foo { --test: 1; --test: 2 !important }", ".foo{--test:2!important}");
+    minify_test(".foo { --test: 1; --Test: 2 }", ".foo{--test:1;--Test:2}");
     minify_test(".foo { --test: var(--foo, 20px); }", ".foo{--test:var(--foo,20px)}");
     minify_test(
       ".foo { transition: var(--foo, 20px),\nvar(--bar, 40px); }",
@@ -16153,6 +16785,8 @@ mod tests {
     minify_test(".foo { fill: none; }", ".foo{fill:none}");
     minify_test(".foo { fill: context-fill; }", ".foo{fill:context-fill}");
     minify_test(".foo { fill: context-stroke; }", ".foo{fill:context-stroke}");
+    minify_test(".foo { fill: url(#grad) rgb(255, 0, 0); }", ".foo{fill:url(#grad) red}");
+    minify_test(".foo { fill: URL(#grad) }", ".foo{fill:url(#grad)}");
 
     minify_test(".foo { stroke: yellow; }", ".foo{stroke:#ff0}");
     minify_test(".foo { stroke: url(#foo); }", ".foo{stroke:url(#foo)}");
@@ -16890,6 +17524,35 @@ mod tests {
       ".foo { filter: contrast(175%) brightness(3%); }",
       ".foo{filter:contrast(175%)brightness(3%)}",
     );
+    minify_test(
+      ".foo { filter: blur(5px) brightness(1) opacity(100%) grayscale(0) hue-rotate(0deg); }",
+      ".foo{filter:blur(5px)}",
+    );
+    minify_test(
+      ".foo { filter: brightness(1) drop-shadow(16px 16px yellow) blur(0) sepia(50%); }",
+      ".foo{filter:brightness()drop-shadow(16px 16px #ff0)blur()sepia(50%)}",
+    );
+    minify_test(
+      ".foo { filter: url(filters.svg#a) invert(0) url(filters.svg#b) grayscale(0); }",
+      ".foo{filter:url(filters.svg#a)invert(0)url(filters.svg#b)}",
+    );
+    minify_test(".foo { filter: opacity(1) saturate(100%); }", ".foo{filter:opacity()}");
+    minify_test(
+      ".foo { backdrop-filter: contrast(1) blur(2px); }",
+      ".foo{backdrop-filter:contrast()blur(2px)}",
+    );
+    minify_test(
+      ".foo { backdrop-filter: blur(2px) contrast(1); }",
+      ".foo{backdrop-filter:blur(2px)}",
+    );
+    test(
+      ".foo { filter: blur(5px) brightness(1); }",
+      indoc! {r#"
+      .foo {
+        filter: blur(5px);
+      }
+    "#},
+    );
 
     prefix_test(
       ".foo { filter: blur(5px) }",
@@ -17038,39 +17701,136 @@ mod tests {
     }"#,
       "@-ms-viewport{width:device-width}",
     );
-  }
-
-  #[test]
-  fn test_custom_media() {
-    custom_media_test(
+    minify_test(
       r#"
-      @custom-media --modern (color), (hover);
-
-      @media (--modern) and (width > 1024px) {
-        .a {
-          color: green;
-        }
-      }
-      "#,
+    @-o-viewport {
+      width: device-width;
+    }"#,
+      "@-o-viewport{width:device-width}",
+    );
+    test(
+      "@viewport { width: device-width; zoom: 1 }",
       indoc! {r#"
-      @media ((color) or (hover)) and (width > 1024px) {
-        .a {
-          color: green;
-        }
+      @viewport {
+        width: device-width;
+        zoom: 1;
       }
-      "#},
+    "#},
     );
 
-    custom_media_test(
-      r#"
-      @custom-media --color (color);
-
-      @media (--color) and (width > 1024px) {
-        .a {
-          color: green;
-        }
-      }
-      "#,
+    let source = r#"
+      @viewport { width: device-width }
+      @-ms-viewport { width: device-width }
+      @-o-viewport { width: device-width }
+      .foo { color: red }
+    "#;
+    prefix_test(
+      source,
+      indoc! {r#"
+      .foo {
+        color: red;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      source,
+      indoc! {r#"
+      @viewport {
+        width: device-width;
+      }
+
+      @-ms-viewport {
+        width: device-width;
+      }
+
+      .foo {
+        color: red;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      source,
+      indoc! {r#"
+      @viewport {
+        width: device-width;
+      }
+
+      @-ms-viewport {
+        width: device-width;
+      }
+
+      .foo {
+        color: red;
+      }
+    "#},
+      Browsers {
+        edge: Some(18 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      source,
+      indoc! {r#"
+      @viewport {
+        width: device-width;
+      }
+
+      @-o-viewport {
+        width: device-width;
+      }
+
+      .foo {
+        color: red;
+      }
+    "#},
+      Browsers {
+        opera: Some(12 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_custom_media() {
+    custom_media_test(
+      r#"
+      @custom-media --modern (color), (hover);
+
+      @media (--modern) and (width > 1024px) {
+        .a {
+          color: green;
+        }
+      }
+      "#,
+      indoc! {r#"
+      @media ((color) or (hover)) and (width > 1024px) {
+        .a {
+          color: green;
+        }
+      }
+      "#},
+    );
+
+    custom_media_test(
+      r#"
+      @custom-media --color (color);
+
+      @media (--color) and (width > 1024px) {
+        .a {
+          color: green;
+        }
+      }
+      "#,
       indoc! {r#"
       @media (color) and (width > 1024px) {
         .a {
@@ -17414,6 +18174,7 @@ mod tests {
             source_index: 0,
             line: 1,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17440,6 +18201,7 @@ mod tests {
             source_index: 0,
             line: 1,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17463,6 +18225,7 @@ mod tests {
             source_index: 0,
             line: 2,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17486,6 +18249,7 @@ mod tests {
             source_index: 0,
             line: 2,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17509,6 +18273,7 @@ mod tests {
             source_index: 0,
             line: 1,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17536,6 +18301,7 @@ mod tests {
             source_index: 0,
             line: 2,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17562,6 +18328,7 @@ mod tests {
             source_index: 0,
             line: 1,
             column: 7,
+            generated: false,
           },
         },
         loc: Some(ErrorLocation {
@@ -17718,6 +18485,23 @@ mod tests {
       ".foo{behavior:url(\"Zn9-2q\")}",
       vec![("#foo", "Zn9-2q")],
     );
+
+    dep_test(".foo { fill: url(#grad) }", ".foo{fill:url(#grad)}", vec![]);
+    dep_test(
+      ".foo { stroke: url(#grad) red; marker-end: url(#arrow); clip-path: url(#clip) }",
+      ".foo{stroke:url(#grad) red;marker-end:url(#arrow);clip-path:url(#clip)}",
+      vec![],
+    );
+    dep_test(
+      ".foo { filter: url(#blur); mask: url(#mask) }",
+      ".foo{filter:url(#blur);mask:url(#mask)}",
+      vec![],
+    );
+    dep_test(
+      ".foo { fill: url(paint.svg#grad) }",
+      ".foo{fill:url(\"_3bNjxq\")}",
+      vec![("paint.svg#grad", "_3bNjxq")],
+    );
   }
 
   #[test]
@@ -18019,5 +18803,5919 @@ mod tests {
     "#,
       "@property --property-name{syntax:\"<color>+\";inherits:false;initial-value:#ff0 #00f}",
     );
+
+    minify_test(
+      r#"
+      @property --property-name {
+        syntax: '<length-percentage>';
+        inherits: false;
+        initial-value: calc(10% + 5px);
+      }
+    "#,
+      "@property --property-name{syntax:\"<length-percentage>\";inherits:false;initial-value:calc(10% + 5px)}",
+    );
+
+    minify_test(
+      r#"
+      @property --property-name {
+        syntax: '<length>+';
+        inherits: false;
+        initial-value: 1in 10px;
+      }
+    "#,
+      "@property --property-name{syntax:\"<length>+\";inherits:false;initial-value:1in 10px}",
+    );
+
+    error_test(
+      r#"
+      @property --property-name {
+        syntax: '<length>';
+        inherits: false;
+        initial-value: 1em;
+      }
+    "#,
+      ParserError::ComputationallyDependentInitialValue,
+    );
+
+    error_test(
+      r#"
+      @property --property-name {
+        syntax: '<length>';
+        inherits: false;
+        initial-value: calc(10px + 2vw);
+      }
+    "#,
+      ParserError::ComputationallyDependentInitialValue,
+    );
+
+    error_test(
+      r#"
+      @property --property-name {
+        syntax: '<length-percentage>#';
+        inherits: false;
+        initial-value: 10px, 50%, 2rem;
+      }
+    "#,
+      ParserError::ComputationallyDependentInitialValue,
+    );
+
+    error_test(
+      r#"
+      @property --property-name {
+        syntax: '<length>';
+        inherits: false;
+        initial-value: 10px 20px;
+      }
+    "#,
+      ParserError::UnexpectedToken(crate::properties::custom::Token::Dimension {
+        has_sign: false,
+        value: 20.0,
+        int_value: Some(20),
+        unit: "px".into(),
+      }),
+    );
+
+    error_test(
+      r#"
+      @property --property-name {
+        syntax: '<color> | none';
+        inherits: false;
+        initial-value: none red;
+      }
+    "#,
+      ParserError::UnexpectedToken(crate::properties::custom::Token::Ident("red".into())),
+    );
+
+    fn error_location(source: &str) -> (u32, u32) {
+      match StyleSheet::parse("test.css".into(), source, ParserOptions::default()) {
+        Ok(_) => unreachable!(),
+        Err(e) => {
+          let loc = e.loc.unwrap();
+          (loc.line, loc.column)
+        }
+      }
+    }
+
+    assert_eq!(
+      error_location("@property --foo { syntax: '<length>'; inherits: false; initial-value: 1em }"),
+      (0, 71)
+    );
+    assert_eq!(
+      error_location(
+        "@property --foo {\n  syntax: '<length>';\n  inherits: false;\n  initial-value: 10px 20px;\n}"
+      ),
+      (3, 23)
+    );
+  }
+
+  #[test]
+  fn test_reduced_motion() {
+    fn reduced_motion_test(source: &str, expected: &str, mode: ReducedMotion) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          reduced_motion: Some(mode),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    reduced_motion_test(
+      ".foo { animation: foo 1s; color: red }",
+      ".foo{color:red;animation:foo 1s}@media (prefers-reduced-motion:reduce){.foo{animation:none}}",
+      ReducedMotion::Reset,
+    );
+    reduced_motion_test(
+      ".foo { transition: opacity .2s !important }",
+      ".foo{transition:opacity .2s!important}@media (prefers-reduced-motion:reduce){.foo{transition-duration:0s!important;transition-delay:0s!important}}",
+      ReducedMotion::Reset,
+    );
+    reduced_motion_test(
+      ".foo { -webkit-animation-name: foo; animation-name: foo; animation-duration: 1s }",
+      ".foo{-webkit-animation-name:foo;animation-name:foo;animation-duration:1s}@media (prefers-reduced-motion:reduce){.foo{-webkit-animation:none;animation:none}}",
+      ReducedMotion::Reset,
+    );
+    reduced_motion_test(".foo { color: red }", ".foo{color:red}", ReducedMotion::Reset);
+    reduced_motion_test(
+      ".foo { transform: rotate(10deg); animation-timeline: scroll() }",
+      ".foo{transform:rotate(10deg);animation-timeline:scroll()}",
+      ReducedMotion::Reset,
+    );
+    reduced_motion_test(
+      "@media (prefers-reduced-motion: no-preference) { .foo { animation: foo 1s } }",
+      "@media (prefers-reduced-motion:no-preference){.foo{animation:foo 1s}}",
+      ReducedMotion::Reset,
+    );
+    reduced_motion_test(
+      ".foo { color: red; transition: opacity .2s }",
+      ".foo{color:red}@media (prefers-reduced-motion:no-preference){.foo{transition:opacity .2s}}",
+      ReducedMotion::Guard,
+    );
+    reduced_motion_test(
+      ".foo { animation: foo 1s }",
+      "@media (prefers-reduced-motion:no-preference){.foo{animation:foo 1s}}",
+      ReducedMotion::Guard,
+    );
+    reduced_motion_test(
+      "@media (prefers-reduced-motion: no-preference) { .foo { animation: foo 1s } }",
+      "@media (prefers-reduced-motion:no-preference){.foo{animation:foo 1s}}",
+      ReducedMotion::Guard,
+    );
+  }
+
+  #[test]
+  fn test_property_id_longhands() {
+    assert_eq!(
+      PropertyId::Margin.longhands(),
+      &[
+        PropertyId::MarginTop,
+        PropertyId::MarginRight,
+        PropertyId::MarginBottom,
+        PropertyId::MarginLeft
+      ]
+    );
+    assert_eq!(
+      PropertyId::Transition(VendorPrefix::WebKit).longhands(),
+      &[
+        PropertyId::TransitionProperty(VendorPrefix::None),
+        PropertyId::TransitionDuration(VendorPrefix::None),
+        PropertyId::TransitionTimingFunction(VendorPrefix::None),
+        PropertyId::TransitionDelay(VendorPrefix::None)
+      ]
+    );
+    assert_eq!(
+      PropertyId::MarginInlineStart.longhands(),
+      &[PropertyId::MarginLeft, PropertyId::MarginRight]
+    );
+    assert_eq!(
+      PropertyId::OverscrollBehaviorInline.longhands(),
+      &[PropertyId::OverscrollBehaviorX, PropertyId::OverscrollBehaviorY]
+    );
+    assert!(PropertyId::MarginTop.longhands().is_empty());
+    assert!(PropertyId::All.longhands().is_empty());
+    assert!(PropertyId::Custom("foo".into()).longhands().is_empty());
+
+    let all: Vec<_> = PropertyId::all().collect();
+    assert!(all.contains(&PropertyId::Margin));
+    assert!(all.contains(&PropertyId::Transition(VendorPrefix::None)));
+    assert!(all.contains(&PropertyId::BoxOrient(VendorPrefix::WebKit | VendorPrefix::Moz)));
+    assert!(all.contains(&PropertyId::All));
+
+    // Longhands are themselves known properties.
+    for id in &all {
+      for longhand in id.longhands() {
+        assert!(all.iter().any(|p| p.name() == longhand.name()), "{:?}", longhand);
+      }
+    }
+  }
+
+  #[test]
+  fn test_px_to_rem() {
+    fn px_to_rem_test(source: &str, expected: &str, options: PxToRemOptions) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          px_to_rem: Some(options),
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    px_to_rem_test(
+      ".foo { width: 32px; height: calc(100% - 8px) }",
+      ".foo{width:2rem;height:calc(100% - .5rem)}",
+      PxToRemOptions::default(),
+    );
+    px_to_rem_test(
+      ".foo { margin: 0px 10px; border: 1px solid red }",
+      ".foo{border:1px solid red;margin:0 .625rem}",
+      PxToRemOptions::default(),
+    );
+    px_to_rem_test(
+      ".foo { border-width: 1px; top: 0.5px }",
+      ".foo{border-width:.0625rem;top:.03125rem}",
+      PxToRemOptions {
+        keep_hairlines: false,
+        ..PxToRemOptions::default()
+      },
+    );
+    px_to_rem_test(
+      ".foo { width: 10px }",
+      ".foo{width:.67rem}",
+      PxToRemOptions {
+        root_font_size: 15.0,
+        precision: 2,
+        ..PxToRemOptions::default()
+      },
+    );
+    px_to_rem_test(
+      ".foo { width: 32px; font-size: 32px }",
+      ".foo{width:2rem;font-size:32px}",
+      PxToRemOptions {
+        exclude: vec!["font-size".into()],
+        ..PxToRemOptions::default()
+      },
+    );
+    px_to_rem_test(
+      "@media (min-width: 320px) { .foo { --foo: 32px; width: 32px } }",
+      "@media (min-width:320px){.foo{--foo:32px;width:2rem}}",
+      PxToRemOptions::default(),
+    );
+
+    fn px_to_rem_error_test(options: PxToRemOptions, error: PrinterErrorKind) {
+      let stylesheet =
+        StyleSheet::parse("test.css".into(), ".foo { width: 32px }", ParserOptions::default()).unwrap();
+      let res = stylesheet.to_css(PrinterOptions {
+        px_to_rem: Some(options),
+        ..PrinterOptions::default()
+      });
+      assert_eq!(res.unwrap_err().kind, error);
+    }
+
+    px_to_rem_error_test(
+      PxToRemOptions {
+        root_font_size: 0.0,
+        ..PxToRemOptions::default()
+      },
+      PrinterErrorKind::InvalidPxToRemRootFontSize,
+    );
+    px_to_rem_error_test(
+      PxToRemOptions {
+        root_font_size: -16.0,
+        ..PxToRemOptions::default()
+      },
+      PrinterErrorKind::InvalidPxToRemRootFontSize,
+    );
+    px_to_rem_error_test(
+      PxToRemOptions {
+        root_font_size: f32::NAN,
+        ..PxToRemOptions::default()
+      },
+      PrinterErrorKind::InvalidPxToRemRootFontSize,
+    );
+    px_to_rem_error_test(
+      PxToRemOptions {
+        precision: 11,
+        ..PxToRemOptions::default()
+      },
+      PrinterErrorKind::InvalidPxToRemPrecision { max: 10 },
+    );
+    px_to_rem_test(
+      ".foo { width: 10px }",
+      ".foo{width:.625rem}",
+      PxToRemOptions {
+        precision: 10,
+        ..PxToRemOptions::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_nth_child_of() {
+    minify_test(".foo:nth-child(2n + 1) {color: red}", ".foo:nth-child(odd){color:red}");
+    minify_test(".foo:nth-child(even) {color: red}", ".foo:nth-child(2n){color:red}");
+    minify_test(
+      ".foo:nth-child(2n+1 of .bar, .baz) {color: red}",
+      ".foo:nth-child(odd of .bar,.baz){color:red}",
+    );
+    minify_test(
+      ".foo:nth-child(even of :not(.hidden)) {color: red}",
+      ".foo:nth-child(2n of :not(.hidden)){color:red}",
+    );
+    minify_test(
+      ".foo:nth-last-child(-n + 3 of li.important) {color: red}",
+      ".foo:nth-last-child(-n+3 of li.important){color:red}",
+    );
+    test(
+      ".foo:nth-child(3n of .bar > .baz) {color: red}",
+      indoc! {r#"
+      .foo:nth-child(3n of .bar > .baz) {
+        color: red;
+      }
+    "#},
+    );
+
+    warning_test(
+      ".foo:nth-child(odd of .bar) {color: red}",
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+      vec![MinifyWarningKind::UnsupportedSelector {
+        selector: ".foo:nth-child(odd of .bar)".into(),
+      }],
+    );
+    warning_test(
+      ".foo:nth-child(odd of .bar) {color: red}",
+      Browsers {
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+      vec![],
+    );
+  }
+
+  #[test]
+  fn test_nth_normalization() {
+    minify_test(":nth-child(2n+1) {color: red}", ":nth-child(odd){color:red}");
+    minify_test(":nth-child(2n-1) {color: red}", ":nth-child(odd){color:red}");
+    minify_test(":nth-child(odd) {color: red}", ":nth-child(odd){color:red}");
+    minify_test(":nth-child(even) {color: red}", ":nth-child(2n){color:red}");
+    minify_test(":nth-child(2n+0) {color: red}", ":nth-child(2n){color:red}");
+    minify_test(":nth-child(0n+3) {color: red}", ":nth-child(3){color:red}");
+    minify_test(":nth-child(1n+0) {color: red}", ":nth-child(n){color:red}");
+    minify_test(":nth-child(n-2) {color: red}", ":nth-child(n){color:red}");
+    minify_test(":nth-child(3n-4) {color: red}", ":nth-child(3n+2){color:red}");
+    minify_test(":nth-child(3n-3) {color: red}", ":nth-child(3n){color:red}");
+    minify_test(":nth-child(n+5) {color: red}", ":nth-child(n+5){color:red}");
+    minify_test(":nth-child(-n+3) {color: red}", ":nth-child(-n+3){color:red}");
+    minify_test(":nth-child(-2n+3) {color: red}", ":nth-child(-2n+3){color:red}");
+    minify_test(":nth-child(-n-3) {color: red}", ":nth-child(-n-3){color:red}");
+    minify_test(":nth-last-child(2n+1) {color: red}", ":nth-last-child(odd){color:red}");
+    minify_test(":nth-of-type(1n+0) {color: red}", ":nth-of-type(n){color:red}");
+    minify_test(
+      ":nth-last-of-type(0n+1) {color: red}",
+      ":nth-last-of-type(1){color:red}",
+    );
+  }
+
+  #[test]
+  fn test_import_layer_dependencies() {
+    fn import_layer_test(source: &str, expected: Vec<(&str, Option<ImportLayer>)>) {
+      let stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          analyze_dependencies: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      let dependencies = res.dependencies.unwrap();
+      assert_eq!(dependencies.len(), expected.len());
+      for (dep, (url, layer)) in dependencies.into_iter().zip(expected) {
+        match dep {
+          Dependency::Import(dep) => {
+            assert_eq!(dep.url, url);
+            assert_eq!(dep.layer, layer);
+          }
+          _ => unreachable!(),
+        }
+      }
+    }
+
+    import_layer_test(
+      r#"
+      @import "a.css";
+      @import "b.css" layer;
+      @import "c.css" layer(foo.bar);
+      @import "d.css" layer(foo) supports(display: grid) screen;
+    "#,
+      vec![
+        ("a.css", None),
+        ("b.css", Some(ImportLayer::Anonymous)),
+        ("c.css", Some(ImportLayer::Named { name: "foo.bar".into() })),
+        ("d.css", Some(ImportLayer::Named { name: "foo".into() })),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_wide_keywords() {
+    minify_test(".foo { color: revert-layer }", ".foo{color:revert-layer}");
+    minify_test(".foo { color: revert }", ".foo{color:revert}");
+    minify_test(".foo { -webkit-box-shadow: revert }", ".foo{-webkit-box-shadow:revert}");
+    minify_test(
+      ".foo { margin: 10px; margin-top: revert }",
+      ".foo{margin:10px;margin-top:revert}",
+    );
+    minify_test(
+      ".foo { margin-top: revert; margin: 10px }",
+      ".foo{margin-top:revert;margin:10px}",
+    );
+    minify_test(".foo { all: revert }", ".foo{all:revert}");
+    minify_test(".foo { all: initial }", ".foo{all:initial}");
+    minify_test(".foo { color: red; all: revert }", ".foo{all:revert}");
+    minify_test(".foo { all: revert; color: red }", ".foo{all:revert;color:red}");
+    minify_test(
+      ".foo { --foo: red; color: red; all: revert-layer }",
+      ".foo{--foo:red;all:revert-layer}",
+    );
+    minify_test(
+      ".foo { color: red !important; all: unset }",
+      ".foo{all:unset;color:red!important}",
+    );
+
+    assert_eq!(
+      Property::parse_string("color", "revert-layer", ParserOptions::default()).unwrap(),
+      Property::WideKeyword(WideKeywordProperty {
+        property_id: PropertyId::Color,
+        keyword: CSSWideKeyword::RevertLayer,
+      })
+    );
+
+    warning_test(
+      ".foo { color: revert-layer }",
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+      vec![MinifyWarningKind::UnsupportedValue {
+        property: "color".into(),
+        value: "revert-layer".into(),
+      }],
+    );
+    warning_test(
+      ".foo { color: revert-layer }",
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+      vec![],
+    );
+    warning_test(
+      ".foo { all: revert }",
+      Browsers {
+        firefox: Some(60 << 16),
+        ..Browsers::default()
+      },
+      vec![MinifyWarningKind::UnsupportedValue {
+        property: "all".into(),
+        value: "revert".into(),
+      }],
+    );
+  }
+
+  #[test]
+  fn test_all_property() {
+    minify_test(".foo { all: unset; color: red }", ".foo{all:unset;color:red}");
+    test(
+      ".foo { all: unset; color: red }",
+      indoc! {r#"
+      .foo {
+        all: unset;
+        color: red;
+      }
+    "#},
+    );
+    minify_test(
+      ".foo { all: inherit; margin: 10px; padding: 0 }",
+      ".foo{all:inherit;margin:10px;padding:0}",
+    );
+    minify_test(".foo { color: red; all: initial }", ".foo{all:initial}");
+    minify_test(".foo { all: unset; all: revert }", ".foo{all:revert}");
+    minify_test(
+      ".foo { all: unset !important; color: red }",
+      ".foo{color:red;all:unset!important}",
+    );
+    minify_test(
+      ".foo { color: red !important; all: unset; color: blue }",
+      ".foo{all:unset;color:red!important}",
+    );
+    minify_test(".foo { all: var(--foo) }", ".foo{all:var(--foo)}");
+
+    assert_eq!(
+      Property::parse_string("all", "unset", ParserOptions::default()).unwrap(),
+      Property::All(CSSWideKeyword::Unset)
+    );
+    assert_eq!(
+      Property::parse_string("all", "REVERT-LAYER", ParserOptions::default()).unwrap(),
+      Property::All(CSSWideKeyword::RevertLayer)
+    );
+  }
+
+  #[derive(Debug)]
+  struct ApplyHandler;
+
+  impl AtRuleHandler for ApplyHandler {}
+
+  #[derive(Debug)]
+  struct TailwindHandler;
+
+  impl AtRuleHandler for TailwindHandler {
+    fn to_css<'a>(
+      &self,
+      rule: &CustomAtRule,
+      dest: &mut Printer<'a, dyn std::fmt::Write + 'a>,
+    ) -> Result<bool, PrinterError> {
+      dest.write_str("/*")?;
+      dest.whitespace()?;
+      dest.write_char('@')?;
+      dest.write_str(&rule.name)?;
+      dest.whitespace()?;
+      dest.write_str("*/")?;
+      Ok(true)
+    }
+  }
+
+  #[test]
+  fn test_custom_at_rules() {
+    fn custom_at_rule_test(source: &str, expected: &str, minify: bool) {
+      let mut at_rule_handlers: HashMap<String, Arc<dyn AtRuleHandler>> = HashMap::new();
+      at_rule_handlers.insert("apply".into(), Arc::new(ApplyHandler));
+      at_rule_handlers.insert("tailwind".into(), Arc::new(TailwindHandler));
+      let stylesheet = StyleSheet::parse(
+        "test.css".into(),
+        source,
+        ParserOptions {
+          at_rule_handlers,
+          ..ParserOptions::default()
+        },
+      )
+      .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    custom_at_rule_test(
+      "@apply utility; .foo { color: red }",
+      "@apply utility;.foo{color:red}",
+      true,
+    );
+    custom_at_rule_test("@APPLY  utility  other;", "@APPLY utility other;", true);
+    custom_at_rule_test(
+      "@media print { @apply utility; }",
+      "@media print{@apply utility;}",
+      true,
+    );
+    custom_at_rule_test(
+      "@apply utility { color: red }",
+      indoc! {r#"
+      @apply utility {
+        color: red
+      }
+    "#},
+      false,
+    );
+    custom_at_rule_test(
+      "@tailwind base;",
+      indoc! {r#"
+      /* @tailwind */
+    "#},
+      false,
+    );
+    custom_at_rule_test(
+      "@tailwind base; .foo { color: red }",
+      "/*@tailwind*/.foo{color:red}",
+      true,
+    );
+    custom_at_rule_test(
+      "@media print { @tailwind base; }",
+      indoc! {r#"
+      @media print {
+        /* @tailwind */
+      }
+    "#},
+      false,
+    );
+
+    // Unknown at-rules without a registered handler are skipped when handlers are registered.
+    custom_at_rule_test(
+      "@unknown foo { .foo { color: red } } .bar { color: red }",
+      ".bar{color:red}",
+      true,
+    );
+
+    // Otherwise, they are an error as before.
+    assert!(matches!(
+      StyleSheet::parse(
+        "test.css".into(),
+        "@apply utility; .foo { color: red }",
+        ParserOptions::default()
+      ),
+      Err(Error {
+        kind: ParserError::AtRuleInvalid(_),
+        ..
+      })
+    ));
+  }
+
+  #[test]
+  fn test_apply() {
+    fn apply_test(source: &str, expected: &str) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+      let utilities = stylesheet.utilities();
+      stylesheet.apply_utilities(&utilities).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    fn apply_error_test(source: &str, error: MinifyErrorKind) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+      let utilities = stylesheet.utilities();
+      match stylesheet.apply_utilities(&utilities) {
+        Err(e) => assert_eq!(e.kind, error),
+        Ok(()) => unreachable!(),
+      }
+    }
+
+    apply_test(
+      ".btn { padding: 10px } .red { color: red } .foo { @apply btn red; margin: 0 }",
+      ".btn{padding:10px}.red{color:red}.foo{padding:10px;color:red;margin:0}",
+    );
+    apply_test(
+      ".btn { padding: 10px } .red { color: red } .foo { margin: 0; @apply .btn .red; padding: 0 }",
+      ".btn{padding:10px}.red{color:red}.foo{margin:0;padding:10px;color:red;padding:0}",
+    );
+    apply_test(
+      ".btn { padding: 10px } .btn { margin: 0 } .foo { @apply btn }",
+      ".btn{padding:10px}.btn{margin:0}.foo{padding:10px;margin:0}",
+    );
+    apply_test(
+      ".a { color: red !important } .foo { @apply a; color: red !important }",
+      ".a{color:red!important}.foo{color:red!important;color:red!important}",
+    );
+    apply_test(
+      ".btn { padding: 10px } .foo { @apply btn !important }",
+      ".btn{padding:10px}.foo{padding:10px!important}",
+    );
+    apply_test(
+      ".btn { padding: 10px } .primary { @apply btn; color: red } .foo { @apply primary }",
+      ".btn{padding:10px}.primary{padding:10px;color:red}.foo{padding:10px;color:red}",
+    );
+    apply_test(
+      ".btn { padding: 10px } @media print { .foo { @apply btn } }",
+      ".btn{padding:10px}@media print{.foo{padding:10px}}",
+    );
+    apply_test(
+      r".w-1\/2 { width: 50% } .hover\:underline { text-decoration: underline } .foo { @apply w-1/2 hover:underline }",
+      r".w-1\/2{width:50%}.hover\:underline{text-decoration:underline}.foo{width:50%;text-decoration:underline}",
+    );
+    apply_error_test(
+      ".foo { @apply btn }",
+      MinifyErrorKind::UtilityNotDefined { name: "btn".into() },
+    );
+    apply_error_test(
+      ".a { @apply b } .b { @apply a }",
+      MinifyErrorKind::CircularApply { name: "b".into() },
+    );
+
+    minify_test(".foo { @apply btn red; color: red }", ".foo{@apply btn red;color:red}");
+    minify_test(".foo { @apply btn !important }", ".foo{@apply btn!important}");
+    minify_test(
+      ".foo { @apply md:w-1/2 btn !important }",
+      r".foo{@apply md\:w-1\/2 btn!important}",
+    );
+    minify_test(
+      ".foo { color: red; @apply btn; color: blue }",
+      ".foo{color:red;@apply btn;color:blue}",
+    );
+    minify_test(
+      ".foo { color: red !important; @apply btn; margin: 0 }",
+      ".foo{@apply btn;margin:0;color:red!important}",
+    );
+    minify_test(".foo { @apply btn } .foo { color: red }", ".foo{@apply btn;color:red}");
+    test(
+      ".foo { color: red; @apply btn }",
+      indoc! {r#"
+      .foo {
+        color: red;
+        @apply btn;
+      }
+    "#},
+    );
+  }
+
+  #[test]
+  fn test_empty_rules() {
+    minify_test(".foo {}", "");
+    minify_test(".foo {} .bar { color: red }", ".bar{color:red}");
+    minify_test("@media print { .foo {} }", "");
+    minify_test("@supports (display: grid) { .foo {} }", "");
+    minify_test("@media print { @supports (display: grid) { .foo {} } }", "");
+    minify_test(
+      "@supports (display: grid) { @media print { .foo {} } .bar { color: red } }",
+      "@supports (display: grid){.bar{color:red}}",
+    );
+    minify_test("@media print { @media (min-width: 100px) { .foo {} } }", "");
+    minify_test("@-moz-document url-prefix() { .foo {} }", "");
+    minify_test("@layer { .foo {} }", "");
+    minify_test("@layer foo { .foo {} }", "@layer foo{}");
+    minify_test("@layer foo { @media print { .foo {} } }", "@layer foo{}");
+    minify_test("@layer { @layer foo { .foo {} } }", "@layer{@layer foo{}}");
+
+    let mut stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ":root {} @media print { .foo {} } @media print { @supports (display: grid) {} }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    stylesheet
+      .minify(MinifyOptions {
+        preserve_empty_rules: true,
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ":root{}@media print{.foo{}}");
+  }
+
+  #[test]
+  fn test_contain_intrinsic_size() {
+    minify_test(".foo { content-visibility: auto }", ".foo{content-visibility:auto}");
+    minify_test(".foo { content-visibility: HIDDEN }", ".foo{content-visibility:hidden}");
+    minify_test(
+      ".foo { contain-intrinsic-size: 10px }",
+      ".foo{contain-intrinsic-size:10px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: 10px 10px }",
+      ".foo{contain-intrinsic-size:10px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: 10px 20px }",
+      ".foo{contain-intrinsic-size:10px 20px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: auto 10px auto 10px }",
+      ".foo{contain-intrinsic-size:auto 10px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: auto 10px 20px }",
+      ".foo{contain-intrinsic-size:auto 10px 20px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: none }",
+      ".foo{contain-intrinsic-size:none}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: auto none }",
+      ".foo{contain-intrinsic-size:auto none}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: auto none auto none }",
+      ".foo{contain-intrinsic-size:auto none}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: auto none 10px }",
+      ".foo{contain-intrinsic-size:auto none 10px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-width: 10px; contain-intrinsic-height: 10px }",
+      ".foo{contain-intrinsic-size:10px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-width: auto 10px; contain-intrinsic-height: 20px }",
+      ".foo{contain-intrinsic-size:auto 10px 20px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: 10px; contain-intrinsic-height: auto 20px }",
+      ".foo{contain-intrinsic-size:10px auto 20px}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-width: auto none }",
+      ".foo{contain-intrinsic-width:auto none}",
+    );
+    minify_test(
+      ".foo { contain-intrinsic-size: 10px; contain-intrinsic-width: var(--w) }",
+      ".foo{contain-intrinsic-size:10px;contain-intrinsic-width:var(--w)}",
+    );
+  }
+
+  #[test]
+  fn test_scrollbar() {
+    minify_test(".foo { scrollbar-gutter: auto }", ".foo{scrollbar-gutter:auto}");
+    minify_test(".foo { scrollbar-gutter: stable }", ".foo{scrollbar-gutter:stable}");
+    minify_test(
+      ".foo { scrollbar-gutter: stable both-edges }",
+      ".foo{scrollbar-gutter:stable both-edges}",
+    );
+    minify_test(
+      ".foo { scrollbar-gutter: both-edges stable }",
+      ".foo{scrollbar-gutter:stable both-edges}",
+    );
+    minify_test(
+      ".foo { scrollbar-gutter: both-edges }",
+      ".foo{scrollbar-gutter:both-edges}",
+    );
+    minify_test(".foo { scrollbar-width: auto }", ".foo{scrollbar-width:auto}");
+    minify_test(".foo { scrollbar-width: THIN }", ".foo{scrollbar-width:thin}");
+    minify_test(".foo { scrollbar-width: none }", ".foo{scrollbar-width:none}");
+    minify_test(".foo { scrollbar-color: auto }", ".foo{scrollbar-color:auto}");
+    minify_test(
+      ".foo { scrollbar-color: yellow rgb(255, 0, 0) }",
+      ".foo{scrollbar-color:#ff0 red}",
+    );
+    minify_test(".foo { scrollbar-color: yellow }", ".foo{scrollbar-color:yellow}");
+    minify_test(
+      ".foo { scrollbar-color: #ffff00 transparent }",
+      ".foo{scrollbar-color:#ff0 #0000}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        scrollbar-color: lab(40% 56.6 39) yellow;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        scrollbar-color: #b32323 #ff0;
+        scrollbar-color: lab(40% 56.6 39) #ff0;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_diff() {
+    use crate::diff::Change;
+
+    fn loc(line: u32, column: u32) -> Location {
+      Location {
+        source_index: 0,
+        line,
+        column,
+        generated: false,
+      }
+    }
+
+    let old = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { color: red }\n.bar { width: 10px }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { color: red; background: blue }\n.bar { width: 20px }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let background = Property::parse_string("background", "blue", ParserOptions::default()).unwrap();
+    let old_width = Property::parse_string("width", "10px", ParserOptions::default()).unwrap();
+    let new_width = Property::parse_string("width", "20px", ParserOptions::default()).unwrap();
+    assert_eq!(
+      old.diff(&new),
+      vec![
+        Change::DeclarationAdded {
+          property: &background,
+          important: false,
+          old_loc: loc(0, 1),
+          new_loc: loc(0, 1),
+        },
+        Change::DeclarationChanged {
+          old: &old_width,
+          new: &new_width,
+          important: false,
+          old_loc: loc(1, 1),
+          new_loc: loc(1, 1),
+        }
+      ]
+    );
+
+    // Formatting differences are ignored.
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      "\n\n.foo{color:red}.bar{width:10px}",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    assert!(old.diff(&new).is_empty());
+
+    // Declarations parsed with their source text are compared by value.
+    let raw = ParserOptions {
+      preserve_raw_values: true,
+      ..ParserOptions::default()
+    };
+    let a = StyleSheet::parse("test.css".into(), ".foo { color: RED; width: 10PX }", raw.clone()).unwrap();
+    let b = StyleSheet::parse("test.css".into(), ".foo { color: red; width: 10px }", raw.clone()).unwrap();
+    assert!(a.diff(&b).is_empty());
+    let b = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { color: red; width: 10px }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    assert!(a.diff(&b).is_empty());
+
+    // Reordered rules are reported as moves.
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      ".bar { width: 10px }\n.foo { color: red }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+      old.diff(&new),
+      vec![Change::RuleMoved {
+        rule: &new.rules.0[1],
+        old_loc: loc(0, 1),
+        new_loc: loc(1, 1),
+      }]
+    );
+
+    // Urls are compared by value, regardless of where they appear in the source.
+    let old = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { color: red }\n.bar { background: url(a.png) }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      ".bar { background: url(a.png) }\n.foo { color: red }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+      old.diff(&new),
+      vec![Change::RuleMoved {
+        rule: &new.rules.0[1],
+        old_loc: loc(0, 1),
+        new_loc: loc(1, 1),
+      }]
+    );
+
+    let old = StyleSheet::parse(
+      "test.css".into(),
+      "@media print {\n  .foo { color: red }\n  .bar { color: red }\n}",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      "@media print {\n  .foo { color: red }\n  .baz { color: red }\n}",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let (old_rules, new_rules) = match (&old.rules.0[0], &new.rules.0[0]) {
+      (CssRule::Media(a), CssRule::Media(b)) => (&a.rules.0, &b.rules.0),
+      _ => unreachable!(),
+    };
+    assert_eq!(
+      old.diff(&new),
+      vec![
+        Change::RuleRemoved {
+          rule: &old_rules[1],
+          old_loc: loc(2, 3),
+        },
+        Change::RuleAdded {
+          rule: &new_rules[1],
+          new_loc: loc(2, 3),
+        }
+      ]
+    );
+
+    let old = StyleSheet::parse(
+      "test.css".into(),
+      "@keyframes foo { from { opacity: 0 } }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let new = StyleSheet::parse(
+      "test.css".into(),
+      "@keyframes foo { from { opacity: 1 } }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+      old.diff(&new),
+      vec![Change::RuleChanged {
+        old: &old.rules.0[0],
+        new: &new.rules.0[0],
+        old_loc: loc(0, 1),
+        new_loc: loc(0, 1),
+      }]
+    );
+  }
+
+  #[test]
+  fn test_motion_path() {
+    minify_test(
+      ".foo { offset-path: path(\"M 0 0 L 100 100\") }",
+      ".foo{offset-path:path(\"M 0 0 L 100 100\")}",
+    );
+    minify_test(
+      ".foo { offset-path: path(nonzero, 'M 0 0 L 100 100') }",
+      ".foo{offset-path:path(\"M 0 0 L 100 100\")}",
+    );
+    minify_test(
+      ".foo { offset-path: path(evenodd, \"M 0 0 L 100 100\") }",
+      ".foo{offset-path:path(evenodd,\"M 0 0 L 100 100\")}",
+    );
+    test(
+      ".foo { offset-path: path(evenodd, \"M 0 0 L 100 100\") }",
+      indoc! {r#"
+      .foo {
+        offset-path: path(evenodd, "M 0 0 L 100 100");
+      }
+    "#},
+    );
+    minify_test(".foo { offset-path: none }", ".foo{offset-path:none}");
+    minify_test(".foo { offset-path: ray(45deg) }", ".foo{offset-path:ray(45deg)}");
+    minify_test(
+      ".foo { offset-path: ray(contain closest-corner 0.25turn) }",
+      ".foo{offset-path:ray(.25turn closest-corner contain)}",
+    );
+    minify_test(".foo { offset-path: url(#path) }", ".foo{offset-path:url(#path)}");
+    minify_test(
+      ".foo { offset-path: circle(50px at 0 100px) }",
+      ".foo{offset-path:circle(50px at 0 100px)}",
+    );
+    minify_test(".foo { offset-path: padding-box }", ".foo{offset-path:padding-box}");
+    minify_test(".foo { offset-distance: 50% }", ".foo{offset-distance:50%}");
+    minify_test(".foo { offset-rotate: auto }", ".foo{offset-rotate:auto}");
+    minify_test(".foo { offset-rotate: auto 0deg }", ".foo{offset-rotate:auto}");
+    minify_test(".foo { offset-rotate: 30deg auto }", ".foo{offset-rotate:auto 30deg}");
+    minify_test(".foo { offset-rotate: reverse }", ".foo{offset-rotate:reverse}");
+    minify_test(".foo { offset-rotate: 45deg }", ".foo{offset-rotate:45deg}");
+    minify_test(".foo { offset-anchor: auto }", ".foo{offset-anchor:auto}");
+    minify_test(".foo { offset-anchor: 10px 20px }", ".foo{offset-anchor:10px 20px}");
+    minify_test(".foo { offset-position: normal }", ".foo{offset-position:normal}");
+    minify_test(".foo { offset-position: auto }", ".foo{offset-position:auto}");
+    minify_test(".foo { offset-position: 10px 20px }", ".foo{offset-position:10px 20px}");
+
+    minify_test(".foo { offset: none }", ".foo{offset:none}");
+    minify_test(".foo { offset: normal }", ".foo{offset:none}");
+    minify_test(".foo { offset: auto }", ".foo{offset:auto}");
+    minify_test(
+      ".foo { offset: path(\"M 0 0 L 100 100\") 10px auto }",
+      ".foo{offset:path(\"M 0 0 L 100 100\") 10px}",
+    );
+    minify_test(
+      ".foo { offset: path(\"M 0 0 L 100 100\") reverse 45deg 50% }",
+      ".foo{offset:path(\"M 0 0 L 100 100\") 50% reverse 45deg}",
+    );
+    minify_test(
+      ".foo { offset: 10px 20px ray(45deg) / 30px 40px }",
+      ".foo{offset:10px 20px ray(45deg)/30px 40px}",
+    );
+    minify_test(".foo { offset: 10px 20px }", ".foo{offset:10px 20px}");
+    minify_test(".foo { offset: none 10px }", ".foo{offset:none 10px}");
+    minify_test(".foo { offset: none / 10px 20px }", ".foo{offset:none/10px 20px}");
+    test(
+      ".foo { offset: ray(45deg) / 10px 20px }",
+      indoc! {r#"
+      .foo {
+        offset: ray(45deg) / 10px 20px;
+      }
+    "#},
+    );
+
+    minify_test(
+      ".foo { offset-position: normal; offset-path: path(\"M 0 0\"); offset-distance: 10px; offset-rotate: auto; offset-anchor: auto }",
+      ".foo{offset:path(\"M 0 0\") 10px}",
+    );
+    minify_test(
+      ".foo { offset: path(\"M 0 0\"); offset-distance: 10px }",
+      ".foo{offset:path(\"M 0 0\") 10px}",
+    );
+    minify_test(
+      ".foo { offset-path: path(\"M 0 0\"); offset-distance: 10px }",
+      ".foo{offset-path:path(\"M 0 0\");offset-distance:10px}",
+    );
+    minify_test(
+      ".foo { offset: path(\"M 0 0\"); offset-distance: var(--distance) }",
+      ".foo{offset:path(\"M 0 0\");offset-distance:var(--distance)}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        offset: path("M 0 0") 10px;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        offset-position: normal;
+        offset-path: path("M 0 0");
+        offset-distance: 10px;
+        offset-rotate: auto;
+        offset-anchor: auto;
+      }
+    "#},
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        offset: path("M 0 0") 10px;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        offset: path("M 0 0") 10px;
+      }
+    "#},
+      Browsers {
+        safari: Some(16 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { offset-path: url(motion.svg#path) }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: true,
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let dependencies = res.dependencies.unwrap();
+    assert_eq!(dependencies.len(), 1);
+    match &dependencies[0] {
+      Dependency::Url(dep) => assert_eq!(dep.url, "motion.svg#path"),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn test_discrete_media_features() {
+    minify_test(
+      "@media (pointer: FINE) { .foo { color: red } }",
+      "@media (pointer:fine){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (any-pointer: coarse) and (any-hover: none) { .foo { color: red } }",
+      "@media (any-pointer:coarse) and (any-hover:none){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (prefers-color-scheme: dark) { .foo { color: red } }",
+      "@media (prefers-color-scheme:dark){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (forced-colors: active) or (prefers-contrast: more) { .foo { color: red } }",
+      "@media (forced-colors:active) or (prefers-contrast:more){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (pointer: hover) { .foo { color: red } }",
+      "@media (pointer:hover){.foo{color:red}}",
+    );
+
+    let res = StyleSheet::parse(
+      "test.css".into(),
+      "@media (pointer: hover) { .foo { color: red } }",
+      ParserOptions {
+        strict: true,
+        ..ParserOptions::default()
+      },
+    );
+    match res {
+      Ok(_) => unreachable!(),
+      Err(e) => assert_eq!(e.kind, ParserError::InvalidMediaQuery),
+    }
+
+    let res = StyleSheet::parse(
+      "test.css".into(),
+      "@media (pointer: fine) { .foo { color: red } }",
+      ParserOptions {
+        strict: true,
+        ..ParserOptions::default()
+      },
+    );
+    assert!(res.is_ok());
+  }
+
+  #[test]
+  fn test_max_nesting_depth() {
+    use cssparser::{ParseErrorKind, Parser, ParserInput};
+
+    let source = format!(".foo {{ width: calc({}1px{}) }}", "(".repeat(10000), ")".repeat(10000));
+    error_test(&source, ParserError::MaximumNestingDepthExceeded);
+
+    let source = format!("{}.foo{} {{ color: red }}", ":is(".repeat(10000), ")".repeat(10000));
+    error_test(&source, ParserError::MaximumNestingDepthExceeded);
+
+    let source = format!(
+      "{}.foo {{ color: red }}{}",
+      "@media print {".repeat(10000),
+      "}".repeat(10000)
+    );
+    error_test(&source, ParserError::MaximumNestingDepthExceeded);
+
+    let source = format!("width: calc({}1px{})", "(".repeat(10000), ")".repeat(10000));
+    match StyleAttribute::parse(&source) {
+      Ok(_) => unreachable!(),
+      Err(e) => assert_eq!(e.kind, ParserError::MaximumNestingDepthExceeded),
+    }
+
+    // Brackets within strings and comments do not count towards the limit.
+    let options = ParserOptions {
+      max_nesting_depth: 2,
+      ..ParserOptions::default()
+    };
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { content: \"(((\"; /* ((( */ }",
+      options.clone(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ".foo{content:\"(((\"}");
+
+    // Columns are counted in characters rather than bytes.
+    let res = StyleSheet::parse(
+      "test.css".into(),
+      ".é { width: calc((1px + 2px) * 2) }",
+      options.clone(),
+    );
+    match res {
+      Ok(_) => unreachable!(),
+      Err(e) => {
+        assert_eq!(e.kind, ParserError::MaximumNestingDepthExceeded);
+        let loc = e.loc.unwrap();
+        assert_eq!((loc.line, loc.column), (0, 19));
+      }
+    }
+
+    // The limit also applies when parsing individual rules, properties and media queries.
+    let source = format!(
+      "@media print {{ .foo {{ width: calc({}1px{}) }} }}",
+      "(".repeat(10000),
+      ")".repeat(10000)
+    );
+    match CssRule::parse_string(&source, ParserOptions::default()) {
+      Ok(_) => unreachable!(),
+      Err(e) => assert_eq!(e.kind, ParseErrorKind::Custom(ParserError::MaximumNestingDepthExceeded)),
+    }
+
+    let source = format!("calc({}1px{})", "(".repeat(10000), ")".repeat(10000));
+    match Property::parse_string("width", &source, ParserOptions::default()) {
+      Ok(_) => unreachable!(),
+      Err(e) => assert_eq!(e.kind, ParseErrorKind::Custom(ParserError::MaximumNestingDepthExceeded)),
+    }
+
+    let source = format!("{}(width: 100px){}", "(".repeat(10000), ")".repeat(10000));
+    let mut input = ParserInput::new(&source);
+    let mut parser = Parser::new(&mut input);
+    match crate::media_query::MediaList::parse(&mut parser) {
+      Ok(_) => unreachable!(),
+      Err(e) => assert_eq!(e.kind, ParseErrorKind::Custom(ParserError::MaximumNestingDepthExceeded)),
+    }
+
+    let source = format!("{}(width: 100px){}", "(".repeat(3), ")".repeat(3));
+    let mut input = ParserInput::new(&source);
+    let mut parser = Parser::new(&mut input);
+    assert!(crate::media_query::MediaList::parse_with_options(&mut parser, &options).is_err());
+    let mut input = ParserInput::new(&source);
+    let mut parser = Parser::new(&mut input);
+    assert!(crate::media_query::MediaList::parse(&mut parser).is_ok());
+  }
+
+  #[cfg(feature = "parallel")]
+  #[test]
+  fn test_parallel_minify() {
+    let source = r#"
+      @custom-media --narrow (max-width: 600px);
+      .foo { color: red; margin-inline-start: 2px }
+      .foo { background: lab(40% 56.6 39) }
+      .bar { color: red; margin-inline-start: 2px }
+      .baz { transition: opacity 200ms; unknown: value }
+      @media (--narrow) {
+        .foo { color: lch(50.998% 135.363 338) }
+        .foo { padding: 2px }
+      }
+      @supports (display: grid) {
+        .grid { display: grid; inset-inline: 0 }
+      }
+      @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+      @-webkit-keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+      .unused { color: red }
+      ::selection { color: red }
+      .qux::placeholder { color: red }
+      @layer base { .foo { color: red } .foo { color: blue } }
+      .empty {}
+    "#;
+
+    let minify = |parallel| {
+      let mut stylesheet = StyleSheet::parse(
+        "test.css".into(),
+        source,
+        ParserOptions {
+          custom_media: true,
+          ..ParserOptions::default()
+        },
+      )
+      .unwrap();
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            safari: Some(8 << 16),
+            chrome: Some(60 << 16),
+            ..Browsers::default()
+          }),
+          unused_symbols: vec!["unused".into()].into_iter().collect(),
+          reduced_motion: true,
+          warnings: Some(warnings.clone()),
+          parallel,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      let warnings = warnings.read().unwrap().len();
+      (res.code, warnings)
+    };
+
+    assert_eq!(minify(true), minify(false));
+  }
+
+  #[test]
+  fn test_interned_strings() {
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".md\\:flex { display: flex } .md\\:flex:hover { color: red }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let class_names: Vec<&str> = stylesheet
+      .rules
+      .0
+      .iter()
+      .map(|rule| match rule {
+        CssRule::Style(style) => style.selectors.0[0]
+          .iter()
+          .find_map(|component| match component {
+            parcel_selectors::parser::Component::Class(name) => Some(name.0.as_ref()),
+            _ => None,
+          })
+          .unwrap(),
+        _ => unreachable!(),
+      })
+      .collect();
+
+    // Both escaped class names share a single allocation.
+    assert_eq!(class_names, ["md:flex", "md:flex"]);
+    assert_eq!(class_names[0].as_ptr(), class_names[1].as_ptr());
+  }
+
+  #[test]
+  fn test_merge_root_variables() {
+    minify_test(
+      ":root { --primary: red; --secondary: blue } .foo { color: var(--primary) } :root { --primary: green; --tertiary: yellow }",
+      ":root{--secondary:blue;--primary:green;--tertiary:yellow}.foo{color:var(--primary)}",
+    );
+    minify_test(
+      ":root { --a: 1 !important; --b: 2 } .foo { color: red } :root { --a: 3 } .bar { color: blue } :root { --b: 4 }",
+      ":root{--b:4;--a:1!important}.foo{color:red}.bar{color:#00f}",
+    );
+
+    // Rules with other declarations, or other selectors, are not merged.
+    minify_test(
+      ":root { --a: 1 } .foo { color: red } :root { --b: 2; color: red } .bar { color: blue } :root, .baz { --c: 3 }",
+      ":root{--a:1}.foo{color:red}:root{--b:2;color:red}.bar{color:#00f}:root,.baz{--c:3}",
+    );
+    minify_test(
+      ":root { --a: 1 } .foo { color: red } html:root { --b: 2 }",
+      ":root{--a:1}.foo{color:red}html:root{--b:2}",
+    );
+
+    // Rules are not moved before other rules declaring the same custom properties.
+    minify_test(
+      ":root { --a: 1 } [data-theme] { --a: 2 } :root { --a: 3; --b: 4 }",
+      ":root{--a:1}[data-theme]{--a:2}:root{--a:3;--b:4}",
+    );
+    minify_test(
+      ":root { --a: 1 } @media print { .foo { --b: 2 } } :root { --b: 3 } .bar { color: blue } :root { --c: 4 }",
+      ":root{--a:1;--c:4}@media print{.foo{--b:2}}:root{--b:3}.bar{color:#00f}",
+    );
+  }
+
+  #[test]
+  fn test_nested_conditional_rules() {
+    nesting_test(
+      r#"
+        .foo {
+          color: red;
+
+          @media (orientation: landscape) {
+            color: blue;
+
+            & .bar {
+              color: green;
+            }
+          }
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          color: red;
+        }
+
+        @media (orientation: landscape) {
+          .foo {
+            color: #00f;
+          }
+
+          .foo .bar {
+            color: green;
+          }
+        }
+      "#},
+    );
+
+    nesting_test(
+      r#"
+        .foo {
+          display: grid;
+
+          @supports (display: grid) {
+            grid-auto-flow: column !important;
+          }
+        }
+      "#,
+      indoc! {r#"
+        .foo {
+          display: grid;
+        }
+
+        @supports (display: grid) {
+          .foo {
+            grid-auto-flow: column !important;
+          }
+        }
+      "#},
+    );
+  }
+
+  #[test]
+  fn test_transform_one_shot() {
+    let res = crate::transform(
+      ".foo { box-shadow: 12px 12px yellow }",
+      TransformOptions {
+        minify: MinifyOptions {
+          targets: Some(Browsers {
+            chrome: Some(4 << 16),
+            ..Browsers::default()
+          }),
+          ..MinifyOptions::default()
+        },
+        printer: PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        },
+        ..TransformOptions::default()
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      res.code,
+      ".foo{-webkit-box-shadow:12px 12px #ff0;box-shadow:12px 12px #ff0}"
+    );
+
+    match crate::transform(
+      "@media (pointer: hover) {}",
+      TransformOptions {
+        filename: "test.css".into(),
+        parser: ParserOptions {
+          strict: true,
+          ..ParserOptions::default()
+        },
+        ..TransformOptions::default()
+      },
+    ) {
+      Err(crate::error::TransformError::Parser(err)) => {
+        assert_eq!(err.kind, ParserError::InvalidMediaQuery);
+        assert_eq!(err.loc.unwrap().filename, "test.css");
+      }
+      _ => unreachable!(),
+    }
+
+    match crate::transform(
+      "@media (--undefined) { .foo { color: red } }",
+      TransformOptions {
+        parser: ParserOptions {
+          custom_media: true,
+          ..ParserOptions::default()
+        },
+        minify: MinifyOptions {
+          targets: Some(Browsers {
+            chrome: Some(95 << 16),
+            ..Browsers::default()
+          }),
+          ..MinifyOptions::default()
+        },
+        ..TransformOptions::default()
+      },
+    ) {
+      Err(crate::error::TransformError::Minify(err)) => assert_eq!(
+        err.kind,
+        MinifyErrorKind::CustomMediaNotDefined {
+          name: "--undefined".into()
+        }
+      ),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn test_unknown_pseudos() {
+    minify_test(
+      ".foo::-webkit-scrollbar-thumb:hover { color: red }",
+      ".foo::-webkit-scrollbar-thumb:hover{color:red}",
+    );
+    minify_test(
+      ".foo::-webkit-inner-spin-button { color: red }",
+      ".foo::-webkit-inner-spin-button{color:red}",
+    );
+    minify_test(".foo::unknown { color: red }", ".foo::unknown{color:red}");
+    minify_test(".foo:unknown { color: red }", ".foo:unknown{color:red}");
+    minify_test(
+      "::highlight(my-highlight) { color: red }",
+      "::highlight(my-highlight){color:red}",
+    );
+    minify_test(
+      ".foo::part(label  thumb) { color: red }",
+      ".foo::part(label  thumb){color:red}",
+    );
+    minify_test(
+      ".foo:unknown-fn( a b ) { color: red }",
+      ".foo:unknown-fn(a b){color:red}",
+    );
+    test(
+      ".foo:unknown-fn(.bar > .baz) { color: red }",
+      indoc! {r#"
+      .foo:unknown-fn(.bar > .baz) {
+        color: red;
+      }
+    "#},
+    );
+  }
+
+  #[test]
+  fn test_flex_gap_warning() {
+    let safari = Browsers {
+      safari: Some(13 << 16),
+      ..Browsers::default()
+    };
+    warning_test(
+      ".foo { display: flex; gap: 10px }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedFlexGap],
+    );
+    warning_test(
+      ".foo { display: inline-flex; row-gap: 10px }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedFlexGap],
+    );
+    warning_test(".foo { display: grid; gap: 10px }", safari, vec![]);
+    warning_test(".foo { gap: 10px }", safari, vec![]);
+    warning_test(".foo { display: flex; gap: normal }", safari, vec![]);
+    warning_test(
+      ".foo { display: flex; gap: 10px }",
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+      vec![],
+    );
+  }
+
+  #[test]
+  fn test_media_resolution() {
+    let chrome = Browsers {
+      chrome: Some(90 << 16),
+      ..Browsers::default()
+    };
+    let safari = Browsers {
+      safari: Some(13 << 16),
+      ..Browsers::default()
+    };
+
+    minify_test(
+      "@media (min-resolution: 2dppx) { .foo { color: red } }",
+      "@media (min-resolution:2dppx){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-resolution: 192dpi) { .foo { color: red } }",
+      "@media (min-resolution:192dpi){.foo{color:red}}",
+    );
+    prefix_test(
+      "@media (min-resolution: 192dpi) { .foo { color: red } }",
+      indoc! {r#"
+        @media (min-resolution: 2dppx) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      chrome,
+    );
+    prefix_test(
+      "@media (resolution >= 144dpi) { .foo { color: red } }",
+      indoc! {r#"
+        @media (resolution >= 1.5dppx) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      chrome,
+    );
+    prefix_test(
+      "@media (min-resolution: 100dpi) { .foo { color: red } }",
+      indoc! {r#"
+        @media (min-resolution: 100dpi) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      chrome,
+    );
+    prefix_test(
+      "@media (min-resolution: 192dpi) { .foo { color: red } }",
+      indoc! {r#"
+        @media (-webkit-min-device-pixel-ratio: 2), (min-resolution: 192dpi) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      safari,
+    );
+    prefix_test(
+      "@media screen and (resolution >= 2dppx) { .foo { color: red } }",
+      indoc! {r#"
+        @media screen and (-webkit-min-device-pixel-ratio: 2), screen and (min-resolution: 2dppx) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      safari,
+    );
+    prefix_test(
+      "@media (max-resolution: 2dppx), (min-width: 100px) { .foo { color: red } }",
+      indoc! {r#"
+        @media (-webkit-max-device-pixel-ratio: 2), (max-resolution: 2dppx), (min-width: 100px) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      safari,
+    );
+    prefix_test(
+      "@media (-webkit-min-device-pixel-ratio: 2), (min-resolution: 2dppx) { .foo { color: red } }",
+      indoc! {r#"
+        @media (-webkit-min-device-pixel-ratio: 2), (min-resolution: 2dppx) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      safari,
+    );
+  }
+
+  #[test]
+  fn test_clamp_values() {
+    fn clamp_test(source: &str, expected: &str) {
+      let stylesheet = StyleSheet::parse(
+        "test.css".into(),
+        source,
+        ParserOptions {
+          clamp_values: true,
+          ..ParserOptions::default()
+        },
+      )
+      .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    fn strict_error_test(source: &str) {
+      let res = StyleSheet::parse(
+        "test.css".into(),
+        source,
+        ParserOptions {
+          strict: true,
+          ..ParserOptions::default()
+        },
+      );
+      match res {
+        Ok(_) => unreachable!(),
+        Err(e) => assert_eq!(e.kind, ParserError::InvalidValue),
+      }
+    }
+
+    fn strict_test(source: &str, expected: &str) {
+      let stylesheet = StyleSheet::parse(
+        "test.css".into(),
+        source,
+        ParserOptions {
+          strict: true,
+          ..ParserOptions::default()
+        },
+      )
+      .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    clamp_test(".foo { opacity: 1.5 }", ".foo{opacity:1}");
+    clamp_test(".foo { opacity: 200% }", ".foo{opacity:1}");
+    clamp_test(".foo { opacity: -0.5 }", ".foo{opacity:0}");
+    clamp_test(".foo { opacity: 0.5 }", ".foo{opacity:.5}");
+    clamp_test(".foo { fill-opacity: 2 }", ".foo{fill-opacity:1}");
+    clamp_test(".foo { flex-grow: -1 }", ".foo{flex-grow:0}");
+    clamp_test(".foo { stroke-miterlimit: 0.5 }", ".foo{stroke-miterlimit:1}");
+    clamp_test(".foo { opacity: var(--x) }", ".foo{opacity:var(--x)}");
+    clamp_test(
+      ".foo { -webkit-box-ordinal-group: 0 }",
+      ".foo{-webkit-box-ordinal-group:1}",
+    );
+    clamp_test(".foo { -webkit-box-flex-group: -2 }", ".foo{-webkit-box-flex-group:1}");
+    clamp_test(".foo { order: -1 }", ".foo{order:-1}");
+    clamp_test(".foo { -ms-flex-order: -1 }", ".foo{-ms-flex-order:-1}");
+
+    // Alpha values are clamped at computed value time, so they are valid in strict mode.
+    strict_test(".foo { opacity: 1.5 }", ".foo{opacity:1}");
+    strict_test(".foo { opacity: 200% }", ".foo{opacity:1}");
+    strict_test(".foo { stroke-opacity: -1 }", ".foo{stroke-opacity:0}");
+    strict_test(".foo { order: -1 }", ".foo{order:-1}");
+    strict_test(".foo { flex-grow: 0 }", ".foo{flex-grow:0}");
+
+    strict_error_test(".foo { flex-shrink: -1 }");
+    strict_error_test(".foo { stroke-miterlimit: 0.5 }");
+    strict_error_test(".foo { -webkit-box-ordinal-group: 0 }");
+    strict_error_test(".foo { -webkit-box-flex-group: 0 }");
+
+    // Without either option, out of range values are preserved.
+    minify_test(".foo { opacity: 1.5 }", ".foo{opacity:1.5}");
+  }
+
+  #[test]
+  fn test_url_quotes() {
+    fn url_test(source: &str, expected: &str, url_quotes: UrlQuotes, minify: bool) {
+      let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify,
+          url_quotes,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    url_test(
+      ".foo { background: url(foo.png) }",
+      ".foo{background:url(\"foo.png\")}",
+      UrlQuotes::Always,
+      true,
+    );
+    url_test(
+      ".foo { background: url('foo.png') }",
+      ".foo{background:url(\"foo.png\")}",
+      UrlQuotes::Always,
+      true,
+    );
+    url_test(
+      ".foo { background: url(\"foo.png\") }",
+      ".foo{background:url(foo.png)}",
+      UrlQuotes::Never,
+      true,
+    );
+    url_test(
+      "@font-face { src: url(\"foo.woff2\") }",
+      "@font-face{src:url(foo.woff2)}",
+      UrlQuotes::Never,
+      true,
+    );
+    url_test(
+      ".foo { --bg: url(\"foo.png\") }",
+      ".foo{--bg:url(foo.png)}",
+      UrlQuotes::Never,
+      true,
+    );
+    url_test(
+      ".foo { background: url(data:image/png;base64,iVBORw0KGgo=) }",
+      ".foo{background:url(data:image/png;base64,iVBORw0KGgo=)}",
+      UrlQuotes::Never,
+      true,
+    );
+
+    // Urls that would need escaping are still quoted.
+    url_test(
+      ".foo { background: url(\"foo bar.png\") }",
+      ".foo{background:url(\"foo bar.png\")}",
+      UrlQuotes::Never,
+      true,
+    );
+    url_test(
+      ".foo { background: url(\"foo(1).png\") }",
+      ".foo{background:url(\"foo(1).png\")}",
+      UrlQuotes::Never,
+      true,
+    );
+    url_test(
+      ".foo { background: url(\"data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg'></svg>\") }",
+      ".foo{background:url(\"data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg'></svg>\")}",
+      UrlQuotes::Never,
+      true,
+    );
+
+    // The default only adds quotes when minifying and shorter.
+    url_test(
+      ".foo { background: url(\"foo.png\") }",
+      indoc! {r#"
+      .foo {
+        background: url(foo.png);
+      }
+    "#},
+      UrlQuotes::Auto,
+      false,
+    );
+  }
+
+  #[test]
+  fn test_important_overrides() {
+    minify_test(
+      ".foo { margin: 0 !important; margin-top: 5px }",
+      ".foo{margin:0!important}",
+    );
+    minify_test(
+      ".foo { margin-top: 5px; margin: 0 !important }",
+      ".foo{margin:0!important}",
+    );
+    minify_test(
+      ".foo { margin: 0; margin-top: 5px !important }",
+      ".foo{margin:0;margin-top:5px!important}",
+    );
+    minify_test(
+      ".foo { margin-top: 5px !important; margin: 0 }",
+      ".foo{margin:0;margin-top:5px!important}",
+    );
+    minify_test(
+      ".foo { padding: 0 !important; padding-inline-start: 5px; padding-left: 2px }",
+      ".foo{padding:0!important}",
+    );
+    minify_test(
+      ".foo { margin-inline: 0 !important; margin-inline-start: 5px }",
+      ".foo{margin-inline:0!important}",
+    );
+    minify_test(
+      ".foo { color: red !important; color: blue }",
+      ".foo{color:red!important}",
+    );
+    minify_test(
+      ".foo { border: 1px solid red !important; border-top: 2px dashed }",
+      ".foo{border:1px solid red!important}",
+    );
+
+    // Properties that only share a name prefix are not overridden.
+    minify_test(
+      ".foo { flex: 1 !important; flex-direction: column }",
+      ".foo{flex-direction:column;flex:1!important}",
+    );
+    minify_test(
+      ".foo { margin-top: 0 !important; margin: 5px }",
+      ".foo{margin:5px;margin-top:0!important}",
+    );
+  }
+
+  #[test]
+  fn test_printer_targets() {
+    fn print(stylesheet: &StyleSheet, targets: Option<Browsers>) -> String {
+      stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          targets,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code
+    }
+
+    let chrome = Browsers {
+      chrome: Some(90 << 16),
+      ..Browsers::default()
+    };
+    let ie = Browsers {
+      ie: Some(11 << 16),
+      ..Browsers::default()
+    };
+
+    // Printer targets apply without minifying first.
+    let source = ".foo { color: rgba(255, 0, 0, .5) }";
+    let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    assert_eq!(print(&stylesheet, None), ".foo{color:#ff000080}");
+    assert_eq!(print(&stylesheet, Some(chrome)), ".foo{color:#ff000080}");
+    assert_eq!(print(&stylesheet, Some(ie)), ".foo{color:rgba(255,0,0,.5)}");
+
+    // When both are set, the printer targets decide formatting.
+    let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    stylesheet
+      .minify(MinifyOptions {
+        targets: Some(chrome),
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    assert_eq!(print(&stylesheet, Some(ie)), ".foo{color:rgba(255,0,0,.5)}");
+  }
+
+  #[test]
+  fn test_options_builder() {
+    let options = ParserOptions::builder()
+      .nesting(true)
+      .css_modules(true)
+      .source_index(2)
+      .max_nesting_depth(16)
+      .build();
+    let expected = ParserOptions {
+      nesting: true,
+      css_modules: true,
+      source_index: 2,
+      max_nesting_depth: 16,
+      ..ParserOptions::default()
+    };
+    assert_eq!(format!("{:?}", options), format!("{:?}", expected));
+    assert_eq!(
+      format!("{:?}", ParserOptions::builder().build()),
+      format!("{:?}", ParserOptions::default())
+    );
+
+    let targets = Browsers {
+      safari: Some(13 << 16),
+      ..Browsers::default()
+    };
+    let options = PrinterOptions::builder()
+      .minify(true)
+      .targets(targets)
+      .url_quotes(UrlQuotes::Always)
+      .build();
+    let expected = PrinterOptions {
+      minify: true,
+      targets: Some(targets),
+      url_quotes: UrlQuotes::Always,
+      ..PrinterOptions::default()
+    };
+    assert_eq!(options.minify, expected.minify);
+    assert_eq!(format!("{:?}", options.targets), format!("{:?}", expected.targets));
+    assert_eq!(options.url_quotes, expected.url_quotes);
+    assert_eq!(options.analyze_dependencies, expected.analyze_dependencies);
+    assert!(options.pseudo_classes.is_none() && options.px_to_rem.is_none());
+    #[cfg(feature = "std")]
+    assert!(options.source_map.is_none());
+
+    let source = ".foo { background: url(foo.png) }";
+    let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::builder().build()).unwrap();
+    let res = stylesheet.to_css(options).unwrap();
+    assert_eq!(res.code, ".foo{background:url(\"foo.png\")}");
+  }
+
+  #[test]
+  fn test_object_fit() {
+    minify_test(".foo { object-fit: cover }", ".foo{object-fit:cover}");
+    minify_test(".foo { object-fit: Scale-Down }", ".foo{object-fit:scale-down}");
+    minify_test(".foo { object-position: center center }", ".foo{object-position:50%}");
+    minify_test(".foo { object-position: left top }", ".foo{object-position:0 0}");
+    minify_test(
+      ".foo { object-position: 0 0; object-position: 50% 50% }",
+      ".foo{object-position:50%}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        object-fit: contain;
+        object-position: center top;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -o-object-fit: contain;
+        object-fit: contain;
+        -o-object-position: top;
+        object-position: top;
+      }
+    "#},
+      Browsers {
+        opera: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -o-object-fit: contain;
+        object-fit: contain;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        object-fit: contain;
+      }
+    "#},
+      Browsers {
+        opera: Some(20 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_image_rendering() {
+    minify_test(".foo { image-rendering: pixelated }", ".foo{image-rendering:pixelated}");
+    minify_test(
+      ".foo { image-rendering: CRISP-EDGES }",
+      ".foo{image-rendering:crisp-edges}",
+    );
+    minify_test(
+      ".foo { image-rendering: optimizeSpeed }",
+      ".foo{image-rendering:optimizespeed}",
+    );
+    minify_test(
+      ".foo { image-rendering: -webkit-optimize-contrast }",
+      ".foo{image-rendering:-webkit-optimize-contrast}",
+    );
+
+    // Legacy values are kept as fallbacks for older browsers.
+    minify_test(
+      ".foo { image-rendering: -webkit-optimize-contrast; image-rendering: -moz-crisp-edges; image-rendering: crisp-edges }",
+      ".foo{image-rendering:-webkit-optimize-contrast;image-rendering:-moz-crisp-edges;image-rendering:crisp-edges}",
+    );
+  }
+
+  #[test]
+  fn test_print_to_core_fmt_write() {
+    struct Bytes(Vec<u8>);
+    impl std::fmt::Write for Bytes {
+      fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+      }
+    }
+
+    let stylesheet =
+      StyleSheet::parse("test.css".into(), ".foo { color: red }", ParserOptions::default()).unwrap();
+    let mut dest = Bytes(Vec::new());
+    let mut printer = Printer::new(
+      &mut dest,
+      PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      },
+    );
+    stylesheet.rules.to_css(&mut printer).unwrap();
+    assert_eq!(dest.0, b".foo{color:red}");
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn test_source_map_result() {
+    use parcel_sourcemap::SourceMap;
+
+    let source = ".foo {\n  color: red;\n}\n\n.bar {\n  color: blue;\n}\n";
+    let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+
+    // The source map is only encoded when requested.
+    let mut source_map = SourceMap::new("/");
+    source_map.add_source("test.css");
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        source_map: Some(&mut source_map),
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert!(res.source_map.is_none());
+
+    let mut source_map = SourceMap::new("/");
+    source_map.add_source("test.css");
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        source_map: Some(&mut source_map),
+        encode_source_map: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let map = res.source_map.unwrap();
+    assert_eq!(map.version, 3);
+    assert_eq!(map.sources, vec!["test.css".to_string()]);
+    assert_eq!(map.sources_content, None);
+    assert!(!map.mappings.is_empty());
+    assert!(map.mappings.chars().all(|c| c.is_ascii_alphanumeric()
+      || c == '+'
+      || c == '/'
+      || c == ','
+      || c == ';'));
+    // Segments of different output lines are separated by semicolons.
+    assert!(map.mappings.contains(';'));
+
+    let mut source_map = SourceMap::new("/");
+    source_map.add_source("test.css");
+    let _ = source_map.set_source_content(0, source);
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        source_map: Some(&mut source_map),
+        encode_source_map: true,
+        include_sources_content: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let map = res.source_map.unwrap();
+    assert_eq!(map.sources.len(), 1);
+    assert_eq!(map.sources_content, Some(vec![Some(source.to_string())]));
+
+    let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
+    assert!(res.source_map.is_none());
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn test_source_map_sources_content() {
+    use parcel_sourcemap::SourceMap;
+
+    let source = ".foo { color: red }";
+    let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    let print = |source_map: &mut SourceMap, include_sources_content: bool, source_root: Option<String>| {
+      stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          source_map: Some(source_map),
+          encode_source_map: true,
+          include_sources_content,
+          source_root,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .source_map
+        .unwrap()
+    };
+
+    let mut source_map = SourceMap::new("/");
+    source_map.add_source("test.css");
+    let map = print(&mut source_map, false, None);
+    assert_eq!(map.sources_content, None);
+    assert_eq!(map.source_root, None);
+
+    // The contents come from the style sheet itself.
+    let mut source_map = SourceMap::new("/");
+    source_map.add_source("test.css");
+    let map = print(&mut source_map, true, Some("/src/".into()));
+    assert_eq!(map.sources_content, Some(vec![Some(source.to_string())]));
+    assert_eq!(map.source_root, Some("/src/".into()));
+    assert!(source_map.get_sources_content().iter().all(|content| content.is_empty()));
+
+    // Missing sources are added from the style sheet, without modifying the caller's source map.
+    let mut source_map = SourceMap::new("/");
+    let map = print(&mut source_map, true, None);
+    assert_eq!(map.sources, vec!["test.css".to_string()]);
+    assert_eq!(map.sources_content, Some(vec![Some(source.to_string())]));
+    assert!(source_map.get_sources().is_empty());
+
+    // Each source of a concatenated style sheet has its own contents.
+    let a = ".a { color: red }";
+    let b = ".b { color: blue }";
+    let stylesheet = StyleSheet::concat(vec![
+      StyleSheet::parse("a.css".into(), a, ParserOptions::default()).unwrap(),
+      StyleSheet::parse("b.css".into(), b, ParserOptions::default()).unwrap(),
+    ]);
+    let mut source_map = SourceMap::new("/");
+    let map = stylesheet
+      .to_css(PrinterOptions {
+        source_map: Some(&mut source_map),
+        encode_source_map: true,
+        include_sources_content: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap()
+      .source_map
+      .unwrap();
+    assert_eq!(map.sources, vec!["a.css".to_string(), "b.css".to_string()]);
+    assert_eq!(
+      map.sources_content,
+      Some(vec![Some(a.to_string()), Some(b.to_string())])
+    );
+  }
+
+  #[test]
+  fn test_remove_interpolated_keyframes() {
+    fn keyframes_test(source: &str, expected: &str, remove_interpolated_keyframes: bool) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          remove_interpolated_keyframes,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    let source = "@keyframes foo { from { opacity: 0 } 50% { opacity: .5 } to { opacity: 1 } }";
+    keyframes_test(source, "@keyframes foo{0%{opacity:0}to{opacity:1}}", true);
+    keyframes_test(
+      source,
+      "@keyframes foo{0%{opacity:0}50%{opacity:.5}to{opacity:1}}",
+      false,
+    );
+
+    keyframes_test(
+      "@keyframes foo { 0% { width: 0px } 25% { width: 25px } 50% { width: 50px } 100% { width: 100px } }",
+      "@keyframes foo{0%{width:0}to{width:100px}}",
+      true,
+    );
+    keyframes_test(
+      "@keyframes foo { from { color: red } 50% { color: purple } to { color: blue } }",
+      "@keyframes foo{0%{color:red}to{color:#00f}}",
+      true,
+    );
+    keyframes_test(
+      "@keyframes foo { from { opacity: 0; color: red } 50% { opacity: .5; color: red } to { opacity: 1; color: red } }",
+      "@keyframes foo{0%{opacity:0;color:red}to{opacity:1;color:red}}",
+      true,
+    );
+
+    // Frames that are not on the interpolated path are kept.
+    keyframes_test(
+      "@keyframes foo { from { opacity: 0 } 50% { opacity: .6 } to { opacity: 1 } }",
+      "@keyframes foo{0%{opacity:0}50%{opacity:.6}to{opacity:1}}",
+      true,
+    );
+    keyframes_test(
+      "@keyframes foo { from { opacity: 0 } 50% { opacity: .5; color: red } to { opacity: 1 } }",
+      "@keyframes foo{0%{opacity:0}50%{opacity:.5;color:red}to{opacity:1}}",
+      true,
+    );
+    keyframes_test(
+      "@keyframes foo { from { width: 0px } 50% { width: 50% } to { width: 100px } }",
+      "@keyframes foo{0%{width:0}50%{width:50%}to{width:100px}}",
+      true,
+    );
+
+    // Easing makes the intermediate frame significant.
+    keyframes_test(
+      "@keyframes foo { from { opacity: 0; animation-timing-function: ease-in } 50% { opacity: .5; animation-timing-function: ease-in } to { opacity: 1; animation-timing-function: ease-in } }",
+      "@keyframes foo{0%{opacity:0;animation-timing-function:ease-in}50%{opacity:.5;animation-timing-function:ease-in}to{opacity:1;animation-timing-function:ease-in}}",
+      true,
+    );
+  }
+
+  #[test]
+  fn test_value_to_css_string() {
+    let property = Property::Color(CssColor::RGBA(cssparser::RGBA::new(255, 0, 0, 255)));
+    assert_eq!(property.value_to_css_string(PrinterOptions::default()).unwrap(), "red");
+    assert_eq!(
+      property.to_css_string(false, PrinterOptions::default()).unwrap(),
+      "color: red"
+    );
+
+    let value = |name: &'static str, value: &'static str, minify: bool| {
+      Property::parse_string(name, value, ParserOptions::default())
+        .unwrap()
+        .value_to_css_string(PrinterOptions {
+          minify,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+    };
+
+    assert_eq!(value("margin", "10px 20px 10px 20px", false), "10px 20px");
+    assert_eq!(value("border", "2px solid rgb(255, 0, 0)", true), "2px solid red");
+    assert_eq!(value("color", "var(--foo, rgb(255, 255, 0))", true), "var(--foo,#ff0)");
+    assert_eq!(value("--foo", "red", false), "red");
+  }
+
+  #[test]
+  fn test_mask_layers() {
+    minify_test(
+      ".foo { mask-image: url(a.svg), url(b.svg); mask-position: left, right; mask-size: 16px, 16px; mask-repeat: repeat-y, repeat-y; mask-clip: border-box, border-box; mask-origin: border-box, border-box; mask-composite: add, add; mask-mode: match-source, match-source }",
+      ".foo{mask:url(a.svg) 0/16px repeat-y,url(b.svg) 100%/16px repeat-y}",
+    );
+    minify_test(
+      ".foo { mask: url(a.svg) left / 16px repeat-y, url(b.svg) right / 16px repeat-y; mask-size: 32px, 32px }",
+      ".foo{mask:url(a.svg) 0/32px repeat-y,url(b.svg) 100%/32px repeat-y}",
+    );
+
+    // mask-border is a separate shorthand, and is not reset by mask.
+    minify_test(
+      ".foo { mask: url(a.svg); mask-border: url(b.png) 25 }",
+      ".foo{mask:url(a.svg);mask-border:url(b.png) 25}",
+    );
+
+    prefix_test(
+      ".foo { mask: url(a.svg) left / 16px repeat-y, url(b.svg) right / 16px repeat-y }",
+      indoc! { r#"
+        .foo {
+          -webkit-mask: url(a.svg) 0 / 16px repeat-y, url(b.svg) 100% / 16px repeat-y;
+          mask: url(a.svg) 0 / 16px repeat-y, url(b.svg) 100% / 16px repeat-y;
+        }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      ".foo { mask: url(a.svg) left / 16px repeat-y, url(b.svg) right / 16px repeat-y }",
+      indoc! { r#"
+        .foo {
+          mask: url(a.svg) 0 / 16px repeat-y, url(b.svg) 100% / 16px repeat-y;
+        }
+    "#},
+      Browsers {
+        firefox: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { mask-image: url(a.svg), url(b.svg) }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let urls: Vec<&str> = res
+      .dependencies
+      .as_ref()
+      .unwrap()
+      .iter()
+      .map(|dep| match dep {
+        Dependency::Url(dep) => dep.url.as_str(),
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(urls, vec!["a.svg", "b.svg"]);
+  }
+
+  #[test]
+  fn test_white_space_longhands() {
+    minify_test(".foo { text-wrap: balance }", ".foo{text-wrap:balance}");
+    minify_test(".foo { text-wrap: Pretty }", ".foo{text-wrap:pretty}");
+    minify_test(
+      ".foo { white-space-collapse: preserve-breaks }",
+      ".foo{white-space-collapse:preserve-breaks}",
+    );
+
+    // Longhands are combined into the equivalent white-space keyword.
+    minify_test(
+      ".foo { white-space-collapse: collapse; text-wrap: nowrap }",
+      ".foo{white-space:nowrap}",
+    );
+    minify_test(
+      ".foo { white-space-collapse: preserve; text-wrap: wrap }",
+      ".foo{white-space:pre-wrap}",
+    );
+    minify_test(
+      ".foo { white-space-collapse: preserve; text-wrap: nowrap }",
+      ".foo{white-space:pre}",
+    );
+    minify_test(
+      ".foo { white-space-collapse: preserve-breaks; text-wrap: wrap }",
+      ".foo{white-space:pre-line}",
+    );
+    minify_test(
+      ".foo { white-space-collapse: break-spaces; text-wrap: wrap }",
+      ".foo{white-space:break-spaces}",
+    );
+
+    // Legacy keywords are combined with the longhands that follow them when the result is equivalent.
+    minify_test(
+      ".foo { white-space: pre; text-wrap: wrap }",
+      ".foo{white-space:pre-wrap}",
+    );
+    minify_test(
+      ".foo { white-space: nowrap; text-wrap: wrap }",
+      ".foo{white-space:normal}",
+    );
+    minify_test(
+      ".foo { white-space: pre-wrap; white-space-collapse: collapse }",
+      ".foo{white-space:normal}",
+    );
+    minify_test(
+      ".foo { white-space: pre; text-wrap: balance }",
+      ".foo{white-space:pre;text-wrap:balance}",
+    );
+    minify_test(
+      ".foo { white-space: normal; text-wrap: balance }",
+      ".foo{white-space:normal;text-wrap:balance}",
+    );
+    minify_test(
+      ".foo { white-space: break-spaces; text-wrap: nowrap }",
+      ".foo{white-space:break-spaces;text-wrap:nowrap}",
+    );
+    minify_test(
+      ".foo { white-space: nowrap; white-space-collapse: discard }",
+      ".foo{white-space:nowrap;white-space-collapse:discard}",
+    );
+    minify_test(
+      ".foo { white-space: pre; text-wrap: var(--wrap) }",
+      ".foo{white-space:pre;text-wrap:var(--wrap)}",
+    );
+
+    // The shorthand only overrides preceding longhands in browsers that support them.
+    minify_test(
+      ".foo { text-wrap: balance; white-space: pre }",
+      ".foo{text-wrap:balance;white-space:pre}",
+    );
+
+    // Longhands are not combined if some targets don't support them.
+    let safari = Browsers {
+      safari: Some(16 << 16),
+      ..Browsers::default()
+    };
+    prefix_test(
+      ".foo { white-space: pre; text-wrap: wrap }",
+      indoc! {r#"
+      .foo {
+        white-space: pre;
+        text-wrap: wrap;
+      }
+    "#},
+      safari,
+    );
+    prefix_test(
+      ".foo { white-space-collapse: preserve; text-wrap: nowrap }",
+      indoc! {r#"
+      .foo {
+        white-space-collapse: preserve;
+        text-wrap: nowrap;
+      }
+    "#},
+      safari,
+    );
+    prefix_test(
+      ".foo { white-space: pre; text-wrap: wrap }",
+      indoc! {r#"
+      .foo {
+        white-space: pre-wrap;
+      }
+    "#},
+      Browsers {
+        safari: Some(18 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_line_clamp() {
+    minify_test(".foo { line-clamp: 3 }", ".foo{line-clamp:3}");
+    minify_test(".foo { line-clamp: none }", ".foo{line-clamp:none}");
+    minify_test(".foo { -webkit-line-clamp: 3 }", ".foo{-webkit-line-clamp:3}");
+    minify_test(
+      ".foo { -webkit-line-clamp: 3; line-clamp: 3 }",
+      ".foo{-webkit-line-clamp:3;line-clamp:3}",
+    );
+    minify_test(".foo { line-clamp: 2; line-clamp: 3 }", ".foo{line-clamp:3}");
+    minify_test(".foo { line-clamp: 0 }", ".foo{line-clamp:0}");
+
+    prefix_test(
+      r#"
+      .foo {
+        line-clamp: 3;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        display: -webkit-box;
+        -webkit-box-orient: vertical;
+        -webkit-line-clamp: 3;
+        line-clamp: 3;
+      }
+    "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    // Existing display and box-orient declarations are not overridden.
+    prefix_test(
+      r#"
+      .foo {
+        display: -webkit-inline-box;
+        line-clamp: 3;
+        overflow: hidden;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        display: -webkit-inline-box;
+        overflow: hidden;
+        -webkit-box-orient: vertical;
+        -webkit-line-clamp: 3;
+        line-clamp: 3;
+      }
+    "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -webkit-line-clamp: 2;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-line-clamp: 2;
+      }
+    "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        line-clamp: none;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-line-clamp: none;
+        line-clamp: none;
+      }
+    "#},
+      Browsers {
+        chrome: Some(100 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_parser_warnings() {
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    let source = indoc! {r#"
+      .foo {
+        colr: red;
+        width: 10px 20px;
+        color: var(--foo) bar;
+        margin: env(safe-area-inset-top) 1px;
+        --custom: whatever;
+        height: 10px;
+      }
+    "#};
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      source,
+      ParserOptions {
+        warnings: Some(warnings.clone()),
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+
+    let warnings = warnings.read().unwrap();
+    let kinds: Vec<_> = warnings.iter().map(|w| w.kind.clone()).collect();
+    assert_eq!(
+      kinds,
+      vec![
+        ParserWarningKind::UnknownProperty {
+          property: "colr".into()
+        },
+        ParserWarningKind::InvalidPropertyValue {
+          property: "width".into(),
+          value: "10px 20px".into()
+        },
+      ]
+    );
+    assert_eq!(warnings[0].loc.line, 1);
+    assert_eq!(warnings[1].loc.line, 2);
+
+    // Declarations are still preserved as is.
+    let res = stylesheet.to_css(PrinterOptions::default()).unwrap();
+    assert!(res.code.contains("colr: red"));
+    assert!(res.code.contains("width: 10px 20px"));
+  }
+
+  #[test]
+  fn test_zoom() {
+    fn zoom_test(source: &str, expected: &str, targets: Browsers) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(targets),
+          convert_zoom: true,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    minify_test(".foo { zoom: 1.5 }", ".foo{zoom:1.5}");
+    minify_test(".foo { zoom: 150% }", ".foo{zoom:150%}");
+    minify_test(".foo { zoom: normal }", ".foo{zoom:normal}");
+    minify_test(".foo { zoom: reset }", ".foo{zoom:reset}");
+    minify_test(".foo { zoom: -1 }", ".foo{zoom:-1}");
+
+    let firefox = Browsers {
+      firefox: Some(100 << 16),
+      ..Browsers::default()
+    };
+    let safari = Browsers {
+      safari: Some(14 << 16),
+      ..Browsers::default()
+    };
+
+    zoom_test(
+      ".foo { zoom: 1.5 }",
+      ".foo{transform:scale(1.5);transform-origin:0 0}",
+      firefox,
+    );
+    zoom_test(
+      ".foo { zoom: 200% }",
+      ".foo{transform:scale(2);transform-origin:0 0}",
+      firefox,
+    );
+    zoom_test(".foo { zoom: 1.5 }", ".foo{zoom:1.5}", safari);
+    zoom_test(
+      ".foo { zoom: 1.5 }",
+      ".foo{zoom:1.5}",
+      Browsers {
+        firefox: Some(126 << 16),
+        ..Browsers::default()
+      },
+    );
+    zoom_test(".foo { zoom: normal }", ".foo{zoom:normal}", firefox);
+    zoom_test(
+      ".foo { zoom: 1.5; transform: rotate(45deg) }",
+      ".foo{transform:rotate(45deg);zoom:1.5}",
+      firefox,
+    );
+    zoom_test(
+      ".foo { transform-origin: 10px 10px; zoom: 2 }",
+      ".foo{transform-origin:10px 10px;zoom:2}",
+      firefox,
+    );
+  }
+
+  #[test]
+  fn test_media_simplify() {
+    minify_test(
+      "@media not (not (color)) { .foo { color: red }}",
+      "@media (color){.foo{color:red}}",
+    );
+    minify_test(
+      "@media screen and (not (not (color))) { .foo { color: red }}",
+      "@media screen and (color){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (color) and (color) { .foo { color: red }}",
+      "@media (color){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (color) or (hover) or (color) { .foo { color: red }}",
+      "@media (color) or (hover){.foo{color:red}}",
+    );
+    minify_test(
+      "@media ((color)) { .foo { color: red }}",
+      "@media (color){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (color) and ((hover) and (width > 100px)) { .foo { color: red }}",
+      "@media (color) and (hover) and (width>100px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (color) and ((hover) or (width > 100px)) { .foo { color: red }}",
+      "@media (color) and ((hover) or (width>100px)){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (not (color)) and (not (hover)) { .foo { color: red }}",
+      "@media not ((color) or (hover)){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (not (color)) or (hover) { .foo { color: red }}",
+      "@media (not (color)) or (hover){.foo{color:red}}",
+    );
+    minify_test(
+      "@media print, print { .foo { color: red }}",
+      "@media print{.foo{color:red}}",
+    );
+    minify_test(
+      "@media not print and (color) and (color) { .foo { color: red }}",
+      "@media not print and (color){.foo{color:red}}",
+    );
+
+    prefix_test(
+      "@media only screen and (color) { .foo { color: red }}",
+      indoc! {r#"
+        @media screen and (color) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      Browsers {
+        chrome: Some(95 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      "@media only screen and (color) { .foo { color: red }}",
+      indoc! {r#"
+        @media only screen and (color) {
+          .foo {
+            color: red;
+          }
+        }
+      "#},
+      Browsers {
+        ie: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_quotes() {
+    minify_test(".foo { quotes: auto }", ".foo{quotes:auto}");
+    minify_test(".foo { quotes: NONE }", ".foo{quotes:none}");
+    minify_test(".foo { quotes: '«' '»' }", ".foo{quotes:\"«\"\"»\"}");
+    minify_test(
+      r#".foo { quotes: "\201C" "\201D" "\2018" "\2019" '«' '»' "‹" "›" }"#,
+      r#".foo{quotes:"“""”""‘""’""«""»""‹""›"}"#,
+    );
+    test(
+      r#".foo { quotes: "“" "”" "‘" "’" }"#,
+      indoc! {r#"
+      .foo {
+        quotes: "“" "”" "‘" "’";
+      }
+      "#},
+    );
+
+    prefix_test(
+      ".foo { hyphens: auto }",
+      indoc! {r#"
+      .foo {
+        -webkit-hyphens: auto;
+        -ms-hyphens: auto;
+        hyphens: auto;
+      }
+      "#},
+      Browsers {
+        safari: Some(14 << 16),
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_tab_size_prefix() {
+    prefix_test(
+      ".foo { tab-size: 2em }",
+      indoc! {r#"
+      .foo {
+        -moz-tab-size: 2em;
+        tab-size: 2em;
+      }
+      "#},
+      Browsers {
+        firefox: Some(60 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { tab-size: 4 }",
+      indoc! {r#"
+      .foo {
+        tab-size: 4;
+      }
+      "#},
+      Browsers {
+        firefox: Some(91 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { -moz-tab-size: 4; tab-size: 4 }",
+      indoc! {r#"
+      .foo {
+        tab-size: 4;
+      }
+      "#},
+      Browsers {
+        firefox: Some(91 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    // The initial value is preserved, since it may override an inherited value.
+    minify_test(".foo { tab-size: 8 }", ".foo{tab-size:8}");
+    minify_test(".foo { tab-size: 8px }", ".foo{tab-size:8px}");
+  }
+
+  #[test]
+  fn test_concat() {
+    let a = StyleSheet::parse("a.css".into(), ".a { color: red }", ParserOptions::default()).unwrap();
+    let b = StyleSheet::parse(
+      "b.css".into(),
+      ".b { color: green }\n@media print { .c { color: blue } }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let stylesheet = StyleSheet::concat(vec![a, b]);
+    assert_eq!(stylesheet.sources, vec!["a.css".to_string(), "b.css".to_string()]);
+    assert_eq!(stylesheet.rules.0.len(), 3);
+
+    let locs: Vec<_> = stylesheet
+      .rules
+      .0
+      .iter()
+      .map(|rule| match rule {
+        CssRule::Style(style) => (style.loc.source_index, style.loc.line),
+        CssRule::Media(media) => match &media.rules.0[0] {
+          CssRule::Style(style) => {
+            assert_eq!(style.loc.source_index, media.loc.source_index);
+            (media.loc.source_index, media.loc.line)
+          }
+          _ => unreachable!(),
+        },
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(locs, vec![(0, 0), (1, 0), (1, 1)]);
+
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ".a{color:red}.b{color:green}@media print{.c{color:#00f}}");
+
+    // Style sheets that were already combined keep their relative source indices.
+    let c = StyleSheet::parse("c.css".into(), ".c { color: red }", ParserOptions::default()).unwrap();
+    let stylesheet = StyleSheet::concat(vec![c, stylesheet]);
+    assert_eq!(
+      stylesheet.sources,
+      vec!["c.css".to_string(), "a.css".to_string(), "b.css".to_string()]
+    );
+    let indices: Vec<_> = stylesheet
+      .rules
+      .0
+      .iter()
+      .map(|rule| match rule {
+        CssRule::Style(style) => style.loc.source_index,
+        CssRule::Media(media) => media.loc.source_index,
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(indices, vec![0, 1, 2, 2]);
+
+    // CSS module names are hashed based on the original file.
+    let options = ParserOptions {
+      css_modules: true,
+      ..ParserOptions::default()
+    };
+    let a = StyleSheet::parse("a.css".into(), ".foo { color: red }", options.clone()).unwrap();
+    let b = StyleSheet::parse("b.css".into(), ".bar { color: green }", options).unwrap();
+    let res = StyleSheet::concat(vec![a, b])
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let exports = res.exports.unwrap();
+    assert_eq!(
+      exports["foo"].name,
+      format!("{}_foo", crate::css_modules::hash("a.css"))
+    );
+    assert_eq!(
+      exports["bar"].name,
+      format!("{}_bar", crate::css_modules::hash("b.css"))
+    );
+
+    // The same name exported from several files refers to the hashed name from each of them.
+    let options = ParserOptions {
+      css_modules: true,
+      ..ParserOptions::default()
+    };
+    let a = StyleSheet::parse("a.css".into(), ".foo { color: red }", options.clone()).unwrap();
+    let b = StyleSheet::parse("b.css".into(), ".foo { color: green }", options).unwrap();
+    let res = StyleSheet::concat(vec![a, b])
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let a_foo = format!("{}_foo", crate::css_modules::hash("a.css"));
+    let b_foo = format!("{}_foo", crate::css_modules::hash("b.css"));
+    assert_eq!(res.code, format!(".{}{{color:red}}.{}{{color:green}}", a_foo, b_foo));
+    let exports = res.exports.unwrap();
+    assert_eq!(exports.len(), 1);
+    assert_eq!(exports["foo"].name, a_foo);
+    assert_eq!(
+      exports["foo"].composes,
+      vec![crate::css_modules::CssModuleReference::Local { name: b_foo }]
+    );
+  }
+
+  #[test]
+  fn test_misplaced_imports() {
+    fn import_test(sources: &[&str], expected: &str, expected_warnings: Vec<(&str, u32)>) {
+      let sheets = sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| StyleSheet::parse(format!("{}.css", i), source, ParserOptions::default()).unwrap())
+        .collect();
+      let mut stylesheet = StyleSheet::concat(sheets);
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          warnings: Some(warnings.clone()),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+
+      let warnings = warnings.read().unwrap();
+      assert!(warnings
+        .iter()
+        .all(|warning| warning.kind == MinifyWarningKind::MisplacedImportRule));
+      let locs: Vec<_> = warnings
+        .iter()
+        .map(|warning| {
+          let loc = warning.loc.as_ref().unwrap();
+          (loc.filename.as_str(), loc.line)
+        })
+        .collect();
+      assert_eq!(locs, expected_warnings);
+    }
+
+    import_test(
+      &["@import \"a.css\"; .foo { color: red }"],
+      "@import \"a.css\";.foo{color:red}",
+      vec![],
+    );
+    import_test(
+      &[
+        "@layer foo; @import \"a.css\";",
+        "@import \"b.css\"; .foo { color: red }",
+      ],
+      "@layer foo;@import \"a.css\";@import \"b.css\";.foo{color:red}",
+      vec![],
+    );
+    import_test(
+      &[".foo { color: red }", "@import \"b.css\";\n.bar { color: green }"],
+      ".foo{color:red}.bar{color:green}",
+      vec![("1.css", 0)],
+    );
+    import_test(
+      &["@import \"a.css\"; @layer foo;", "@import \"b.css\";"],
+      "@import \"a.css\";@layer foo;",
+      vec![("1.css", 0)],
+    );
+    import_test(
+      &["@namespace \"http://example.com\";", "@import \"b.css\";"],
+      "@namespace \"http://example.com\";",
+      vec![("1.css", 0)],
+    );
+  }
+
+  #[test]
+  fn test_preserve_unknown_at_rules() {
+    fn unknown_at_rule_test(source: &str, expected: &str, minify: bool) {
+      let options = ParserOptions {
+        preserve_unknown_at_rules: true,
+        ..ParserOptions::default()
+      };
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, options).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    unknown_at_rule_test(
+      "@unknown foo { bar: baz }",
+      indoc! {r#"
+      @unknown foo {
+        bar: baz
+      }
+    "#},
+      false,
+    );
+    unknown_at_rule_test("@unknown foo;", "@unknown foo;\n", false);
+    unknown_at_rule_test(
+      "@unknown foo; .foo { color: red }",
+      "@unknown foo;.foo{color:red}",
+      true,
+    );
+    unknown_at_rule_test("@media print { @unknown foo; }", "@media print{@unknown foo;}", true);
+
+    // Braces within strings and nested blocks are part of the at-rule.
+    let options = ParserOptions {
+      preserve_unknown_at_rules: true,
+      ..ParserOptions::default()
+    };
+    let source = r#"@unknown foo { a { b: "}" } } .foo { color: red }"#;
+    let stylesheet = StyleSheet::parse("test.css".into(), source, options.clone()).unwrap();
+    assert_eq!(stylesheet.rules.0.len(), 2);
+    assert!(matches!(&stylesheet.rules.0[0], CssRule::Custom(rule) if rule.name == "unknown"));
+    assert!(matches!(&stylesheet.rules.0[1], CssRule::Style(_)));
+    let code = stylesheet.to_css(PrinterOptions::default()).unwrap().code;
+    assert!(code.contains(r#""}""#));
+
+    // The output is stable when parsed again.
+    let stylesheet = StyleSheet::parse("test.css".into(), &code, options).unwrap();
+    assert_eq!(stylesheet.to_css(PrinterOptions::default()).unwrap().code, code);
+
+    // Unknown at-rules are dropped by default.
+    minify_test("@unknown foo { bar: baz } .foo { color: red }", ".foo{color:red}");
+  }
+
+  #[test]
+  fn test_unsupported_has() {
+    fn has_test(source: &str, expected: &str, unsupported_has: UnsupportedHasBehavior) -> Vec<MinifyWarningKind> {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            chrome: Some(100 << 16),
+            ..Browsers::default()
+          }),
+          unsupported_has,
+          warnings: Some(warnings.clone()),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+      let warnings = warnings.read().unwrap().iter().map(|w| w.kind.clone()).collect();
+      warnings
+    }
+
+    let source = ".foo, .bar:has(.baz) { color: red }";
+    assert_eq!(
+      has_test(source, ".foo,.bar:has(.baz){color:red}", UnsupportedHasBehavior::Keep),
+      vec![]
+    );
+    assert_eq!(
+      has_test(source, ".foo,.bar:has(.baz){color:red}", UnsupportedHasBehavior::Warn),
+      vec![MinifyWarningKind::UnsupportedSelector {
+        selector: ".foo, .bar:has(.baz)".into()
+      }]
+    );
+    assert_eq!(
+      has_test(source, ".foo{color:red}", UnsupportedHasBehavior::Drop),
+      vec![]
+    );
+    assert_eq!(
+      has_test(
+        ".foo:not(:has(.bar)) { color: red } .baz { color: green }",
+        ".baz{color:green}",
+        UnsupportedHasBehavior::Drop
+      ),
+      vec![]
+    );
+    assert_eq!(
+      has_test(".foo:has(> .bar) { color: red }", "", UnsupportedHasBehavior::Drop),
+      vec![]
+    );
+
+    // :is() and :where() ignore invalid arguments, so only those using :has() are removed.
+    assert_eq!(
+      has_test(
+        ".foo:is(.bar, .baz:has(.qux)) { color: red }",
+        ".foo:is(.bar){color:red}",
+        UnsupportedHasBehavior::Drop
+      ),
+      vec![]
+    );
+    assert_eq!(
+      has_test(
+        ":where(.foo:has(.bar), .baz) .qux { color: red }",
+        ":where(.baz) .qux{color:red}",
+        UnsupportedHasBehavior::Drop
+      ),
+      vec![]
+    );
+    assert_eq!(
+      has_test(
+        ".foo, :is(.bar:has(.baz)) { color: red }",
+        ".foo{color:red}",
+        UnsupportedHasBehavior::Drop
+      ),
+      vec![]
+    );
+
+    // Targets that support :has() are unaffected.
+    let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    stylesheet
+      .minify(MinifyOptions {
+        targets: Some(Browsers {
+          chrome: Some(105 << 16),
+          ..Browsers::default()
+        }),
+        unsupported_has: UnsupportedHasBehavior::Drop,
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ".foo,.bar:has(.baz){color:red}");
+
+    // When only some targets support :has(), the selectors are kept with a warning.
+    let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    stylesheet
+      .minify(MinifyOptions {
+        targets: Some(Browsers {
+          chrome: Some(105 << 16),
+          safari: Some(14 << 16),
+          ..Browsers::default()
+        }),
+        unsupported_has: UnsupportedHasBehavior::Drop,
+        warnings: Some(warnings.clone()),
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ".foo,.bar:has(.baz){color:red}");
+    assert_eq!(
+      warnings.read().unwrap().iter().map(|w| w.kind.clone()).collect::<Vec<_>>(),
+      vec![MinifyWarningKind::UnsupportedSelector {
+        selector: ".foo, .bar:has(.baz)".into()
+      }]
+    );
+  }
+
+  #[test]
+  fn test_property_same_value() {
+    fn same_value(source: &str) -> bool {
+      let block = StyleAttribute::parse(source).unwrap().declarations;
+      block.declarations[0].same_value(&block.important_declarations[0])
+    }
+
+    assert!(same_value("color: red; color: red !important"));
+    assert!(same_value(
+      "width: calc(100% - 10px); width: calc(100% - 10px) !important"
+    ));
+    assert!(!same_value("color: red; color: blue !important"));
+    assert!(!same_value("margin-left: 10px; margin-right: 10px !important"));
+
+    // Unparsed values are compared by property and tokens.
+    assert!(same_value("color: var(--foo); color: var(--foo) !important"));
+    assert!(same_value("color: var(--foo)  ; color: var(--foo)!important"));
+    assert!(!same_value("color: var(--foo); color: var(--bar) !important"));
+    assert!(!same_value(
+      "color: var(--foo); background-color: var(--foo) !important"
+    ));
+
+    // Custom properties are compared by name and tokens, ignoring url locations.
+    assert!(same_value("--foo: url(a.png); --foo: url(a.png) !important"));
+    assert!(!same_value("--foo: url(a.png); --foo: url(b.png) !important"));
+    assert!(!same_value("--foo: 1px; --bar: 1px !important"));
+    assert!(same_value(
+      "--foo: var(--bar, url(a.png)); --foo: var(--bar, url(a.png)) !important"
+    ));
+    assert!(!same_value(
+      "--foo: var(--bar, url(a.png)); --foo: var(--bar, url(b.png)) !important"
+    ));
+
+    // Properties parsed with their source text are compared by their parsed value.
+    let raw = ParserOptions {
+      preserve_raw_values: true,
+      ..ParserOptions::default()
+    };
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { color: RED; color: red !important; width: 1px; width: 2px !important }",
+      raw,
+    )
+    .unwrap();
+    match &stylesheet.rules.0[0] {
+      CssRule::Style(style) => {
+        let block = &style.declarations;
+        assert!(matches!(block.declarations[0], Property::Verbatim(..)));
+        assert!(block.declarations[0].same_value(&block.important_declarations[0]));
+        assert!(!block.declarations[1].same_value(&block.important_declarations[1]));
+      }
+      _ => unreachable!(),
+    }
+
+    // Parsed urls are compared by value, regardless of their location.
+    assert!(same_value(
+      "background-image: url(a.png); background-image: url(a.png) !important"
+    ));
+    assert!(!same_value(
+      "background-image: url(a.png); background-image: url(b.png) !important"
+    ));
+  }
+
+  #[test]
+  fn test_var_fallbacks() {
+    minify_test(".foo { color: var( --foo ); }", ".foo{color:var(--foo)}");
+    minify_test(".foo { color: var( --foo , red ); }", ".foo{color:var(--foo,red)}");
+    minify_test(
+      ".foo { width: var( --foo , var( --bar , 10px ) ); }",
+      ".foo{width:var(--foo,var(--bar,10px))}",
+    );
+    minify_test(
+      ".foo { --test: var( --foo , var( --bar , 10px ) ); }",
+      ".foo{--test:var(--foo,var(--bar,10px))}",
+    );
+    test(
+      ".foo { width: var( --foo , var( --bar , 10px ) ) }",
+      indoc! {r#"
+      .foo {
+        width: var(--foo, var(--bar, 10px));
+      }
+    "#},
+    );
+
+    // Commas within the fallback are part of the fallback.
+    minify_test(
+      ".foo { font-family: var(--font, Helvetica , Arial, sans-serif); }",
+      ".foo{font-family:var(--font,Helvetica,Arial,sans-serif)}",
+    );
+    minify_test(
+      ".foo { transition: var(--foo, opacity 1s, transform 2s), color 1s; }",
+      ".foo{transition:var(--foo,opacity 1s,transform 2s),color 1s}",
+    );
+    test(
+      ".foo { font-family: var(--font, Helvetica, Arial) }",
+      indoc! {r#"
+      .foo {
+        font-family: var(--font, Helvetica, Arial);
+      }
+    "#},
+    );
+
+    // An empty fallback is only redundant when the var() is the entire value of a normal property.
+    minify_test(".foo { color: var(--foo, ); }", ".foo{color:var(--foo)}");
+    minify_test(".foo { color: var(--foo,); }", ".foo{color:var(--foo)}");
+    minify_test(
+      ".foo { width: var(--foo, var(--bar,)); }",
+      ".foo{width:var(--foo,var(--bar))}",
+    );
+    minify_test(".foo { margin: var(--foo,) 10px; }", ".foo{margin:var(--foo,)10px}");
+    minify_test(".foo { --test: var(--foo, ); }", ".foo{--test:var(--foo,)}");
+    test(
+      ".foo { color: var(--foo, ) }",
+      indoc! {r#"
+      .foo {
+        color: var(--foo,);
+      }
+    "#},
+    );
+  }
+
+  #[test]
+  fn test_precision() {
+    fn precision_test(source: &str, expected: &str, precision: u8, round_authored_values: bool) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          precision: Some(precision),
+          round_authored_values,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    minify_test(".foo { width: calc(100px / 3) }", ".foo{width:33.3333px}");
+    precision_test(".foo { width: calc(100px / 3) }", ".foo{width:33.3px}", 3, false);
+    precision_test(".foo { width: calc(100px / 3) }", ".foo{width:30px}", 1, false);
+    precision_test(".foo { width: calc(2px / 3) }", ".foo{width:.67px}", 2, false);
+    precision_test(
+      ".foo { margin-left: calc(-2px / 3) }",
+      ".foo{margin-left:-.67px}",
+      2,
+      false,
+    );
+    precision_test(
+      ".foo { font-size: calc(100vw / 35) }",
+      ".foo{font-size:2.86vw}",
+      3,
+      false,
+    );
+    precision_test(".foo { opacity: calc(1 / 3) }", ".foo{opacity:.3333}", 4, false);
+    precision_test(".foo { width: calc(100% / 3) }", ".foo{width:33.3%}", 3, false);
+
+    // Exact values are not rounded, and trailing zeros are trimmed.
+    precision_test(".foo { width: calc(100vw / 2) }", ".foo{width:50vw}", 1, false);
+    precision_test(".foo { width: calc(1px / 8) }", ".foo{width:.125px}", 2, false);
+
+    // Authored values are only rounded when enabled.
+    precision_test(".foo { width: 1.23456px }", ".foo{width:1.23456px}", 3, false);
+    precision_test(".foo { width: 1.23456px }", ".foo{width:1.23px}", 3, true);
+    precision_test(".foo { width: 1.99999px }", ".foo{width:2px}", 3, true);
+  }
+
+  #[test]
+  fn test_touch_action() {
+    minify_test(".foo { touch-action: auto }", ".foo{touch-action:auto}");
+    minify_test(".foo { touch-action: none }", ".foo{touch-action:none}");
+    minify_test(".foo { touch-action: Manipulation }", ".foo{touch-action:manipulation}");
+    minify_test(".foo { touch-action: pan-x }", ".foo{touch-action:pan-x}");
+    minify_test(".foo { touch-action: pan-x pan-y }", ".foo{touch-action:pan-x pan-y}");
+    minify_test(
+      ".foo { touch-action: pinch-zoom pan-down pan-left }",
+      ".foo{touch-action:pan-left pan-down pinch-zoom}",
+    );
+    minify_test(".foo { touch-action: pan-y pan-x }", ".foo{touch-action:pan-x pan-y}");
+    minify_test(".foo { touch-action: pinch-zoom }", ".foo{touch-action:pinch-zoom}");
+
+    // Invalid combinations are not parsed.
+    for value in [
+      "none pan-x",
+      "pan-x auto",
+      "manipulation pinch-zoom",
+      "pan-x pan-left",
+      "pan-up pan-y",
+      "pan-x pan-x",
+      "pinch-zoom pinch-zoom",
+      "pan-z",
+    ] {
+      assert!(matches!(
+        Property::parse_string("touch-action", value, ParserOptions::default()),
+        Ok(Property::Unparsed(_)) | Err(_)
+      ));
+    }
+    minify_test(".foo { touch-action: none pan-x }", ".foo{touch-action:none pan-x}");
+
+    prefix_test(
+      ".foo { touch-action: pan-y pan-x }",
+      indoc! {r#"
+      .foo {
+        -ms-touch-action: pan-x pan-y;
+        touch-action: pan-x pan-y;
+      }
+    "#},
+      Browsers {
+        ie: Some(10 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { -ms-touch-action: none; touch-action: none }",
+      indoc! {r#"
+      .foo {
+        touch-action: none;
+      }
+    "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_stylesheet_selectors() {
+    let source = ".foo, .bar > .baz::before { color: red }\n#qux:hover { color: blue }";
+    let stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+    let loc = |line, column| Location {
+      source_index: 0,
+      line,
+      column,
+      generated: false,
+    };
+    assert_eq!(
+      stylesheet.selectors(),
+      vec![
+        (".foo".into(), loc(0, 1)),
+        (".bar > .baz:before".into(), loc(0, 1)),
+        ("#qux:hover".into(), loc(1, 1)),
+      ]
+    );
+
+    let source = r#"
+@media print {
+  .foo { color: red }
+}
+@supports (display: grid) {
+  @layer base {
+    .bar ~ .baz { color: red }
+  }
+}
+.a, .b {
+  color: red;
+  & > .c { color: blue }
+  @nest .d & { color: green }
+}
+@font-face { font-family: Foo }
+"#;
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      source,
+      ParserOptions {
+        nesting: true,
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+    let selectors: Vec<_> = stylesheet.selectors().into_iter().map(|(s, _)| s).collect();
+    assert_eq!(
+      selectors,
+      vec![".foo", ".bar ~ .baz", ".a", ".b", ":is(.a, .b) > .c", ".d :is(.a, .b)"]
+    );
+  }
+
+  #[test]
+  fn test_background_repeat() {
+    minify_test(
+      ".foo { background-repeat: repeat repeat }",
+      ".foo{background-repeat:repeat}",
+    );
+    minify_test(
+      ".foo { background-repeat: no-repeat no-repeat }",
+      ".foo{background-repeat:no-repeat}",
+    );
+    minify_test(
+      ".foo { background-repeat: repeat no-repeat }",
+      ".foo{background-repeat:repeat-x}",
+    );
+    minify_test(
+      ".foo { background-repeat: no-repeat repeat }",
+      ".foo{background-repeat:repeat-y}",
+    );
+    minify_test(
+      ".foo { background-repeat: space space }",
+      ".foo{background-repeat:space}",
+    );
+    minify_test(
+      ".foo { background-repeat: round round }",
+      ".foo{background-repeat:round}",
+    );
+    minify_test(
+      ".foo { background-repeat: repeat-x }",
+      ".foo{background-repeat:repeat-x}",
+    );
+    minify_test(
+      ".foo { background-repeat: REPEAT-Y }",
+      ".foo{background-repeat:repeat-y}",
+    );
+
+    // Space and round have no single keyword form when combined with other values.
+    minify_test(
+      ".foo { background-repeat: space round }",
+      ".foo{background-repeat:space round}",
+    );
+    minify_test(
+      ".foo { background-repeat: repeat space }",
+      ".foo{background-repeat:repeat space}",
+    );
+    minify_test(
+      ".foo { background-repeat: no-repeat round }",
+      ".foo{background-repeat:no-repeat round}",
+    );
+
+    minify_test(
+      ".foo { background-repeat: repeat repeat, repeat no-repeat }",
+      ".foo{background-repeat:repeat,repeat-x}",
+    );
+    minify_test(
+      ".foo { background: url(foo.png) no-repeat repeat }",
+      ".foo{background:url(foo.png) repeat-y}",
+    );
+  }
+
+  #[test]
+  fn test_list_style_none() {
+    use crate::properties::list::{CounterStyle, ListStylePosition, ListStyleType, PredefinedCounterStyle};
+    use crate::values::image::Image;
+
+    minify_test(".foo { list-style: none }", ".foo{list-style:none}");
+    minify_test(".foo { list-style: none none }", ".foo{list-style:none}");
+    minify_test(".foo { list-style: none inside }", ".foo{list-style:none inside}");
+    minify_test(".foo { list-style: inside none }", ".foo{list-style:none inside}");
+    minify_test(
+      ".foo { list-style: none url(foo.png) }",
+      ".foo{list-style:none url(foo.png)}",
+    );
+    minify_test(
+      ".foo { list-style: url(foo.png) none }",
+      ".foo{list-style:none url(foo.png)}",
+    );
+    minify_test(".foo { list-style: none square }", ".foo{list-style:square}");
+    minify_test(
+      ".foo { list-style: square none inside }",
+      ".foo{list-style:square inside}",
+    );
+    minify_test(".foo { list-style: none none none }", ".foo{list-style:none none none}");
+    minify_test(
+      ".foo { list-style: none square url(foo.png) }",
+      ".foo{list-style:none square url(foo.png)}",
+    );
+
+    let list_style = |value| match Property::parse_string("list-style", value, ParserOptions::default()) {
+      Ok(Property::ListStyle(list_style)) => Some(list_style),
+      _ => None,
+    };
+    let none = list_style("none").unwrap();
+    assert_eq!(none.list_style_type, ListStyleType::None);
+    assert_eq!(none.image, Image::None);
+    let none = list_style("none square").unwrap();
+    assert_eq!(
+      none.list_style_type,
+      ListStyleType::CounterStyle(CounterStyle::Predefined(PredefinedCounterStyle::Square))
+    );
+    assert_eq!(none.image, Image::None);
+    assert!(matches!(list_style("none url(foo.png)").unwrap().image, Image::Url(_)));
+    assert_eq!(
+      list_style("none url(foo.png)").unwrap().list_style_type,
+      ListStyleType::None
+    );
+    assert_eq!(list_style("none none none"), None);
+    assert_eq!(list_style("none square url(foo.png)"), None);
+    for value in ["none inside", "inside none"] {
+      let none = list_style(value).unwrap();
+      assert_eq!(none.list_style_type, ListStyleType::None);
+      assert_eq!(none.image, Image::None);
+      assert_eq!(none.position, ListStylePosition::Inside);
+    }
+
+    // Longhands are merged into the shorthand.
+    test(
+      r#"
+      .foo {
+        list-style-type: none;
+        list-style-image: none;
+        list-style-position: inside;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        list-style: none inside;
+      }
+    "#},
+    );
+    test(
+      r#"
+      .foo {
+        list-style: none;
+        list-style-type: square;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        list-style: square;
+      }
+    "#},
+    );
+    test(
+      r#"
+      .foo {
+        list-style: none;
+        list-style-image: url(foo.png);
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        list-style: none url(foo.png);
+      }
+    "#},
+    );
+
+    // Images are reported as dependencies.
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { list-style: square url(foo.png) inside }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: true,
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let dependencies = res.dependencies.unwrap();
+    assert_eq!(dependencies.len(), 1);
+    match &dependencies[0] {
+      Dependency::Url(dep) => {
+        assert_eq!(dep.url, "foo.png");
+        assert_eq!(
+          res.code,
+          format!(".foo{{list-style:square url({:?}) inside}}", dep.placeholder)
+        );
+      }
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn test_media_feature_value_error_location() {
+    fn error_location(source: &str) -> (u32, u32) {
+      let options = ParserOptions {
+        strict: true,
+        ..ParserOptions::default()
+      };
+      match StyleSheet::parse("test.css".into(), source, options) {
+        Ok(_) => unreachable!(),
+        Err(e) => {
+          assert_eq!(e.kind, ParserError::InvalidMediaQuery);
+          let loc = e.loc.unwrap();
+          (loc.line, loc.column)
+        }
+      }
+    }
+
+    assert_eq!(
+      error_location("@media (min-width: red) { .foo { color: red } }"),
+      (0, 20)
+    );
+    assert_eq!(error_location("@media (width > red) { .foo { color: red } }"), (0, 17));
+    assert_eq!(error_location("@media (red < width) { .foo { color: red } }"), (0, 9));
+    assert_eq!(
+      error_location("@media (100px < width < red) { .foo { color: red } }"),
+      (0, 25)
+    );
+    assert_eq!(
+      error_location("@media screen and (color) and (max-height: red) { .foo { color: red } }"),
+      (0, 44)
+    );
+    assert_eq!(
+      error_location("@media ((hover) or (min-width: red)) and (color) { .foo { color: red } }"),
+      (0, 32)
+    );
+    assert_eq!(
+      error_location("@media (color) and\n  (not (aspect-ratio: red)) { .foo { color: red } }"),
+      (1, 23)
+    );
+
+    // Keywords are allowed for unknown features.
+    minify_test(
+      "@media (min-foo: red) { .foo { color: red } }",
+      "@media (min-foo:red){.foo{color:red}}",
+    );
+
+    // Without strict mode, the query is preserved and a warning is reported at the value.
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      "@media (min-width: red) { .foo { color: red } }",
+      ParserOptions {
+        warnings: Some(warnings.clone()),
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, "@media (min-width:red){.foo{color:red}}");
+    let warnings = warnings.read().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+      warnings[0].kind,
+      ParserWarningKind::InvalidMediaFeatureValue {
+        feature: "min-width".into(),
+        value: "red".into()
+      }
+    );
+    assert_eq!((warnings[0].loc.line, warnings[0].loc.column), (0, 20));
+  }
+
+  #[test]
+  fn test_split_by_media() {
+    use crate::media_query::{DiscreteMediaFeature, DiscreteMediaFeatureValue, MediaContext, MediaType};
+
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      r#"
+      @layer base, components;
+      .a { color: red }
+      @media (min-width: 600px) { .b { color: green } }
+      @media (min-width: 1000px) { .c { color: red } }
+      @media (max-width: 700px) { .d { color: red } }
+      @media print { .e { color: red } }
+      @media (prefers-color-scheme: dark) { .f { color: red } }
+      @supports (display: grid) {
+        @media (width >= 800px) { .g { color: red } }
+        @media (400px < width < 900px) { .h { color: red } }
+      }
+      @layer base {
+        @media (min-width: 800px) and (prefers-color-scheme: dark) { .i { color: red } }
+      }
+      "#,
+      ParserOptions::default(),
+    )
+    .unwrap();
+
+    let (matching, rest) = stylesheet.split_by_media(MediaContext {
+      media_type: Some(MediaType::Screen),
+      min_width: Some(800.0),
+      features: vec![(
+        DiscreteMediaFeature::PrefersColorScheme,
+        DiscreteMediaFeatureValue::Dark,
+      )],
+      ..MediaContext::default()
+    });
+
+    let to_css = |stylesheet: &StyleSheet| {
+      stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code
+    };
+    assert_eq!(
+      to_css(&matching),
+      "@layer base,components;.a{color:red}.b{color:green}.f{color:red}@supports (display:grid){.g{color:red}}@layer base{.i{color:red}}"
+    );
+    assert_eq!(
+      to_css(&rest),
+      "@layer base,components;@media (min-width:1000px){.c{color:red}}@media (max-width:700px){.d{color:red}}@media print{.e{color:red}}@supports (display:grid){@media (400px<width<900px){.h{color:red}}}"
+    );
+
+    // Queries that depend on unknown values are not known to match.
+    let (matching, rest) = stylesheet.split_by_media(MediaContext {
+      min_width: Some(800.0),
+      ..MediaContext::default()
+    });
+    assert_eq!(
+      to_css(&matching),
+      "@layer base,components;.a{color:red}.b{color:green}@supports (display:grid){.g{color:red}}"
+    );
+    assert_eq!(
+      to_css(&rest),
+      "@layer base,components;@media (min-width:1000px){.c{color:red}}@media (max-width:700px){.d{color:red}}@media print{.e{color:red}}@media (prefers-color-scheme:dark){.f{color:red}}@supports (display:grid){@media (400px<width<900px){.h{color:red}}}@layer base{@media (min-width:800px) and (prefers-color-scheme:dark){.i{color:red}}}"
+    );
+
+    // Rules that define names or page styles are included in both style sheets.
+    let stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      r#"
+      @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+      @font-face { font-family: Foo; src: url(foo.woff) }
+      @property --x { syntax: "<length>"; inherits: false; initial-value: 0px }
+      @counter-style foo { system: cyclic; symbols: a b }
+      @page { margin: 1cm }
+      .a { animation: fade 1s }
+      @media (min-width: 1000px) { .b { font-family: Foo } }
+      "#,
+      ParserOptions::default(),
+    )
+    .unwrap();
+    let (matching, rest) = stylesheet.split_by_media(MediaContext {
+      min_width: Some(800.0),
+      ..MediaContext::default()
+    });
+    let shared = "@keyframes fade{0%{opacity:0}to{opacity:1}}@font-face{font-family:Foo;src:url(foo.woff)}@property --x{syntax:\"<length>\";inherits:false;initial-value:0}@counter-style foo{system:cyclic;symbols:a b}@page{margin:1cm}";
+    assert_eq!(to_css(&matching), format!("{}.a{{animation:fade 1s}}", shared));
+    assert_eq!(
+      to_css(&rest),
+      format!("{}@media (min-width:1000px){{.b{{font-family:Foo}}}}", shared)
+    );
+  }
+
+  #[test]
+  fn test_counters() {
+    use crate::properties::list::{Counter, CounterList};
+    use crate::values::ident::CustomIdent;
+
+    minify_test(
+      ".foo { counter-increment: foo 1 bar 2 baz }",
+      ".foo{counter-increment:foo bar 2 baz}",
+    );
+    minify_test(".foo { counter-increment: foo -1 }", ".foo{counter-increment:foo -1}");
+    minify_test(".foo { counter-reset: foo 0 bar 1 }", ".foo{counter-reset:foo bar 1}");
+    minify_test(".foo { counter-set: foo 0 bar 1 }", ".foo{counter-set:foo bar 1}");
+    minify_test(".foo { counter-set: foo 1 }", ".foo{counter-set:foo 1}");
+    minify_test(".foo { counter-reset: none }", ".foo{counter-reset:none}");
+    minify_test(".foo { counter-increment: NONE }", ".foo{counter-increment:none}");
+    minify_test(
+      ".foo { counter-reset: reversed(foo) 0 bar 0 }",
+      ".foo{counter-reset:reversed(foo) 0 bar}",
+    );
+    minify_test(
+      ".foo { counter-reset: reversed(foo) }",
+      ".foo{counter-reset:reversed(foo)}",
+    );
+    test(
+      ".foo { counter-reset: foo 2 bar reversed(baz) 5 }",
+      indoc! {r#"
+      .foo {
+        counter-reset: foo 2 bar reversed(baz) 5;
+      }
+    "#},
+    );
+
+    // Invalid values are preserved as is.
+    minify_test(
+      ".foo { counter-increment: reversed(foo) }",
+      ".foo{counter-increment:reversed(foo)}",
+    );
+    minify_test(".foo { counter-reset: foo none }", ".foo{counter-reset:foo none}");
+    minify_test(".foo { counter-reset: foo 1.5 }", ".foo{counter-reset:foo 1.5}");
+
+    let property = Property::parse_string("counter-increment", "foo 1 bar 2", ParserOptions::default()).unwrap();
+    match &property {
+      Property::CounterIncrement(list) => {
+        assert_eq!(
+          *list,
+          CounterList::Counters(vec![
+            Counter {
+              name: CustomIdent("foo".into()),
+              value: Some(1),
+              reversed: false,
+            },
+            Counter {
+              name: CustomIdent("bar".into()),
+              value: Some(2),
+              reversed: false,
+            },
+          ])
+        );
+      }
+      _ => unreachable!(),
+    }
+    assert_eq!(
+      property.value_to_css_string(PrinterOptions::default()).unwrap(),
+      "foo 1 bar 2"
+    );
+  }
+
+  #[test]
+  fn test_logical_fallback() {
+    fn logical_test(source: &str, expected: &str, logical_fallback: LogicalFallback) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            safari: Some(8 << 16),
+            ..Browsers::default()
+          }),
+          logical_fallback,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    let source = ".foo { margin-block: 2px 4px; margin-inline: 3px; padding-block-start: 1px }";
+    logical_test(
+      source,
+      ".foo{margin-top:2px;margin-bottom:4px;margin-left:3px;margin-right:3px;padding-top:1px}",
+      LogicalFallback::Convert,
+    );
+    logical_test(
+      source,
+      ".foo{margin-top:2px;margin-bottom:4px;margin-left:3px;margin-right:3px;padding-top:1px}@supports (margin-inline-start: 0){.foo{margin-block:2px 4px;margin-inline:3px;padding-block-start:1px}}",
+      LogicalFallback::PhysicalWithSupports,
+    );
+    logical_test(
+      source,
+      ".foo{margin-block:2px 4px;margin-inline:3px;padding-block-start:1px}@supports not (margin-inline-start: 0){.foo{margin-top:2px;margin-bottom:4px;margin-left:3px;margin-right:3px;padding-top:1px}}",
+      LogicalFallback::LogicalWithSupports,
+    );
+
+    // Properties with different browser support use separate conditions.
+    logical_test(
+      ".foo { inset-block-start: 0; margin-block-start: 1px }",
+      ".foo{margin-top:1px;top:0}@supports (margin-inline-start: 0){.foo{margin-block-start:1px}}@supports (inset-inline-start: 0){.foo{inset-block-start:0}}",
+      LogicalFallback::PhysicalWithSupports,
+    );
+
+    // Physical properties are unaffected.
+    logical_test(
+      ".foo { margin-top: 1px }",
+      ".foo{margin-top:1px}",
+      LogicalFallback::PhysicalWithSupports,
+    );
+
+    // Logical properties followed by physical properties are converted, so the later ones still win.
+    let source = ".foo { margin-block-start: 10px; margin-top: 0 }";
+    logical_test(
+      source,
+      ".foo{margin-top:10px;margin-top:0}",
+      LogicalFallback::PhysicalWithSupports,
+    );
+    logical_test(
+      source,
+      ".foo{margin-top:10px;margin-top:0}",
+      LogicalFallback::LogicalWithSupports,
+    );
+    logical_test(
+      ".foo { margin-top: 0; margin-block-start: 10px }",
+      ".foo{margin-top:0;margin-top:10px}@supports (margin-inline-start: 0){.foo{margin-block-start:10px}}",
+      LogicalFallback::PhysicalWithSupports,
+    );
+  }
+
+  #[test]
+  fn test_font_variant() {
+    minify_test(
+      ".foo { font-variant: small-caps tabular-nums no-common-ligatures; }",
+      ".foo{font-variant:no-common-ligatures small-caps tabular-nums}",
+    );
+    minify_test(".foo { font-variant: normal; }", ".foo{font-variant:normal}");
+    minify_test(".foo { font-variant: none; }", ".foo{font-variant:none}");
+    minify_test(
+      ".foo { font-variant: styleset(a, b) swash(c) historical-forms; }",
+      ".foo{font-variant:historical-forms styleset(a,b) swash(c)}",
+    );
+    minify_test(
+      ".foo { font-variant-east-asian: ruby jis78 full-width; }",
+      ".foo{font-variant-east-asian:jis78 full-width ruby}",
+    );
+    minify_test(
+      ".foo { font-variant-numeric: slashed-zero oldstyle-nums; }",
+      ".foo{font-variant-numeric:oldstyle-nums slashed-zero}",
+    );
+
+    minify_test(
+      r#"
+      .foo {
+        font-variant-ligatures: discretionary-ligatures;
+        font-variant-caps: small-caps;
+        font-variant-alternates: normal;
+        font-variant-numeric: tabular-nums;
+        font-variant-east-asian: normal;
+        font-variant-position: super;
+      }
+    "#,
+      ".foo{font-variant:discretionary-ligatures small-caps tabular-nums super}",
+    );
+    minify_test(
+      r#"
+      .foo {
+        font-variant-ligatures: none;
+        font-variant-caps: normal;
+        font-variant-alternates: normal;
+        font-variant-numeric: normal;
+        font-variant-east-asian: normal;
+        font-variant-position: normal;
+      }
+    "#,
+      ".foo{font-variant:none}",
+    );
+    minify_test(
+      r#"
+      .foo {
+        font-variant-ligatures: none;
+        font-variant-caps: small-caps;
+        font-variant-alternates: normal;
+        font-variant-numeric: tabular-nums;
+        font-variant-east-asian: normal;
+        font-variant-position: normal;
+      }
+    "#,
+      ".foo{font-variant:none;font-variant-caps:small-caps;font-variant-numeric:tabular-nums}",
+    );
+    minify_test(
+      ".foo { font-variant-numeric: tabular-nums; font-variant-caps: small-caps; }",
+      ".foo{font-variant-caps:small-caps;font-variant-numeric:tabular-nums}",
+    );
+    minify_test(
+      ".foo { font-variant-numeric: tabular-nums; font-variant: normal; }",
+      ".foo{font-variant:normal}",
+    );
+    minify_test(
+      ".foo { font-variant: small-caps; font-variant-ligatures: none; }",
+      ".foo{font-variant:none;font-variant-caps:small-caps}",
+    );
+    minify_test(
+      ".foo { font-variant: none; font-variant-numeric: ordinal; }",
+      ".foo{font-variant:none;font-variant-numeric:ordinal}",
+    );
+    minify_test(
+      ".foo { font-variant-numeric: tabular-nums; font: 12px Helvetica; }",
+      ".foo{font:12px Helvetica}",
+    );
+    minify_test(
+      ".foo { font: 12px Helvetica; font-variant-numeric: tabular-nums; }",
+      ".foo{font:12px Helvetica;font-variant-numeric:tabular-nums}",
+    );
+  }
+
+  #[test]
+  fn test_dedupe_font_faces() {
+    fn font_face_test(source: &str, expected: &str, dedupe_font_faces: bool) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          dedupe_font_faces,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    let source = r#"
+      @font-face { font-family: Foo; src: url(foo.woff2) format("woff2"); }
+      .a { color: red }
+      @font-face { src: url(foo.woff2) format("woff2"); font-family: "Foo"; }
+    "#;
+    font_face_test(
+      source,
+      ".a{color:red}@font-face{src:url(foo.woff2)format(\"woff2\");font-family:Foo}",
+      true,
+    );
+    font_face_test(
+      source,
+      "@font-face{font-family:Foo;src:url(foo.woff2)format(\"woff2\")}.a{color:red}@font-face{src:url(foo.woff2)format(\"woff2\");font-family:Foo}",
+      false,
+    );
+
+    font_face_test(
+      r#"
+      @font-face { font-family: Foo; src: url(foo.woff2); unicode-range: U+0025-00FF; }
+      @font-face { font-family: Foo; src: url(foo.woff2); unicode-range: U+0100-024F; }
+    "#,
+      "@font-face{font-family:Foo;src:url(foo.woff2);unicode-range:U+25-FF}@font-face{font-family:Foo;src:url(foo.woff2);unicode-range:U+100-24F}",
+      true,
+    );
+    font_face_test(
+      r#"
+      @font-face { font-family: Foo; src: url(foo.woff2); font-weight: 300; }
+      @font-face { font-family: Foo; src: url(foo.woff2); font-weight: bold; }
+      @font-face { font-family: Foo; src: url(foo.woff2); font-weight: 300; }
+    "#,
+      "@font-face{font-family:Foo;src:url(foo.woff2);font-weight:700}@font-face{font-family:Foo;src:url(foo.woff2);font-weight:300}",
+      true,
+    );
+
+    // Duplicates are found regardless of where their urls appear in the source.
+    font_face_test(
+      r#"
+      @font-face {
+        font-family: Foo;
+        src: url(foo.woff2) format("woff2"), url(foo.woff) format("woff");
+      }
+      .a { color: red }
+      @font-face { font-family: Foo; src:   url(foo.woff2) format("woff2"),
+        url(foo.woff) format("woff") }
+    "#,
+      ".a{color:red}@font-face{font-family:Foo;src:url(foo.woff2)format(\"woff2\"),url(foo.woff)format(\"woff\")}",
+      true,
+    );
+
+    // The last occurrence is kept, so it still takes precedence over faces declared in between.
+    font_face_test(
+      r#"
+      @font-face { font-family: Foo; src: url(a.woff2); }
+      @font-face { font-family: Foo; src: url(b.woff2); }
+      @font-face { font-family: Foo; src: url(a.woff2); }
+    "#,
+      "@font-face{font-family:Foo;src:url(b.woff2)}@font-face{font-family:Foo;src:url(a.woff2)}",
+      true,
+    );
+    font_face_test(
+      r#"
+      @font-face { font-family: Foo; src: url(foo.woff2); }
+      @media print {
+        @font-face { font-family: Foo; src: url(foo.woff2); }
+      }
+    "#,
+      "@font-face{font-family:Foo;src:url(foo.woff2)}@media print{@font-face{font-family:Foo;src:url(foo.woff2)}}",
+      true,
+    );
+  }
+
+  #[test]
+  fn test_minify_already_minified() {
+    fn minify(source: &str) -> String {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code
+    }
+
+    let source = r#"
+      @import "foo.css" print;
+      :root { --accent: #ff0000; }
+      .a { margin-top: 10px; margin-right: 10px; margin-bottom: 10px; margin-left: 10px; color: rgb(255, 0, 0); }
+      .b { margin-top: 10px; margin-right: 10px; margin-bottom: 10px; margin-left: 10px; color: rgb(255, 0, 0); }
+      .c { font-family: "Helvetica"; font-size: 12px; font-weight: bold; font-style: italic; line-height: 1.2; }
+      .d { background-color: blue; background-image: url(img.png); transition: opacity 200ms ease; }
+      .e { counter-reset: item 0; list-style: none; padding: 0 0 0 0; }
+      @media (min-width: 640px) {
+        .a { border: 1px solid red; border-color: blue; }
+      }
+      @supports (display: grid) {
+        .f { display: grid; grid-template-columns: repeat(2, 1fr); gap: 10px 10px; }
+      }
+      @keyframes spin { from { transform: rotate(0deg) } to { transform: rotate(360deg) } }
+    "#;
+
+    // Minifying already minified output must not change it, so any shortcut taken
+    // for minified input has to produce the same result as the full pipeline.
+    let minified = minify(source);
+    assert_eq!(minify(&minified), minified);
+  }
+
+  #[test]
+  fn test_generated_rule_location() {
+    fn minify(source: &str, logical_fallback: LogicalFallback) -> StyleSheet {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            safari: Some(8 << 16),
+            ..Browsers::default()
+          }),
+          logical_fallback,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      stylesheet
+    }
+
+    fn assert_generated(rules: &[CssRule], generated: bool) {
+      for rule in rules {
+        let loc = rule.loc().unwrap();
+        assert_eq!(loc.generated, generated);
+        assert_eq!((loc.line, loc.column), (0, 1));
+        if let CssRule::Supports(supports) = rule {
+          assert_generated(&supports.rules.0, generated);
+        }
+      }
+    }
+
+    let stylesheet = minify(
+      ".foo { color: red; margin-inline-start: 10px }",
+      LogicalFallback::Convert,
+    );
+    assert_eq!(stylesheet.rules.0.len(), 3);
+    assert_generated(&stylesheet.rules.0[..1], false);
+    assert_generated(&stylesheet.rules.0[1..], true);
+
+    let stylesheet = minify(
+      ".foo { color: red; margin-block-start: 10px }",
+      LogicalFallback::PhysicalWithSupports,
+    );
+    assert_eq!(stylesheet.rules.0.len(), 2);
+    assert_generated(&stylesheet.rules.0[..1], false);
+    assert_generated(&stylesheet.rules.0[1..], true);
+  }
+
+  #[test]
+  fn test_shape_outside() {
+    minify_test(".foo { shape-outside: none; }", ".foo{shape-outside:none}");
+    minify_test(".foo { shape-outside: margin-box; }", ".foo{shape-outside:margin-box}");
+    minify_test(
+      ".foo { shape-outside: circle(closest-side at center); }",
+      ".foo{shape-outside:circle()}",
+    );
+    minify_test(
+      ".foo { shape-outside: circle(50% at 50% 50%) margin-box; }",
+      ".foo{shape-outside:circle(50%)}",
+    );
+    minify_test(
+      ".foo { shape-outside: content-box circle(50px at 0 100px); }",
+      ".foo{shape-outside:circle(50px at 0 100px) content-box}",
+    );
+    minify_test(
+      ".foo { shape-outside: polygon(evenodd, 0 0, 100% 0, 50% 100%) padding-box; }",
+      ".foo{shape-outside:polygon(evenodd,0 0,100% 0,50% 100%) padding-box}",
+    );
+    minify_test(
+      ".foo { shape-outside: url('shape.png'); }",
+      ".foo{shape-outside:url(shape.png)}",
+    );
+
+    minify_test(
+      ".foo { clip-path: path('M 0 0 L 10 10 Z'); }",
+      ".foo{clip-path:path(\"M 0 0 L 10 10 Z\")}",
+    );
+    minify_test(
+      ".foo { clip-path: path(nonzero, 'M 0 0 L 10 10 Z'); }",
+      ".foo{clip-path:path(\"M 0 0 L 10 10 Z\")}",
+    );
+    minify_test(
+      ".foo { clip-path: path(evenodd, 'M 0 0 L 10 10 Z'); }",
+      ".foo{clip-path:path(evenodd,\"M 0 0 L 10 10 Z\")}",
+    );
+
+    let mut stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { clip-path: url('clip.svg#star'); shape-outside: url(shape.png) }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    stylesheet.minify(MinifyOptions::default()).unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        analyze_dependencies: true,
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    let dependencies = res.dependencies.unwrap();
+    let urls: Vec<&str> = dependencies
+      .iter()
+      .map(|dep| match dep {
+        Dependency::Url(dep) => dep.url.as_str(),
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(urls, vec!["clip.svg#star", "shape.png"]);
+  }
+
+  #[test]
+  fn test_unknown_transition_property() {
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    let source = indoc! {r#"
+      .foo {
+        transition-property: opacity, colr;
+        transition: transform 200ms, --custom 1s, all 2s;
+        -webkit-transition: -webkit-transform 200ms, -webkit-colr 1s;
+        transition-property: none;
+      }
+    "#};
+    StyleSheet::parse(
+      "test.css".into(),
+      source,
+      ParserOptions {
+        warnings: Some(warnings.clone()),
+        ..ParserOptions::default()
+      },
+    )
+    .unwrap();
+
+    let warnings = warnings.read().unwrap();
+    let kinds: Vec<_> = warnings.iter().map(|w| w.kind.clone()).collect();
+    assert_eq!(
+      kinds,
+      vec![
+        ParserWarningKind::UnknownTransitionProperty {
+          property: "transition-property".into(),
+          name: "colr".into()
+        },
+        ParserWarningKind::UnknownTransitionProperty {
+          property: "transition".into(),
+          name: "-webkit-colr".into()
+        },
+      ]
+    );
+    assert_eq!(warnings[0].loc.line, 1);
+    assert_eq!(warnings[1].loc.line, 3);
+
+    let strict = || ParserOptions {
+      strict: true,
+      ..ParserOptions::default()
+    };
+    assert!(Property::parse_string("transition-property", "colr", strict()).is_err());
+    assert!(Property::parse_string("transition", "colr 1s", strict()).is_err());
+    assert!(Property::parse_string("transition-property", "-webkit-colr, -moz-foo", strict()).is_ok());
+    assert!(Property::parse_string("transition-property", "color, --x", strict()).is_ok());
+    assert!(Property::parse_string("transition-property", "all", strict()).is_ok());
+    assert!(Property::parse_string("transition-property", "colr", ParserOptions::default()).is_ok());
+  }
+
+  #[test]
+  fn test_transition_all() {
+    minify_test(
+      ".foo { transition: opacity 200ms, all 200ms }",
+      ".foo{transition:all .2s}",
+    );
+    minify_test(
+      ".foo { transition: all 200ms, opacity 200ms, transform .2s }",
+      ".foo{transition:all .2s}",
+    );
+    minify_test(
+      ".foo { transition: all 1s ease-in, opacity 1s }",
+      ".foo{transition:all 1s ease-in,opacity 1s}",
+    );
+    minify_test(
+      ".foo { transition: all 200ms, opacity 1s }",
+      ".foo{transition:all .2s,opacity 1s}",
+    );
+    minify_test(
+      ".foo { transition: opacity 1s, all 200ms }",
+      ".foo{transition:opacity 1s,all .2s}",
+    );
+    minify_test(
+      ".foo { transition: all 200ms 1s, opacity 200ms }",
+      ".foo{transition:all .2s 1s,opacity .2s}",
+    );
+    minify_test(
+      ".foo { transition: all 1s, all 1s, opacity 2s }",
+      ".foo{transition:all 1s,opacity 2s}",
+    );
+
+    // A property that is listed again after `all` takes the timing of its last occurrence.
+    minify_test(
+      ".foo { transition: all 1s, opacity 2s, opacity 1s }",
+      ".foo{transition:all 1s,opacity 2s,opacity 1s}",
+    );
+    minify_test(
+      ".foo { transition-property: opacity, all; transition-duration: 1s }",
+      ".foo{transition-property:opacity,all;transition-duration:1s}",
+    );
+  }
+
+  #[test]
+  fn test_preserve_raw_values() {
+    let source = ".foo {\n      COLOR: #FF0000;\n  margin: 0px   0PX;\n\tfont-family: 'Helvetica', sans-serif;\n  width: calc(100px + 2em);\n  --Custom:  foo ;\n}\n";
+    let options = ParserOptions::builder().preserve_raw_values(true).build();
+    let stylesheet = StyleSheet::parse("test.css".into(), source, options.clone()).unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions::builder().preserve_raw_values(true).build())
+      .unwrap();
+    assert_eq!(
+      res.code,
+      ".foo {\n  COLOR: #FF0000;\n  margin: 0px   0PX;\n  font-family: 'Helvetica', sans-serif;\n  width: calc(100px + 2em);\n  --Custom: foo;\n}\n"
+    );
+
+    // Without the printer option, values are re-serialized as usual.
+    let normalized = StyleSheet::parse("test.css".into(), source, ParserOptions::default())
+      .unwrap()
+      .to_css(PrinterOptions::default())
+      .unwrap();
+    assert_eq!(
+      stylesheet.to_css(PrinterOptions::default()).unwrap().code,
+      normalized.code
+    );
+
+    // Minifying discards the source text.
+    let minify = |options: ParserOptions| {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, options).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      stylesheet
+        .to_css(PrinterOptions::builder().minify(true).preserve_raw_values(true).build())
+        .unwrap()
+        .code
+    };
+    assert_eq!(minify(options), minify(ParserOptions::default()));
+
+    // Values are re-serialized when names are scoped or urls are replaced with placeholders.
+    let source = ".foo { animation-name: Fade; background: URL(foo.png) }";
+    let print = |css_modules: bool, analyze_dependencies: bool| {
+      let options = ParserOptions {
+        css_modules,
+        preserve_raw_values: true,
+        ..ParserOptions::default()
+      };
+      StyleSheet::parse("test.css".into(), source, options)
+        .unwrap()
+        .to_css(PrinterOptions {
+          minify: true,
+          preserve_raw_values: true,
+          analyze_dependencies,
+          ..PrinterOptions::default()
+        })
+        .unwrap()
+        .code
+    };
+    assert_eq!(print(false, false), ".foo{animation-name:Fade;background:URL(foo.png)}");
+    assert_eq!(
+      print(true, false),
+      ".EgL3uq_foo{animation-name:EgL3uq_Fade;background:url(foo.png)}"
+    );
+    assert_eq!(
+      print(false, true),
+      ".foo{animation-name:Fade;background:url(\"Vwkwkq\")}"
+    );
+  }
+
+  #[test]
+  fn test_overscroll_behavior() {
+    minify_test(
+      ".foo { overscroll-behavior: contain auto }",
+      ".foo{overscroll-behavior:contain auto}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: contain contain }",
+      ".foo{overscroll-behavior:contain}",
+    );
+    minify_test(".foo { overscroll-behavior: NONE }", ".foo{overscroll-behavior:none}");
+    minify_test(
+      ".foo { overscroll-behavior-x: none; overscroll-behavior-y: none }",
+      ".foo{overscroll-behavior:none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-x: none; overscroll-behavior-y: auto }",
+      ".foo{overscroll-behavior:none auto}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: auto; overscroll-behavior-y: contain }",
+      ".foo{overscroll-behavior:auto contain}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-x: contain }",
+      ".foo{overscroll-behavior-x:contain}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-x: contain; overscroll-behavior-x: none }",
+      ".foo{overscroll-behavior-x:none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior: contain; overscroll-behavior-y: var(--y) }",
+      ".foo{overscroll-behavior:contain;overscroll-behavior-y:var(--y)}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-inline: contain; overscroll-behavior-block: none }",
+      ".foo{overscroll-behavior-inline:contain;overscroll-behavior-block:none}",
+    );
+    minify_test(
+      ".foo { overscroll-behavior-inline: contain; overscroll-behavior-x: none }",
+      ".foo{overscroll-behavior-inline:contain;overscroll-behavior-x:none}",
+    );
+
+    prefix_test(
+      ".foo { overscroll-behavior-inline: contain; overscroll-behavior-block: none }",
+      indoc! {r#"
+      .foo {
+        overscroll-behavior: contain none;
+      }
+    "#},
+      Browsers {
+        chrome: Some(70 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { overscroll-behavior: auto; overscroll-behavior-inline: contain }",
+      indoc! {r#"
+      .foo {
+        overscroll-behavior: contain auto;
+      }
+    "#},
+      Browsers {
+        chrome: Some(70 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { overscroll-behavior-block: var(--y) }",
+      indoc! {r#"
+      .foo {
+        overscroll-behavior-y: var(--y);
+      }
+    "#},
+      Browsers {
+        chrome: Some(70 << 16),
+        ..Browsers::default()
+      },
+    );
+    prefix_test(
+      ".foo { overscroll-behavior-inline: contain }",
+      indoc! {r#"
+      .foo {
+        overscroll-behavior-inline: contain;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_color_scheme() {
+    minify_test(".foo { color-scheme: normal }", ".foo{color-scheme:normal}");
+    minify_test(".foo { color-scheme: light }", ".foo{color-scheme:light}");
+    minify_test(".foo { color-scheme: DARK }", ".foo{color-scheme:dark}");
+    minify_test(".foo { color-scheme: light dark }", ".foo{color-scheme:light dark}");
+    minify_test(".foo { color-scheme: dark light }", ".foo{color-scheme:dark light}");
+    minify_test(".foo { color-scheme: only light }", ".foo{color-scheme:light only}");
+    minify_test(
+      ".foo { color-scheme: only light dark }",
+      ".foo{color-scheme:light dark only}",
+    );
+    minify_test(
+      ".foo { color-scheme: light dark only }",
+      ".foo{color-scheme:light dark only}",
+    );
+    minify_test(".foo { color-scheme: light sepia }", ".foo{color-scheme:light sepia}");
+    minify_test(".foo { color-scheme: only }", ".foo{color-scheme:only}");
+    minify_test(".foo { color-scheme: normal only }", ".foo{color-scheme:normal only}");
+    minify_test(
+      ".foo { color-scheme: only light only }",
+      ".foo{color-scheme:only light only}",
+    );
+
+    let safari = Browsers {
+      safari: Some(12 << 16),
+      ..Browsers::default()
+    };
+    warning_test(
+      ".foo { color-scheme: light dark }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedProperty {
+        property: "color-scheme".into(),
+      }],
+    );
+    warning_test(
+      ".foo { color-scheme: var(--scheme) }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedProperty {
+        property: "color-scheme".into(),
+      }],
+    );
+    warning_test(
+      ".foo { color-scheme: light dark }",
+      Browsers {
+        safari: Some(13 << 16),
+        ..Browsers::default()
+      },
+      vec![],
+    );
+  }
+
+  #[test]
+  fn test_max_output_size() {
+    let source =
+      ".foo { color: red; background: url(foo.png) } .bar { font-family: \"Helvetica Neue\", sans-serif }\n"
+        .repeat(20);
+    let stylesheet = StyleSheet::parse("test.css".into(), &source, ParserOptions::default()).unwrap();
+    let code = stylesheet.to_css(PrinterOptions::default()).unwrap().code;
+
+    let res = stylesheet.to_css(PrinterOptions::builder().max_output_size(100).build());
+    assert_eq!(
+      res.unwrap_err().kind,
+      PrinterErrorKind::MaximumOutputSizeExceeded { max: 100 }
+    );
+
+    // Errors from values serialized through `std::fmt::Write` are reported the same way.
+    let res = stylesheet.to_css(
+      PrinterOptions::builder()
+        .max_output_size(code.find("Helvetica").unwrap())
+        .build(),
+    );
+    assert!(matches!(
+      res.unwrap_err().kind,
+      PrinterErrorKind::MaximumOutputSizeExceeded { .. }
+    ));
+
+    let res = stylesheet.to_css(PrinterOptions::builder().max_output_size(code.len()).build());
+    assert_eq!(res.unwrap().code, code);
+
+    let res = StyleAttribute::parse("color: red; background: yellow")
+      .unwrap()
+      .to_css(PrinterOptions::builder().max_output_size(10).build());
+    assert_eq!(
+      res.unwrap_err().kind,
+      PrinterErrorKind::MaximumOutputSizeExceeded { max: 10 }
+    );
+  }
+
+  #[test]
+  fn test_document_rule() {
+    fn document_test(source: &str, expected: &str, minify: bool) {
+      let options = ParserOptions::builder().document_rules(true).build();
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, options.clone()).unwrap();
+      stylesheet.minify(MinifyOptions::default()).unwrap();
+      let res = stylesheet.to_css(PrinterOptions::builder().minify(minify).build()).unwrap();
+      assert_eq!(res.code, expected);
+
+      // The output is stable when parsed again.
+      let stylesheet = StyleSheet::parse("test.css".into(), &res.code, options).unwrap();
+      let code = stylesheet
+        .to_css(PrinterOptions::builder().minify(minify).build())
+        .unwrap()
+        .code;
+      assert_eq!(code, expected);
+    }
+
+    document_test(
+      "@-moz-document url-prefix(https://) { h1 { color: red } }",
+      indoc! {r#"
+      @-moz-document url-prefix("https://") {
+        h1 {
+          color: red;
+        }
+      }
+    "#},
+      false,
+    );
+    document_test(
+      "@-moz-document url-prefix() { h1 { color: red } }",
+      "@-moz-document url-prefix(){h1{color:red}}",
+      true,
+    );
+    document_test(
+      "@-moz-document url-prefix('') { h1 { color: red } }",
+      "@-moz-document url-prefix(){h1{color:red}}",
+      true,
+    );
+    document_test(
+      r#"@document url(http://www.w3.org/), url-prefix("http://www.w3.org/Style/"), domain(mozilla.org), regexp("https:.*") { .foo { color: red } }"#,
+      r#"@document url("http://www.w3.org/"),url-prefix("http://www.w3.org/Style/"),domain("mozilla.org"),regexp("https:.*"){.foo{color:red}}"#,
+      true,
+    );
+    document_test(
+      "@-moz-document domain(example.com) { .foo { margin-top: 1px; margin-bottom: 1px; margin-left: 1px; margin-right: 1px } }",
+      "@-moz-document domain(\"example.com\"){.foo{margin:1px}}",
+      true,
+    );
+    document_test(
+      r#"@document url('http://www.w3.org/'), url("http://example.com/") { .foo { color: red } }"#,
+      r#"@document url("http://www.w3.org/"),url("http://example.com/"){.foo{color:red}}"#,
+      true,
+    );
+    document_test("@document domain(example.com) { .foo {} }", "", true);
+
+    let options = ParserOptions::builder().document_rules(true).build();
+    let res = StyleSheet::parse(
+      "test.css".into(),
+      "@document unknown(foo) { .foo { color: red } }",
+      options,
+    );
+    assert!(res.is_err());
+
+    // Without the option, only the url-prefix() hack is supported.
+    minify_test("@document url-prefix() { .foo { color: red } }", "");
+    error_test(
+      "@-moz-document domain(example.com) {}",
+      ParserError::UnexpectedToken(crate::properties::custom::Token::Function("domain".into())),
+    );
+  }
+
+  #[test]
+  fn test_optimization_warnings() {
+    fn optimization_test(
+      source: &str,
+      expected: &str,
+      disabled: &[Optimization],
+      expected_warnings: Vec<(Optimization, u32)>,
+    ) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          warn_optimizations: true,
+          disabled_optimizations: disabled.iter().cloned().collect(),
+          warnings: Some(warnings.clone()),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet.to_css(PrinterOptions::builder().minify(true).build()).unwrap();
+      assert_eq!(res.code, expected);
+
+      let warnings: Vec<_> = warnings
+        .read()
+        .unwrap()
+        .iter()
+        .map(|w| (w.kind.clone(), w.loc.as_ref().unwrap().line))
+        .collect();
+      let expected_warnings: Vec<_> = expected_warnings
+        .into_iter()
+        .map(|(optimization, line)| (MinifyWarningKind::OptimizationApplied { optimization }, line))
+        .collect();
+      assert_eq!(warnings, expected_warnings);
+    }
+
+    optimization_test(
+      ".foo { color: red }\n.foo { background: blue }\n.bar { color: red }",
+      ".foo{color:red;background:#00f}.bar{color:red}",
+      &[],
+      vec![(Optimization::RuleMerge, 1)],
+    );
+    optimization_test(
+      ".foo { color: red }\n.foo { background: blue }\n.bar { color: red }",
+      ".foo{color:red}.foo{background:#00f}.bar{color:red}",
+      &[Optimization::RuleMerge],
+      vec![],
+    );
+    optimization_test(
+      ".foo { color: red }\n.bar { color: red }",
+      ".foo,.bar{color:red}",
+      &[],
+      vec![(Optimization::RuleMerge, 1)],
+    );
+    optimization_test(
+      ".foo { color: red }\n.bar { color: red }",
+      ".foo{color:red}.bar{color:red}",
+      &[Optimization::RuleMerge],
+      vec![],
+    );
+    optimization_test(
+      ":root { --a: 1 }\n.foo { color: red }\n:root { --b: 2 }",
+      ":root{--a:1;--b:2}.foo{color:red}",
+      &[],
+      vec![(Optimization::RootVariableMerge, 2)],
+    );
+    optimization_test(
+      ":root { --a: 1 }\n.foo { color: red }\n:root { --b: 2 }",
+      ":root{--a:1}.foo{color:red}:root{--b:2}",
+      &[Optimization::RootVariableMerge],
+      vec![],
+    );
+    optimization_test(
+      ".foo { color: red }\n.bar { filter: blur(5px) brightness(1) }",
+      ".foo{color:red}.bar{filter:blur(5px)}",
+      &[],
+      vec![(Optimization::IdentityFilterRemoval, 1)],
+    );
+    optimization_test(
+      ".foo { color: red }\n.bar { filter: blur(5px) brightness(1) }",
+      ".foo{color:red}.bar{filter:blur(5px)brightness()}",
+      &[Optimization::IdentityFilterRemoval],
+      vec![],
+    );
+    optimization_test(
+      ".foo { transition: opacity 1s, all 1s }",
+      ".foo{transition:all 1s}",
+      &[],
+      vec![(Optimization::TransitionAllMerge, 0)],
+    );
+    optimization_test(
+      ".foo { transition: opacity 1s, all 1s }",
+      ".foo{transition:opacity 1s,all 1s}",
+      &[Optimization::TransitionAllMerge],
+      vec![],
+    );
+    optimization_test(
+      ".foo { --a: 1; --a: 2 }",
+      ".foo{--a:2}",
+      &[],
+      vec![(Optimization::CustomPropertyDedupe, 0)],
+    );
+    optimization_test(
+      ".foo { --a: 1; --a: 2 }",
+      ".foo{--a:1;--a:2}",
+      &[Optimization::CustomPropertyDedupe],
+      vec![],
+    );
+    optimization_test(
+      ".foo { color: red; color: blue !important }",
+      ".foo{color:#00f!important}",
+      &[],
+      vec![(Optimization::DeclarationDrop, 0)],
+    );
+    optimization_test(
+      ".foo { color: red; color: blue !important }",
+      ".foo{color:red;color:#00f!important}",
+      &[Optimization::DeclarationDrop],
+      vec![],
+    );
+
+    fn color_fold_test(disabled: &[Optimization], expected: &str, expected_warnings: usize) {
+      let mut stylesheet = StyleSheet::parse(
+        "test.css".into(),
+        ".foo { background-color: oklab(59.686% 0.1009 0.1192) }",
+        ParserOptions::default(),
+      )
+      .unwrap();
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            safari: Some(15 << 16),
+            ..Browsers::default()
+          }),
+          warn_optimizations: true,
+          disabled_optimizations: disabled.iter().cloned().collect(),
+          warnings: Some(warnings.clone()),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet.to_css(PrinterOptions::builder().minify(true).build()).unwrap();
+      assert_eq!(res.code, expected);
+      let warnings = warnings.read().unwrap();
+      assert_eq!(warnings.len(), expected_warnings);
+      for warning in warnings.iter() {
+        assert_eq!(
+          warning.kind,
+          MinifyWarningKind::OptimizationApplied {
+            optimization: Optimization::ColorFold
+          }
+        );
+      }
+    }
+
+    color_fold_test(&[], ".foo{background-color:lab(52.2319% 40.1449 59.9171)}", 1);
+    color_fold_test(
+      &[Optimization::ColorFold],
+      ".foo{background-color:lab(52.2319% 40.1449 59.9171);background-color:oklab(59.686% .1009 .1192)}",
+      0,
+    );
+
+    // Style attributes have no source locations.
+    let mut attr = StyleAttribute::parse("color: red; color: blue !important").unwrap();
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    attr.minify(MinifyOptions {
+      warn_optimizations: true,
+      warnings: Some(warnings.clone()),
+      ..MinifyOptions::default()
+    });
+    assert_eq!(
+      *warnings.read().unwrap(),
+      vec![Error {
+        kind: MinifyWarningKind::OptimizationApplied {
+          optimization: Optimization::DeclarationDrop
+        },
+        loc: None,
+      }]
+    );
+
+    // No warnings are emitted unless requested.
+    warning_test(
+      ".foo { color: red } .foo { background: blue }",
+      Browsers::default(),
+      vec![],
+    );
+  }
+
+  #[test]
+  fn test_media_never_matches() {
+    minify_test(
+      "@media (min-width: 99999px) and (max-width: 0px) { .foo { color: red } }",
+      "",
+    );
+    minify_test("@media (width > 500px) and (width < 200px) { .foo { color: red } }", "");
+    minify_test(
+      "@media (width >= 500px) and (width < 500px) { .foo { color: red } }",
+      "",
+    );
+    minify_test("@media (500px < width < 200px) { .foo { color: red } }", "");
+    minify_test(
+      "@media screen and (min-width: 1in) and (max-width: 95px) { .foo { color: red } }",
+      "",
+    );
+    minify_test(
+      "@media (min-aspect-ratio: 2/1) and (max-aspect-ratio: 1/1) { .foo { color: red } }",
+      "",
+    );
+    minify_test(
+      "@media (color) and ((min-width: 500px) and (max-width: 200px)) { .foo { color: red } }",
+      "",
+    );
+    minify_test(
+      "@media (min-width: 500px) and (max-width: 200px), print and (width: 100px) and (width: 200px) { .foo { color: red } }",
+      "",
+    );
+    minify_test(
+      "@media (width >= 500px) and (width <= 500px) { .foo { color: red } }",
+      "@media (width>=500px) and (width<=500px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 30em) and (max-width: 200px) { .foo { color: red } }",
+      "@media (min-width:30em) and (max-width:200px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 500px) and (max-height: 200px) { .foo { color: red } }",
+      "@media (min-width:500px) and (max-height:200px){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (min-width: 500px) and (max-width: 200px), print { .foo { color: red } }",
+      "@media print{.foo{color:red}}",
+    );
+    minify_test(
+      "@media (color) or ((min-width: 500px) and (max-width: 200px)) { .foo { color: red } }",
+      "@media (color){.foo{color:red}}",
+    );
+    minify_test(
+      "@media ((min-width: 500px) and (max-width: 200px)) or ((min-width: 100px) and (max-width: 200px)) { .foo { color: red } }",
+      "@media ((min-width:100px) and (max-width:200px)){.foo{color:red}}",
+    );
+    minify_test(
+      "@media (not ((min-width: 500px) and (max-width: 200px))) { .foo { color: red } }",
+      "@media (not ((min-width:500px) and (max-width:200px))){.foo{color:red}}",
+    );
+  }
+
+  #[test]
+  fn test_accent_color() {
+    minify_test(".foo { accent-color: auto }", ".foo{accent-color:auto}");
+    minify_test(".foo { accent-color: AUTO }", ".foo{accent-color:auto}");
+    minify_test(".foo { accent-color: yellow }", ".foo{accent-color:#ff0}");
+    minify_test(
+      ".foo { accent-color: rgb(255 0 0 / 50%) }",
+      ".foo{accent-color:#ff000080}",
+    );
+    minify_test(".foo { caret-color: AUTO }", ".foo{caret-color:auto}");
+    minify_test(".foo { caret-color: rgb(255, 0, 0) }", ".foo{caret-color:red}");
+
+    prefix_test(
+      ".foo { accent-color: lch(50.998% 135.363 338) }",
+      indoc! { r#"
+        .foo {
+          accent-color: #ee00be;
+          accent-color: color(display-p3 .972962 -.362078 .804206);
+          accent-color: lch(50.998% 135.363 338);
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        safari: Some(14 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      ".foo { accent-color: auto }",
+      indoc! { r#"
+        .foo {
+          accent-color: auto;
+        }
+      "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    let safari = Browsers {
+      safari: Some(15 << 16),
+      ..Browsers::default()
+    };
+    warning_test(
+      ".foo { accent-color: red }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedProperty {
+        property: "accent-color".into(),
+      }],
+    );
+    warning_test(
+      ".foo { accent-color: var(--accent) }",
+      safari,
+      vec![MinifyWarningKind::UnsupportedProperty {
+        property: "accent-color".into(),
+      }],
+    );
+    warning_test(
+      ".foo { accent-color: red }",
+      Browsers {
+        safari: Some((15 << 16) | (4 << 8)),
+        chrome: Some(93 << 16),
+        ..Browsers::default()
+      },
+      vec![],
+    );
+    warning_test(".foo { caret-color: red }", safari, vec![]);
+  }
+
+  #[test]
+  fn test_mixed_color_components() {
+    minify_test(".foo { color: rgb(255 50% 0 / 0.5) }", ".foo{color:#ff800080}");
+    minify_test(".foo { color: rgb(100% 128 0) }", ".foo{color:#ff8000}");
+    minify_test(".foo { color: rgba(255 50% 0 / 50%) }", ".foo{color:#ff800080}");
+    minify_test(".foo { color: rgb(255 50% none) }", ".foo{color:#ff8000}");
+    minify_test(".foo { color: rgb(none 50% 255 / 50%) }", ".foo{color:#0080ff80}");
+    minify_test(".foo { color: hsl(100 100 50) }", ".foo{color:#5f0}");
+    minify_test(".foo { color: hsl(100deg 100% 50 / .8) }", ".foo{color:#5f0c}");
+    minify_test(".foo { color: hsl(100 none 50%) }", ".foo{color:gray}");
+    minify_test(".foo { color: hwb(194 0 0) }", ".foo{color:#00c4ff}");
+
+    prefix_test(
+      ".foo { color: rgb(255 50% 0 / 0.5) }",
+      indoc! { r#"
+        .foo {
+          color: rgba(255, 128, 0, .5);
+        }
+      "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      ".foo { color: hsl(100 100 50 / .8) }",
+      indoc! { r#"
+        .foo {
+          color: rgba(85, 255, 0, .8);
+        }
+      "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      ".foo { color: rgb(255 50% none / 0.5) }",
+      indoc! { r#"
+        .foo {
+          color: #ff800080;
+        }
+      "#},
+      Browsers {
+        chrome: Some(95 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_remove_initial_values() {
+    fn initial_test(source: &str, expected: &str) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          remove_initial_values: true,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    initial_test(".foo { opacity: 1; color: red }", ".foo{color:red}");
+    initial_test(
+      ".foo { transform: none; filter: none; perspective: none; clip-path: none }",
+      "",
+    );
+    initial_test(".foo { transform: none; max-width: none }", ".foo{max-width:none}");
+    initial_test(
+      ".foo { background-color: transparent; clip-path: none }",
+      ".foo{background-color:#0000}",
+    );
+    initial_test(".foo { position: static }", ".foo{position:static}");
+    initial_test(".foo { box-sizing: content-box }", ".foo{box-sizing:content-box}");
+    initial_test(".foo { text-decoration-line: none }", ".foo{text-decoration-line:none}");
+    initial_test(".foo { opacity: .5 }", ".foo{opacity:.5}");
+    initial_test(".foo { opacity: 1 !important }", ".foo{opacity:1!important}");
+    initial_test(".foo { -webkit-transform: none }", ".foo{-webkit-transform:none}");
+    initial_test(".foo { color: transparent }", ".foo{color:#0000}");
+    initial_test(
+      ".foo { text-decoration: var(--decoration); text-decoration-line: none }",
+      ".foo{text-decoration:var(--decoration);text-decoration-line:none}",
+    );
+    initial_test(
+      "@keyframes test { from { opacity: 1 } to { opacity: 0 } }",
+      "@keyframes test{0%{opacity:1}to{opacity:0}}",
+    );
+    initial_test(
+      ".foo { opacity: .5 } .bar { opacity: 1 }",
+      ".foo{opacity:.5}.bar{opacity:1}",
+    );
+    initial_test(
+      ".foo { transform: scale(2) } .bar { color: red; transform: none }",
+      ".foo{transform:scale(2)}.bar{color:red;transform:none}",
+    );
+    initial_test(
+      ".foo { background: red } .bar { background-color: transparent }",
+      ".foo{background:red}.bar{background-color:#0000}",
+    );
+    initial_test(
+      ".foo { opacity: 1 } @media print { .foo { opacity: .5 } }",
+      ".foo{opacity:1}@media print{.foo{opacity:.5}}",
+    );
+    initial_test(".foo { color: red } .bar { opacity: 1 }", ".foo{color:red}");
+    minify_test(".foo { opacity: 1 }", ".foo{opacity:1}");
+
+    let mut attr = StyleAttribute::parse("opacity: 1; color: red").unwrap();
+    attr.minify(MinifyOptions {
+      remove_initial_values: true,
+      ..MinifyOptions::default()
+    });
+    let res = attr
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, "color:red");
+  }
+
+  #[test]
+  fn test_property_syntax_serialization() {
+    fn syntax_test(syntax: &str, expected: &str, expected_minified: &str) {
+      let source = format!(
+        "@property --foo {{ syntax: '{}'; inherits: false; initial-value: 10px }}",
+        syntax
+      );
+      test(
+        &source,
+        &format!(
+          "@property --foo {{\n  syntax: \"{}\";\n  inherits: false;\n  initial-value: 10px;\n}}\n",
+          expected
+        ),
+      );
+      minify_test(
+        &source,
+        &format!(
+          "@property --foo{{syntax:\"{}\";inherits:false;initial-value:10px}}",
+          expected_minified
+        ),
+      );
+    }
+
+    syntax_test(
+      "<length> | <percentage>",
+      "<length> | <percentage>",
+      "<length>|<percentage>",
+    );
+    syntax_test(
+      "<length>|<percentage>",
+      "<length> | <percentage>",
+      "<length>|<percentage>",
+    );
+    syntax_test(
+      "  <LENGTH>   |   <Percentage>  ",
+      "<length> | <percentage>",
+      "<length>|<percentage>",
+    );
+    syntax_test("<length>+ | <length>#", "<length>+ | <length>#", "<length>+|<length>#");
+    syntax_test("<length> | <length> | auto", "<length> | auto", "<length>|auto");
+    syntax_test(
+      "<length>+ | <length> | <length>+",
+      "<length>+ | <length>",
+      "<length>+|<length>",
+    );
+    syntax_test("<length> | Auto | auto", "<length> | Auto | auto", "<length>|Auto|auto");
+
+    minify_test(
+      "@property --foo { syntax: ' * '; inherits: false }",
+      "@property --foo{syntax:\"*\";inherits:false}",
+    );
+    test(
+      "@property --foo { syntax: '*'; inherits: false }",
+      "@property --foo {\n  syntax: \"*\";\n  inherits: false\n}\n",
+    );
+  }
+
+  #[test]
+  fn test_registered_custom_properties() {
+    minify_test(
+      r#"
+      @property --x {
+        syntax: '<length>';
+        inherits: false;
+        initial-value: 0px;
+      }
+      .foo { --x: 0px; --y: 0px }
+    "#,
+      "@property --x{syntax:\"<length>\";inherits:false;initial-value:0}.foo{--x:0;--y:0px}",
+    );
+    minify_test(
+      r#"
+      .foo { --x: calc(10px + 5px) }
+      @property --x {
+        syntax: '<length>';
+        inherits: false;
+        initial-value: 0px;
+      }
+    "#,
+      ".foo{--x:15px}@property --x{syntax:\"<length>\";inherits:false;initial-value:0}",
+    );
+    minify_test(
+      r#"
+      @property --x {
+        syntax: '<length>+';
+        inherits: false;
+        initial-value: 0px;
+      }
+      .foo { --x: 0px 1.0em }
+      .bar { --x: var(--y) }
+      .baz { --x: foo }
+    "#,
+      "@property --x{syntax:\"<length>+\";inherits:false;initial-value:0}.foo{--x:0 1em}.bar{--x:var(--y)}.baz{--x:foo}",
+    );
+    minify_test(
+      r#"
+      @property --x {
+        syntax: '*';
+        inherits: false;
+      }
+      .foo { --x: 0px }
+    "#,
+      "@property --x{syntax:\"*\";inherits:false}.foo{--x:0px}",
+    );
+    minify_test(
+      r#"
+      @property --t {
+        syntax: '<time>';
+        inherits: false;
+        initial-value: 0s;
+      }
+      @property --p {
+        syntax: '<percentage>#';
+        inherits: false;
+        initial-value: 0%;
+      }
+      .foo { --t: 1000ms; --p: 50.0%, 100%; --c: foo }
+      .bar { --t: 50ms }
+    "#,
+      "@property --t{syntax:\"<time>\";inherits:false;initial-value:0s}@property --p{syntax:\"<percentage>#\";inherits:false;initial-value:0%}.foo{--t:1s;--p:50%,100%;--c:foo}.bar{--t:50ms}",
+    );
+
+    fn targets_test(source: &str, expected: &str, targets: Browsers) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(targets),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    // Browsers without support for @property substitute the value as written.
+    let source = ".foo { --x: 0px } @property --x { syntax: '<length>'; inherits: false; initial-value: 0px }";
+    targets_test(
+      source,
+      ".foo{--x:0px}@property --x{syntax:\"<length>\";inherits:false;initial-value:0}",
+      Browsers {
+        safari: Some(15 << 16),
+        ..Browsers::default()
+      },
+    );
+    targets_test(
+      source,
+      ".foo{--x:0}@property --x{syntax:\"<length>\";inherits:false;initial-value:0}",
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    use crate::values::syntax::SyntaxString;
+    let mut registered_properties = HashMap::new();
+    registered_properties.insert("--x".into(), SyntaxString::parse_string("<length-percentage>").unwrap());
+    let mut stylesheet = StyleSheet::parse(
+      "test.css".into(),
+      ".foo { --x: 0px; --y: 0px }",
+      ParserOptions::default(),
+    )
+    .unwrap();
+    stylesheet
+      .minify(MinifyOptions {
+        registered_properties,
+        ..MinifyOptions::default()
+      })
+      .unwrap();
+    let res = stylesheet
+      .to_css(PrinterOptions {
+        minify: true,
+        ..PrinterOptions::default()
+      })
+      .unwrap();
+    assert_eq!(res.code, ".foo{--x:0;--y:0px}");
+  }
+
+  #[test]
+  fn test_writing_mode() {
+    minify_test(".foo { writing-mode: vertical-rl }", ".foo{writing-mode:vertical-rl}");
+    minify_test(".foo { writing-mode: sideways-lr }", ".foo{writing-mode:sideways-lr}");
+    minify_test(".foo { writing-mode: tb-rl }", ".foo{writing-mode:tb-rl}");
+    minify_test(".foo { writing-mode: foo }", ".foo{writing-mode:foo}");
+
+    prefix_test(
+      r#"
+      .foo {
+        writing-mode: vertical-rl;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -ms-writing-mode: tb-rl;
+        -webkit-writing-mode: vertical-rl;
+        writing-mode: vertical-rl;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        writing-mode: horizontal-tb;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-writing-mode: horizontal-tb;
+        writing-mode: horizontal-tb;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        writing-mode: vertical-lr;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -ms-writing-mode: tb-lr;
+        writing-mode: vertical-lr;
+      }
+    "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        writing-mode: sideways-rl;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        writing-mode: sideways-rl;
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -webkit-writing-mode: vertical-lr;
+        -ms-writing-mode: tb-lr;
+        writing-mode: vertical-lr;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        writing-mode: vertical-lr;
+      }
+    "#},
+      Browsers {
+        chrome: Some(90 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        writing-mode: vertical-lr;
+        writing-mode: sideways-lr;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -ms-writing-mode: tb-lr;
+        writing-mode: vertical-lr;
+        writing-mode: sideways-lr;
+      }
+    "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+    minify_test(
+      ".foo { writing-mode: vertical-lr; writing-mode: sideways-lr }",
+      ".foo{writing-mode:sideways-lr}",
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        -ms-writing-mode: tb-rl;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -ms-writing-mode: tb-rl;
+      }
+    "#},
+      Browsers {
+        ie: Some(11 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_individual_transforms() {
+    fn individual_transform_test(source: &str, expected: &str, targets: Browsers) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(targets),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    minify_test(".foo { scale: 50% }", ".foo{scale:.5}");
+    minify_test(".foo { scale: 100% 100% }", ".foo{scale:1}");
+    minify_test(".foo { scale: 200% 2 }", ".foo{scale:2}");
+    minify_test(".foo { rotate: 0 0 2 10deg }", ".foo{rotate:10deg}");
+    minify_test(".foo { rotate: 2 0 0 10deg }", ".foo{rotate:x 10deg}");
+    minify_test(".foo { rotate: 0 3 0 10deg }", ".foo{rotate:y 10deg}");
+    minify_test(".foo { rotate: x 0deg }", ".foo{rotate:none}");
+    minify_test(".foo { rotate: 1 1 1 0deg }", ".foo{rotate:none}");
+    minify_test(
+      ".foo { transform: rotate(45deg); translate: 100px 200px }",
+      ".foo{transform:rotate(45deg);translate:100px 200px}",
+    );
+    minify_test(
+      ".foo { transform: scale(2); scale: 1 }",
+      ".foo{transform:scale(2);scale:1}",
+    );
+
+    let chrome = Browsers {
+      chrome: Some(90 << 16),
+      ..Browsers::default()
+    };
+    individual_transform_test(
+      ".foo { translate: 10px 20px }",
+      ".foo{transform:translate(10px,20px)}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { rotate: 45deg; translate: 100px 200px }",
+      ".foo{transform:translate(100px,200px)rotate(45deg)}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { scale: 2; rotate: 45deg; transform: translate(100px, 200px) }",
+      ".foo{transform:rotate(45deg)scale(2)translate(100px,200px)}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { translate: 100px 200px; transform: rotate(45deg) }",
+      ".foo{transform:translate(100px,200px)rotate(45deg)}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { transform: scale(3); scale: 0.5 }",
+      ".foo{transform:scale(1.5)}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { transform: scale(2); scale: 1 }",
+      ".foo{transform:scale(2)}",
+      chrome,
+    );
+    individual_transform_test(".foo { scale: 2; translate: 0 }", ".foo{transform:scale(2)}", chrome);
+    // Prefixed transforms are only for the browsers that need the prefix, so they are not combined.
+    individual_transform_test(
+      ".foo { -webkit-transform: rotate(45deg); translate: 10px }",
+      ".foo{-webkit-transform:rotate(45deg);translate:10px}",
+      chrome,
+    );
+    individual_transform_test(
+      ".foo { translate: none; rotate: none; scale: none }",
+      ".foo{transform:none}",
+      chrome,
+    );
+    individual_transform_test(".foo { translate: var(--x) }", ".foo{translate:var(--x)}", chrome);
+    individual_transform_test(
+      ".foo { translate: 10px 20px }",
+      ".foo{translate:10px 20px}",
+      Browsers {
+        chrome: Some(104 << 16),
+        ..Browsers::default()
+      },
+    );
+
+    prefix_test(
+      r#"
+      .foo {
+        scale: 2;
+      }
+    "#,
+      indoc! {r#"
+      .foo {
+        -webkit-transform: scale(2);
+        transform: scale(2);
+      }
+    "#},
+      Browsers {
+        safari: Some(8 << 16),
+        ..Browsers::default()
+      },
+    );
+  }
+
+  #[test]
+  fn test_remove_unneeded_prefixes() {
+    fn prefixes_test(source: &str, expected: &str, remove_unneeded_prefixes: bool) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(Browsers {
+            chrome: Some(90 << 16),
+            ..Browsers::default()
+          }),
+          remove_unneeded_prefixes,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    // Prefixes that are redundant with the unprefixed property are always removed.
+    prefixes_test(
+      ".foo { -webkit-border-radius: 5px; border-radius: 5px }",
+      ".foo{border-radius:5px}",
+      false,
+    );
+    prefixes_test(
+      ".foo { -webkit-border-radius: 5px; border-radius: 5px }",
+      ".foo{border-radius:5px}",
+      true,
+    );
+
+    // Prefixes without an unprefixed property are only removed when enabled.
+    prefixes_test(
+      ".foo { -webkit-border-radius: 5px; color: red }",
+      ".foo{-webkit-border-radius:5px;color:red}",
+      false,
+    );
+    prefixes_test(
+      ".foo { -webkit-border-radius: 5px; color: red }",
+      ".foo{color:red}",
+      true,
+    );
+    prefixes_test(".foo { -moz-user-select: none !important }", "", true);
+    prefixes_test(".foo { -webkit-transform: var(--transform) }", "", true);
+
+    // Prefixes that are still needed by the targets are kept.
+    prefixes_test(
+      ".foo { -webkit-mask-image: url(foo.png) }",
+      ".foo{-webkit-mask-image:url(foo.png)}",
+      true,
+    );
+
+    // Properties that are only supported with a prefix are kept.
+    prefixes_test(".foo { -webkit-line-clamp: 3 }", ".foo{-webkit-line-clamp:3}", true);
+    prefixes_test(
+      ".foo { -webkit-box-orient: vertical }",
+      ".foo{-webkit-box-orient:vertical}",
+      true,
+    );
+    prefixes_test(
+      ".foo { -webkit-font-smoothing: antialiased }",
+      ".foo{-webkit-font-smoothing:antialiased}",
+      true,
+    );
+  }
+
+  #[test]
+  fn test_nest_rules() {
+    fn nest_test(source: &str, expected: &str, targets: Option<Browsers>) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      stylesheet
+        .minify(MinifyOptions {
+          targets,
+          nest_rules: true,
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          targets,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+    }
+
+    let nesting = Some(Browsers {
+      chrome: Some(120 << 16),
+      ..Browsers::default()
+    });
+
+    nest_test(
+      ".card { color: red } .card .title { color: blue } .card > .body { color: green }",
+      ".card{color:red;& .title{color:#00f}&>.body{color:green}}",
+      nesting,
+    );
+    nest_test(
+      ".card { color: red } .card .title, .card .subtitle::before { color: blue }",
+      ".card{color:red;& .title,& .subtitle:before{color:#00f}}",
+      nesting,
+    );
+    nest_test(
+      ".a .b { color: red } .a .b + .c .d { color: blue }",
+      ".a .b{color:red;&+.c .d{color:#00f}}",
+      nesting,
+    );
+    nest_test(
+      "@media print { .card { color: red } .card .title { color: blue } }",
+      "@media print{.card{color:red;& .title{color:#00f}}}",
+      nesting,
+    );
+
+    // Not all selectors share the prefix.
+    nest_test(
+      ".card { color: red } .card .title, .title { color: blue }",
+      ".card{color:red}.card .title,.title{color:#00f}",
+      nesting,
+    );
+    // Compound selectors must match entirely.
+    nest_test(
+      ".card { color: red } .card.active .title { color: blue }",
+      ".card{color:red}.card.active .title{color:#00f}",
+      nesting,
+    );
+    // A rule in between would be moved after the nested rule.
+    nest_test(
+      ".card { color: red } .other { color: green } .card .title { color: blue }",
+      ".card{color:red}.other{color:green}.card .title{color:#00f}",
+      nesting,
+    );
+    // The parent must have a single selector, since `&` would have the specificity of the most specific one.
+    nest_test(
+      ".card, #main { color: red } .card .title, #main .title { color: blue }",
+      ".card,#main{color:red}.card .title,#main .title{color:#00f}",
+      nesting,
+    );
+    // Nesting is only applied for targets that support it.
+    nest_test(
+      ".card { color: red } .card .title { color: blue }",
+      ".card{color:red}.card .title{color:#00f}",
+      None,
+    );
+    // Targets that do not support nesting.
+    nest_test(
+      ".card { color: red } .card .title { color: blue }",
+      ".card{color:red}.card .title{color:#00f}",
+      Some(Browsers {
+        chrome: Some(95 << 16),
+        ..Browsers::default()
+      }),
+    );
+  }
+
+  #[test]
+  fn test_wrap_unsupported_features() {
+    fn wrap_test(
+      source: &str,
+      expected: &str,
+      expected_warnings: Vec<MinifyWarningKind>,
+      targets: Browsers,
+      wrap_unsupported_features: bool,
+    ) {
+      let mut stylesheet = StyleSheet::parse("test.css".into(), source, ParserOptions::default()).unwrap();
+      let warnings = Arc::new(RwLock::new(Vec::new()));
+      stylesheet
+        .minify(MinifyOptions {
+          targets: Some(targets),
+          wrap_unsupported_features,
+          warnings: Some(warnings.clone()),
+          ..MinifyOptions::default()
+        })
+        .unwrap();
+      let res = stylesheet
+        .to_css(PrinterOptions {
+          minify: true,
+          ..PrinterOptions::default()
+        })
+        .unwrap();
+      assert_eq!(res.code, expected);
+      let warnings: Vec<_> = warnings.read().unwrap().iter().map(|w| w.kind.clone()).collect();
+      assert_eq!(warnings, expected_warnings);
+    }
+
+    let targets = Browsers {
+      chrome: Some(100 << 16),
+      firefox: Some(120 << 16),
+      ..Browsers::default()
+    };
+    wrap_test(
+      ".foo { zoom: 1.5 }",
+      "@supports (zoom: 1.5){.foo{zoom:1.5}}@supports not (zoom: 1.5){.foo{transform:scale(1.5);transform-origin:0 0}}",
+      vec![],
+      targets,
+      true,
+    );
+    wrap_test(
+      ".foo { color: red; zoom: 1.5 !important }",
+      ".foo{color:red}@supports (zoom: 1.5){.foo{zoom:1.5!important}}@supports not (zoom: 1.5){.foo{transform:scale(1.5)!important;transform-origin:0 0!important}}",
+      vec![],
+      targets,
+      true,
+    );
+    // A fallback transform would override the one declared by the rule.
+    wrap_test(
+      ".foo { zoom: 1.5; transform: rotate(45deg) }",
+      ".foo{transform:rotate(45deg)}@supports (zoom: 1.5){.foo{zoom:1.5}}",
+      vec![],
+      targets,
+      true,
+    );
+    wrap_test(
+      ".foo { color: red; zoom: var(--zoom) }",
+      ".foo{color:red;zoom:var(--zoom)}",
+      vec![MinifyWarningKind::UnsupportedProperty {
+        property: "zoom".into(),
+      }],
+      targets,
+      true,
+    );
+    wrap_test(".foo { zoom: 1.5 }", ".foo{zoom:1.5}", vec![], targets, false);
+    // None of the targets support zoom, so the `@supports` test would always fail.
+    wrap_test(
+      ".foo { zoom: 1.5 }",
+      ".foo{zoom:1.5}",
+      vec![],
+      Browsers {
+        firefox: Some(120 << 16),
+        ..Browsers::default()
+      },
+      true,
+    );
+    wrap_test(
+      ".foo { zoom: 1.5 }",
+      ".foo{zoom:1.5}",
+      vec![],
+      Browsers {
+        firefox: Some(126 << 16),
+        ..Browsers::default()
+      },
+      true,
+    );
+
+    // Browsers that only support gap in grid containers pass `@supports (gap: 1rem)`, so it is not wrapped.
+    wrap_test(
+      ".foo { display: flex; gap: 1rem }",
+      ".foo{display:flex;gap:1rem}",
+      vec![MinifyWarningKind::UnsupportedFlexGap],
+      Browsers {
+        safari: Some(13 << 16),
+        ..Browsers::default()
+      },
+      true,
+    );
   }
 }
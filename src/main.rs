@@ -3,7 +3,6 @@ use parcel_css::bundler::{Bundler, FileProvider};
 use parcel_css::stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet};
 use parcel_css::targets::Browsers;
 use parcel_sourcemap::SourceMap;
-use serde::Serialize;
 use std::{ffi, fs, io, path, path::Path};
 
 #[cfg(target_os = "macos")]
@@ -40,16 +39,6 @@ struct CliArgs {
   targets: Vec<String>,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-struct SourceMapJson<'a> {
-  version: u8,
-  mappings: String,
-  sources: &'a Vec<String>,
-  sources_content: &'a Vec<String>,
-  names: &'a Vec<String>,
-}
-
 pub fn main() -> Result<(), std::io::Error> {
   let cli_args = CliArgs::parse();
   let source = fs::read_to_string(&cli_args.input_file)?;
@@ -77,7 +66,6 @@ pub fn main() -> Result<(), std::io::Error> {
   } else {
     if let Some(sm) = &mut source_map {
       sm.add_source(&filename);
-      let _ = sm.set_source_content(0, &source);
     }
     StyleSheet::parse(filename.into(), &source, options).unwrap()
   };
@@ -94,29 +82,14 @@ pub fn main() -> Result<(), std::io::Error> {
     .to_css(PrinterOptions {
       minify: cli_args.minify,
       source_map: source_map.as_mut(),
+      encode_source_map: true,
+      include_sources_content: true,
       targets,
       ..PrinterOptions::default()
     })
     .unwrap();
 
-  let map = if let Some(ref mut source_map) = source_map {
-    let mut vlq_output: Vec<u8> = Vec::new();
-    source_map
-      .write_vlq(&mut vlq_output)
-      .map_err(|_| io::Error::new(io::ErrorKind::Other, "Error writing sourcemap vlq"))?;
-
-    let sm = SourceMapJson {
-      version: 3,
-      mappings: unsafe { String::from_utf8_unchecked(vlq_output) },
-      sources: source_map.get_sources(),
-      sources_content: source_map.get_sources_content(),
-      names: source_map.get_names(),
-    };
-
-    serde_json::to_vec(&sm).ok()
-  } else {
-    None
-  };
+  let map = res.source_map.as_ref().and_then(|map| serde_json::to_vec(map).ok());
 
   if let Some(output_file) = &cli_args.output_file {
     let mut code = res.code;
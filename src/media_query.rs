@@ -1,12 +1,21 @@
 use crate::compat::Feature;
-use crate::error::{ErrorWithLocation, MinifyError, MinifyErrorKind, ParserError, PrinterError};
+use crate::error::{
+  ErrorWithLocation, MinifyError, MinifyErrorKind, ParserError, ParserWarning, ParserWarningKind, PrinterError,
+};
 use crate::macros::enum_property;
+use crate::parser::{
+  is_nesting_depth_error, parse_nested_block, starts_with_ignore_ascii_case, with_max_nesting_depth, ParserOptions,
+};
+use crate::prefixes::Feature as PrefixFeature;
 use crate::printer::Printer;
 use crate::rules::custom_media::CustomMediaRule;
 use crate::rules::Location;
+use crate::targets::Browsers;
 use crate::traits::{Parse, ToCss};
+use crate::values::length::serialize_dimension;
 use crate::values::string::CowArcStr;
 use crate::values::{length::Length, ratio::Ratio, resolution::Resolution};
+use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
 use retain_mut::RetainMut;
 use std::collections::{HashMap, HashSet};
@@ -26,26 +35,44 @@ impl<'i> MediaList<'i> {
   /// Parse a media query list from CSS.
   /// <https://drafts.csswg.org/mediaqueries/#error-handling>
   pub fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    let mut media_queries = vec![];
-    loop {
-      match input.parse_until_before(Delimiter::Comma, |i| MediaQuery::parse(i)) {
-        Ok(mq) => {
-          media_queries.push(mq);
+    Self::parse_with_options(input, &ParserOptions::default())
+  }
+
+  /// Parse a media query list from CSS, with the given parser options.
+  ///
+  /// In [strict](ParserOptions::strict) mode, unknown values for discrete media features such as
+  /// `pointer`, and keyword values for range media features such as `width`, are an error. Otherwise,
+  /// they are preserved as is, and the latter are reported as [warnings](ParserOptions::warnings).
+  pub fn parse_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    with_max_nesting_depth(options.max_nesting_depth, || {
+      let mut media_queries = vec![];
+      loop {
+        let location = input.current_source_location();
+        match input.parse_until_before(Delimiter::Comma, |i| MediaQuery::parse_with_options(i, options)) {
+          Ok(mq) => {
+            if options.strict && mq.has_invalid_discrete_feature() {
+              return Err(location.new_custom_error(ParserError::InvalidMediaQuery));
+            }
+            media_queries.push(mq);
+          }
+          Err(err) => match err.kind {
+            ParseErrorKind::Basic(BasicParseErrorKind::EndOfInput) => break,
+            _ => return Err(err),
+          },
         }
-        Err(err) => match err.kind {
-          ParseErrorKind::Basic(BasicParseErrorKind::EndOfInput) => break,
-          _ => return Err(err),
-        },
-      }
 
-      match input.next() {
-        Ok(&Token::Comma) => {}
-        Ok(_) => unreachable!(),
-        Err(_) => break,
+        match input.next() {
+          Ok(&Token::Comma) => {}
+          Ok(_) => unreachable!(),
+          Err(_) => break,
+        }
       }
-    }
 
-    Ok(MediaList { media_queries })
+      Ok(MediaList { media_queries })
+    })
   }
 
   pub(crate) fn transform_custom_media(
@@ -59,6 +86,52 @@ impl<'i> MediaList<'i> {
     Ok(())
   }
 
+  /// Converts `resolution` values to `dppx` where supported, and adds queries using the
+  /// `-webkit-device-pixel-ratio` feature for legacy WebKit browsers.
+  pub(crate) fn transform_resolution(&mut self, targets: Browsers) {
+    let normalize = Feature::DppxResolution.is_compatible(targets);
+    let webkit = PrefixFeature::AtResolution.prefixes_for(targets).contains(VendorPrefix::WebKit);
+    if !normalize && !webkit {
+      return;
+    }
+
+    let queries = std::mem::take(&mut self.media_queries);
+    for query in &queries {
+      if webkit {
+        if let Some(legacy) = query.to_webkit_device_pixel_ratio() {
+          if !queries.contains(&legacy) && !self.media_queries.contains(&legacy) {
+            self.media_queries.push(legacy);
+          }
+        }
+      }
+
+      let mut query = query.clone();
+      if normalize {
+        if let Some(condition) = &mut query.condition {
+          condition.normalize_resolution();
+        }
+      }
+      self.media_queries.push(query);
+    }
+  }
+
+  /// Simplifies the boolean logic of each query, and removes duplicate queries.
+  /// The `only` qualifier is removed unless the targets include browsers that don't support media queries.
+  /// Queries that can never match are removed, unless every query in the list can never match.
+  pub(crate) fn simplify(&mut self, targets: Option<Browsers>) {
+    let queries = std::mem::take(&mut self.media_queries);
+    for mut query in queries {
+      query.simplify(targets);
+      if !self.media_queries.contains(&query) {
+        self.media_queries.push(query);
+      }
+    }
+
+    if !self.never_matches() {
+      self.media_queries.retain(|query| !query.never_matches());
+    }
+  }
+
   pub fn always_matches(&self) -> bool {
     // If the media list is empty, it always matches.
     self.media_queries.is_empty() || self.media_queries.iter().all(|mq| mq.always_matches())
@@ -68,6 +141,32 @@ impl<'i> MediaList<'i> {
     !self.media_queries.is_empty() && self.media_queries.iter().all(|mq| mq.never_matches())
   }
 
+  /// Evaluates the media list in the given context. Returns `Some(true)` if it matches in every
+  /// environment described by the context, `Some(false)` if it matches in none, and `None` otherwise.
+  pub fn evaluate(&self, context: &MediaContext) -> Option<bool> {
+    if self.media_queries.is_empty() {
+      return Some(true);
+    }
+
+    let mut result = Some(false);
+    for query in &self.media_queries {
+      match query.evaluate(context) {
+        Some(true) => return Some(true),
+        Some(false) => {}
+        None => result = None,
+      }
+    }
+    result
+  }
+
+  /// Returns whether any query in the list references the given media feature.
+  pub fn has_feature(&self, name: &str) -> bool {
+    self
+      .media_queries
+      .iter()
+      .any(|mq| matches!(&mq.condition, Some(condition) if condition.has_feature(name)))
+  }
+
   pub fn and(&mut self, b: &MediaList<'i>) -> Result<(), ()> {
     if self.media_queries.is_empty() {
       self.media_queries.extend(b.media_queries.iter().cloned());
@@ -166,6 +265,14 @@ impl<'i> MediaQuery<'i> {
   ///
   /// Returns an error if any of the expressions is unknown.
   pub fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    Self::parse_with_options(input, &ParserOptions::default())
+  }
+
+  /// Parse a media query given css input, with the given parser options.
+  pub fn parse_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     let (qualifier, explicit_media_type) = input
       .try_parse(|input| -> Result<_, ParseError<'i, ParserError<'i>>> {
         let qualifier = input.try_parse(Qualifier::parse).ok();
@@ -175,9 +282,9 @@ impl<'i> MediaQuery<'i> {
       .unwrap_or_default();
 
     let condition = if explicit_media_type.is_none() {
-      Some(MediaCondition::parse(input, true)?)
+      Some(MediaCondition::parse_with_options(input, true, options)?)
     } else if input.try_parse(|i| i.expect_ident_matching("and")).is_ok() {
-      Some(MediaCondition::parse(input, false)?)
+      Some(MediaCondition::parse_with_options(input, false, options)?)
     } else {
       None
     };
@@ -211,12 +318,70 @@ impl<'i> MediaQuery<'i> {
     Ok(())
   }
 
+  /// Returns a copy of the query with `resolution` features replaced by `-webkit-device-pixel-ratio`,
+  /// if it contains any that can be converted.
+  fn to_webkit_device_pixel_ratio(&self) -> Option<MediaQuery<'i>> {
+    let mut condition = self.condition.clone()?;
+    if !condition.to_webkit_device_pixel_ratio()? {
+      return None;
+    }
+
+    Some(MediaQuery {
+      qualifier: self.qualifier,
+      media_type: self.media_type.clone(),
+      condition: Some(condition),
+    })
+  }
+
+  fn simplify(&mut self, targets: Option<Browsers>) {
+    // `only` hides the query from legacy browsers that only understand media types.
+    if self.qualifier == Some(Qualifier::Only) {
+      if let Some(targets) = targets {
+        if Feature::MediaQueries.is_compatible(targets) {
+          self.qualifier = None;
+        }
+      }
+    }
+
+    if let Some(condition) = self.condition.take() {
+      self.condition = Some(condition.simplify());
+    }
+  }
+
   pub fn always_matches(&self) -> bool {
     self.qualifier == None && self.media_type == MediaType::All && self.condition == None
   }
 
+  fn has_invalid_discrete_feature(&self) -> bool {
+    matches!(&self.condition, Some(condition) if condition.has_invalid_discrete_feature())
+  }
+
   pub fn never_matches(&self) -> bool {
-    self.qualifier == Some(Qualifier::Not) && self.media_type == MediaType::All
+    if self.qualifier == Some(Qualifier::Not) {
+      return self.media_type == MediaType::All;
+    }
+
+    matches!(&self.condition, Some(condition) if condition.never_matches())
+  }
+
+  /// Evaluates the media query in the given context. See [MediaList::evaluate](MediaList::evaluate).
+  pub fn evaluate(&self, context: &MediaContext) -> Option<bool> {
+    let media_type = match (&self.media_type, &context.media_type) {
+      (MediaType::All, _) => Some(true),
+      (_, None) => None,
+      (a, Some(b)) => Some(a == b),
+    };
+
+    let result = match &self.condition {
+      Some(condition) => and(media_type, condition.evaluate(context)),
+      None => media_type,
+    };
+
+    if self.qualifier == Some(Qualifier::Not) {
+      result.map(|r| !r)
+    } else {
+      result
+    }
   }
 
   pub fn and<'a>(&mut self, b: &MediaQuery<'i>) -> Result<(), ()> {
@@ -344,6 +509,15 @@ pub enum MediaCondition<'i> {
 impl<'i> MediaCondition<'i> {
   /// Parse a single media condition.
   pub fn parse<'t>(input: &mut Parser<'i, 't>, allow_or: bool) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    Self::parse_with_options(input, allow_or, &ParserOptions::default())
+  }
+
+  /// Parse a single media condition, with the given parser options.
+  pub fn parse_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    allow_or: bool,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     let location = input.current_source_location();
     let is_negation = match *input.next()? {
       Token::ParenthesisBlock => false,
@@ -352,12 +526,12 @@ impl<'i> MediaCondition<'i> {
     };
 
     if is_negation {
-      let inner_condition = Self::parse_in_parens(input)?;
+      let inner_condition = Self::parse_in_parens_with_options(input, options)?;
       return Ok(MediaCondition::Not(Box::new(inner_condition)));
     }
 
     // ParenthesisBlock.
-    let first_condition = Self::parse_paren_block(input)?;
+    let first_condition = Self::parse_paren_block(input, options)?;
     let operator = match input.try_parse(Operator::parse) {
       Ok(op) => op,
       Err(..) => return Ok(first_condition),
@@ -369,7 +543,7 @@ impl<'i> MediaCondition<'i> {
 
     let mut conditions = vec![];
     conditions.push(first_condition);
-    conditions.push(Self::parse_in_parens(input)?);
+    conditions.push(Self::parse_in_parens_with_options(input, options)?);
 
     let delim = match operator {
       Operator::And => "and",
@@ -381,24 +555,208 @@ impl<'i> MediaCondition<'i> {
         return Ok(MediaCondition::Operation(conditions, operator));
       }
 
-      conditions.push(Self::parse_in_parens(input)?);
+      conditions.push(Self::parse_in_parens_with_options(input, options)?);
+    }
+  }
+
+  fn evaluate(&self, context: &MediaContext) -> Option<bool> {
+    match self {
+      MediaCondition::Feature(feature) => feature.evaluate(context),
+      MediaCondition::Not(c) => c.evaluate(context).map(|r| !r),
+      MediaCondition::InParens(c) => c.evaluate(context),
+      MediaCondition::Operation(conditions, Operator::And) => {
+        conditions.iter().fold(Some(true), |result, c| and(result, c.evaluate(context)))
+      }
+      MediaCondition::Operation(conditions, Operator::Or) => {
+        conditions.iter().fold(Some(false), |result, c| or(result, c.evaluate(context)))
+      }
+    }
+  }
+
+  fn has_feature(&self, name: &str) -> bool {
+    match self {
+      MediaCondition::Feature(feature) => feature.name().eq_ignore_ascii_case(name),
+      MediaCondition::Not(c) | MediaCondition::InParens(c) => c.has_feature(name),
+      MediaCondition::Operation(conditions, _) => conditions.iter().any(|c| c.has_feature(name)),
+    }
+  }
+
+  /// Returns whether the condition can never match because it requires mutually exclusive
+  /// ranges of a feature, e.g. `(min-width: 500px) and (max-width: 200px)`.
+  fn never_matches(&self) -> bool {
+    match self {
+      MediaCondition::Not(_) => false,
+      MediaCondition::InParens(c) => c.never_matches(),
+      MediaCondition::Operation(conditions, Operator::Or) => conditions.iter().all(|c| c.never_matches()),
+      MediaCondition::Operation(conditions, Operator::And) if conditions.iter().any(|c| c.never_matches()) => true,
+      _ => {
+        let mut bounds = Vec::new();
+        self.add_bounds(&mut bounds);
+        bounds.iter().any(|(_, b)| b.is_empty())
+      }
+    }
+  }
+
+  /// Intersects the bounds of each range feature that must match for the condition to match.
+  fn add_bounds<'a>(&'a self, bounds: &mut Vec<(&'a str, FeatureBounds)>) {
+    match self {
+      MediaCondition::Feature(feature) => feature.add_bounds(bounds),
+      MediaCondition::InParens(c) => c.add_bounds(bounds),
+      MediaCondition::Operation(conditions, Operator::And) => {
+        for c in conditions {
+          c.add_bounds(bounds);
+        }
+      }
+      MediaCondition::Not(_) | MediaCondition::Operation(_, Operator::Or) => {}
+    }
+  }
+
+  fn has_invalid_discrete_feature(&self) -> bool {
+    match self {
+      MediaCondition::Feature(feature) => feature.is_invalid_discrete_feature(),
+      MediaCondition::Not(c) | MediaCondition::InParens(c) => c.has_invalid_discrete_feature(),
+      MediaCondition::Operation(conditions, _) => conditions.iter().any(|c| c.has_invalid_discrete_feature()),
+    }
+  }
+
+  /// Replaces `resolution` features with the equivalent `-webkit-device-pixel-ratio` features.
+  /// Returns whether any were replaced, or `None` if one cannot be converted.
+  fn to_webkit_device_pixel_ratio(&mut self) -> Option<bool> {
+    match self {
+      MediaCondition::Feature(feature) => feature.to_webkit_device_pixel_ratio(),
+      MediaCondition::Not(c) | MediaCondition::InParens(c) => c.to_webkit_device_pixel_ratio(),
+      MediaCondition::Operation(conditions, _) => {
+        let mut replaced = false;
+        for c in conditions {
+          replaced |= c.to_webkit_device_pixel_ratio()?;
+        }
+        Some(replaced)
+      }
+    }
+  }
+
+  /// Removes double negations, redundant parentheses, and duplicate operands, and applies
+  /// De Morgan's laws to negated operands. Media conditions use three-valued logic, in which
+  /// all of these transformations preserve the result.
+  fn simplify(self) -> MediaCondition<'i> {
+    match self {
+      MediaCondition::Feature(_) => self,
+      MediaCondition::InParens(c) => match c.simplify() {
+        // `((color))` => `(color)`
+        c @ (MediaCondition::Feature(_) | MediaCondition::InParens(_)) => c,
+        c => MediaCondition::InParens(Box::new(c)),
+      },
+      MediaCondition::Not(c) => match c.simplify() {
+        // `not (not (color))` => `(color)`
+        MediaCondition::InParens(c) if matches!(*c, MediaCondition::Not(_)) => match *c {
+          MediaCondition::Not(c) => *c,
+          _ => unreachable!(),
+        },
+        c => MediaCondition::Not(Box::new(c)),
+      },
+      MediaCondition::Operation(list, operator) => {
+        let mut conditions = Vec::with_capacity(list.len());
+        for c in list {
+          match c.simplify() {
+            // `(a) and ((b) and (c))` => `(a) and (b) and (c)`
+            MediaCondition::InParens(inner) if matches!(&*inner, MediaCondition::Operation(_, op) if *op == operator) => {
+              if let MediaCondition::Operation(inner, _) = *inner {
+                for c in inner {
+                  if !conditions.contains(&c) {
+                    conditions.push(c);
+                  }
+                }
+              }
+            }
+            // `(a) and (a)` => `(a)`
+            c => {
+              if !conditions.contains(&c) {
+                conditions.push(c);
+              }
+            }
+          }
+        }
+
+        // `(a) or ((min-width: 500px) and (max-width: 200px))` => `(a)`
+        if operator == Operator::Or && !conditions.iter().all(|c| c.never_matches()) {
+          conditions.retain(|c| !c.never_matches());
+        }
+
+        if conditions.len() == 1 {
+          return conditions.remove(0);
+        }
+
+        // `(not (a)) and (not (b))` => `not ((a) or (b))`
+        let all_negated = conditions
+          .iter()
+          .all(|c| matches!(c, MediaCondition::InParens(c) if matches!(**c, MediaCondition::Not(_))));
+        if all_negated {
+          let conditions = conditions
+            .into_iter()
+            .map(|c| match c {
+              MediaCondition::InParens(c) => match *c {
+                MediaCondition::Not(c) => *c,
+                _ => unreachable!(),
+              },
+              _ => unreachable!(),
+            })
+            .collect();
+          let operator = match operator {
+            Operator::And => Operator::Or,
+            Operator::Or => Operator::And,
+          };
+          return MediaCondition::Not(Box::new(MediaCondition::InParens(Box::new(MediaCondition::Operation(
+            conditions, operator,
+          )))));
+        }
+
+        MediaCondition::Operation(conditions, operator)
+      }
+    }
+  }
+
+  fn normalize_resolution(&mut self) {
+    match self {
+      MediaCondition::Feature(feature) => feature.normalize_resolution(),
+      MediaCondition::Not(c) | MediaCondition::InParens(c) => c.normalize_resolution(),
+      MediaCondition::Operation(conditions, _) => {
+        for c in conditions {
+          c.normalize_resolution();
+        }
+      }
     }
   }
 
   /// Parse a media condition in parentheses.
   pub fn parse_in_parens<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    Self::parse_in_parens_with_options(input, &ParserOptions::default())
+  }
+
+  fn parse_in_parens_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     input.expect_parenthesis_block()?;
-    Self::parse_paren_block(input)
+    Self::parse_paren_block(input, options)
   }
 
-  fn parse_paren_block<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    input.parse_nested_block(|input| {
-      if let Ok(inner) = input.try_parse(|i| Self::parse(i, true)) {
-        return Ok(MediaCondition::InParens(Box::new(inner)));
-      }
+  fn parse_paren_block<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    parse_nested_block(input, |input| {
+      let err = match input.try_parse(|i| Self::parse_with_options(i, true, options)) {
+        Ok(inner) => return Ok(MediaCondition::InParens(Box::new(inner))),
+        Err(err) if is_nesting_depth_error(&err) => return Err(err),
+        Err(err) => err,
+      };
 
-      let feature = MediaFeature::parse(input)?;
-      Ok(MediaCondition::Feature(feature))
+      // Errors within nested conditions are more specific than the feature error.
+      match MediaFeature::parse_with_options(input, options) {
+        Ok(feature) => Ok(MediaCondition::Feature(feature)),
+        Err(_) if matches!(err.kind, ParseErrorKind::Custom(ParserError::InvalidMediaQuery)) => Err(err),
+        Err(e) => Err(e),
+      }
     })
   }
 }
@@ -495,6 +853,11 @@ pub enum MediaFeature<'i> {
   },
   // e.g. (hover)
   Boolean(CowArcStr<'i>),
+  /// A discrete feature with a known keyword value, or in boolean context, e.g. (pointer: fine) or (hover).
+  Discrete {
+    feature: DiscreteMediaFeature,
+    value: Option<DiscreteMediaFeatureValue>,
+  },
   // e.g. (width > 240px)
   Range {
     name: CowArcStr<'i>,
@@ -513,25 +876,244 @@ pub enum MediaFeature<'i> {
 
 impl<'i> Parse<'i> for MediaFeature<'i> {
   fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    if let Ok(res) = input.try_parse(Self::parse_name_first) {
-      return Ok(res);
-    }
+    Self::parse_with_options(input, &ParserOptions::default())
+  }
+}
 
-    Self::parse_value_first(input)
+impl<'i> MediaFeature<'i> {
+  fn parse_with_options<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let err = match input.try_parse(|input| Self::parse_name_first(input, options)) {
+      Ok(res) => return Ok(res),
+      Err(err) => err,
+    };
+
+    // Report invalid values of known features at the value rather than at the start of the feature.
+    match input.try_parse(|input| Self::parse_value_first(input, options)) {
+      Ok(res) => Ok(res),
+      Err(_) if matches!(err.kind, ParseErrorKind::Custom(ParserError::InvalidMediaQuery)) => Err(err),
+      Err(e) => Err(e),
+    }
   }
 }
 
 impl<'i> MediaFeature<'i> {
-  fn parse_name_first<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    let name = input.expect_ident()?.into();
+  /// Returns the name of the media feature.
+  pub fn name(&self) -> &str {
+    match self {
+      MediaFeature::Plain { name, .. }
+      | MediaFeature::Boolean(name)
+      | MediaFeature::Range { name, .. }
+      | MediaFeature::Interval { name, .. } => name.as_ref(),
+      MediaFeature::Discrete { feature, .. } => feature.name(),
+    }
+  }
+
+  /// Returns whether this is a discrete media feature used with a value it does not accept,
+  /// or in a range context.
+  fn is_invalid_discrete_feature(&self) -> bool {
+    match self {
+      MediaFeature::Plain { name, .. }
+      | MediaFeature::Range { name, .. }
+      | MediaFeature::Interval { name, .. } => DiscreteMediaFeature::from_name(name).is_some(),
+      MediaFeature::Boolean(..) | MediaFeature::Discrete { .. } => false,
+    }
+  }
+
+  fn evaluate(&self, context: &MediaContext) -> Option<bool> {
+    match self {
+      MediaFeature::Discrete { feature, value } => {
+        let (_, actual) = context.features.iter().find(|(f, _)| f == feature)?;
+        Some(match value {
+          Some(value) => value == actual,
+          None => !matches!(
+            actual,
+            DiscreteMediaFeatureValue::None | DiscreteMediaFeatureValue::NoPreference
+          ),
+        })
+      }
+      MediaFeature::Plain { name, value } => {
+        if starts_with_ignore_ascii_case(name, "min-") {
+          context.evaluate_range(&name[4..], MediaFeatureComparison::GreaterThanEqual, value)
+        } else if starts_with_ignore_ascii_case(name, "max-") {
+          context.evaluate_range(&name[4..], MediaFeatureComparison::LessThanEqual, value)
+        } else {
+          context.evaluate_range(name, MediaFeatureComparison::Equal, value)
+        }
+      }
+      MediaFeature::Range { name, operator, value } => context.evaluate_range(name, *operator, value),
+      MediaFeature::Interval {
+        name,
+        start,
+        start_operator,
+        end,
+        end_operator,
+      } => and(
+        context.evaluate_range(name, start_operator.opposite(), start),
+        context.evaluate_range(name, *end_operator, end),
+      ),
+      MediaFeature::Boolean(..) => None,
+    }
+  }
+
+  /// Intersects the bounds placed on the value of a range feature with the existing bounds for that feature.
+  /// Values that cannot be compared, such as font-relative lengths, are ignored.
+  fn add_bounds<'a>(&'a self, bounds: &mut Vec<(&'a str, FeatureBounds)>) {
+    let (name, constraints) = match self {
+      MediaFeature::Plain { name, value } => {
+        let value = match value.to_number() {
+          Some(value) => value,
+          None => return,
+        };
+        if starts_with_ignore_ascii_case(name, "min-") {
+          (
+            &name[4..],
+            [Some((MediaFeatureComparison::GreaterThanEqual, value)), None],
+          )
+        } else if starts_with_ignore_ascii_case(name, "max-") {
+          (&name[4..], [Some((MediaFeatureComparison::LessThanEqual, value)), None])
+        } else {
+          (name.as_ref(), [Some((MediaFeatureComparison::Equal, value)), None])
+        }
+      }
+      MediaFeature::Range { name, operator, value } => {
+        (name.as_ref(), [value.to_number().map(|value| (*operator, value)), None])
+      }
+      MediaFeature::Interval {
+        name,
+        start,
+        start_operator,
+        end,
+        end_operator,
+      } => (
+        name.as_ref(),
+        [
+          start.to_number().map(|start| (start_operator.opposite(), start)),
+          end.to_number().map(|end| (*end_operator, end)),
+        ],
+      ),
+      MediaFeature::Boolean(..) | MediaFeature::Discrete { .. } => return,
+    };
+
+    if !is_range_feature(name) {
+      return;
+    }
+
+    let index = match bounds.iter().position(|(n, _)| n.eq_ignore_ascii_case(name)) {
+      Some(index) => index,
+      None => {
+        bounds.push((name, FeatureBounds::default()));
+        bounds.len() - 1
+      }
+    };
+
+    for &(operator, value) in constraints.iter().flatten() {
+      bounds[index].1.add(operator, value);
+    }
+  }
+
+  fn to_webkit_device_pixel_ratio(&mut self) -> Option<bool> {
+    let (name, value) = match self {
+      MediaFeature::Plain {
+        name,
+        value: MediaFeatureValue::Resolution(res),
+      } => {
+        let name = match_ignore_ascii_case! { name.as_ref(),
+          "resolution" => "-webkit-device-pixel-ratio",
+          "min-resolution" => "-webkit-min-device-pixel-ratio",
+          "max-resolution" => "-webkit-max-device-pixel-ratio",
+          _ => return Some(false)
+        };
+        (name, res.to_dppx())
+      }
+      MediaFeature::Range {
+        name,
+        operator,
+        value: MediaFeatureValue::Resolution(res),
+      } if name.eq_ignore_ascii_case("resolution") => match operator {
+        MediaFeatureComparison::Equal => ("-webkit-device-pixel-ratio", res.to_dppx()),
+        MediaFeatureComparison::GreaterThan => ("-webkit-min-device-pixel-ratio", res.to_dppx() + 0.001),
+        MediaFeatureComparison::GreaterThanEqual => ("-webkit-min-device-pixel-ratio", res.to_dppx()),
+        MediaFeatureComparison::LessThan => ("-webkit-max-device-pixel-ratio", res.to_dppx() - 0.001),
+        MediaFeatureComparison::LessThanEqual => ("-webkit-max-device-pixel-ratio", res.to_dppx()),
+      },
+      MediaFeature::Interval { name, .. } | MediaFeature::Boolean(name)
+        if name.eq_ignore_ascii_case("resolution") =>
+      {
+        return None
+      }
+      _ => return Some(false),
+    };
+
+    *self = MediaFeature::Plain {
+      name: name.into(),
+      value: MediaFeatureValue::Number(value),
+    };
+    Some(true)
+  }
+
+  /// Converts resolution values to `dppx` when they can be represented exactly.
+  fn normalize_resolution(&mut self) {
+    let normalize = |value: &mut MediaFeatureValue| {
+      if let MediaFeatureValue::Resolution(res) = value {
+        let dppx = res.to_dppx();
+        if (dppx * 100.0).fract() == 0.0 {
+          *res = Resolution::Dppx(dppx);
+        }
+      }
+    };
+
+    match self {
+      MediaFeature::Plain { value, .. } | MediaFeature::Range { value, .. } => normalize(value),
+      MediaFeature::Interval { start, end, .. } => {
+        normalize(start);
+        normalize(end);
+      }
+      MediaFeature::Boolean(..) | MediaFeature::Discrete { .. } => {}
+    }
+  }
+
+  fn parse_name_first<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let name: CowArcStr<'i> = input.expect_ident()?.into();
+    let discrete = DiscreteMediaFeature::from_name(&name);
 
     let operator = input.try_parse(|input| consume_operation_or_colon(input, true));
     let operator = match operator {
-      Err(..) => return Ok(MediaFeature::Boolean(name)),
+      Err(..) => {
+        if let Some(feature) = discrete {
+          return Ok(MediaFeature::Discrete { feature, value: None });
+        }
+        return Ok(MediaFeature::Boolean(name));
+      }
       Ok(operator) => operator,
     };
 
+    if let (Some(feature), None) = (discrete, operator) {
+      // Unknown values are preserved as plain features.
+      if let Ok(value) = input.try_parse(|input| {
+        let location = input.current_source_location();
+        let value = DiscreteMediaFeatureValue::parse(input)?;
+        if !feature.values().contains(&value) {
+          return Err(location.new_custom_error(ParserError::InvalidMediaQuery));
+        }
+        input.expect_exhausted()?;
+        Ok(value)
+      }) {
+        return Ok(MediaFeature::Discrete {
+          feature,
+          value: Some(value),
+        });
+      }
+    }
+
+    let location = input.current_source_location();
     let value = MediaFeatureValue::parse(input)?;
+    check_range_feature_value(&name, &value, location, options)?;
 
     if let Some(operator) = operator {
       Ok(MediaFeature::Range { name, operator, value })
@@ -540,10 +1122,15 @@ impl<'i> MediaFeature<'i> {
     }
   }
 
-  fn parse_value_first<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+  fn parse_value_first<'t>(
+    input: &mut Parser<'i, 't>,
+    options: &ParserOptions,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let location = input.current_source_location();
     let value = MediaFeatureValue::parse(input)?;
     let operator = consume_operation_or_colon(input, false)?;
-    let name = input.expect_ident()?.into();
+    let name: CowArcStr<'i> = input.expect_ident()?.into();
+    check_range_feature_value(&name, &value, location, options)?;
 
     if let Ok(end_operator) = input.try_parse(|input| consume_operation_or_colon(input, false)) {
       let start_operator = operator.unwrap();
@@ -560,7 +1147,9 @@ impl<'i> MediaFeature<'i> {
         | (MediaFeatureComparison::LessThanEqual, MediaFeatureComparison::LessThan) => {}
         _ => return Err(input.new_custom_error(ParserError::InvalidMediaQuery)),
       };
+      let location = input.current_source_location();
       let end_value = MediaFeatureValue::parse(input)?;
+      check_range_feature_value(&name, &end_value, location, options)?;
       Ok(MediaFeature::Interval {
         name,
         start: value,
@@ -586,6 +1175,13 @@ impl<'i> ToCss for MediaFeature<'i> {
       MediaFeature::Boolean(name) => {
         serialize_identifier(name, dest)?;
       }
+      MediaFeature::Discrete { feature, value } => {
+        dest.write_str(feature.name())?;
+        if let Some(value) = value {
+          dest.delim(':', false)?;
+          value.to_css(dest)?;
+        }
+      }
       MediaFeature::Plain { name, value } => {
         serialize_identifier(name, dest)?;
         dest.delim(':', false)?;
@@ -669,6 +1265,105 @@ where
   Ok(())
 }
 
+/// A media feature that accepts one of a fixed set of keyword values,
+/// and evaluates to true in a boolean context unless its value is `none` or `no-preference`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum DiscreteMediaFeature {
+  Hover,
+  AnyHover,
+  Pointer,
+  AnyPointer,
+  Update,
+  PrefersColorScheme,
+  PrefersReducedMotion,
+  PrefersContrast,
+  ForcedColors,
+  Scripting,
+  DevicePosture,
+}
+
+impl DiscreteMediaFeature {
+  /// Returns the discrete media feature with the given name, if any.
+  pub fn from_name(name: &str) -> Option<DiscreteMediaFeature> {
+    use DiscreteMediaFeature::*;
+    Some(match_ignore_ascii_case! { name,
+      "hover" => Hover,
+      "any-hover" => AnyHover,
+      "pointer" => Pointer,
+      "any-pointer" => AnyPointer,
+      "update" => Update,
+      "prefers-color-scheme" => PrefersColorScheme,
+      "prefers-reduced-motion" => PrefersReducedMotion,
+      "prefers-contrast" => PrefersContrast,
+      "forced-colors" => ForcedColors,
+      "scripting" => Scripting,
+      "device-posture" => DevicePosture,
+      _ => return None
+    })
+  }
+
+  /// Returns the name of the media feature.
+  pub fn name(&self) -> &'static str {
+    use DiscreteMediaFeature::*;
+    match self {
+      Hover => "hover",
+      AnyHover => "any-hover",
+      Pointer => "pointer",
+      AnyPointer => "any-pointer",
+      Update => "update",
+      PrefersColorScheme => "prefers-color-scheme",
+      PrefersReducedMotion => "prefers-reduced-motion",
+      PrefersContrast => "prefers-contrast",
+      ForcedColors => "forced-colors",
+      Scripting => "scripting",
+      DevicePosture => "device-posture",
+    }
+  }
+
+  /// Returns the values that the media feature accepts.
+  pub fn values(&self) -> &'static [DiscreteMediaFeatureValue] {
+    use DiscreteMediaFeature::*;
+    use DiscreteMediaFeatureValue as V;
+    match self {
+      Hover | AnyHover => &[V::None, V::Hover],
+      Pointer | AnyPointer => &[V::None, V::Coarse, V::Fine],
+      Update => &[V::None, V::Slow, V::Fast],
+      PrefersColorScheme => &[V::Light, V::Dark],
+      PrefersReducedMotion => &[V::NoPreference, V::Reduce],
+      PrefersContrast => &[V::NoPreference, V::More, V::Less, V::Custom],
+      ForcedColors => &[V::None, V::Active],
+      Scripting => &[V::None, V::InitialOnly, V::Enabled],
+      DevicePosture => &[V::Continuous, V::Folded],
+    }
+  }
+}
+
+enum_property! {
+  /// A keyword value for a [discrete media feature](DiscreteMediaFeature).
+  #[allow(missing_docs)]
+  pub enum DiscreteMediaFeatureValue {
+    "none": None,
+    "hover": Hover,
+    "coarse": Coarse,
+    "fine": Fine,
+    "slow": Slow,
+    "fast": Fast,
+    "light": Light,
+    "dark": Dark,
+    "no-preference": NoPreference,
+    "reduce": Reduce,
+    "more": More,
+    "less": Less,
+    "custom": Custom,
+    "active": Active,
+    "initial-only": InitialOnly,
+    "enabled": Enabled,
+    "continuous": Continuous,
+    "folded": Folded,
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum MediaFeatureValue<'i> {
   Length(Length),
@@ -713,6 +1408,8 @@ impl<'i> ToCss for MediaFeatureValue<'i> {
     match self {
       MediaFeatureValue::Length(len) => len.to_css(dest),
       MediaFeatureValue::Number(num) => num.to_css(dest),
+      // The `x` alias is not supported in media queries by older browsers.
+      MediaFeatureValue::Resolution(Resolution::Dppx(dppx)) => serialize_dimension(*dppx, "dppx", dest),
       MediaFeatureValue::Resolution(res) => res.to_css(dest),
       MediaFeatureValue::Ratio(ratio) => ratio.to_css(dest),
       MediaFeatureValue::Ident(id) => {
@@ -723,6 +1420,19 @@ impl<'i> ToCss for MediaFeatureValue<'i> {
   }
 }
 
+impl<'i> MediaFeatureValue<'i> {
+  /// Returns the value as a number that can be compared with other values of the same feature,
+  /// with lengths converted to pixels.
+  fn to_number(&self) -> Option<f32> {
+    match self {
+      MediaFeatureValue::Length(length) => length.to_px(),
+      MediaFeatureValue::Number(number) => Some(*number),
+      MediaFeatureValue::Ratio(Ratio(a, b)) if *b != 0.0 => Some(a / b),
+      _ => None,
+    }
+  }
+}
+
 impl<'i> std::ops::Add<f32> for MediaFeatureValue<'i> {
   type Output = Self;
 
@@ -737,6 +1447,184 @@ impl<'i> std::ops::Add<f32> for MediaFeatureValue<'i> {
   }
 }
 
+/// A description of the environments in which a style sheet is displayed, used to evaluate media queries.
+/// Properties that are `None` are unknown, and queries that depend on them are only known to match if
+/// they match regardless of the value. For example, a context with a `min_width` of `800` matches
+/// `(min-width: 600px)`, but not `(min-width: 1000px)` or `print`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaContext {
+  /// The media type, e.g. `screen`.
+  pub media_type: Option<MediaType<'static>>,
+  /// The minimum viewport width, in pixels.
+  pub min_width: Option<f32>,
+  /// The maximum viewport width, in pixels.
+  pub max_width: Option<f32>,
+  /// The minimum viewport height, in pixels.
+  pub min_height: Option<f32>,
+  /// The maximum viewport height, in pixels.
+  pub max_height: Option<f32>,
+  /// The values of discrete media features, e.g. `prefers-color-scheme: dark`.
+  pub features: Vec<(DiscreteMediaFeature, DiscreteMediaFeatureValue)>,
+}
+
+impl MediaContext {
+  fn evaluate_range(
+    &self,
+    name: &str,
+    operator: MediaFeatureComparison,
+    value: &MediaFeatureValue,
+  ) -> Option<bool> {
+    let (min, max) = match_ignore_ascii_case! { name,
+      "width" => (self.min_width, self.max_width),
+      "height" => (self.min_height, self.max_height),
+      _ => return None
+    };
+
+    let value = match value {
+      MediaFeatureValue::Length(length) => length.to_px()?,
+      MediaFeatureValue::Number(number) if *number == 0.0 => 0.0,
+      _ => return None,
+    };
+
+    let min = min.unwrap_or(0.0);
+    let max = max.unwrap_or(f32::INFINITY);
+    let (all, none) = match operator {
+      MediaFeatureComparison::Equal => (min == value && max == value, value < min || value > max),
+      MediaFeatureComparison::GreaterThan => (min > value, max <= value),
+      MediaFeatureComparison::GreaterThanEqual => (min >= value, max < value),
+      MediaFeatureComparison::LessThan => (max < value, min >= value),
+      MediaFeatureComparison::LessThanEqual => (max <= value, min > value),
+    };
+
+    if all {
+      Some(true)
+    } else if none {
+      Some(false)
+    } else {
+      None
+    }
+  }
+}
+
+/// The range of values a media feature may have for a condition to match.
+#[derive(Clone, Copy, Debug)]
+struct FeatureBounds {
+  min: f32,
+  min_inclusive: bool,
+  max: f32,
+  max_inclusive: bool,
+}
+
+impl Default for FeatureBounds {
+  fn default() -> FeatureBounds {
+    FeatureBounds {
+      min: f32::NEG_INFINITY,
+      min_inclusive: true,
+      max: f32::INFINITY,
+      max_inclusive: true,
+    }
+  }
+}
+
+impl FeatureBounds {
+  fn add(&mut self, operator: MediaFeatureComparison, value: f32) {
+    match operator {
+      MediaFeatureComparison::Equal => {
+        self.add(MediaFeatureComparison::GreaterThanEqual, value);
+        self.add(MediaFeatureComparison::LessThanEqual, value);
+      }
+      MediaFeatureComparison::GreaterThan | MediaFeatureComparison::GreaterThanEqual => {
+        let inclusive = operator == MediaFeatureComparison::GreaterThanEqual;
+        if value > self.min || (value == self.min && !inclusive) {
+          self.min = value;
+          self.min_inclusive = inclusive;
+        }
+      }
+      MediaFeatureComparison::LessThan | MediaFeatureComparison::LessThanEqual => {
+        let inclusive = operator == MediaFeatureComparison::LessThanEqual;
+        if value < self.max || (value == self.max && !inclusive) {
+          self.max = value;
+          self.max_inclusive = inclusive;
+        }
+      }
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.min > self.max || (self.min == self.max && !(self.min_inclusive && self.max_inclusive))
+  }
+}
+
+#[inline]
+fn and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+  match (a, b) {
+    (Some(false), _) | (_, Some(false)) => Some(false),
+    (Some(true), Some(true)) => Some(true),
+    _ => None,
+  }
+}
+
+#[inline]
+fn or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+  match (a, b) {
+    (Some(true), _) | (_, Some(true)) => Some(true),
+    (Some(false), Some(false)) => Some(false),
+    _ => None,
+  }
+}
+
+/// Returns whether the given name is a known range media feature, which does not accept keyword values.
+fn is_range_feature(name: &str) -> bool {
+  let name = if starts_with_ignore_ascii_case(name, "min-") || starts_with_ignore_ascii_case(name, "max-") {
+    &name[4..]
+  } else {
+    name
+  };
+
+  match_ignore_ascii_case! { name,
+    "width" | "height" | "aspect-ratio" | "resolution" | "color" | "color-index" | "monochrome"
+      | "device-width" | "device-height" | "device-aspect-ratio" => true,
+    _ => false
+  }
+}
+
+/// Checks that a keyword is not used as the value of a range media feature, such as `min-width: red`.
+/// This is an error in strict mode. Otherwise, the value is preserved, and a warning is reported.
+fn check_range_feature_value<'i>(
+  name: &str,
+  value: &MediaFeatureValue<'i>,
+  location: SourceLocation,
+  options: &ParserOptions,
+) -> Result<(), ParseError<'i, ParserError<'i>>> {
+  let ident = match value {
+    MediaFeatureValue::Ident(ident) if is_range_feature(name) => ident,
+    _ => return Ok(()),
+  };
+
+  if options.strict {
+    return Err(location.new_custom_error(ParserError::InvalidMediaQuery));
+  }
+
+  if let Some(warnings) = &options.warnings {
+    if let Ok(mut warnings) = warnings.write() {
+      warnings.push(ParserWarning {
+        kind: ParserWarningKind::InvalidMediaFeatureValue {
+          feature: name.to_string(),
+          value: ident.to_string(),
+        },
+        loc: Location {
+          source_index: options.source_index,
+          line: location.line,
+          column: location.column,
+          generated: false,
+        },
+      });
+    }
+  }
+
+  Ok(())
+}
+
 /// Consumes an operation or a colon, or returns an error.
 fn consume_operation_or_colon<'i, 't>(
   input: &mut Parser<'i, 't>,
@@ -952,4 +1840,92 @@ mod tests {
     assert_eq!(and("only screen", "all"), "only screen");
     assert_eq!(and("print", "print"), "print");
   }
+
+  fn parse_list(s: &str, strict: bool) -> Result<MediaList, ()> {
+    let mut input = ParserInput::new(&s);
+    let mut parser = Parser::new(&mut input);
+    let options = ParserOptions {
+      strict,
+      ..ParserOptions::default()
+    };
+    MediaList::parse_with_options(&mut parser, &options).map_err(|_| ())
+  }
+
+  #[test]
+  fn test_discrete() {
+    for (s, value) in [
+      ("(pointer: none)", DiscreteMediaFeatureValue::None),
+      ("(pointer: coarse)", DiscreteMediaFeatureValue::Coarse),
+      ("(pointer: FINE)", DiscreteMediaFeatureValue::Fine),
+    ] {
+      assert_eq!(
+        parse(s).condition,
+        Some(MediaCondition::Feature(MediaFeature::Discrete {
+          feature: DiscreteMediaFeature::Pointer,
+          value: Some(value)
+        }))
+      );
+      assert!(parse_list(s, true).is_ok());
+    }
+
+    assert_eq!(
+      parse("(pointer)").condition,
+      Some(MediaCondition::Feature(MediaFeature::Discrete {
+        feature: DiscreteMediaFeature::Pointer,
+        value: None
+      }))
+    );
+
+    for s in ["(pointer: hover)", "(pointer: 10px)", "(pointer > fine)"] {
+      assert!(matches!(
+        parse(s).condition,
+        Some(MediaCondition::Feature(
+          MediaFeature::Plain { .. } | MediaFeature::Range { .. }
+        ))
+      ));
+      assert!(parse_list(s, false).is_ok());
+      assert!(parse_list(s, true).is_err());
+    }
+  }
+
+  #[test]
+  fn test_scripting() {
+    for (s, value) in [
+      ("(scripting: none)", DiscreteMediaFeatureValue::None),
+      ("(scripting: initial-only)", DiscreteMediaFeatureValue::InitialOnly),
+      ("(scripting: ENABLED)", DiscreteMediaFeatureValue::Enabled),
+    ] {
+      assert_eq!(
+        parse(s).condition,
+        Some(MediaCondition::Feature(MediaFeature::Discrete {
+          feature: DiscreteMediaFeature::Scripting,
+          value: Some(value)
+        }))
+      );
+      assert!(parse_list(s, true).is_ok());
+    }
+
+    for (s, value) in [
+      ("(device-posture: continuous)", DiscreteMediaFeatureValue::Continuous),
+      ("(device-posture: folded)", DiscreteMediaFeatureValue::Folded),
+    ] {
+      assert_eq!(
+        parse(s).condition,
+        Some(MediaCondition::Feature(MediaFeature::Discrete {
+          feature: DiscreteMediaFeature::DevicePosture,
+          value: Some(value)
+        }))
+      );
+    }
+
+    // Values of other features are not accepted.
+    for s in ["(scripting: fine)", "(scripting: 1)", "(device-posture: none)"] {
+      assert!(matches!(
+        parse(s).condition,
+        Some(MediaCondition::Feature(MediaFeature::Plain { .. }))
+      ));
+      assert!(parse_list(s, false).is_ok());
+      assert!(parse_list(s, true).is_err());
+    }
+  }
 }
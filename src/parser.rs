@@ -1,14 +1,17 @@
-use crate::declaration::{parse_declaration, DeclarationBlock, DeclarationList};
-use crate::error::ParserError;
+use crate::declaration::{parse_apply, parse_declaration, DeclarationBlock, DeclarationList};
+use crate::error::{ParserError, ParserWarning};
 use crate::media_query::*;
+use crate::properties::apply::{Apply, ApplyRule};
+use crate::properties::custom::TokenList;
 use crate::rules::font_palette_values::FontPaletteValuesRule;
 use crate::rules::layer::{LayerBlockRule, LayerStatementRule};
 use crate::rules::property::PropertyRule;
 use crate::rules::viewport::ViewportRule;
 use crate::rules::{
   counter_style::CounterStyleRule,
+  custom_at_rule::{AtRuleHandler, CustomAtRule, UnknownAtRuleHandler},
   custom_media::CustomMediaRule,
-  document::MozDocumentRule,
+  document::{DocumentCondition, MozDocumentRule},
   font_face::{FontFaceDeclarationParser, FontFaceRule},
   import::ImportRule,
   keyframes::{KeyframeListParser, KeyframesRule},
@@ -28,14 +31,168 @@ use crate::values::string::CowArcStr;
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
 use parcel_selectors::{parser::NestingRequirement, SelectorList};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
-#[derive(Default, Clone, Debug)]
+/// The default value for [ParserOptions::max_nesting_depth].
+const DEFAULT_MAX_NESTING_DEPTH: usize = 128;
+
+#[derive(Clone, Debug)]
 pub struct ParserOptions {
   pub nesting: bool,
   pub custom_media: bool,
   pub css_modules: bool,
   pub source_index: u32,
+  /// Handlers for custom at-rules, keyed by lowercase name without the leading `@`.
+  pub at_rule_handlers: HashMap<String, Arc<dyn AtRuleHandler>>,
+  /// Whether to error on values that are syntactically valid but not allowed, e.g. unknown keywords
+  /// for media features, or unknown unprefixed property names in transitions. Otherwise, such values
+  /// are preserved as is.
+  pub strict: bool,
+  /// The maximum depth of nested blocks, including rules, selectors such as `:is()`, and functions
+  /// such as `calc()`. Deeper input results in a [MaximumNestingDepthExceeded](ParserError::MaximumNestingDepthExceeded)
+  /// error rather than overflowing the stack.
+  pub max_nesting_depth: usize,
+  /// Whether to clamp values of properties with a restricted range, such as `flex-grow`, into that range.
+  /// In [strict](ParserOptions::strict) mode, out of range values are an error instead, except for alpha
+  /// values such as `opacity`, which are always clamped.
+  pub clamp_values: bool,
+  /// If provided, declarations with an unknown property name or a value that could not be parsed
+  /// are reported here. Values containing `var()` or `env()` are not reported.
+  pub warnings: Option<Arc<RwLock<Vec<ParserWarning>>>>,
+  /// Whether to preserve at-rules with no registered [handler](ParserOptions::at_rule_handlers) as is,
+  /// including their prelude and block, rather than dropping them. They are parsed as a
+  /// [CustomAtRule](crate::rules::custom_at_rule::CustomAtRule) with a default handler.
+  pub preserve_unknown_at_rules: bool,
+  /// Whether to retain the source text of each declaration's name and value. Declarations are parsed
+  /// as usual, and wrapped in a [VerbatimProperty](crate::properties::custom::VerbatimProperty), which
+  /// reproduces the source text when [preserve_raw_values](crate::stylesheet::PrinterOptions::preserve_raw_values)
+  /// is enabled in the printer. Minifying a style sheet discards the source text.
+  pub preserve_raw_values: bool,
+  /// Whether to parse the non-standard [@document](crate::rules::document::MozDocumentRule) rule,
+  /// with or without the `-moz-` prefix, and all of its matching functions. Otherwise, only the
+  /// `@-moz-document url-prefix()` hack is supported.
+  pub document_rules: bool,
+}
+
+impl Default for ParserOptions {
+  fn default() -> ParserOptions {
+    ParserOptions {
+      nesting: false,
+      custom_media: false,
+      css_modules: false,
+      source_index: 0,
+      at_rule_handlers: HashMap::new(),
+      strict: false,
+      max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+      clamp_values: false,
+      warnings: None,
+      preserve_unknown_at_rules: false,
+      preserve_raw_values: false,
+      document_rules: false,
+    }
+  }
+}
+
+impl ParserOptions {
+  /// Returns a builder for [ParserOptions], starting from the defaults.
+  pub fn builder() -> ParserOptionsBuilder {
+    ParserOptionsBuilder::default()
+  }
+
+  fn at_rule_handler(&self, name: &str) -> Option<&Arc<dyn AtRuleHandler>> {
+    if self.at_rule_handlers.is_empty() {
+      return None;
+    }
+
+    self.at_rule_handlers.get(&name.to_ascii_lowercase())
+  }
+}
+
+/// A builder for [ParserOptions], created via [ParserOptions::builder].
+#[derive(Clone, Debug, Default)]
+pub struct ParserOptionsBuilder {
+  options: ParserOptions,
+}
+
+impl ParserOptionsBuilder {
+  /// Sets [ParserOptions::nesting].
+  pub fn nesting(mut self, nesting: bool) -> Self {
+    self.options.nesting = nesting;
+    self
+  }
+
+  /// Sets [ParserOptions::custom_media].
+  pub fn custom_media(mut self, custom_media: bool) -> Self {
+    self.options.custom_media = custom_media;
+    self
+  }
+
+  /// Sets [ParserOptions::css_modules].
+  pub fn css_modules(mut self, css_modules: bool) -> Self {
+    self.options.css_modules = css_modules;
+    self
+  }
+
+  /// Sets [ParserOptions::source_index].
+  pub fn source_index(mut self, source_index: u32) -> Self {
+    self.options.source_index = source_index;
+    self
+  }
+
+  /// Registers a handler for the custom at-rule with the given name, without the leading `@`.
+  pub fn at_rule_handler(mut self, name: &str, handler: Arc<dyn AtRuleHandler>) -> Self {
+    self.options.at_rule_handlers.insert(name.to_ascii_lowercase(), handler);
+    self
+  }
+
+  /// Sets [ParserOptions::strict].
+  pub fn strict(mut self, strict: bool) -> Self {
+    self.options.strict = strict;
+    self
+  }
+
+  /// Sets [ParserOptions::max_nesting_depth].
+  pub fn max_nesting_depth(mut self, max_nesting_depth: usize) -> Self {
+    self.options.max_nesting_depth = max_nesting_depth;
+    self
+  }
+
+  /// Sets [ParserOptions::clamp_values].
+  pub fn clamp_values(mut self, clamp_values: bool) -> Self {
+    self.options.clamp_values = clamp_values;
+    self
+  }
+
+  /// Sets [ParserOptions::warnings].
+  pub fn warnings(mut self, warnings: Arc<RwLock<Vec<ParserWarning>>>) -> Self {
+    self.options.warnings = Some(warnings);
+    self
+  }
+
+  /// Sets [ParserOptions::preserve_unknown_at_rules].
+  pub fn preserve_unknown_at_rules(mut self, preserve_unknown_at_rules: bool) -> Self {
+    self.options.preserve_unknown_at_rules = preserve_unknown_at_rules;
+    self
+  }
+
+  /// Sets [ParserOptions::preserve_raw_values].
+  pub fn preserve_raw_values(mut self, preserve_raw_values: bool) -> Self {
+    self.options.preserve_raw_values = preserve_raw_values;
+    self
+  }
+
+  /// Sets [ParserOptions::document_rules].
+  pub fn document_rules(mut self, document_rules: bool) -> Self {
+    self.options.document_rules = document_rules;
+    self
+  }
+
+  /// Returns the configured [ParserOptions].
+  pub fn build(self) -> ParserOptions {
+    self.options
+  }
 }
 
 #[derive(PartialEq, PartialOrd)]
@@ -98,8 +255,8 @@ pub enum AtRulePrelude<'i> {
   Keyframes(CustomIdent<'i>, VendorPrefix),
   /// A @page rule prelude.
   Page(Vec<PageSelector<'i>>),
-  /// A @-moz-document rule.
-  MozDocument,
+  /// A @document rule, with its vendor prefix and matching functions.
+  MozDocument(VendorPrefix, Vec<DocumentCondition<'i>>),
   /// A @import rule prelude.
   Import(
     CowRcStr<'i>,
@@ -117,6 +274,10 @@ pub enum AtRulePrelude<'i> {
   Layer(Vec<LayerName<'i>>),
   /// An @property prelude.
   Property(DashedIdent<'i>),
+  /// An @apply prelude, and whether it is important.
+  Apply(Apply<'i>, bool),
+  /// A custom at-rule prelude, with the handler that parsed it.
+  Custom(CowRcStr<'i>, TokenList<'i>, Arc<dyn AtRuleHandler>),
 }
 
 impl<'a, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'i> {
@@ -153,7 +314,7 @@ impl<'a, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'i> {
         } else {
           None
         };
-        let media = MediaList::parse(input)?;
+        let media = MediaList::parse_with_options(input, self.options)?;
         return Ok(AtRulePrelude::Import(url_string, media, supports, layer));
       },
       "namespace" => {
@@ -175,7 +336,7 @@ impl<'a, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'i> {
       },
       "custom-media" if self.options.custom_media => {
         let name = DashedIdent::parse(input)?;
-        let media = MediaList::parse(input)?;
+        let media = MediaList::parse_with_options(input, self.options)?;
         return Ok(AtRulePrelude::CustomMedia(name, media))
       },
       "property" => {
@@ -207,6 +368,7 @@ impl<'a, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'i> {
       source_index: self.options.source_index,
       line: loc.line,
       column: loc.column,
+      generated: false,
     };
 
     let rule = match prelude {
@@ -249,6 +411,10 @@ impl<'a, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'i> {
         AtRuleParser::rule_without_block(&mut self.nested(), prelude, start)?
       }
       AtRulePrelude::Charset => CssRule::Ignored,
+      AtRulePrelude::Custom(..) => {
+        self.state = State::Body;
+        AtRuleParser::rule_without_block(&mut self.nested(), prelude, start)?
+      }
       _ => return Err(()),
     };
 
@@ -290,7 +456,10 @@ struct NestedRuleParser<'a, 'i> {
 }
 
 impl<'a, 'b, 'i> NestedRuleParser<'a, 'i> {
-  fn parse_nested_rules<'t>(&mut self, input: &mut Parser<'i, 't>) -> CssRuleList<'i> {
+  fn parse_nested_rules<'t>(
+    &mut self,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<CssRuleList<'i>, ParseError<'i, ParserError<'i>>> {
     let nested_parser = NestedRuleParser {
       default_namespace: self.default_namespace,
       namespace_prefixes: self.namespace_prefixes,
@@ -303,13 +472,14 @@ impl<'a, 'b, 'i> NestedRuleParser<'a, 'i> {
       match result {
         Ok(CssRule::Ignored) => {}
         Ok(rule) => rules.push(rule),
+        Err((err, _)) if is_nesting_depth_error(&err) => return Err(err),
         Err(_) => {
           // TODO
         }
       }
     }
 
-    CssRuleList(rules)
+    Ok(CssRuleList(rules))
   }
 
   fn loc(&self, start: &ParserState) -> Location {
@@ -318,6 +488,7 @@ impl<'a, 'b, 'i> NestedRuleParser<'a, 'i> {
       source_index: self.options.source_index,
       line: loc.line,
       column: loc.column,
+      generated: false,
     }
   }
 }
@@ -334,7 +505,7 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
   ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
     match_ignore_ascii_case! { &*name,
       "media" => {
-        let media = MediaList::parse(input)?;
+        let media = MediaList::parse_with_options(input, self.options)?;
         Ok(AtRulePrelude::Media(media))
       },
       "supports" => {
@@ -360,9 +531,11 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
         let name = CustomIdent::parse(input)?;
         Ok(AtRulePrelude::CounterStyle(name))
       },
-      "viewport" | "-ms-viewport" => {
+      "viewport" | "-ms-viewport" | "-o-viewport" => {
         let prefix = if starts_with_ignore_ascii_case(&*name, "-ms") {
           VendorPrefix::Ms
+        } else if starts_with_ignore_ascii_case(&*name, "-o") {
+          VendorPrefix::O
         } else {
           VendorPrefix::None
         };
@@ -394,6 +567,15 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
         let selectors = input.try_parse(|input| input.parse_comma_separated(PageSelector::parse)).unwrap_or_default();
         Ok(AtRulePrelude::Page(selectors))
       },
+      "document" | "-moz-document" if self.options.document_rules => {
+        let prefix = if starts_with_ignore_ascii_case(&*name, "-moz-") {
+          VendorPrefix::Moz
+        } else {
+          VendorPrefix::None
+        };
+        let conditions = input.parse_comma_separated(DocumentCondition::parse)?;
+        Ok(AtRulePrelude::MozDocument(prefix, conditions))
+      },
       "-moz-document" => {
         // Firefox only supports the url-prefix() function with no arguments as a legacy CSS hack.
         // See https://css-tricks.com/snippets/css/css-hacks-targeting-firefox/
@@ -412,7 +594,7 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
           Ok(())
         })?;
 
-        Ok(AtRulePrelude::MozDocument)
+        Ok(AtRulePrelude::MozDocument(VendorPrefix::Moz, vec![DocumentCondition::UrlPrefix("".into())]))
       },
       "layer" => {
         let names = match Vec::<LayerName>::parse(input) {
@@ -422,7 +604,7 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
         };
         Ok(AtRulePrelude::Layer(names))
       },
-      _ => Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
+      _ => parse_custom_at_rule_prelude(name, input, self.options)
     }
   }
 
@@ -432,6 +614,7 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
     start: &ParserState,
     input: &mut Parser<'i, 't>,
   ) -> Result<CssRule<'i>, ParseError<'i, Self::Error>> {
+    let _level = NestingLevel::enter(input)?;
     let loc = self.loc(start);
     match prelude {
       AtRulePrelude::FontFace => {
@@ -471,12 +654,12 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
       })),
       AtRulePrelude::Media(query) => Ok(CssRule::Media(MediaRule {
         query,
-        rules: self.parse_nested_rules(input),
+        rules: self.parse_nested_rules(input)?,
         loc,
       })),
       AtRulePrelude::Supports(condition) => Ok(CssRule::Supports(SupportsRule {
         condition,
-        rules: self.parse_nested_rules(input),
+        rules: self.parse_nested_rules(input)?,
         loc,
       })),
       AtRulePrelude::Viewport(vendor_prefix) => {
@@ -502,8 +685,10 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
         declarations: DeclarationBlock::parse(input, self.options)?,
         loc,
       })),
-      AtRulePrelude::MozDocument => Ok(CssRule::MozDocument(MozDocumentRule {
-        rules: self.parse_nested_rules(input),
+      AtRulePrelude::MozDocument(vendor_prefix, conditions) => Ok(CssRule::MozDocument(MozDocumentRule {
+        conditions,
+        rules: self.parse_nested_rules(input)?,
+        vendor_prefix,
         loc,
       })),
       AtRulePrelude::Layer(names) => {
@@ -517,11 +702,18 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
 
         Ok(CssRule::LayerBlock(LayerBlockRule {
           name,
-          rules: self.parse_nested_rules(input),
+          rules: self.parse_nested_rules(input)?,
           loc,
         }))
       }
       AtRulePrelude::Property(name) => Ok(CssRule::Property(PropertyRule::parse(name, input, loc)?)),
+      AtRulePrelude::Custom(name, prelude, handler) => Ok(CssRule::Custom(CustomAtRule {
+        name: name.into(),
+        prelude,
+        block: Some(handler.parse_block(input)?),
+        loc,
+        handler,
+      })),
       AtRulePrelude::Import(..)
       | AtRulePrelude::Namespace(..)
       | AtRulePrelude::CustomMedia(..)
@@ -529,7 +721,7 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
         // These rules don't have blocks.
         Err(input.new_unexpected_token_error(Token::CurlyBracketBlock))
       }
-      AtRulePrelude::FontFeatureValues | AtRulePrelude::Nest(..) => unreachable!(),
+      AtRulePrelude::FontFeatureValues | AtRulePrelude::Nest(..) | AtRulePrelude::Apply(..) => unreachable!(),
     }
   }
 
@@ -544,6 +736,13 @@ impl<'a, 'b, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
 
         Ok(CssRule::LayerStatement(LayerStatementRule { names, loc }))
       }
+      AtRulePrelude::Custom(name, prelude, handler) => Ok(CssRule::Custom(CustomAtRule {
+        name: name.into(),
+        prelude,
+        block: None,
+        loc,
+        handler,
+      })),
       _ => Err(()),
     }
   }
@@ -573,6 +772,7 @@ impl<'a, 'b, 'i> QualifiedRuleParser<'i> for NestedRuleParser<'a, 'i> {
     start: &ParserState,
     input: &mut Parser<'i, 't>,
   ) -> Result<CssRule<'i>, ParseError<'i, Self::Error>> {
+    let _level = NestingLevel::enter(input)?;
     let loc = self.loc(start);
     let (declarations, rules) = if self.options.nesting {
       parse_declarations_and_nested_rules(input, self.default_namespace, self.namespace_prefixes, self.options)?
@@ -597,6 +797,7 @@ fn parse_declarations_and_nested_rules<'a, 'i, 't>(
 ) -> Result<(DeclarationBlock<'i>, CssRuleList<'i>), ParseError<'i, ParserError<'i>>> {
   let mut important_declarations = DeclarationList::new();
   let mut declarations = DeclarationList::new();
+  let mut apply = Vec::new();
   let mut rules = CssRuleList(vec![]);
   let parser = StyleRuleParser {
     default_namespace,
@@ -604,6 +805,7 @@ fn parse_declarations_and_nested_rules<'a, 'i, 't>(
     options,
     declarations: &mut declarations,
     important_declarations: &mut important_declarations,
+    apply: &mut apply,
     rules: &mut rules,
   };
 
@@ -612,6 +814,7 @@ fn parse_declarations_and_nested_rules<'a, 'i, 't>(
   while let Some(decl) = declaration_parser.next() {
     match decl {
       Ok(_) => {}
+      Err((err, _)) if is_nesting_depth_error(&err) => return Err(err),
       _ => {
         declaration_parser.input.reset(&last);
         break;
@@ -632,6 +835,7 @@ fn parse_declarations_and_nested_rules<'a, 'i, 't>(
     DeclarationBlock {
       declarations,
       important_declarations,
+      apply,
     },
     rules,
   ))
@@ -643,6 +847,7 @@ pub struct StyleRuleParser<'a, 'i> {
   options: &'a ParserOptions,
   declarations: &'a mut DeclarationList<'i>,
   important_declarations: &'a mut DeclarationList<'i>,
+  apply: &'a mut Vec<ApplyRule<'i>>,
   rules: &'a mut CssRuleList<'i>,
 }
 
@@ -682,7 +887,7 @@ impl<'a, 'i> AtRuleParser<'i> for StyleRuleParser<'a, 'i> {
   ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
     match_ignore_ascii_case! { &*name,
       "media" => {
-        let media = MediaList::parse(input)?;
+        let media = MediaList::parse_with_options(input, self.options)?;
         Ok(AtRulePrelude::Media(media))
       },
       "supports" => {
@@ -699,6 +904,10 @@ impl<'a, 'i> AtRuleParser<'i> for StyleRuleParser<'a, 'i> {
         let selectors = SelectorList::parse(&selector_parser, input, NestingRequirement::Contained)?;
         Ok(AtRulePrelude::Nest(selectors))
       },
+      "apply" => {
+        let (apply, important) = parse_apply(name, input)?;
+        Ok(AtRulePrelude::Apply(apply, important))
+      },
       _ => Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
     }
   }
@@ -709,11 +918,13 @@ impl<'a, 'i> AtRuleParser<'i> for StyleRuleParser<'a, 'i> {
     start: &ParserState,
     input: &mut Parser<'i, 't>,
   ) -> Result<(), ParseError<'i, Self::Error>> {
+    let _level = NestingLevel::enter(input)?;
     let loc = start.source_location();
     let loc = Location {
       source_index: self.options.source_index,
       line: loc.line,
       column: loc.column,
+      generated: false,
     };
     match prelude {
       AtRulePrelude::Media(query) => {
@@ -768,6 +979,49 @@ impl<'a, 'i> AtRuleParser<'i> for StyleRuleParser<'a, 'i> {
       }
     }
   }
+
+  fn rule_without_block(&mut self, prelude: AtRulePrelude<'i>, _start: &ParserState) -> Result<(), ()> {
+    match prelude {
+      AtRulePrelude::Apply(apply, important) => {
+        if !self.rules.0.is_empty() {
+          // Declarations cannot come after nested rules.
+          return Err(());
+        }
+
+        let index = if important {
+          self.important_declarations.len()
+        } else {
+          self.declarations.len()
+        };
+        self.apply.push(ApplyRule {
+          apply,
+          important,
+          index,
+        });
+        Ok(())
+      }
+      _ => Err(()),
+    }
+  }
+}
+
+fn parse_custom_at_rule_prelude<'i, 't>(
+  name: CowRcStr<'i>,
+  input: &mut Parser<'i, 't>,
+  options: &ParserOptions,
+) -> Result<AtRulePrelude<'i>, ParseError<'i, ParserError<'i>>> {
+  if let Some(handler) = options.at_rule_handler(&name) {
+    let prelude = handler.parse_prelude(input)?;
+    return Ok(AtRulePrelude::Custom(name, prelude, handler.clone()));
+  }
+
+  if options.preserve_unknown_at_rules {
+    let handler: Arc<dyn AtRuleHandler> = Arc::new(UnknownAtRuleHandler);
+    let prelude = handler.parse_prelude(input)?;
+    return Ok(AtRulePrelude::Custom(name, prelude, handler));
+  }
+
+  Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
 }
 
 #[inline]
@@ -783,6 +1037,7 @@ fn parse_nested_at_rule<'a, 'i, 't>(
     source_index,
     line: loc.line,
     column: loc.column,
+    generated: false,
   };
 
   // Declarations can be immediately within @media and @supports blocks that are nested within a parent style rule.
@@ -790,7 +1045,7 @@ fn parse_nested_at_rule<'a, 'i, 't>(
   let (declarations, mut rules) =
     parse_declarations_and_nested_rules(input, default_namespace, namespace_prefixes, options)?;
 
-  if declarations.declarations.len() > 0 {
+  if !declarations.is_empty() {
     rules.0.insert(
       0,
       CssRule::Style(StyleRule {
@@ -832,6 +1087,7 @@ impl<'a, 'b, 'i> QualifiedRuleParser<'i> for StyleRuleParser<'a, 'i> {
     start: &ParserState,
     input: &mut Parser<'i, 't>,
   ) -> Result<(), ParseError<'i, Self::Error>> {
+    let _level = NestingLevel::enter(input)?;
     let loc = start.source_location();
     let (declarations, rules) =
       parse_declarations_and_nested_rules(input, self.default_namespace, self.namespace_prefixes, self.options)?;
@@ -844,6 +1100,7 @@ impl<'a, 'b, 'i> QualifiedRuleParser<'i> for StyleRuleParser<'a, 'i> {
         source_index: self.options.source_index,
         line: loc.line,
         column: loc.column,
+        generated: false,
       },
     }));
     Ok(())
@@ -854,3 +1111,95 @@ impl<'a, 'b, 'i> QualifiedRuleParser<'i> for StyleRuleParser<'a, 'i> {
 pub fn starts_with_ignore_ascii_case(string: &str, prefix: &str) -> bool {
   string.len() >= prefix.len() && string.as_bytes()[0..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
 }
+
+thread_local! {
+  /// The depth of nested blocks currently being parsed on this thread, and the maximum allowed depth.
+  /// Value parsers only receive the cssparser input, so this is where the parser state is kept.
+  static NESTING_DEPTH: Cell<(usize, usize)> = Cell::new((0, DEFAULT_MAX_NESTING_DEPTH));
+}
+
+/// Runs `parse` with the given maximum nesting depth, restoring the previous maximum afterward.
+/// Entry points that receive [ParserOptions] call this, so that their limit applies to all nested parsers.
+pub(crate) fn with_max_nesting_depth<T>(max_depth: usize, parse: impl FnOnce() -> T) -> T {
+  let prev = NESTING_DEPTH.with(|cell| {
+    let (depth, prev) = cell.get();
+    cell.set((depth, max_depth));
+    prev
+  });
+  let res = parse();
+  NESTING_DEPTH.with(|cell| cell.set((cell.get().0, prev)));
+  res
+}
+
+/// One level of block nesting, which is left when dropped.
+///
+/// All recursion in the parser, e.g. for nested rules, selectors such as `:is()`, media conditions,
+/// and functions such as `calc()`, passes through a nested block that enters a level first. This means
+/// the recursive parse functions never go deeper than [ParserOptions::max_nesting_depth].
+pub(crate) struct NestingLevel(());
+
+impl NestingLevel {
+  /// Enters a nested block, or returns a [MaximumNestingDepthExceeded](ParserError::MaximumNestingDepthExceeded)
+  /// error at the current location if the maximum depth has been reached.
+  pub(crate) fn enter<'i, 't>(input: &Parser<'i, 't>) -> Result<NestingLevel, ParseError<'i, ParserError<'i>>> {
+    NESTING_DEPTH.with(|cell| {
+      let (depth, max_depth) = cell.get();
+      if depth >= max_depth {
+        return Err(input.new_custom_error(ParserError::MaximumNestingDepthExceeded));
+      }
+
+      cell.set((depth + 1, max_depth));
+      Ok(NestingLevel(()))
+    })
+  }
+}
+
+impl Drop for NestingLevel {
+  fn drop(&mut self) {
+    NESTING_DEPTH.with(|cell| {
+      let (depth, max_depth) = cell.get();
+      cell.set((depth - 1, max_depth));
+    })
+  }
+}
+
+/// Parses the contents of a nested block like [Parser::parse_nested_block], within a new nesting level.
+pub(crate) fn parse_nested_block<'i, 't, T, F>(
+  input: &mut Parser<'i, 't>,
+  parse: F,
+) -> Result<T, ParseError<'i, ParserError<'i>>>
+where
+  F: for<'tt> FnOnce(&mut Parser<'i, 'tt>) -> Result<T, ParseError<'i, ParserError<'i>>>,
+{
+  let _level = NestingLevel::enter(input)?;
+  input.parse_nested_block(parse)
+}
+
+/// Checks that the rest of the input contains no error tokens like [Parser::expect_no_error_token],
+/// entering a nesting level for each nested block.
+pub(crate) fn expect_no_error_token<'i, 't>(
+  input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, ParserError<'i>>> {
+  loop {
+    match input.next_including_whitespace_and_comments() {
+      Ok(Token::Function(_) | Token::ParenthesisBlock | Token::SquareBracketBlock | Token::CurlyBracketBlock) => {
+        parse_nested_block(input, expect_no_error_token)?
+      }
+      Ok(token) if token.is_parse_error() => {
+        let token = token.clone();
+        return Err(input.new_unexpected_token_error(token));
+      }
+      Ok(_) => {}
+      Err(_) => return Ok(()),
+    }
+  }
+}
+
+/// Returns whether the error is due to exceeding the maximum nesting depth. Such errors are not recovered
+/// from, since parsing the same input in another way would exceed the limit as well.
+pub(crate) fn is_nesting_depth_error(err: &ParseError<'_, ParserError<'_>>) -> bool {
+  matches!(
+    err.kind,
+    ParseErrorKind::Custom(ParserError::MaximumNestingDepthExceeded)
+  )
+}
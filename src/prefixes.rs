@@ -2200,6 +2200,182 @@ impl Feature {
     }
     prefixes
   }
+
+  pub fn from_name(name: &str) -> Option<Feature> {
+    Some(match name {
+      "align-content" => Feature::AlignContent,
+      "align-items" => Feature::AlignItems,
+      "align-self" => Feature::AlignSelf,
+      "animation" => Feature::Animation,
+      "animation-delay" => Feature::AnimationDelay,
+      "animation-direction" => Feature::AnimationDirection,
+      "animation-duration" => Feature::AnimationDuration,
+      "animation-fill-mode" => Feature::AnimationFillMode,
+      "animation-iteration-count" => Feature::AnimationIterationCount,
+      "animation-name" => Feature::AnimationName,
+      "animation-play-state" => Feature::AnimationPlayState,
+      "animation-timing-function" => Feature::AnimationTimingFunction,
+      "any-pseudo" => Feature::AnyPseudo,
+      "appearance" => Feature::Appearance,
+      "backdrop-filter" => Feature::BackdropFilter,
+      "backface-visibility" => Feature::BackfaceVisibility,
+      "background-clip" => Feature::BackgroundClip,
+      "background-origin" => Feature::BackgroundOrigin,
+      "background-size" => Feature::BackgroundSize,
+      "border-block-end" => Feature::BorderBlockEnd,
+      "border-block-start" => Feature::BorderBlockStart,
+      "border-bottom-left-radius" => Feature::BorderBottomLeftRadius,
+      "border-bottom-right-radius" => Feature::BorderBottomRightRadius,
+      "border-image" => Feature::BorderImage,
+      "border-inline-end" => Feature::BorderInlineEnd,
+      "border-inline-start" => Feature::BorderInlineStart,
+      "border-radius" => Feature::BorderRadius,
+      "border-top-left-radius" => Feature::BorderTopLeftRadius,
+      "border-top-right-radius" => Feature::BorderTopRightRadius,
+      "box-decoration-break" => Feature::BoxDecorationBreak,
+      "box-shadow" => Feature::BoxShadow,
+      "box-sizing" => Feature::BoxSizing,
+      "break-after" => Feature::BreakAfter,
+      "break-before" => Feature::BreakBefore,
+      "break-inside" => Feature::BreakInside,
+      "calc" => Feature::Calc,
+      "clip-path" => Feature::ClipPath,
+      "color-adjust" => Feature::ColorAdjust,
+      "column-count" => Feature::ColumnCount,
+      "column-fill" => Feature::ColumnFill,
+      "column-gap" => Feature::ColumnGap,
+      "column-rule" => Feature::ColumnRule,
+      "column-rule-color" => Feature::ColumnRuleColor,
+      "column-rule-style" => Feature::ColumnRuleStyle,
+      "column-rule-width" => Feature::ColumnRuleWidth,
+      "column-span" => Feature::ColumnSpan,
+      "column-width" => Feature::ColumnWidth,
+      "columns" => Feature::Columns,
+      "cross-fade" => Feature::CrossFade,
+      "display-flex" => Feature::DisplayFlex,
+      "display-grid" => Feature::DisplayGrid,
+      "element" => Feature::Element,
+      "fill" => Feature::Fill,
+      "fill-available" => Feature::FillAvailable,
+      "filter" => Feature::Filter,
+      "filter-function" => Feature::FilterFunction,
+      "fit-content" => Feature::FitContent,
+      "flex" => Feature::Flex,
+      "flex-basis" => Feature::FlexBasis,
+      "flex-direction" => Feature::FlexDirection,
+      "flex-flow" => Feature::FlexFlow,
+      "flex-grow" => Feature::FlexGrow,
+      "flex-shrink" => Feature::FlexShrink,
+      "flex-wrap" => Feature::FlexWrap,
+      "flow-from" => Feature::FlowFrom,
+      "flow-into" => Feature::FlowInto,
+      "font-feature-settings" => Feature::FontFeatureSettings,
+      "font-kerning" => Feature::FontKerning,
+      "font-language-override" => Feature::FontLanguageOverride,
+      "font-variant-ligatures" => Feature::FontVariantLigatures,
+      "grab" => Feature::Grab,
+      "grabbing" => Feature::Grabbing,
+      "grid-area" => Feature::GridArea,
+      "grid-column" => Feature::GridColumn,
+      "grid-column-align" => Feature::GridColumnAlign,
+      "grid-column-end" => Feature::GridColumnEnd,
+      "grid-column-start" => Feature::GridColumnStart,
+      "grid-row" => Feature::GridRow,
+      "grid-row-align" => Feature::GridRowAlign,
+      "grid-row-end" => Feature::GridRowEnd,
+      "grid-row-start" => Feature::GridRowStart,
+      "grid-template" => Feature::GridTemplate,
+      "grid-template-areas" => Feature::GridTemplateAreas,
+      "grid-template-columns" => Feature::GridTemplateColumns,
+      "grid-template-rows" => Feature::GridTemplateRows,
+      "hyphens" => Feature::Hyphens,
+      "image-rendering" => Feature::ImageRendering,
+      "image-set" => Feature::ImageSet,
+      "inline-flex" => Feature::InlineFlex,
+      "inline-grid" => Feature::InlineGrid,
+      "isolate" => Feature::Isolate,
+      "isolate-override" => Feature::IsolateOverride,
+      "justify-content" => Feature::JustifyContent,
+      "linear-gradient" => Feature::LinearGradient,
+      "margin-block-end" => Feature::MarginBlockEnd,
+      "margin-block-start" => Feature::MarginBlockStart,
+      "margin-inline-end" => Feature::MarginInlineEnd,
+      "margin-inline-start" => Feature::MarginInlineStart,
+      "mask" => Feature::Mask,
+      "mask-border" => Feature::MaskBorder,
+      "mask-border-outset" => Feature::MaskBorderOutset,
+      "mask-border-repeat" => Feature::MaskBorderRepeat,
+      "mask-border-slice" => Feature::MaskBorderSlice,
+      "mask-border-source" => Feature::MaskBorderSource,
+      "mask-border-width" => Feature::MaskBorderWidth,
+      "mask-clip" => Feature::MaskClip,
+      "mask-composite" => Feature::MaskComposite,
+      "mask-image" => Feature::MaskImage,
+      "mask-origin" => Feature::MaskOrigin,
+      "mask-position" => Feature::MaskPosition,
+      "mask-repeat" => Feature::MaskRepeat,
+      "mask-size" => Feature::MaskSize,
+      "max-content" => Feature::MaxContent,
+      "min-content" => Feature::MinContent,
+      "object-fit" => Feature::ObjectFit,
+      "object-position" => Feature::ObjectPosition,
+      "order" => Feature::Order,
+      "overscroll-behavior" => Feature::OverscrollBehavior,
+      "padding-block-end" => Feature::PaddingBlockEnd,
+      "padding-block-start" => Feature::PaddingBlockStart,
+      "padding-inline-end" => Feature::PaddingInlineEnd,
+      "padding-inline-start" => Feature::PaddingInlineStart,
+      "perspective" => Feature::Perspective,
+      "perspective-origin" => Feature::PerspectiveOrigin,
+      "pixelated" => Feature::Pixelated,
+      "place-self" => Feature::PlaceSelf,
+      "plaintext" => Feature::Plaintext,
+      "radial-gradient" => Feature::RadialGradient,
+      "region-fragment" => Feature::RegionFragment,
+      "repeating-linear-gradient" => Feature::RepeatingLinearGradient,
+      "repeating-radial-gradient" => Feature::RepeatingRadialGradient,
+      "scroll-snap-coordinate" => Feature::ScrollSnapCoordinate,
+      "scroll-snap-destination" => Feature::ScrollSnapDestination,
+      "scroll-snap-points-x" => Feature::ScrollSnapPointsX,
+      "scroll-snap-points-y" => Feature::ScrollSnapPointsY,
+      "scroll-snap-type" => Feature::ScrollSnapType,
+      "shape-image-threshold" => Feature::ShapeImageThreshold,
+      "shape-margin" => Feature::ShapeMargin,
+      "shape-outside" => Feature::ShapeOutside,
+      "sticky" => Feature::Sticky,
+      "stretch" => Feature::Stretch,
+      "tab-size" => Feature::TabSize,
+      "text-align-last" => Feature::TextAlignLast,
+      "text-decoration" => Feature::TextDecoration,
+      "text-decoration-color" => Feature::TextDecorationColor,
+      "text-decoration-line" => Feature::TextDecorationLine,
+      "text-decoration-skip" => Feature::TextDecorationSkip,
+      "text-decoration-skip-ink" => Feature::TextDecorationSkipInk,
+      "text-decoration-style" => Feature::TextDecorationStyle,
+      "text-emphasis" => Feature::TextEmphasis,
+      "text-emphasis-color" => Feature::TextEmphasisColor,
+      "text-emphasis-position" => Feature::TextEmphasisPosition,
+      "text-emphasis-style" => Feature::TextEmphasisStyle,
+      "text-orientation" => Feature::TextOrientation,
+      "text-overflow" => Feature::TextOverflow,
+      "text-size-adjust" => Feature::TextSizeAdjust,
+      "text-spacing" => Feature::TextSpacing,
+      "touch-action" => Feature::TouchAction,
+      "transform" => Feature::Transform,
+      "transform-origin" => Feature::TransformOrigin,
+      "transform-style" => Feature::TransformStyle,
+      "transition" => Feature::Transition,
+      "transition-delay" => Feature::TransitionDelay,
+      "transition-duration" => Feature::TransitionDuration,
+      "transition-property" => Feature::TransitionProperty,
+      "transition-timing-function" => Feature::TransitionTimingFunction,
+      "user-select" => Feature::UserSelect,
+      "writing-mode" => Feature::WritingMode,
+      "zoom-in" => Feature::ZoomIn,
+      "zoom-out" => Feature::ZoomOut,
+      _ => return None,
+    })
+  }
 }
 
 pub fn is_flex_2009(browsers: Browsers) -> bool {
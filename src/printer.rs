@@ -5,18 +5,230 @@ use crate::rules::Location;
 use crate::targets::Browsers;
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::{serialize_identifier, SourceLocation};
+#[cfg(feature = "std")]
 use parcel_sourcemap::{OriginalLocation, SourceMap};
 
 #[derive(Default)]
 pub struct PrinterOptions<'a> {
   pub minify: bool,
+  /// Requires the `std` feature.
+  #[cfg(feature = "std")]
   pub source_map: Option<&'a mut SourceMap>,
+  /// Browser targets used for serialization decisions, such as whether `#rrggbbaa` colors can be used.
+  /// These apply even when the style sheet was not minified, and take precedence over
+  /// [MinifyOptions::targets](crate::stylesheet::MinifyOptions::targets) for formatting.
   pub targets: Option<Browsers>,
   pub analyze_dependencies: bool,
   pub pseudo_classes: Option<PseudoClasses<'a>>,
+  /// Converts `px` lengths in declaration values to `rem` when set.
+  pub px_to_rem: Option<PxToRemOptions>,
+  /// Controls whether `url()` values are quoted.
+  pub url_quotes: UrlQuotes,
+  /// Whether to encode the [source map](PrinterOptions::source_map) as JSON in
+  /// [ToCssResult::source_map](crate::stylesheet::ToCssResult::source_map) once printing is done.
+  /// Mappings are added to the source map either way.
+  pub encode_source_map: bool,
+  /// Whether to embed the contents of the original sources in the generated
+  /// [source map](crate::stylesheet::ToCssResult::source_map).
+  pub include_sources_content: bool,
+  /// The `sourceRoot` of the generated source map, which source paths are resolved against.
+  pub source_root: Option<String>,
+  /// The maximum number of significant digits (at least 1) of serialized numbers, if any.
+  /// By default, only computed values, e.g. the result of `calc()` or color conversions, are
+  /// rounded. These are detected as values needing more significant digits than an authored
+  /// value can be stored with.
+  pub precision: Option<u8>,
+  /// Whether [precision](PrinterOptions::precision) also applies to authored values.
+  pub round_authored_values: bool,
+  /// Whether to print declarations parsed with [preserve_raw_values](crate::stylesheet::ParserOptions::preserve_raw_values)
+  /// as written in the source, rather than re-serializing their values. Other declarations are unaffected.
+  /// Values are still re-serialized when CSS modules or [analyze_dependencies](PrinterOptions::analyze_dependencies)
+  /// are enabled, since they may contain names that are scoped or urls that are replaced with placeholders.
+  pub preserve_raw_values: bool,
+  /// The maximum size of the output in bytes, if any. Printing stops with a
+  /// [MaximumOutputSizeExceeded](PrinterErrorKind::MaximumOutputSizeExceeded) error
+  /// as soon as the output would exceed this size.
+  pub max_output_size: Option<usize>,
 }
 
-#[derive(Default, Debug)]
+impl<'a> PrinterOptions<'a> {
+  /// Returns a builder for [PrinterOptions], starting from the defaults.
+  pub fn builder() -> PrinterOptionsBuilder<'a> {
+    PrinterOptionsBuilder::default()
+  }
+}
+
+/// A builder for [PrinterOptions], created via [PrinterOptions::builder].
+#[derive(Default)]
+pub struct PrinterOptionsBuilder<'a> {
+  options: PrinterOptions<'a>,
+}
+
+impl<'a> PrinterOptionsBuilder<'a> {
+  /// Sets [PrinterOptions::minify].
+  pub fn minify(mut self, minify: bool) -> Self {
+    self.options.minify = minify;
+    self
+  }
+
+  /// Sets the source map that mappings are added to while printing.
+  #[cfg(feature = "std")]
+  pub fn source_map(mut self, source_map: &'a mut SourceMap) -> Self {
+    self.options.source_map = Some(source_map);
+    self
+  }
+
+  /// Sets [PrinterOptions::targets].
+  pub fn targets(mut self, targets: Browsers) -> Self {
+    self.options.targets = Some(targets);
+    self
+  }
+
+  /// Sets [PrinterOptions::analyze_dependencies].
+  pub fn analyze_dependencies(mut self, analyze_dependencies: bool) -> Self {
+    self.options.analyze_dependencies = analyze_dependencies;
+    self
+  }
+
+  /// Sets [PrinterOptions::pseudo_classes].
+  pub fn pseudo_classes(mut self, pseudo_classes: PseudoClasses<'a>) -> Self {
+    self.options.pseudo_classes = Some(pseudo_classes);
+    self
+  }
+
+  /// Sets [PrinterOptions::px_to_rem].
+  pub fn px_to_rem(mut self, px_to_rem: PxToRemOptions) -> Self {
+    self.options.px_to_rem = Some(px_to_rem);
+    self
+  }
+
+  /// Sets [PrinterOptions::url_quotes].
+  pub fn url_quotes(mut self, url_quotes: UrlQuotes) -> Self {
+    self.options.url_quotes = url_quotes;
+    self
+  }
+
+  /// Sets [PrinterOptions::encode_source_map].
+  pub fn encode_source_map(mut self, encode_source_map: bool) -> Self {
+    self.options.encode_source_map = encode_source_map;
+    self
+  }
+
+  /// Sets [PrinterOptions::include_sources_content].
+  pub fn include_sources_content(mut self, include_sources_content: bool) -> Self {
+    self.options.include_sources_content = include_sources_content;
+    self
+  }
+
+  /// Sets [PrinterOptions::source_root].
+  pub fn source_root(mut self, source_root: &str) -> Self {
+    self.options.source_root = Some(source_root.into());
+    self
+  }
+
+  /// Sets [PrinterOptions::precision].
+  pub fn precision(mut self, precision: u8) -> Self {
+    self.options.precision = Some(precision);
+    self
+  }
+
+  /// Sets [PrinterOptions::round_authored_values].
+  pub fn round_authored_values(mut self, round_authored_values: bool) -> Self {
+    self.options.round_authored_values = round_authored_values;
+    self
+  }
+
+  /// Sets [PrinterOptions::preserve_raw_values].
+  pub fn preserve_raw_values(mut self, preserve_raw_values: bool) -> Self {
+    self.options.preserve_raw_values = preserve_raw_values;
+    self
+  }
+
+  /// Sets [PrinterOptions::max_output_size].
+  pub fn max_output_size(mut self, max_output_size: usize) -> Self {
+    self.options.max_output_size = Some(max_output_size);
+    self
+  }
+
+  /// Returns the configured [PrinterOptions].
+  pub fn build(self) -> PrinterOptions<'a> {
+    self.options
+  }
+}
+
+/// Controls how `url()` values are quoted when serialized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UrlQuotes {
+  /// Urls are unquoted, except when minifying and quoting is shorter.
+  Auto,
+  /// Urls are always quoted, e.g. `url("foo.png")`.
+  Always,
+  /// Urls are never quoted, unless they contain whitespace, parentheses, quotes,
+  /// or other characters that would otherwise need to be escaped.
+  Never,
+}
+
+impl Default for UrlQuotes {
+  fn default() -> UrlQuotes {
+    UrlQuotes::Auto
+  }
+}
+
+/// Options for converting `px` lengths to `rem`.
+#[derive(Debug, Clone)]
+pub struct PxToRemOptions {
+  /// The root font size, in pixels. Defaults to 16.
+  pub root_font_size: f32,
+  /// The number of decimal places converted values are rounded to, up to 10. Defaults to 5.
+  pub precision: u8,
+  /// Whether to keep lengths of 1px or less (e.g. hairline borders) in pixels. Defaults to true.
+  pub keep_hairlines: bool,
+  /// Names of properties whose values should not be converted.
+  pub exclude: Vec<String>,
+}
+
+impl Default for PxToRemOptions {
+  fn default() -> PxToRemOptions {
+    PxToRemOptions {
+      root_font_size: 16.0,
+      precision: 5,
+      keep_hairlines: true,
+      exclude: Vec::new(),
+    }
+  }
+}
+
+impl PxToRemOptions {
+  /// The maximum precision, since 10^10 is the largest power of ten that an `f32` represents exactly.
+  const MAX_PRECISION: u8 = 10;
+
+  /// Returns an error if the root font size is not a positive, finite number, or the precision is too large.
+  pub(crate) fn validate(&self) -> Result<(), PrinterError> {
+    let kind = if !(self.root_font_size > 0.0 && self.root_font_size.is_finite()) {
+      PrinterErrorKind::InvalidPxToRemRootFontSize
+    } else if self.precision > PxToRemOptions::MAX_PRECISION {
+      PrinterErrorKind::InvalidPxToRemPrecision {
+        max: PxToRemOptions::MAX_PRECISION,
+      }
+    } else {
+      return Ok(());
+    };
+
+    Err(PrinterError { kind, loc: None })
+  }
+
+  /// Returns the `rem` value for the given `px` value, or `None` if it should not be converted.
+  pub(crate) fn convert(&self, px: f32) -> Option<f32> {
+    if px == 0.0 || (self.keep_hairlines && px.abs() <= 1.0) {
+      return None;
+    }
+
+    let factor = 10f32.powi(self.precision as i32);
+    Some((px / self.root_font_size * factor).round() / factor)
+  }
+}
+
+#[derive(Default, Debug, Clone)]
 pub struct PseudoClasses<'a> {
   pub hover: Option<&'a str>,
   pub active: Option<&'a str>,
@@ -25,10 +237,11 @@ pub struct PseudoClasses<'a> {
   pub focus_within: Option<&'a str>,
 }
 
-pub struct Printer<'a, W> {
+pub struct Printer<'a, W: ?Sized> {
   pub(crate) sources: Option<&'a Vec<String>>,
   dest: &'a mut W,
-  source_map: Option<&'a mut SourceMap>,
+  #[cfg(feature = "std")]
+  pub(crate) source_map: Option<&'a mut SourceMap>,
   pub(crate) source_index: u32,
   indent: u8,
   line: u32,
@@ -42,6 +255,16 @@ pub struct Printer<'a, W> {
   pub(crate) css_module: Option<CssModule<'a>>,
   pub(crate) dependencies: Option<Vec<Dependency>>,
   pub(crate) pseudo_classes: Option<PseudoClasses<'a>>,
+  pub(crate) px_to_rem: Option<PxToRemOptions>,
+  pub(crate) url_quotes: UrlQuotes,
+  /// Whether `px` lengths should currently be converted to `rem`.
+  /// Only enabled while printing declaration values.
+  pub(crate) convert_px: bool,
+  precision: Option<u8>,
+  round_authored_values: bool,
+  pub(crate) preserve_raw_values: bool,
+  max_output_size: Option<usize>,
+  output_size: usize,
 }
 
 impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
@@ -49,6 +272,7 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
     Printer {
       sources: None,
       dest,
+      #[cfg(feature = "std")]
       source_map: options.source_map,
       source_index: 0,
       indent: 0,
@@ -65,6 +289,69 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
         None
       },
       pseudo_classes: options.pseudo_classes,
+      px_to_rem: options.px_to_rem,
+      url_quotes: options.url_quotes,
+      convert_px: false,
+      precision: options.precision,
+      round_authored_values: options.round_authored_values,
+      preserve_raw_values: options.preserve_raw_values,
+      max_output_size: options.max_output_size,
+      output_size: 0,
+    }
+  }
+
+  /// Calls the given function with a printer that writes to the same destination through a trait object,
+  /// so that it can be passed to object safe traits such as [AtRuleHandler](crate::rules::custom_at_rule::AtRuleHandler).
+  /// All state, such as the indentation, source map, and CSS module, is shared with this printer.
+  pub(crate) fn with_dyn<R>(&mut self, f: impl FnOnce(&mut Printer<'_, dyn std::fmt::Write + '_>) -> R) -> R {
+    let dest: &mut dyn std::fmt::Write = &mut *self.dest;
+    let mut printer = Printer {
+      sources: self.sources,
+      dest,
+      #[cfg(feature = "std")]
+      source_map: self.source_map.as_deref_mut(),
+      source_index: self.source_index,
+      indent: self.indent,
+      line: self.line,
+      col: self.col,
+      minify: self.minify,
+      targets: self.targets,
+      vendor_prefix: self.vendor_prefix,
+      in_calc: self.in_calc,
+      css_module: self.css_module.as_mut().map(|css_module| CssModule {
+        hash: css_module.hash,
+        source_hashes: css_module.source_hashes,
+        exports: &mut *css_module.exports,
+      }),
+      dependencies: self.dependencies.take(),
+      pseudo_classes: self.pseudo_classes.clone(),
+      px_to_rem: self.px_to_rem.take(),
+      url_quotes: self.url_quotes,
+      convert_px: self.convert_px,
+      precision: self.precision,
+      round_authored_values: self.round_authored_values,
+      preserve_raw_values: self.preserve_raw_values,
+      max_output_size: self.max_output_size,
+      output_size: self.output_size,
+    };
+
+    let res = f(&mut printer);
+    self.indent = printer.indent;
+    self.line = printer.line;
+    self.col = printer.col;
+    self.output_size = printer.output_size;
+    self.dependencies = printer.dependencies.take();
+    self.px_to_rem = printer.px_to_rem.take();
+    res
+  }
+}
+
+impl<'a, W: std::fmt::Write + ?Sized> Printer<'a, W> {
+  /// Rounds a number to the configured [precision](PrinterOptions::precision), if any.
+  pub(crate) fn round(&self, value: f32) -> f32 {
+    match self.precision {
+      Some(precision) if self.round_authored_values || !is_exact(value) => round_to_precision(value, precision),
+      _ => value,
     }
   }
 
@@ -81,12 +368,14 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
   }
 
   pub fn write_str(&mut self, s: &str) -> Result<(), PrinterError> {
+    self.add_output_size(s.len())?;
     self.col += s.len() as u32;
     self.dest.write_str(s)?;
     Ok(())
   }
 
   pub fn write_char(&mut self, c: char) -> Result<(), PrinterError> {
+    self.add_output_size(c.len_utf8())?;
     if c == '\n' {
       self.line += 1;
       self.col = 0;
@@ -142,23 +431,37 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
     self.indent -= amt;
   }
 
+  /// Returns whether declarations parsed with [preserve_raw_values](crate::stylesheet::ParserOptions::preserve_raw_values)
+  /// are printed as written in the source. Their values are re-serialized when names are scoped by CSS modules
+  /// or urls are replaced by dependency placeholders.
+  pub(crate) fn prints_raw_values(&self) -> bool {
+    self.preserve_raw_values && self.css_module.is_none() && self.dependencies.is_none()
+  }
+
   pub fn is_nested(&self) -> bool {
     self.indent > 2
   }
 
   pub fn add_mapping(&mut self, loc: Location) {
     self.source_index = loc.source_index;
+    #[cfg(feature = "std")]
     if let Some(map) = &mut self.source_map {
-      map.add_mapping(
-        self.line,
-        self.col,
+      // Generated rules have no original source, so they are left unmapped.
+      let original = if loc.generated {
+        None
+      } else {
         Some(OriginalLocation {
           original_line: loc.line,
           original_column: loc.column - 1,
           source: loc.source_index,
           name: None,
-        }),
-      )
+        })
+      };
+      map.add_mapping(self.line, self.col, original)
+    }
+
+    if let Some(css_module) = &mut self.css_module {
+      css_module.set_source_index(loc.source_index);
     }
   }
 
@@ -183,6 +486,31 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
     Ok(())
   }
 
+  /// Adds to the size of the output before it is written, so the output never grows
+  /// beyond the [maximum size](PrinterOptions::max_output_size).
+  fn add_output_size(&mut self, len: usize) -> Result<(), PrinterError> {
+    self.output_size += len;
+    match self.max_output_size {
+      Some(max) if self.output_size > max => Err(PrinterError {
+        kind: PrinterErrorKind::MaximumOutputSizeExceeded { max },
+        loc: None,
+      }),
+      _ => Ok(()),
+    }
+  }
+
+  /// Values serialized through [std::fmt::Write] can only report a generic formatting
+  /// error, so replace it with the specific error if the maximum output size was exceeded.
+  pub(crate) fn map_output_size_error(&self, err: PrinterError) -> PrinterError {
+    match self.max_output_size {
+      Some(max) if err.kind == PrinterErrorKind::FmtError && self.output_size > max => PrinterError {
+        kind: PrinterErrorKind::MaximumOutputSizeExceeded { max },
+        loc: None,
+      },
+      _ => err,
+    }
+  }
+
   pub fn error(&self, kind: PrinterErrorKind, loc: SourceLocation) -> Error<PrinterErrorKind> {
     Error {
       kind,
@@ -195,9 +523,32 @@ impl<'a, W: std::fmt::Write + Sized> Printer<'a, W> {
   }
 }
 
-impl<'a, W: std::fmt::Write + Sized> std::fmt::Write for Printer<'a, W> {
+impl<'a, W: std::fmt::Write + ?Sized> std::fmt::Write for Printer<'a, W> {
   fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    self.add_output_size(s.len()).map_err(|_| std::fmt::Error)?;
     self.col += s.len() as u32;
     self.dest.write_str(s)
   }
 }
+
+/// The number of significant decimal digits that an `f32` is guaranteed to represent exactly.
+/// Authored values within this limit round trip through parsing unchanged.
+const F32_DIGITS: u8 = 6;
+
+/// Returns whether the value could have been authored, i.e. it has no more significant digits than an
+/// `f32` can represent exactly. Values with more digits are the result of a computation.
+fn is_exact(value: f32) -> bool {
+  round_to_precision(value, F32_DIGITS) == value
+}
+
+/// Rounds the value to the given number of significant digits, with halves rounded away from zero.
+fn round_to_precision(value: f32, precision: u8) -> f32 {
+  if value == 0.0 || !value.is_finite() {
+    return value;
+  }
+
+  let value = value as f64;
+  let digits = value.abs().log10().floor() as i32 + 1;
+  let factor = 10f64.powi(precision.max(1) as i32 - digits);
+  ((value * factor).round() / factor) as f32
+}
@@ -0,0 +1,109 @@
+//! The `@apply` rule.
+
+use crate::error::{ParserError, PrinterError};
+use crate::printer::Printer;
+use crate::traits::{Parse, ToCss};
+use crate::values::string::CowArcStr;
+use cssparser::*;
+
+/// The list of utility classes referenced by an `@apply` rule, e.g. `.btn .rounded`.
+///
+/// Names may contain `:` and `/`, e.g. `hover:underline` or `w-1/2`, which reference the utility classes
+/// `.hover\:underline` and `.w-1\/2`. Other characters that are not valid in identifiers must be escaped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Apply<'i> {
+  /// The names of the referenced utilities, without a leading `.`.
+  pub names: Vec<CowArcStr<'i>>,
+}
+
+impl<'i> Parse<'i> for Apply<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut names = Vec::new();
+    while let Ok(name) = input.try_parse(parse_name) {
+      names.push(name);
+    }
+
+    if names.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(Apply { names })
+  }
+}
+
+fn parse_name<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CowArcStr<'i>, ParseError<'i, ParserError<'i>>> {
+  // Utilities may optionally be written as class selectors.
+  let _ = input.try_parse(|input| input.expect_delim('.'));
+  let ident = input.expect_ident_cloned()?;
+
+  // Variants and fractions are separate tokens, so the name continues until the next whitespace.
+  let mut name: Option<String> = None;
+  loop {
+    let state = input.state();
+    let part = match input.next_including_whitespace().map(|token| token.clone()) {
+      Ok(Token::Ident(ident)) => ident,
+      Ok(Token::Colon) | Ok(Token::Delim('/')) | Ok(Token::Number { .. }) | Ok(Token::Dimension { .. }) => {
+        input.slice_from(state.position()).into()
+      }
+      _ => {
+        input.reset(&state);
+        break;
+      }
+    };
+    name.get_or_insert_with(|| ident.to_string()).push_str(&part);
+  }
+
+  Ok(match name {
+    Some(name) => name.into(),
+    None => ident.into(),
+  })
+}
+
+impl<'i> ToCss for Apply<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    let mut first = true;
+    for name in &self.names {
+      if first {
+        first = false;
+      } else {
+        dest.write_char(' ')?;
+      }
+      serialize_identifier(name, dest)?;
+    }
+    Ok(())
+  }
+}
+
+/// An `@apply` rule within a [DeclarationBlock](crate::declaration::DeclarationBlock), which references
+/// the declarations of one or more utility classes, e.g. `@apply .btn .rounded;`.
+///
+/// The referenced declarations can be inlined using
+/// [StyleSheet::apply_utilities](crate::stylesheet::StyleSheet::apply_utilities).
+/// Otherwise, the rule is preserved as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplyRule<'i> {
+  /// The referenced utilities.
+  pub apply: Apply<'i>,
+  /// Whether the rule is marked `!important`.
+  pub important: bool,
+  /// The number of declarations with the same importance that come before the rule in the block.
+  pub index: usize,
+}
+
+impl<'i> ToCss for ApplyRule<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("@apply ")?;
+    self.apply.to_css(dest)?;
+    if self.important {
+      dest.whitespace()?;
+      dest.write_str("!important")?;
+    }
+    Ok(())
+  }
+}
@@ -636,7 +636,7 @@ impl<'i> BackgroundHandler<'i> {
         };
 
         let clip_property = if clip_prefixes != VendorPrefix::None {
-          Some(Property::BackgroundClip(clips.clone(), clip_prefixes))
+          Some(clips.clone())
         } else {
           None
         };
@@ -685,8 +685,9 @@ impl<'i> BackgroundHandler<'i> {
 
         dest.push(Property::Background(backgrounds));
 
-        if let Some(clip) = clip_property {
-          dest.push(clip)
+        if let Some(clips) = clip_property {
+          remove_prefixed_clips(&clips, dest);
+          dest.push(Property::BackgroundClip(clips, clip_prefixes))
         }
 
         self.reset();
@@ -750,6 +751,7 @@ impl<'i> BackgroundHandler<'i> {
 
     if let Some(clips) = clips {
       let prefixes = if let Some(targets) = self.targets {
+        remove_prefixed_clips(&clips, dest);
         if clips.iter().any(|clip| *clip == BackgroundClip::Text) {
           Feature::BackgroundClip.prefixes_for(targets)
         } else {
@@ -777,6 +779,14 @@ impl<'i> BackgroundHandler<'i> {
   }
 }
 
+/// Removes earlier prefixed `background-clip` declarations with the same value as an unprefixed one.
+/// The prefixes are generated for the targets instead, so the prefixed declarations would be duplicated.
+fn remove_prefixed_clips(clips: &SmallVec<[BackgroundClip; 1]>, dest: &mut DeclarationList) {
+  dest.retain(
+    |decl| !matches!(decl, Property::BackgroundClip(c, prefix) if *prefix != VendorPrefix::None && c == clips),
+  );
+}
+
 #[inline]
 fn is_background_property(property_id: &PropertyId) -> bool {
   match property_id {
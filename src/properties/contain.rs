@@ -0,0 +1,154 @@
+//! CSS properties related to containment.
+
+use super::{Property, PropertyId};
+use crate::context::PropertyHandlerContext;
+use crate::declaration::DeclarationList;
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::printer::Printer;
+use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::values::length::Length;
+use cssparser::*;
+
+enum_property! {
+  /// A value for the [content-visibility](https://drafts.csswg.org/css-contain-2/#content-visibility) property.
+  pub enum ContentVisibility {
+    /// The element's contents are rendered as normal.
+    Visible,
+    /// The element's contents are skipped when not relevant to the user.
+    Auto,
+    /// The element's contents are always skipped.
+    Hidden,
+  }
+}
+
+/// A value for the [contain-intrinsic-width](https://drafts.csswg.org/css-sizing-4/#propdef-contain-intrinsic-width)
+/// and [contain-intrinsic-height](https://drafts.csswg.org/css-sizing-4/#propdef-contain-intrinsic-height) properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainIntrinsicLength {
+  /// Whether the last remembered size of the element is used, if any.
+  pub auto: bool,
+  /// The explicit intrinsic size, or `None` for the `none` keyword.
+  pub length: Option<Length>,
+}
+
+impl<'i> Parse<'i> for ContainIntrinsicLength {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let auto = input.try_parse(|input| input.expect_ident_matching("auto")).is_ok();
+    let length = if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      None
+    } else {
+      Some(Length::parse(input)?)
+    };
+
+    Ok(ContainIntrinsicLength { auto, length })
+  }
+}
+
+impl ToCss for ContainIntrinsicLength {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.auto {
+      dest.write_str("auto ")?;
+    }
+
+    match &self.length {
+      Some(length) => length.to_css(dest),
+      None => dest.write_str("none"),
+    }
+  }
+}
+
+/// A value for the [contain-intrinsic-size](https://drafts.csswg.org/css-sizing-4/#propdef-contain-intrinsic-size)
+/// shorthand property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainIntrinsicSize {
+  /// The intrinsic width.
+  pub width: ContainIntrinsicLength,
+  /// The intrinsic height.
+  pub height: ContainIntrinsicLength,
+}
+
+impl<'i> Parse<'i> for ContainIntrinsicSize {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let width = ContainIntrinsicLength::parse(input)?;
+    let height = input.try_parse(ContainIntrinsicLength::parse).unwrap_or_else(|_| width.clone());
+    Ok(ContainIntrinsicSize { width, height })
+  }
+}
+
+impl ToCss for ContainIntrinsicSize {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.width.to_css(dest)?;
+    if self.height != self.width {
+      dest.write_char(' ')?;
+      self.height.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct ContainIntrinsicSizeHandler {
+  width: Option<ContainIntrinsicLength>,
+  height: Option<ContainIntrinsicLength>,
+}
+
+impl<'i> PropertyHandler<'i> for ContainIntrinsicSizeHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    use Property::*;
+
+    match property {
+      ContainIntrinsicWidth(val) => self.width = Some(val.clone()),
+      ContainIntrinsicHeight(val) => self.height = Some(val.clone()),
+      ContainIntrinsicSize(val) => {
+        self.width = Some(val.width.clone());
+        self.height = Some(val.height.clone());
+      }
+      Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::ContainIntrinsicWidth
+            | PropertyId::ContainIntrinsicHeight
+            | PropertyId::ContainIntrinsicSize
+        ) =>
+      {
+        self.finalize(dest, context);
+        dest.push(property.clone());
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    let width = std::mem::take(&mut self.width);
+    let height = std::mem::take(&mut self.height);
+
+    match (width, height) {
+      (Some(width), Some(height)) => {
+        dest.push(Property::ContainIntrinsicSize(ContainIntrinsicSize { width, height }))
+      }
+      (width, height) => {
+        if let Some(width) = width {
+          dest.push(Property::ContainIntrinsicWidth(width))
+        }
+
+        if let Some(height) = height {
+          dest.push(Property::ContainIntrinsicHeight(height))
+        }
+      }
+    }
+  }
+}
@@ -1,15 +1,23 @@
 //! CSS custom properties and unparsed token values.
 
+use crate::compat;
 use crate::error::{ParserError, PrinterError, PrinterErrorKind};
+use crate::macros::enum_property;
+use crate::parser::parse_nested_block;
 use crate::prefixes::Feature;
-use crate::printer::Printer;
-use crate::properties::PropertyId;
+use crate::printer::{Printer, PrinterOptions};
+use crate::properties::{Property, PropertyId};
 use crate::rules::supports::SupportsCondition;
 use crate::targets::Browsers;
 use crate::traits::{Parse, ToCss};
+use crate::values::angle::Angle;
 use crate::values::color::{ColorFallbackKind, CssColor};
-use crate::values::length::serialize_dimension;
+use crate::values::ident::DashedIdent;
+use crate::values::length::{serialize_dimension, Length};
+use crate::values::percentage::DimensionPercentage;
+use crate::values::resolution::Resolution;
 use crate::values::string::CowArcStr;
+use crate::values::syntax::{Multiplier, ParsedComponent, SyntaxString};
 use crate::values::url::Url;
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
@@ -79,6 +87,80 @@ impl<'i> UnparsedProperty<'i> {
   }
 }
 
+/// A property along with the source text of its name and value.
+///
+/// This type is used when [preserve_raw_values](crate::stylesheet::ParserOptions::preserve_raw_values)
+/// is enabled. The source text is printed when [preserve_raw_values](crate::stylesheet::PrinterOptions::preserve_raw_values)
+/// is also enabled in the printer, unless CSS modules or dependency analysis are enabled, and the parsed property otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerbatimProperty<'i> {
+  /// The parsed property.
+  pub property: Box<Property<'i>>,
+  /// The property name, as written in the source.
+  pub name: CowArcStr<'i>,
+  /// The property value, as written in the source, without surrounding whitespace or `!important`.
+  pub value: CowArcStr<'i>,
+}
+
+enum_property! {
+  /// A [CSS-wide keyword](https://drafts.csswg.org/css-cascade-5/#defaulting-keywords),
+  /// which is valid as the value of any property.
+  pub enum CSSWideKeyword {
+    /// The property's initial value.
+    "initial": Initial,
+    /// The property's computed value on the parent element.
+    "inherit": Inherit,
+    /// Either inherit or initial depending on whether the property is inherited.
+    "unset": Unset,
+    /// Rolls back the cascade to the cascaded value of the earlier origin.
+    "revert": Revert,
+    /// Rolls back the cascade to the value of the previous cascade layer.
+    "revert-layer": RevertLayer,
+  }
+}
+
+impl CSSWideKeyword {
+  /// Returns the compatibility feature required to use the keyword, if any.
+  pub(crate) fn feature(&self) -> Option<compat::Feature> {
+    match self {
+      CSSWideKeyword::Revert => Some(compat::Feature::Revert),
+      CSSWideKeyword::RevertLayer => Some(compat::Feature::RevertLayer),
+      _ => None,
+    }
+  }
+}
+
+/// A known property with a [CSS-wide keyword](CSSWideKeyword) as its value.
+///
+/// This type is used for the `revert` and `revert-layer` keywords, which are
+/// valid for all properties. The `all` property is represented by `Property::All`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WideKeywordProperty<'i> {
+  /// The id of the property.
+  pub property_id: PropertyId<'i>,
+  /// The CSS-wide keyword.
+  pub keyword: CSSWideKeyword,
+}
+
+impl<'i> WideKeywordProperty<'i> {
+  /// Parses a CSS-wide keyword for the property with the given id.
+  ///
+  /// Only `revert` and `revert-layer` are accepted.
+  pub fn parse<'t>(
+    property_id: PropertyId<'i>,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let location = input.current_source_location();
+    let keyword = CSSWideKeyword::parse(input)?;
+    match keyword {
+      CSSWideKeyword::Revert | CSSWideKeyword::RevertLayer => {}
+      _ => return Err(location.new_custom_error(ParserError::InvalidValue)),
+    }
+    input.expect_exhausted()?;
+    Ok(WideKeywordProperty { property_id, keyword })
+  }
+}
+
 /// A raw list of CSS tokens, with embedded parsed values.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenList<'i>(pub Vec<TokenOrValue<'i>>);
@@ -92,6 +174,62 @@ pub enum TokenOrValue<'i> {
   Color(CssColor),
   /// A parsed CSS url.
   Url(Url<'i>),
+  /// A parsed `var()` reference.
+  Var(Variable<'i>),
+}
+
+/// A CSS [var()](https://drafts.csswg.org/css-variables/#using-variables) reference,
+/// with an optional fallback value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable<'i> {
+  /// The name of the referenced custom property.
+  pub name: DashedIdent<'i>,
+  /// The fallback value, i.e. all tokens after the first comma, including any further commas.
+  /// An empty fallback is stored as an empty token list.
+  pub fallback: Option<TokenList<'i>>,
+}
+
+impl<'i> Parse<'i> for Variable<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("var")?;
+    parse_nested_block(input, |input| {
+      let name = DashedIdent::parse(input)?;
+      let fallback = if input.try_parse(|input| input.expect_comma()).is_ok() {
+        let mut tokens = vec![];
+        TokenList::parse_into(input, &mut tokens)?;
+
+        // Whitespace around the fallback is not significant.
+        let start = tokens.iter().position(|token| !token.is_whitespace()).unwrap_or(tokens.len());
+        let end = tokens
+          .iter()
+          .rposition(|token| !token.is_whitespace())
+          .map_or(start, |end| end + 1);
+        Some(TokenList(tokens[start..end].to_vec()))
+      } else {
+        None
+      };
+
+      Ok(Variable { name, fallback })
+    })
+  }
+}
+
+impl<'i> Variable<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>, is_custom_property: bool) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("var(")?;
+    serialize_identifier(&self.name.0, dest)?;
+    if let Some(fallback) = &self.fallback {
+      dest.write_char(',')?;
+      if !fallback.0.is_empty() {
+        dest.whitespace()?;
+        fallback.to_css(dest, is_custom_property)?;
+      }
+    }
+    dest.write_char(')')
+  }
 }
 
 impl<'i> From<Token<'i>> for TokenOrValue<'i> {
@@ -105,29 +243,81 @@ impl<'i> TokenOrValue<'i> {
   pub fn is_whitespace(&self) -> bool {
     matches!(self, TokenOrValue::Token(Token::WhiteSpace(_)))
   }
+
+  /// Copies the token or value so that it does not borrow from the source, if supported.
+  fn to_owned_value<'o>(&self) -> Option<TokenOrValue<'o>> {
+    let token = match self {
+      TokenOrValue::Color(color) => return Some(TokenOrValue::Color(color.clone())),
+      TokenOrValue::Token(token) => token,
+      TokenOrValue::Url(_) | TokenOrValue::Var(_) => return None,
+    };
+
+    Some(TokenOrValue::Token(match token {
+      Token::Ident(ident) => Token::Ident(ident.to_string().into()),
+      Token::Function(name) => Token::Function(name.to_string().into()),
+      Token::Number {
+        has_sign,
+        value,
+        int_value,
+      } => Token::Number {
+        has_sign: *has_sign,
+        value: *value,
+        int_value: *int_value,
+      },
+      Token::Percentage {
+        has_sign,
+        unit_value,
+        int_value,
+      } => Token::Percentage {
+        has_sign: *has_sign,
+        unit_value: *unit_value,
+        int_value: *int_value,
+      },
+      Token::Dimension {
+        has_sign,
+        value,
+        int_value,
+        unit,
+      } => Token::Dimension {
+        has_sign: *has_sign,
+        value: *value,
+        int_value: *int_value,
+        unit: unit.to_string().into(),
+      },
+      Token::WhiteSpace(_) => Token::WhiteSpace(" "),
+      Token::Delim(c) => Token::Delim(*c),
+      Token::Comma => Token::Comma,
+      Token::ParenthesisBlock => Token::ParenthesisBlock,
+      Token::CloseParenthesis => Token::CloseParenthesis,
+      _ => return None,
+    }))
+  }
 }
 
 impl<'i> TokenList<'i> {
   fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    input.parse_until_before(Delimiter::Bang | Delimiter::Semicolon, |input| {
-      let mut tokens = vec![];
-      TokenList::parse_into(input, &mut tokens)?;
-
-      // Slice off leading and trailing whitespace if there are at least two tokens.
-      // If there is only one token, we must preserve it. e.g. `--foo: ;` is valid.
-      if tokens.len() >= 2 {
-        let mut slice = &tokens[..];
-        if matches!(tokens.first(), Some(token) if token.is_whitespace()) {
-          slice = &slice[1..];
-        }
-        if matches!(tokens.last(), Some(token) if token.is_whitespace()) {
-          slice = &slice[..slice.len() - 1];
-        }
-        return Ok(TokenList(slice.to_vec()));
+    input.parse_until_before(Delimiter::Bang | Delimiter::Semicolon, TokenList::parse_raw)
+  }
+
+  /// Parses all remaining tokens in the input, including `!` and `;` delimiters.
+  pub(crate) fn parse_raw<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut tokens = vec![];
+    TokenList::parse_into(input, &mut tokens)?;
+
+    // Slice off leading and trailing whitespace if there are at least two tokens.
+    // If there is only one token, we must preserve it. e.g. `--foo: ;` is valid.
+    if tokens.len() >= 2 {
+      let mut slice = &tokens[..];
+      if matches!(tokens.first(), Some(token) if token.is_whitespace()) {
+        slice = &slice[1..];
+      }
+      if matches!(tokens.last(), Some(token) if token.is_whitespace()) {
+        slice = &slice[..slice.len() - 1];
       }
+      return Ok(TokenList(slice.to_vec()));
+    }
 
-      return Ok(TokenList(tokens));
-    })
+    return Ok(TokenList(tokens));
   }
 
   fn parse_into<'t>(
@@ -159,9 +349,13 @@ impl<'i> TokenList<'i> {
             tokens.push(TokenOrValue::Url(Url::parse(input)?));
             last_is_delim = false;
             last_is_whitespace = false;
+          } else if let Some(var) = try_parse_var_token(&f, &state, input) {
+            tokens.push(TokenOrValue::Var(var));
+            last_is_delim = true; // Whitespace is not required after the closing parenthesis.
+            last_is_whitespace = false;
           } else {
             tokens.push(Token::Function(f).into());
-            input.parse_nested_block(|input| TokenList::parse_into(input, tokens))?;
+            parse_nested_block(input, |input| TokenList::parse_into(input, tokens))?;
             tokens.push(Token::CloseParenthesis.into());
             last_is_delim = true; // Whitespace is not required after any of these chars.
             last_is_whitespace = false;
@@ -193,7 +387,7 @@ impl<'i> TokenList<'i> {
             _ => unreachable!(),
           };
 
-          input.parse_nested_block(|input| TokenList::parse_into(input, tokens))?;
+          parse_nested_block(input, |input| TokenList::parse_into(input, tokens))?;
 
           tokens.push(closing_delimiter.into());
           last_is_delim = true; // Whitespace is not required after any of these chars.
@@ -242,7 +436,56 @@ fn try_parse_color_token<'i, 't>(
   None
 }
 
+#[inline]
+fn try_parse_var_token<'i, 't>(
+  f: &CowArcStr<'i>,
+  state: &ParserState,
+  input: &mut Parser<'i, 't>,
+) -> Option<Variable<'i>> {
+  if !f.eq_ignore_ascii_case("var") {
+    return None;
+  }
+
+  // If the var() is malformed, e.g. the name is not a dashed ident, it is stored as raw tokens instead.
+  let s = input.state();
+  input.reset(&state);
+  if let Ok(var) = Variable::parse(input) {
+    return Some(var);
+  }
+  input.reset(&s);
+  None
+}
+
 impl<'i> TokenList<'i> {
+  /// Returns whether the token list contains a `var()` or `env()` reference.
+  pub(crate) fn has_references(&self) -> bool {
+    self.0.iter().any(|token| match token {
+      TokenOrValue::Var(_) => true,
+      TokenOrValue::Token(Token::Function(f)) => f.eq_ignore_ascii_case("var") || f.eq_ignore_ascii_case("env"),
+      _ => false,
+    })
+  }
+
+  /// Minifies the value of a custom property registered with the given syntax, e.g. via `@property`.
+  /// Returns `None` if the syntax is universal, the value contains references, the value does not
+  /// match the syntax, or it contains components that are not a single token, such as images.
+  pub(crate) fn minify_with_syntax(&self, syntax: &SyntaxString) -> Option<TokenList<'i>> {
+    if *syntax == SyntaxString::Universal || self.has_references() {
+      return None;
+    }
+
+    let mut source = String::new();
+    let mut printer = Printer::new(&mut source, PrinterOptions::default());
+    self.to_css(&mut printer, true).ok()?;
+
+    let mut input = ParserInput::new(&source);
+    let mut parser = Parser::new(&mut input);
+    let value = parser.parse_entirely(|input| syntax.parse_value(input)).ok()?;
+    let mut tokens = Vec::new();
+    push_component_tokens(&value, &mut tokens)?;
+    Some(TokenList(tokens))
+  }
+
   pub(crate) fn to_css<W>(&self, dest: &mut Printer<W>, is_custom_property: bool) -> Result<(), PrinterError>
   where
     W: std::fmt::Write,
@@ -251,9 +494,31 @@ impl<'i> TokenList<'i> {
       return Ok(());
     }
 
+    // An empty fallback is equivalent to no fallback when the var() is the entire value of a
+    // normal property: either way, the property is invalid at computed-value time if the variable
+    // is not defined. For custom properties, the empty fallback is a valid value, so it is kept.
+    if dest.minify && !is_custom_property {
+      if let [TokenOrValue::Var(var)] = self.0.as_slice() {
+        if matches!(&var.fallback, Some(fallback) if fallback.0.is_empty()) {
+          return Variable {
+            name: var.name.clone(),
+            fallback: None,
+          }
+          .to_css(dest, is_custom_property);
+        }
+      }
+    }
+
     for (i, token_or_value) in self.0.iter().enumerate() {
       match token_or_value {
         TokenOrValue::Color(color) => color.to_css(dest)?,
+        TokenOrValue::Var(var) => {
+          var.to_css(dest, is_custom_property)?;
+          if !dest.minify && i != self.0.len() - 1 && !matches!(self.0[i + 1], TokenOrValue::Token(Token::Comma)) {
+            // Whitespace is removed during parsing, so add it back if we aren't minifying.
+            dest.write_char(' ')?;
+          }
+        }
         TokenOrValue::Url(url) => {
           if dest.dependencies.is_some() && is_custom_property && !url.is_absolute() {
             return Err(dest.error(
@@ -308,6 +573,104 @@ impl<'i> TokenList<'i> {
   }
 }
 
+/// Pushes the tokens of a component parsed according to a registered syntax. The value of each
+/// component is minified like a typed property value, e.g. zero lengths omit their unit.
+/// Returns `None` for components that are not a single token, such as `calc()` expressions.
+fn push_component_tokens<'o>(component: &ParsedComponent, tokens: &mut Vec<TokenOrValue<'o>>) -> Option<()> {
+  let numeric = |value: f32| {
+    (
+      value < 0.0,
+      if value.fract() == 0.0 { Some(value as i32) } else { None },
+    )
+  };
+  let number = |value: f32| {
+    let (has_sign, int_value) = numeric(value);
+    Token::Number {
+      has_sign,
+      value,
+      int_value,
+    }
+  };
+  let dimension = |value: f32, unit: &str| {
+    let (has_sign, int_value) = numeric(value);
+    Token::Dimension {
+      has_sign,
+      value,
+      int_value,
+      unit: unit.to_string().into(),
+    }
+  };
+
+  let token = match component {
+    ParsedComponent::Length(Length::Value(length))
+    | ParsedComponent::LengthPercentage(DimensionPercentage::Dimension(length)) => {
+      let (value, unit) = length.to_unit_value();
+      if value == 0.0 {
+        number(0.0)
+      } else {
+        dimension(value, unit)
+      }
+    }
+    ParsedComponent::Percentage(percentage)
+    | ParsedComponent::LengthPercentage(DimensionPercentage::Percentage(percentage)) => {
+      let (has_sign, int_value) = numeric(percentage.0 * 100.0);
+      Token::Percentage {
+        has_sign,
+        unit_value: percentage.0,
+        int_value,
+      }
+    }
+    ParsedComponent::Number(value) => number(*value),
+    ParsedComponent::Integer(value) => number(*value as f32),
+    ParsedComponent::Angle(angle) => match angle {
+      Angle::Deg(value) => dimension(*value, "deg"),
+      Angle::Rad(value) => dimension(*value, "rad"),
+      Angle::Grad(value) => dimension(*value, "grad"),
+      Angle::Turn(value) => dimension(*value, "turn"),
+    },
+    ParsedComponent::Time(time) => {
+      // 0.1s is shorter than 100ms, anything smaller is longer.
+      let ms = time.to_ms();
+      if ms > 0.0 && ms < 100.0 {
+        dimension(ms, "ms")
+      } else {
+        dimension(ms / 1000.0, "s")
+      }
+    }
+    ParsedComponent::Resolution(resolution) => match resolution {
+      Resolution::Dpi(value) => dimension(*value, "dpi"),
+      Resolution::Dpcm(value) => dimension(*value, "dpcm"),
+      Resolution::Dppx(value) => dimension(*value, "x"),
+    },
+    ParsedComponent::CustomIdent(ident) => Token::Ident(ident.0.to_string().into()),
+    ParsedComponent::Literal(literal) => Token::Ident(literal.to_string().into()),
+    ParsedComponent::Color(color) => {
+      tokens.push(TokenOrValue::Color(color.clone()));
+      return Some(());
+    }
+    ParsedComponent::Token(token) => {
+      tokens.push(TokenOrValue::Token(token.clone()).to_owned_value()?);
+      return Some(());
+    }
+    ParsedComponent::Repeated(components, multiplier) => {
+      for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+          tokens.push(TokenOrValue::Token(match multiplier {
+            Multiplier::Comma => Token::Comma,
+            _ => Token::WhiteSpace(" "),
+          }));
+        }
+        push_component_tokens(component, tokens)?;
+      }
+      return Some(());
+    }
+    _ => return None,
+  };
+
+  tokens.push(TokenOrValue::Token(token));
+  Some(())
+}
+
 /// A raw CSS token.
 // Copied from cssparser to change CowRcStr to CowArcStr
 #[derive(Debug, Clone, PartialEq)]
@@ -615,8 +978,13 @@ impl<'i> TokenList<'i> {
   pub(crate) fn get_necessary_fallbacks(&self, targets: Browsers) -> ColorFallbackKind {
     let mut fallbacks = ColorFallbackKind::empty();
     for token in &self.0 {
-      if let TokenOrValue::Color(color) = token {
-        fallbacks |= color.get_possible_fallbacks(targets);
+      match token {
+        TokenOrValue::Color(color) => fallbacks |= color.get_possible_fallbacks(targets),
+        TokenOrValue::Var(Variable {
+          fallback: Some(fallback),
+          ..
+        }) => fallbacks |= fallback.get_necessary_fallbacks(targets),
+        _ => {}
       }
     }
 
@@ -629,6 +997,10 @@ impl<'i> TokenList<'i> {
       .iter()
       .map(|token| match token {
         TokenOrValue::Color(color) => TokenOrValue::Color(color.get_fallback(kind)),
+        TokenOrValue::Var(var) => TokenOrValue::Var(Variable {
+          name: var.name.clone(),
+          fallback: var.fallback.as_ref().map(|fallback| fallback.get_fallback(kind)),
+        }),
         _ => token.clone(),
       })
       .collect();
@@ -658,13 +1030,22 @@ impl<'i> TokenList<'i> {
     }
 
     if !lowest_fallback.is_empty() {
-      for token in self.0.iter_mut() {
-        if let TokenOrValue::Color(color) = token {
-          *color = color.get_fallback(lowest_fallback);
-        }
-      }
+      self.apply_fallback(lowest_fallback);
     }
 
     res
   }
+
+  fn apply_fallback(&mut self, kind: ColorFallbackKind) {
+    for token in self.0.iter_mut() {
+      match token {
+        TokenOrValue::Color(color) => *color = color.get_fallback(kind),
+        TokenOrValue::Var(Variable {
+          fallback: Some(fallback),
+          ..
+        }) => fallback.apply_fallback(kind),
+        _ => {}
+      }
+    }
+  }
 }
@@ -176,12 +176,25 @@ impl<'i> ToCss for Filter<'i> {
         val.to_css(dest)?;
         dest.write_char(')')
       }
-      Filter::Url(url) => url.to_css(dest),
+      Filter::Url(url) => url.to_css_reference(dest),
     }
   }
 }
 
 impl<'i> Filter<'i> {
+  /// Returns whether the filter function leaves its input unchanged, e.g. `brightness(1)`.
+  fn is_identity(&self) -> bool {
+    match self {
+      Filter::Blur(val) => val.is_zero(),
+      Filter::Brightness(val) | Filter::Contrast(val) | Filter::Opacity(val) | Filter::Saturate(val) => {
+        *val == 1.0
+      }
+      Filter::Grayscale(val) | Filter::Invert(val) | Filter::Sepia(val) => *val == 0.0,
+      Filter::HueRotate(val) => *val == 0.0,
+      Filter::DropShadow(..) | Filter::Url(..) => false,
+    }
+  }
+
   fn get_fallback(&self, kind: ColorFallbackKind) -> Self {
     match self {
       Filter::DropShadow(shadow) => Filter::DropShadow(shadow.get_fallback(kind)),
@@ -323,6 +336,24 @@ impl<'i> ToCss for FilterList<'i> {
   }
 }
 
+impl<'i> FilterList<'i> {
+  /// Returns whether the list ends with identity functions, such as `brightness(1)`, that can be removed.
+  pub(crate) fn has_trailing_identities(&self) -> bool {
+    matches!(self, FilterList::Filters(filters) if filters.len() > 1 && filters[filters.len() - 1].is_identity())
+  }
+
+  /// Removes trailing identity functions. Those earlier in the list must remain so that interpolation
+  /// between filter lists still matches up the functions. If all of them are identities, one must remain,
+  /// because any filter other than `none` creates a stacking context.
+  pub(crate) fn remove_trailing_identities(&mut self) {
+    if let FilterList::Filters(filters) = self {
+      while filters.len() > 1 && filters[filters.len() - 1].is_identity() {
+        filters.pop();
+      }
+    }
+  }
+}
+
 impl<'i> FallbackValues for FilterList<'i> {
   fn get_fallbacks(&mut self, targets: Browsers) -> Vec<Self> {
     let mut res = Vec::new();
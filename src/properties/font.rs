@@ -7,6 +7,7 @@ use crate::error::{ParserError, PrinterError};
 use crate::macros::*;
 use crate::printer::Printer;
 use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::values::ident::CustomIdent;
 use crate::values::number::CSSNumber;
 use crate::values::string::CowArcStr;
 use crate::values::{angle::Angle, length::LengthPercentage, percentage::Percentage};
@@ -510,6 +511,701 @@ impl FontVariantCapsCSS2 {
   }
 }
 
+/// Writes a value, preceded by a space if a previous value was written.
+macro_rules! write_keywords {
+  ($dest: ident, $needs_space: ident, $val: expr) => {
+    #[allow(unused_assignments)]
+    {
+      if $needs_space {
+        $dest.write_char(' ')?;
+      }
+      $val;
+      $needs_space = true;
+    }
+  };
+}
+
+enum_property! {
+  /// A [`<common-lig-values>`](https://www.w3.org/TR/css-fonts-4/#common-lig-values) value,
+  /// as used in the `font-variant-ligatures` property.
+  pub enum CommonLigatures {
+    /// Enables common ligatures.
+    "common-ligatures": Common,
+    /// Disables common ligatures.
+    "no-common-ligatures": NoCommon,
+  }
+}
+
+enum_property! {
+  /// A [`<discretionary-lig-values>`](https://www.w3.org/TR/css-fonts-4/#discretionary-lig-values) value,
+  /// as used in the `font-variant-ligatures` property.
+  pub enum DiscretionaryLigatures {
+    /// Enables discretionary ligatures.
+    "discretionary-ligatures": Discretionary,
+    /// Disables discretionary ligatures.
+    "no-discretionary-ligatures": NoDiscretionary,
+  }
+}
+
+enum_property! {
+  /// A [`<historical-lig-values>`](https://www.w3.org/TR/css-fonts-4/#historical-lig-values) value,
+  /// as used in the `font-variant-ligatures` property.
+  pub enum HistoricalLigatures {
+    /// Enables historical ligatures.
+    "historical-ligatures": Historical,
+    /// Disables historical ligatures.
+    "no-historical-ligatures": NoHistorical,
+  }
+}
+
+enum_property! {
+  /// A [`<contextual-alt-values>`](https://www.w3.org/TR/css-fonts-4/#contextual-alt-values) value,
+  /// as used in the `font-variant-ligatures` property.
+  pub enum ContextualAlternates {
+    /// Enables contextual alternates.
+    "contextual": Contextual,
+    /// Disables contextual alternates.
+    "no-contextual": NoContextual,
+  }
+}
+
+/// A value for the [font-variant-ligatures](https://www.w3.org/TR/css-fonts-4/#font-variant-ligatures-prop) property.
+///
+/// The default value represents the `normal` keyword.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontVariantLigatures {
+  /// Whether all ligatures are disabled, i.e. the `none` keyword.
+  pub none: bool,
+  /// Common ligatures.
+  pub common: Option<CommonLigatures>,
+  /// Discretionary ligatures.
+  pub discretionary: Option<DiscretionaryLigatures>,
+  /// Historical ligatures.
+  pub historical: Option<HistoricalLigatures>,
+  /// Contextual alternates.
+  pub contextual: Option<ContextualAlternates>,
+}
+
+impl FontVariantLigatures {
+  /// Returns whether the value is `normal`.
+  pub fn is_normal(&self) -> bool {
+    *self == FontVariantLigatures::default()
+  }
+
+  fn try_parse_item<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    macro_rules! item {
+      ($field: ident, $ty: ident) => {
+        if self.$field.is_none() {
+          if let Ok(value) = input.try_parse($ty::parse) {
+            self.$field = Some(value);
+            return true;
+          }
+        }
+      };
+    }
+
+    item!(common, CommonLigatures);
+    item!(discretionary, DiscretionaryLigatures);
+    item!(historical, HistoricalLigatures);
+    item!(contextual, ContextualAlternates);
+    false
+  }
+}
+
+impl<'i> Parse<'i> for FontVariantLigatures {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut result = FontVariantLigatures::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(result);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      result.none = true;
+      return Ok(result);
+    }
+
+    while result.try_parse_item(input) {}
+    if result.is_normal() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(result)
+  }
+}
+
+impl ToCss for FontVariantLigatures {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.none {
+      return dest.write_str("none");
+    }
+
+    if self.is_normal() {
+      return dest.write_str("normal");
+    }
+
+    let mut needs_space = false;
+    if let Some(val) = &self.common {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.discretionary {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.historical {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.contextual {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    Ok(())
+  }
+}
+
+enum_property! {
+  /// A [`<numeric-figure-values>`](https://www.w3.org/TR/css-fonts-4/#numeric-figure-values) value,
+  /// as used in the `font-variant-numeric` property.
+  pub enum NumericFigure {
+    /// Enables lining numerals.
+    "lining-nums": Lining,
+    /// Enables old-style numerals.
+    "oldstyle-nums": OldStyle,
+  }
+}
+
+enum_property! {
+  /// A [`<numeric-spacing-values>`](https://www.w3.org/TR/css-fonts-4/#numeric-spacing-values) value,
+  /// as used in the `font-variant-numeric` property.
+  pub enum NumericSpacing {
+    /// Enables proportional numerals.
+    "proportional-nums": Proportional,
+    /// Enables tabular numerals.
+    "tabular-nums": Tabular,
+  }
+}
+
+enum_property! {
+  /// A [`<numeric-fraction-values>`](https://www.w3.org/TR/css-fonts-4/#numeric-fraction-values) value,
+  /// as used in the `font-variant-numeric` property.
+  pub enum NumericFraction {
+    /// Enables diagonal fractions.
+    "diagonal-fractions": Diagonal,
+    /// Enables stacked fractions.
+    "stacked-fractions": Stacked,
+  }
+}
+
+/// A value for the [font-variant-numeric](https://www.w3.org/TR/css-fonts-4/#font-variant-numeric-prop) property.
+///
+/// The default value represents the `normal` keyword.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontVariantNumeric {
+  /// The numeric figure style.
+  pub figure: Option<NumericFigure>,
+  /// The numeric spacing.
+  pub spacing: Option<NumericSpacing>,
+  /// The fraction style.
+  pub fraction: Option<NumericFraction>,
+  /// Whether ordinal forms are enabled.
+  pub ordinal: bool,
+  /// Whether slashed zeros are enabled.
+  pub slashed_zero: bool,
+}
+
+impl FontVariantNumeric {
+  /// Returns whether the value is `normal`.
+  pub fn is_normal(&self) -> bool {
+    *self == FontVariantNumeric::default()
+  }
+
+  fn try_parse_item<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    macro_rules! item {
+      ($field: ident, $ty: ident) => {
+        if self.$field.is_none() {
+          if let Ok(value) = input.try_parse($ty::parse) {
+            self.$field = Some(value);
+            return true;
+          }
+        }
+      };
+    }
+
+    item!(figure, NumericFigure);
+    item!(spacing, NumericSpacing);
+    item!(fraction, NumericFraction);
+
+    if !self.ordinal && input.try_parse(|input| input.expect_ident_matching("ordinal")).is_ok() {
+      self.ordinal = true;
+      return true;
+    }
+
+    if !self.slashed_zero && input.try_parse(|input| input.expect_ident_matching("slashed-zero")).is_ok() {
+      self.slashed_zero = true;
+      return true;
+    }
+
+    false
+  }
+}
+
+impl<'i> Parse<'i> for FontVariantNumeric {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut result = FontVariantNumeric::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(result);
+    }
+
+    while result.try_parse_item(input) {}
+    if result.is_normal() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(result)
+  }
+}
+
+impl ToCss for FontVariantNumeric {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.is_normal() {
+      return dest.write_str("normal");
+    }
+
+    let mut needs_space = false;
+    if let Some(val) = &self.figure {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.spacing {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.fraction {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if self.ordinal {
+      write_keywords!(dest, needs_space, dest.write_str("ordinal")?);
+    }
+    if self.slashed_zero {
+      write_keywords!(dest, needs_space, dest.write_str("slashed-zero")?);
+    }
+    Ok(())
+  }
+}
+
+enum_property! {
+  /// An [`<east-asian-variant-values>`](https://www.w3.org/TR/css-fonts-4/#east-asian-variant-values) value,
+  /// as used in the `font-variant-east-asian` property.
+  pub enum EastAsianVariant {
+    /// JIS X 0208:1978 glyphs.
+    "jis78": Jis78,
+    /// JIS X 0208:1983 glyphs.
+    "jis83": Jis83,
+    /// JIS X 0208:1990 glyphs.
+    "jis90": Jis90,
+    /// JIS X 0213:2004 glyphs.
+    "jis04": Jis04,
+    /// Simplified Chinese glyphs.
+    "simplified": Simplified,
+    /// Traditional Chinese glyphs.
+    "traditional": Traditional,
+  }
+}
+
+enum_property! {
+  /// An [`<east-asian-width-values>`](https://www.w3.org/TR/css-fonts-4/#east-asian-width-values) value,
+  /// as used in the `font-variant-east-asian` property.
+  pub enum EastAsianWidth {
+    /// Full-width glyphs.
+    "full-width": FullWidth,
+    /// Proportionally spaced glyphs.
+    "proportional-width": ProportionalWidth,
+  }
+}
+
+/// A value for the [font-variant-east-asian](https://www.w3.org/TR/css-fonts-4/#font-variant-east-asian-prop) property.
+///
+/// The default value represents the `normal` keyword.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontVariantEastAsian {
+  /// The glyph variant.
+  pub variant: Option<EastAsianVariant>,
+  /// The glyph width.
+  pub width: Option<EastAsianWidth>,
+  /// Whether ruby variant glyphs are enabled.
+  pub ruby: bool,
+}
+
+impl FontVariantEastAsian {
+  /// Returns whether the value is `normal`.
+  pub fn is_normal(&self) -> bool {
+    *self == FontVariantEastAsian::default()
+  }
+
+  fn try_parse_item<'i, 't>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if self.variant.is_none() {
+      if let Ok(value) = input.try_parse(EastAsianVariant::parse) {
+        self.variant = Some(value);
+        return true;
+      }
+    }
+
+    if self.width.is_none() {
+      if let Ok(value) = input.try_parse(EastAsianWidth::parse) {
+        self.width = Some(value);
+        return true;
+      }
+    }
+
+    if !self.ruby && input.try_parse(|input| input.expect_ident_matching("ruby")).is_ok() {
+      self.ruby = true;
+      return true;
+    }
+
+    false
+  }
+}
+
+impl<'i> Parse<'i> for FontVariantEastAsian {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut result = FontVariantEastAsian::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(result);
+    }
+
+    while result.try_parse_item(input) {}
+    if result.is_normal() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(result)
+  }
+}
+
+impl ToCss for FontVariantEastAsian {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.is_normal() {
+      return dest.write_str("normal");
+    }
+
+    let mut needs_space = false;
+    if let Some(val) = &self.variant {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if let Some(val) = &self.width {
+      write_keywords!(dest, needs_space, val.to_css(dest)?);
+    }
+    if self.ruby {
+      write_keywords!(dest, needs_space, dest.write_str("ruby")?);
+    }
+    Ok(())
+  }
+}
+
+/// A value for the [font-variant-alternates](https://www.w3.org/TR/css-fonts-4/#font-variant-alternates-prop) property.
+///
+/// The default value represents the `normal` keyword. Feature value names are not scoped
+/// when CSS modules are enabled, since they refer to `@font-feature-values` rules.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontVariantAlternates<'i> {
+  /// Whether historical forms are enabled.
+  pub historical_forms: bool,
+  /// The `stylistic()` feature value name.
+  pub stylistic: Option<CustomIdent<'i>>,
+  /// The `styleset()` feature value names.
+  pub styleset: Option<Vec<CustomIdent<'i>>>,
+  /// The `character-variant()` feature value names.
+  pub character_variant: Option<Vec<CustomIdent<'i>>>,
+  /// The `swash()` feature value name.
+  pub swash: Option<CustomIdent<'i>>,
+  /// The `ornaments()` feature value name.
+  pub ornaments: Option<CustomIdent<'i>>,
+  /// The `annotation()` feature value name.
+  pub annotation: Option<CustomIdent<'i>>,
+}
+
+impl<'i> FontVariantAlternates<'i> {
+  /// Returns whether the value is `normal`.
+  pub fn is_normal(&self) -> bool {
+    *self == FontVariantAlternates::default()
+  }
+
+  fn try_parse_item<'t>(&mut self, input: &mut Parser<'i, 't>) -> bool {
+    if !self.historical_forms && input.try_parse(|input| input.expect_ident_matching("historical-forms")).is_ok() {
+      self.historical_forms = true;
+      return true;
+    }
+
+    macro_rules! function {
+      ($field: ident, $name: literal, $parse: expr) => {
+        if self.$field.is_none() {
+          if let Ok(value) = input.try_parse(|input| {
+            input.expect_function_matching($name)?;
+            input.parse_nested_block($parse)
+          }) {
+            self.$field = Some(value);
+            return true;
+          }
+        }
+      };
+    }
+
+    function!(stylistic, "stylistic", CustomIdent::parse);
+    function!(styleset, "styleset", |input| input
+      .parse_comma_separated(CustomIdent::parse));
+    function!(character_variant, "character-variant", |input| input
+      .parse_comma_separated(CustomIdent::parse));
+    function!(swash, "swash", CustomIdent::parse);
+    function!(ornaments, "ornaments", CustomIdent::parse);
+    function!(annotation, "annotation", CustomIdent::parse);
+    false
+  }
+}
+
+impl<'i> Parse<'i> for FontVariantAlternates<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut result = FontVariantAlternates::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(result);
+    }
+
+    while result.try_parse_item(input) {}
+    if result.is_normal() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(result)
+  }
+}
+
+impl<'i> ToCss for FontVariantAlternates<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.is_normal() {
+      return dest.write_str("normal");
+    }
+
+    fn write_function<W>(dest: &mut Printer<W>, name: &str, idents: &[CustomIdent]) -> Result<(), PrinterError>
+    where
+      W: std::fmt::Write,
+    {
+      dest.write_str(name)?;
+      dest.write_char('(')?;
+      let mut first = true;
+      for ident in idents {
+        if first {
+          first = false;
+        } else {
+          dest.delim(',', false)?;
+        }
+        serialize_identifier(&ident.0, dest)?;
+      }
+      dest.write_char(')')
+    }
+
+    let mut needs_space = false;
+    if self.historical_forms {
+      write_keywords!(dest, needs_space, dest.write_str("historical-forms")?);
+    }
+    if let Some(val) = &self.stylistic {
+      write_keywords!(
+        dest,
+        needs_space,
+        write_function(dest, "stylistic", std::slice::from_ref(val))?
+      );
+    }
+    if let Some(val) = &self.styleset {
+      write_keywords!(dest, needs_space, write_function(dest, "styleset", val)?);
+    }
+    if let Some(val) = &self.character_variant {
+      write_keywords!(dest, needs_space, write_function(dest, "character-variant", val)?);
+    }
+    if let Some(val) = &self.swash {
+      write_keywords!(
+        dest,
+        needs_space,
+        write_function(dest, "swash", std::slice::from_ref(val))?
+      );
+    }
+    if let Some(val) = &self.ornaments {
+      write_keywords!(
+        dest,
+        needs_space,
+        write_function(dest, "ornaments", std::slice::from_ref(val))?
+      );
+    }
+    if let Some(val) = &self.annotation {
+      write_keywords!(
+        dest,
+        needs_space,
+        write_function(dest, "annotation", std::slice::from_ref(val))?
+      );
+    }
+    Ok(())
+  }
+}
+
+enum_property! {
+  /// A value for the [font-variant-position](https://www.w3.org/TR/css-fonts-4/#font-variant-position-prop) property.
+  pub enum FontVariantPosition {
+    /// No subscript or superscript glyphs are used.
+    "normal": Normal,
+    /// Subscript glyphs are used.
+    "sub": Sub,
+    /// Superscript glyphs are used.
+    "super": Super,
+  }
+}
+
+impl Default for FontVariantPosition {
+  fn default() -> FontVariantPosition {
+    FontVariantPosition::Normal
+  }
+}
+
+/// A value for the [font-variant](https://www.w3.org/TR/css-fonts-4/#font-variant-prop) shorthand property.
+///
+/// The `none` keyword only affects ligatures, so when `ligatures` is `none`, all other
+/// values must be `normal` for the value to be representable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontVariant<'i> {
+  /// The font variant ligatures.
+  pub ligatures: FontVariantLigatures,
+  /// The font variant caps.
+  pub caps: FontVariantCaps,
+  /// The font variant alternates.
+  pub alternates: FontVariantAlternates<'i>,
+  /// The font variant numeric.
+  pub numeric: FontVariantNumeric,
+  /// The font variant east asian.
+  pub east_asian: FontVariantEastAsian,
+  /// The font variant position.
+  pub position: FontVariantPosition,
+}
+
+impl<'i> Default for FontVariant<'i> {
+  fn default() -> FontVariant<'i> {
+    FontVariant {
+      ligatures: FontVariantLigatures::default(),
+      caps: FontVariantCaps::Normal,
+      alternates: FontVariantAlternates::default(),
+      numeric: FontVariantNumeric::default(),
+      east_asian: FontVariantEastAsian::default(),
+      position: FontVariantPosition::default(),
+    }
+  }
+}
+
+impl<'i> FontVariant<'i> {
+  /// Returns whether all values other than ligatures are `normal`.
+  fn is_normal_except_ligatures(&self) -> bool {
+    self.caps == FontVariantCaps::Normal
+      && self.alternates.is_normal()
+      && self.numeric.is_normal()
+      && self.east_asian.is_normal()
+      && self.position == FontVariantPosition::Normal
+  }
+}
+
+impl<'i> Parse<'i> for FontVariant<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut result = FontVariant::default();
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(result);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      result.ligatures.none = true;
+      return Ok(result);
+    }
+
+    let mut caps = None;
+    let mut position = None;
+    loop {
+      if result.ligatures.try_parse_item(input)
+        || result.alternates.try_parse_item(input)
+        || result.numeric.try_parse_item(input)
+        || result.east_asian.try_parse_item(input)
+      {
+        continue;
+      }
+
+      // The `normal` keyword is only valid on its own.
+      if caps.is_none() {
+        if let Ok(value) = input.try_parse(FontVariantCaps::parse) {
+          if value == FontVariantCaps::Normal {
+            return Err(input.new_custom_error(ParserError::InvalidValue));
+          }
+          caps = Some(value);
+          continue;
+        }
+      }
+
+      if position.is_none() {
+        if let Ok(value) = input.try_parse(FontVariantPosition::parse) {
+          if value == FontVariantPosition::Normal {
+            return Err(input.new_custom_error(ParserError::InvalidValue));
+          }
+          position = Some(value);
+          continue;
+        }
+      }
+
+      break;
+    }
+
+    result.caps = caps.unwrap_or(FontVariantCaps::Normal);
+    result.position = position.unwrap_or_default();
+    if result == FontVariant::default() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(result)
+  }
+}
+
+impl<'i> ToCss for FontVariant<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.ligatures.none {
+      return dest.write_str("none");
+    }
+
+    if *self == FontVariant::default() {
+      return dest.write_str("normal");
+    }
+
+    let mut needs_space = false;
+    if !self.ligatures.is_normal() {
+      write_keywords!(dest, needs_space, self.ligatures.to_css(dest)?);
+    }
+    if self.caps != FontVariantCaps::Normal {
+      write_keywords!(dest, needs_space, self.caps.to_css(dest)?);
+    }
+    if !self.alternates.is_normal() {
+      write_keywords!(dest, needs_space, self.alternates.to_css(dest)?);
+    }
+    if !self.numeric.is_normal() {
+      write_keywords!(dest, needs_space, self.numeric.to_css(dest)?);
+    }
+    if !self.east_asian.is_normal() {
+      write_keywords!(dest, needs_space, self.east_asian.to_css(dest)?);
+    }
+    if self.position != FontVariantPosition::Normal {
+      write_keywords!(dest, needs_space, self.position.to_css(dest)?);
+    }
+    Ok(())
+  }
+}
+
 /// A value for the [line-height](https://www.w3.org/TR/2020/WD-css-inline-3-20200827/#propdef-line-height) property.
 #[derive(Debug, Clone, PartialEq)]
 pub enum LineHeight {
@@ -759,6 +1455,11 @@ pub(crate) struct FontHandler<'i> {
   stretch: Option<FontStretch>,
   line_height: Option<LineHeight>,
   variant_caps: Option<FontVariantCaps>,
+  variant_ligatures: Option<FontVariantLigatures>,
+  variant_alternates: Option<FontVariantAlternates<'i>>,
+  variant_numeric: Option<FontVariantNumeric>,
+  variant_east_asian: Option<FontVariantEastAsian>,
+  variant_position: Option<FontVariantPosition>,
   has_any: bool,
 }
 
@@ -785,6 +1486,20 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
       FontWeight(val) => property!(weight, val),
       FontStretch(val) => property!(stretch, val),
       FontVariantCaps(val) => property!(variant_caps, val),
+      FontVariantLigatures(val) => property!(variant_ligatures, val),
+      FontVariantAlternates(val) => property!(variant_alternates, val),
+      FontVariantNumeric(val) => property!(variant_numeric, val),
+      FontVariantEastAsian(val) => property!(variant_east_asian, val),
+      FontVariantPosition(val) => property!(variant_position, val),
+      FontVariant(val) => {
+        self.variant_caps = Some(val.caps.clone());
+        self.variant_ligatures = Some(val.ligatures.clone());
+        self.variant_alternates = Some(val.alternates.clone());
+        self.variant_numeric = Some(val.numeric.clone());
+        self.variant_east_asian = Some(val.east_asian.clone());
+        self.variant_position = Some(val.position.clone());
+        self.has_any = true;
+      }
       LineHeight(val) => property!(line_height, val),
       Font(val) => {
         self.family = Some(val.family.clone());
@@ -794,6 +1509,12 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
         self.stretch = Some(val.stretch.clone());
         self.line_height = Some(val.line_height.clone());
         self.variant_caps = Some(val.variant_caps.to_font_variant_caps());
+        // The font shorthand resets the other font-variant properties to their initial values.
+        self.variant_ligatures = None;
+        self.variant_alternates = None;
+        self.variant_numeric = None;
+        self.variant_east_asian = None;
+        self.variant_position = None;
         self.has_any = true;
         // TODO: reset other properties
       }
@@ -821,6 +1542,11 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
     let stretch = std::mem::take(&mut self.stretch);
     let line_height = std::mem::take(&mut self.line_height);
     let variant_caps = std::mem::take(&mut self.variant_caps);
+    let variant_ligatures = std::mem::take(&mut self.variant_ligatures);
+    let variant_alternates = std::mem::take(&mut self.variant_alternates);
+    let variant_numeric = std::mem::take(&mut self.variant_numeric);
+    let variant_east_asian = std::mem::take(&mut self.variant_east_asian);
+    let variant_position = std::mem::take(&mut self.variant_position);
 
     if family.is_some()
       && size.is_some()
@@ -846,6 +1572,28 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
       if caps == None {
         decls.push(Property::FontVariantCaps(variant_caps.unwrap()))
       }
+
+      // The `font` property also resets the other font-variant properties to normal,
+      // so only non-normal values need to be added.
+      if let Some(val) = variant_ligatures.filter(|v| !v.is_normal()) {
+        decls.push(Property::FontVariantLigatures(val))
+      }
+
+      if let Some(val) = variant_alternates.filter(|v| !v.is_normal()) {
+        decls.push(Property::FontVariantAlternates(val))
+      }
+
+      if let Some(val) = variant_numeric.filter(|v| !v.is_normal()) {
+        decls.push(Property::FontVariantNumeric(val))
+      }
+
+      if let Some(val) = variant_east_asian.filter(|v| !v.is_normal()) {
+        decls.push(Property::FontVariantEastAsian(val))
+      }
+
+      if let Some(val) = variant_position.filter(|v| *v != FontVariantPosition::Normal) {
+        decls.push(Property::FontVariantPosition(val))
+      }
     } else {
       if let Some(val) = family {
         decls.push(Property::FontFamily(val))
@@ -859,8 +1607,48 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
         decls.push(Property::FontStyle(val))
       }
 
-      if let Some(val) = variant_caps {
-        decls.push(Property::FontVariantCaps(val))
+      if variant_caps.is_some()
+        && variant_ligatures.is_some()
+        && variant_alternates.is_some()
+        && variant_numeric.is_some()
+        && variant_east_asian.is_some()
+        && variant_position.is_some()
+      {
+        push_font_variant(
+          decls,
+          FontVariant {
+            ligatures: variant_ligatures.unwrap(),
+            caps: variant_caps.unwrap(),
+            alternates: variant_alternates.unwrap(),
+            numeric: variant_numeric.unwrap(),
+            east_asian: variant_east_asian.unwrap(),
+            position: variant_position.unwrap(),
+          },
+        );
+      } else {
+        if let Some(val) = variant_ligatures {
+          decls.push(Property::FontVariantLigatures(val))
+        }
+
+        if let Some(val) = variant_caps {
+          decls.push(Property::FontVariantCaps(val))
+        }
+
+        if let Some(val) = variant_alternates {
+          decls.push(Property::FontVariantAlternates(val))
+        }
+
+        if let Some(val) = variant_numeric {
+          decls.push(Property::FontVariantNumeric(val))
+        }
+
+        if let Some(val) = variant_east_asian {
+          decls.push(Property::FontVariantEastAsian(val))
+        }
+
+        if let Some(val) = variant_position {
+          decls.push(Property::FontVariantPosition(val))
+        }
       }
 
       if let Some(val) = weight {
@@ -878,6 +1666,40 @@ impl<'i> PropertyHandler<'i> for FontHandler<'i> {
   }
 }
 
+/// Adds a `font-variant` shorthand, or splits it into longhands when ligatures are disabled
+/// along with other non-normal values, which the `none` keyword cannot express.
+fn push_font_variant<'i>(decls: &mut DeclarationList<'i>, variant: FontVariant<'i>) {
+  if !variant.ligatures.none || variant.is_normal_except_ligatures() {
+    decls.push(Property::FontVariant(variant));
+    return;
+  }
+
+  decls.push(Property::FontVariant(FontVariant {
+    ligatures: variant.ligatures,
+    ..FontVariant::default()
+  }));
+
+  if variant.caps != FontVariantCaps::Normal {
+    decls.push(Property::FontVariantCaps(variant.caps))
+  }
+
+  if !variant.alternates.is_normal() {
+    decls.push(Property::FontVariantAlternates(variant.alternates))
+  }
+
+  if !variant.numeric.is_normal() {
+    decls.push(Property::FontVariantNumeric(variant.numeric))
+  }
+
+  if !variant.east_asian.is_normal() {
+    decls.push(Property::FontVariantEastAsian(variant.east_asian))
+  }
+
+  if variant.position != FontVariantPosition::Normal {
+    decls.push(Property::FontVariantPosition(variant.position))
+  }
+}
+
 #[inline]
 fn is_font_property(property_id: &PropertyId) -> bool {
   match property_id {
@@ -887,6 +1709,12 @@ fn is_font_property(property_id: &PropertyId) -> bool {
     | PropertyId::FontWeight
     | PropertyId::FontStretch
     | PropertyId::FontVariantCaps
+    | PropertyId::FontVariantLigatures
+    | PropertyId::FontVariantAlternates
+    | PropertyId::FontVariantNumeric
+    | PropertyId::FontVariantEastAsian
+    | PropertyId::FontVariantPosition
+    | PropertyId::FontVariant
     | PropertyId::LineHeight
     | PropertyId::Font => true,
     _ => false,
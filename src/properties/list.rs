@@ -4,10 +4,11 @@ use super::{Property, PropertyId};
 use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
 use crate::error::{ParserError, PrinterError};
-use crate::macros::{enum_property, shorthand_handler, shorthand_property};
+use crate::macros::{enum_property, shorthand_handler};
 use crate::printer::Printer;
 use crate::targets::Browsers;
 use crate::traits::{FallbackValues, Parse, PropertyHandler, ToCss};
+use crate::values::number::CSSInteger;
 use crate::values::string::CowArcStr;
 use crate::values::{ident::CustomIdent, image::Image};
 use cssparser::*;
@@ -277,15 +278,316 @@ enum_property! {
   }
 }
 
-shorthand_property! {
-  /// A value for the [list-style](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#list-style-property) shorthand property.
-  pub struct ListStyle<'i> {
-    /// The list style type.
-    list_style_type: ListStyleType<'i>,
-    /// The list marker image.
-    image: Image<'i>,
-    /// The position of the list marker.
-    position: ListStylePosition,
+/// A value for the [quotes](https://www.w3.org/TR/2019/WD-css-content-3-20190802/#quotes) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Quotes<'i> {
+  /// Quotes appropriate for the content language are used.
+  Auto,
+  /// No quotes are generated.
+  None,
+  /// Pairs of open and close quotes. The first pair is used for the outermost level of nesting,
+  /// the second for the next level, and so on.
+  Pairs(Vec<(CowArcStr<'i>, CowArcStr<'i>)>),
+}
+
+impl<'i> Parse<'i> for Quotes<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(Quotes::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(Quotes::None);
+    }
+
+    let mut pairs = Vec::new();
+    loop {
+      let open = match input.try_parse(|input| input.expect_string_cloned()) {
+        Ok(open) => open,
+        Err(_) if !pairs.is_empty() => break,
+        Err(e) => return Err(e.into()),
+      };
+      let close = input.expect_string_cloned()?;
+      pairs.push((open.into(), close.into()));
+    }
+
+    Ok(Quotes::Pairs(pairs))
+  }
+}
+
+impl<'i> ToCss for Quotes<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      Quotes::Auto => dest.write_str("auto"),
+      Quotes::None => dest.write_str("none"),
+      Quotes::Pairs(pairs) => {
+        let mut first = true;
+        for (open, close) in pairs {
+          // Strings are self-delimiting, so no whitespace is needed between them when minifying.
+          if first {
+            first = false;
+          } else {
+            dest.whitespace()?;
+          }
+          serialize_string(&open, dest)?;
+          dest.whitespace()?;
+          serialize_string(&close, dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+/// A counter name with an optional integer, as used in the [counter-reset](CounterReset),
+/// [counter-increment](CounterList), and [counter-set](CounterList) properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counter<'i> {
+  /// The name of the counter.
+  pub name: CustomIdent<'i>,
+  /// The integer value. If omitted, the default for the property is used.
+  pub value: Option<CSSInteger>,
+  /// Whether this is a [reversed counter](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#reversed-counter),
+  /// e.g. `reversed(foo)`. Only allowed in `counter-reset`.
+  pub reversed: bool,
+}
+
+impl<'i> ToCss for Counter<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    // Counter names are not scoped in CSS modules, since references in `counter()` functions are not either.
+    if self.reversed {
+      dest.write_str("reversed(")?;
+      serialize_identifier(&self.name.0, dest)?;
+      dest.write_char(')')?;
+    } else {
+      serialize_identifier(&self.name.0, dest)?;
+    }
+
+    if let Some(value) = self.value {
+      dest.write_char(' ')?;
+      value.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+/// A value for the [counter-increment](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#increment-set)
+/// and [counter-set](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#increment-set) properties.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CounterList<'i> {
+  /// No counters are affected.
+  None,
+  /// A list of counters.
+  Counters(Vec<Counter<'i>>),
+}
+
+impl<'i> Parse<'i> for CounterList<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    Self::parse_counters(input, false)
+  }
+}
+
+impl<'i> CounterList<'i> {
+  fn parse_counters<'t>(
+    input: &mut Parser<'i, 't>,
+    allow_reversed: bool,
+  ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(CounterList::None);
+    }
+
+    let mut counters = Vec::new();
+    loop {
+      let reversed = allow_reversed && input.try_parse(|input| input.expect_function_matching("reversed")).is_ok();
+      let name = if reversed {
+        input.parse_nested_block(CustomIdent::parse)?
+      } else {
+        match input.try_parse(CustomIdent::parse) {
+          Ok(name) => name,
+          Err(_) if !counters.is_empty() => break,
+          Err(e) => return Err(e),
+        }
+      };
+
+      if name.0.eq_ignore_ascii_case("none") {
+        return Err(input.new_custom_error(ParserError::InvalidValue));
+      }
+
+      let value = input.try_parse(CSSInteger::parse).ok();
+      counters.push(Counter { name, value, reversed });
+    }
+
+    Ok(CounterList::Counters(counters))
+  }
+
+  /// Omits integers that are equal to the given default value, except for reversed counters
+  /// whose initial value depends on the number of list items when omitted.
+  fn omit_default(&self, default: CSSInteger) -> CounterList<'i> {
+    match self {
+      CounterList::None => CounterList::None,
+      CounterList::Counters(counters) => CounterList::Counters(
+        counters
+          .iter()
+          .map(|counter| Counter {
+            name: counter.name.clone(),
+            value: counter.value.filter(|v| counter.reversed || *v != default),
+            reversed: counter.reversed,
+          })
+          .collect(),
+      ),
+    }
+  }
+}
+
+impl<'i> ToCss for CounterList<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      CounterList::None => dest.write_str("none"),
+      CounterList::Counters(counters) => {
+        let mut first = true;
+        for counter in counters {
+          if first {
+            first = false;
+          } else {
+            dest.write_char(' ')?;
+          }
+          counter.to_css(dest)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+/// A value for the [counter-reset](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#counter-reset) property,
+/// which also accepts [reversed](Counter::reversed) counters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterReset<'i>(pub CounterList<'i>);
+
+impl<'i> Parse<'i> for CounterReset<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    Ok(CounterReset(CounterList::parse_counters(input, true)?))
+  }
+}
+
+impl<'i> ToCss for CounterReset<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.0.to_css(dest)
+  }
+}
+
+/// A value for the [list-style](https://www.w3.org/TR/2020/WD-css-lists-3-20201117/#list-style-property) shorthand property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListStyle<'i> {
+  /// The list style type.
+  pub list_style_type: ListStyleType<'i>,
+  /// The list marker image.
+  pub image: Image<'i>,
+  /// The position of the list marker.
+  pub position: ListStylePosition,
+}
+
+impl<'i> Parse<'i> for ListStyle<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    // The `none` keyword is valid for both the type and the image, so it is parsed separately
+    // and assigned to whichever of them is not otherwise specified.
+    let mut nones = 0;
+    let mut list_style_type = None;
+    let mut image = None;
+    let mut position = None;
+    loop {
+      if nones < 2 && input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        nones += 1;
+        continue;
+      }
+
+      // The position keywords are also valid counter style names, so they are parsed first.
+      if position.is_none() {
+        if let Ok(val) = input.try_parse(ListStylePosition::parse) {
+          position = Some(val);
+          continue;
+        }
+      }
+
+      if list_style_type.is_none() {
+        if let Ok(val) = input.try_parse(ListStyleType::parse) {
+          list_style_type = Some(val);
+          continue;
+        }
+      }
+
+      if image.is_none() {
+        if let Ok(val) = input.try_parse(Image::parse) {
+          image = Some(val);
+          continue;
+        }
+      }
+
+      break;
+    }
+
+    let (list_style_type, image) = match (nones, list_style_type, image) {
+      (0, list_style_type, image) => (list_style_type.unwrap_or_default(), image.unwrap_or_default()),
+      // A single `none` applies to both the type and the image, unless one of them is specified.
+      (1, None, None) => (ListStyleType::None, Image::None),
+      (1, None, Some(image)) => (ListStyleType::None, image),
+      (1, Some(list_style_type), None) => (list_style_type, Image::None),
+      (2, None, None) => (ListStyleType::None, Image::None),
+      _ => return Err(input.new_custom_error(ParserError::InvalidValue)),
+    };
+
+    Ok(ListStyle {
+      list_style_type,
+      image,
+      position: position.unwrap_or_default(),
+    })
+  }
+}
+
+impl<'i> ToCss for ListStyle<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    let mut needs_space = false;
+    if self.list_style_type != ListStyleType::default() {
+      self.list_style_type.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.image != Image::default() {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.image.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if self.position != ListStylePosition::default() {
+      if needs_space {
+        dest.write_char(' ')?;
+      }
+      self.position.to_css(dest)?;
+      needs_space = true;
+    }
+
+    if !needs_space {
+      self.list_style_type.to_css(dest)?;
+    }
+
+    Ok(())
   }
 }
 
@@ -305,3 +607,29 @@ shorthand_handler!(ListStyleHandler -> ListStyle<'i> {
   image: ListStyleImage(Image<'i>, fallback: true),
   position: ListStylePosition(ListStylePosition),
 });
+
+/// Omits the default integers of the counter properties, i.e. `1` for `counter-increment`,
+/// and `0` for `counter-reset` and `counter-set`.
+#[derive(Default)]
+pub(crate) struct CounterHandler;
+
+impl<'i> PropertyHandler<'i> for CounterHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    _: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    let property = match property {
+      Property::CounterIncrement(val) => Property::CounterIncrement(val.omit_default(1)),
+      Property::CounterReset(val) => Property::CounterReset(CounterReset(val.0.omit_default(0))),
+      Property::CounterSet(val) => Property::CounterSet(val.omit_default(0)),
+      _ => return false,
+    };
+
+    dest.push(property);
+    true
+  }
+
+  fn finalize(&mut self, _: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {}
+}
@@ -3,6 +3,8 @@ use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
 use crate::logical::PropertyCategory;
 use crate::properties::{Property, PropertyId};
+use crate::rules::supports::SupportsCondition;
+use crate::stylesheet::LogicalFallback;
 use crate::traits::PropertyHandler;
 use crate::values::{length::LengthPercentageOrAuto, rect::Rect, size::Size2D};
 
@@ -26,10 +28,13 @@ macro_rules! side_handler {
       fn handle_property(&mut self, property: &Property<'i>, dest: &mut DeclarationList<'i>, context: &mut PropertyHandlerContext<'i>) -> bool {
         use Property::*;
 
+        // Properties moved into @supports rules are printed after all other declarations of the rule, so they
+        // would override any overlapping properties declared after them. Flushing before another property of
+        // this handler therefore always converts them.
         macro_rules! property {
           ($key: ident, $val: ident, $category: ident) => {{
             if PropertyCategory::$category != self.category {
-              self.flush(dest, context);
+              self.flush(dest, context, LogicalFallback::Convert);
             }
             self.$key = Some($val.clone());
             self.category = PropertyCategory::$category;
@@ -40,7 +45,7 @@ macro_rules! side_handler {
         macro_rules! logical_property {
           ($prop: ident, $val: expr) => {{
             if self.category != PropertyCategory::Logical {
-              self.flush(dest, context);
+              self.flush(dest, context, LogicalFallback::Convert);
             }
 
             self.$prop = Some($val);
@@ -87,7 +92,7 @@ macro_rules! side_handler {
               PropertyId::$inline_start => logical_property!(inline_start, property.clone()),
               PropertyId::$inline_end => logical_property!(inline_end, property.clone()),
               _ => {
-                self.flush(dest, context);
+                self.flush(dest, context, LogicalFallback::Convert);
                 dest.push(property.clone());
               }
             }
@@ -99,12 +104,13 @@ macro_rules! side_handler {
       }
 
       fn finalize(&mut self, dest: &mut DeclarationList<'i>, context: &mut PropertyHandlerContext<'i>) {
-        self.flush(dest, context);
+        let fallback = context.logical_fallback;
+        self.flush(dest, context, fallback);
       }
     }
 
     impl<'i> $name<'i> {
-      fn flush(&mut self, dest: &mut DeclarationList<'i>, context: &mut PropertyHandlerContext<'i>) {
+      fn flush(&mut self, dest: &mut DeclarationList<'i>, context: &mut PropertyHandlerContext<'i>, fallback: LogicalFallback) {
         use Property::*;
 
         if !self.has_any {
@@ -146,50 +152,76 @@ macro_rules! side_handler {
         let inline_end = std::mem::take(&mut self.inline_end);
 
         macro_rules! logical_side {
-          ($start: ident, $end: ident, $shorthand_prop: ident, $start_prop: ident, $end_prop: ident) => {
-            if let (Some(Property::$start_prop(start)), Some(Property::$end_prop(end))) = (&$start, &$end) {
-              let size = Size2D(start.clone(), end.clone());
-              dest.push($shorthand_prop(size));
-            } else {
-              if let Some(val) = $start {
-                dest.push(val);
+          ($dest: ident, $start: expr, $end: expr, $shorthand_prop: ident, $start_prop: ident, $end_prop: ident) => {
+            match ($start, $end) {
+              (Some(Property::$start_prop(start)), Some(Property::$end_prop(end))) => {
+                $dest.push($shorthand_prop(Size2D(start, end)));
               }
+              (start, end) => {
+                if let Some(val) = start {
+                  $dest.push(val);
+                }
 
-              if let Some(val) = $end {
-                dest.push(val);
+                if let Some(val) = end {
+                  $dest.push(val);
+                }
               }
             }
           };
         }
 
+        if logical_supported {
+          logical_side!(dest, block_start, block_end, $block_shorthand, $block_start, $block_end);
+          logical_side!(dest, inline_start, inline_end, $inline_shorthand, $inline_start, $inline_end);
+          return
+        }
+
+        // Depending on the strategy, the logical properties are also emitted for browsers that support
+        // them, and either the logical or physical properties are guarded by @supports. Each condition
+        // results in a single @supports rule per style rule, no matter how many properties it contains.
+        let condition = logical_supports_condition(PropertyId::$inline_start);
+        match fallback {
+          LogicalFallback::Convert => {}
+          LogicalFallback::PhysicalWithSupports => {
+            let mut logical = DeclarationList::new();
+            logical_side!(logical, block_start.clone(), block_end.clone(), $block_shorthand, $block_start, $block_end);
+            logical_side!(logical, inline_start.clone(), inline_end.clone(), $inline_shorthand, $inline_start, $inline_end);
+            for property in logical {
+              context.add_conditional_property(condition.clone(), property);
+            }
+          }
+          LogicalFallback::LogicalWithSupports => {
+            logical_side!(dest, block_start.clone(), block_end.clone(), $block_shorthand, $block_start, $block_end);
+            logical_side!(dest, inline_start.clone(), inline_end.clone(), $inline_shorthand, $inline_start, $inline_end);
+          }
+        }
+
+        let mut physical = DeclarationList::new();
+
         macro_rules! prop {
           ($val: ident, $logical: ident, $physical: ident) => {
             match $val {
               Some(Property::$logical(val)) => {
-                dest.push(Property::$physical(val));
+                physical.push(Property::$physical(val));
               }
               Some(Property::Unparsed(val)) => {
-                dest.push(Property::Unparsed(val.with_property_id(PropertyId::$physical)));
+                physical.push(Property::Unparsed(val.with_property_id(PropertyId::$physical)));
               }
               _ => {}
             }
           }
         }
 
-        if logical_supported {
-          logical_side!(block_start, block_end, $block_shorthand, $block_start, $block_end);
-        } else {
-          prop!(block_start, $block_start, $top);
-          prop!(block_end, $block_end, $bottom);
-        }
+        prop!(block_start, $block_start, $top);
+        prop!(block_end, $block_end, $bottom);
 
-        if logical_supported {
-          logical_side!(inline_start, inline_end, $inline_shorthand, $inline_start, $inline_end);
-        } else if inline_start.is_some() || inline_end.is_some() {
+        if inline_start.is_some() || inline_end.is_some() {
           if matches!((&inline_start, &inline_end), (Some(Property::$inline_start(start)), Some(Property::$inline_end(end))) if start == end) {
             prop!(inline_start, $inline_start, $left);
             prop!(inline_end, $inline_end, $right);
           } else {
+            // Values that depend on the direction are always set via :dir() rules, since those
+            // cannot be guarded by @supports. They are equivalent to the logical properties.
             macro_rules! logical_prop {
               ($val: ident, $logical: ident, $ltr: ident, $rtl: ident) => {
                 match $val {
@@ -214,11 +246,32 @@ macro_rules! side_handler {
             logical_prop!(inline_end, $inline_end, $right, $left);
           }
         }
+
+        if fallback == LogicalFallback::LogicalWithSupports {
+          let condition = SupportsCondition::Not(Box::new(condition));
+          for property in physical {
+            context.add_conditional_property(condition.clone(), property);
+          }
+        } else {
+          dest.extend(physical);
+        }
       }
     }
   };
 }
 
+/// Returns the @supports condition that detects support for logical properties, given the inline start property.
+fn logical_supports_condition<'i>(property_id: PropertyId) -> SupportsCondition<'i> {
+  // Logical margins and paddings are supported by the same browsers, so they share a condition,
+  // and therefore a single @supports rule.
+  let property_id = match property_id {
+    PropertyId::PaddingInlineStart => PropertyId::MarginInlineStart,
+    property_id => property_id,
+  };
+
+  SupportsCondition::Declaration(format!("{}: 0", property_id.name()).into())
+}
+
 side_handler!(
   MarginHandler,
   MarginTop,
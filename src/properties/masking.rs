@@ -2,6 +2,7 @@
 
 use super::background::{BackgroundRepeat, BackgroundSize};
 use super::border_image::{BorderImage, BorderImageRepeat, BorderImageSideWidth, BorderImageSlice};
+use super::motion::PathFunction;
 use super::PropertyId;
 use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
@@ -309,7 +310,10 @@ impl<'i> ToCss for Mask<'i> {
   where
     W: std::fmt::Write,
   {
-    self.image.to_css(dest)?;
+    match &self.image {
+      Image::Url(url) => url.to_css_reference(dest)?,
+      image => image.to_css(dest)?,
+    }
 
     if self.position != Position::default() || self.size != BackgroundSize::default() {
       dest.write_char(' ')?;
@@ -370,6 +374,8 @@ pub enum ClipPath<'i> {
   None,
   /// A url reference to an SVG path element.
   Url(Url<'i>),
+  /// An SVG path.
+  Path(PathFunction<'i>),
   /// A basic shape, positioned according to the reference box.
   Shape(Box<BasicShape>, GeometryBox),
   /// A reference box.
@@ -382,6 +388,10 @@ impl<'i> Parse<'i> for ClipPath<'i> {
       return Ok(ClipPath::Url(url));
     }
 
+    if let Ok(path) = input.try_parse(PathFunction::parse) {
+      return Ok(ClipPath::Path(path));
+    }
+
     if let Ok(shape) = input.try_parse(BasicShape::parse) {
       let b = input.try_parse(GeometryBox::parse).unwrap_or_default();
       return Ok(ClipPath::Shape(Box::new(shape), b));
@@ -406,7 +416,8 @@ impl<'i> ToCss for ClipPath<'i> {
   {
     match self {
       ClipPath::None => dest.write_str("none"),
-      ClipPath::Url(url) => url.to_css(dest),
+      ClipPath::Url(url) => url.to_css_reference(dest),
+      ClipPath::Path(path) => path.to_css(dest),
       ClipPath::Shape(shape, b) => {
         shape.to_css(dest)?;
         if *b != GeometryBox::default() {
@@ -86,16 +86,22 @@
 //! If a known property is unable to be parsed, e.g. it contains `var()` references, then it is represented by the
 //! [UnparsedProperty](custom::UnparsedProperty) struct, and the `Property::Unparsed` variant. The value is stored
 //! as a raw [TokenList](custom::TokenList), with a [PropertyId](PropertyId) as the name.
+//!
+//! The `revert` and `revert-layer` keywords are represented by the [WideKeywordProperty](custom::WideKeywordProperty)
+//! struct, and the `Property::WideKeyword` variant. The `all` property, which only accepts CSS-wide keywords,
+//! is represented by the `Property::All` variant.
 
 #![deny(missing_docs)]
 
 pub mod align;
 pub mod animation;
+pub mod apply;
 pub mod background;
 pub mod border;
 pub mod border_image;
 pub mod border_radius;
 pub mod box_shadow;
+pub mod contain;
 pub mod css_modules;
 pub mod custom;
 pub mod display;
@@ -107,20 +113,23 @@ pub mod grid;
 pub mod list;
 pub(crate) mod margin_padding;
 pub mod masking;
+pub mod motion;
 pub mod outline;
 pub mod overflow;
 pub mod position;
 pub(crate) mod prefix_handler;
+pub mod shape;
 pub mod size;
 pub mod svg;
 pub mod text;
 pub mod transform;
 pub mod transition;
 pub mod ui;
+pub mod writing_modes;
 
 use crate::error::{ParserError, PrinterError};
 use crate::parser::starts_with_ignore_ascii_case;
-use crate::parser::ParserOptions;
+use crate::parser::{with_max_nesting_depth, ParserOptions};
 use crate::prefixes::Feature;
 use crate::printer::{Printer, PrinterOptions};
 use crate::targets::Browsers;
@@ -139,6 +148,7 @@ use border::*;
 use border_image::*;
 use border_radius::*;
 use box_shadow::*;
+use contain::*;
 use css_modules::*;
 use cssparser::*;
 use custom::*;
@@ -150,8 +160,10 @@ use font::*;
 use grid::*;
 use list::*;
 use masking::*;
+use motion::*;
 use outline::*;
 use overflow::*;
+use shape::*;
 use size::*;
 use smallvec::{smallvec, SmallVec};
 use svg::*;
@@ -159,6 +171,7 @@ use text::*;
 use transform::*;
 use transition::*;
 use ui::*;
+use writing_modes::*;
 
 macro_rules! define_properties {
   (
@@ -168,7 +181,7 @@ macro_rules! define_properties {
     )+
   ) => {
     /// A CSS property id.
-    #[derive(Debug, Clone, PartialEq)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub enum PropertyId<'i> {
       $(
         #[doc=concat!("The `", $name, "` property.")]
@@ -294,7 +307,7 @@ macro_rules! define_properties {
     }
 
     impl<'i> PropertyId<'i> {
-      fn prefix(&self) -> VendorPrefix {
+      pub(crate) fn prefix(&self) -> VendorPrefix {
         use PropertyId::*;
         match self {
           $(
@@ -317,7 +330,7 @@ macro_rules! define_properties {
         }
       }
 
-      fn with_prefix(&self, prefix: VendorPrefix) -> PropertyId<'i> {
+      pub(crate) fn with_prefix(&self, prefix: VendorPrefix) -> PropertyId<'i> {
         use PropertyId::*;
         match self {
           $(
@@ -364,6 +377,47 @@ macro_rules! define_properties {
         }
       }
 
+      /// Returns an iterator over all known property ids, including the `all` shorthand.
+      /// Custom properties are not included.
+      ///
+      /// Properties that support vendor prefixes are returned unprefixed, unless they
+      /// are only valid with a prefix, in which case all of the allowed prefixes are set.
+      pub fn all() -> impl Iterator<Item = PropertyId<'static>> {
+        macro_rules! get_allowed_prefixes {
+          ($v: literal) => {
+            VendorPrefix::empty()
+          };
+          () => {
+            VendorPrefix::None
+          };
+        }
+
+        vec![
+          $(
+            $(#[$meta])*
+            {
+              macro_rules! get_propertyid {
+                ($v: ty) => {{
+                  let allowed_prefixes = get_allowed_prefixes!($($unprefixed)?) $(| VendorPrefix::$prefix)*;
+                  if allowed_prefixes.contains(VendorPrefix::None) {
+                    PropertyId::$property(VendorPrefix::None)
+                  } else {
+                    PropertyId::$property(allowed_prefixes)
+                  }
+                }};
+                () => {
+                  PropertyId::$property
+                };
+              }
+
+              get_propertyid!($($vp)?)
+            },
+          )+
+          PropertyId::All,
+        ]
+        .into_iter()
+      }
+
       #[allow(dead_code)]
       pub(crate) fn name(&self) -> &str {
         use PropertyId::*;
@@ -389,8 +443,14 @@ macro_rules! define_properties {
       )+
       /// An unparsed property.
       Unparsed(UnparsedProperty<'i>),
+      /// The `all` property.
+      All(CSSWideKeyword),
+      /// A known property with a CSS-wide keyword value.
+      WideKeyword(WideKeywordProperty<'i>),
       /// A custom or unknown property.
       Custom(CustomProperty<'i>),
+      /// A property along with its source text.
+      Verbatim(VerbatimProperty<'i>),
     }
 
     impl<'i> Property<'i> {
@@ -436,7 +496,17 @@ macro_rules! define_properties {
                       };
                     }
 
-                    return Ok(get_property!($($vp)?))
+                    // Values that are only valid with a vendor prefix fall through to an unparsed property.
+                    let mut property = get_property!($($vp)?);
+                    if property.is_valid_for_prefix() {
+                      if property.check_range(options).is_err()
+                        || (options.strict && property.has_unknown_unprefixed_transition_property())
+                      {
+                        return Err(input.new_custom_error(ParserError::InvalidValue))
+                      }
+
+                      return Ok(property)
+                    }
                   }
                 }
 
@@ -455,9 +525,24 @@ macro_rules! define_properties {
               }
             },
           )+
+          "all" if prefix == VendorPrefix::None => {
+            if let Ok(keyword) = input.try_parse(CSSWideKeyword::parse) {
+              if input.expect_exhausted().is_ok() {
+                return Ok(Property::All(keyword))
+              }
+            }
+
+            PropertyId::All
+          },
           _ => return Ok(Property::Custom(CustomProperty::parse(name, input)?))
         };
 
+        // CSS-wide keywords that the property's value type does not handle itself.
+        input.reset(&state);
+        if let Ok(wide) = input.try_parse(|input| WideKeywordProperty::parse(property_id.clone(), input)) {
+          return Ok(Property::WideKeyword(wide))
+        }
+
         // If a value was unable to be parsed, treat as an unparsed property.
         // This is different from a custom property, handled below, in that the property name is known
         // and stored as an enum rather than a string. This lets property handlers more easily deal with it.
@@ -466,6 +551,23 @@ macro_rules! define_properties {
         return Ok(Property::Unparsed(UnparsedProperty::parse(property_id, input)?))
       }
 
+      /// Returns the id of the property.
+      pub fn property_id(&self) -> PropertyId<'i> {
+        use Property::*;
+
+        match self {
+          $(
+            $(#[$meta])*
+            $property(_, $(vp_name!($vp, p))?) => PropertyId::$property$((*vp_name!($vp, p)))?,
+          )+
+          Unparsed(unparsed) => unparsed.property_id.clone(),
+          All(_) => PropertyId::All,
+          WideKeyword(wide) => wide.property_id.clone(),
+          Custom(custom) => PropertyId::Custom(custom.name.clone()),
+          Verbatim(verbatim) => verbatim.property.property_id(),
+        }
+      }
+
       #[allow(dead_code)]
       pub(crate) fn name(&self) -> &str {
         use Property::*;
@@ -476,7 +578,10 @@ macro_rules! define_properties {
             $property(_, $(vp_name!($vp, _p))?) => $name,
           )+
           Unparsed(unparsed) => unparsed.property_id.name(),
+          All(_) => "all",
+          WideKeyword(wide) => wide.property_id.name(),
           Custom(custom) => &custom.name,
+          Verbatim(verbatim) => verbatim.property.name(),
         }
       }
 
@@ -484,7 +589,17 @@ macro_rules! define_properties {
       pub fn parse_string(name: &'i str, input: &'i str, options: ParserOptions) -> Result<Self, ParseError<'i, ParserError<'i>>> {
         let mut input = ParserInput::new(input);
         let mut parser = Parser::new(&mut input);
-        Self::parse(CowRcStr::from(name), &mut parser, &options)
+        with_max_nesting_depth(options.max_nesting_depth, || Self::parse(CowRcStr::from(name), &mut parser, &options))
+      }
+
+      /// Returns whether this property sets the same property to the same value as another.
+      /// The `!important` flag is not part of a property, so declarations from the normal and
+      /// important lists of a [DeclarationBlock](crate::declaration::DeclarationBlock) may be compared.
+      /// Unparsed and custom values are compared by their property and tokens, and urls are compared
+      /// by value regardless of their source locations. [Verbatim](Property::Verbatim) properties are
+      /// compared by their parsed property, ignoring how they were written.
+      pub fn same_value(&self, other: &Property<'i>) -> bool {
+        self.unwrap_verbatim() == other.unwrap_verbatim()
       }
 
       /// Serializes the value of a CSS property without its name or `!important` flag.
@@ -501,9 +616,22 @@ macro_rules! define_properties {
           Unparsed(unparsed) => {
             unparsed.value.to_css(dest, false)
           }
+          All(keyword) => {
+            keyword.to_css(dest)
+          }
+          WideKeyword(wide) => {
+            wide.keyword.to_css(dest)
+          }
           Custom(custom) => {
             custom.value.to_css(dest, custom.name.starts_with("--"))
           }
+          Verbatim(verbatim) => {
+            if dest.prints_raw_values() {
+              dest.write_str(&verbatim.value)
+            } else {
+              verbatim.property.value_to_css(dest)
+            }
+          }
         }
       }
 
@@ -550,6 +678,8 @@ macro_rules! define_properties {
             },
           )+
           Unparsed(unparsed) => (unparsed.property_id.name(), unparsed.property_id.prefix()),
+          All(_) => ("all", VendorPrefix::None),
+          WideKeyword(wide) => (wide.property_id.name(), wide.property_id.prefix()),
           Custom(custom) => {
             // Ensure custom property names are escaped.
             serialize_name(custom.name.as_ref(), dest)?;
@@ -558,6 +688,17 @@ macro_rules! define_properties {
             write_important!();
             return Ok(())
           }
+          Verbatim(verbatim) => {
+            if !dest.prints_raw_values() {
+              return verbatim.property.to_css(dest, important)
+            }
+
+            serialize_name(verbatim.name.as_ref(), dest)?;
+            dest.delim(':', false)?;
+            dest.write_str(&verbatim.value)?;
+            write_important!();
+            return Ok(())
+          }
         };
 
         macro_rules! write {
@@ -573,11 +714,18 @@ macro_rules! define_properties {
           }
         }
 
+        let convert_px = dest.convert_px;
+        dest.convert_px = match &dest.px_to_rem {
+          Some(options) => !options.exclude.iter().any(|p| p.eq_ignore_ascii_case(name)),
+          None => false,
+        };
+
         write!(VendorPrefix::WebKit);
         write!(VendorPrefix::Moz);
         write!(VendorPrefix::Ms);
         write!(VendorPrefix::O);
         write!(VendorPrefix::None);
+        dest.convert_px = convert_px;
         Ok(())
       }
 
@@ -588,6 +736,18 @@ macro_rules! define_properties {
         self.to_css(&mut printer, important)?;
         Ok(s)
       }
+
+      /// Serializes the value of the CSS property to a string, without its name or `!important` flag.
+      pub fn value_to_css_string(&self, options: PrinterOptions) -> Result<String, PrinterError> {
+        let mut s = String::new();
+        let mut printer = Printer::new(&mut s, options);
+        printer.convert_px = match &printer.px_to_rem {
+          Some(options) => !options.exclude.iter().any(|p| p.eq_ignore_ascii_case(self.name())),
+          None => false,
+        };
+        self.value_to_css(&mut printer)?;
+        Ok(s)
+      }
     }
   };
 }
@@ -625,10 +785,27 @@ define_properties! {
   "max-inline-size": MaxInlineSize(MinMaxSize),
   "box-sizing": BoxSizing(BoxSizing, VendorPrefix) / WebKit / Moz,
 
+  // https://drafts.csswg.org/css-images-3/
+  "object-fit": ObjectFit(ObjectFit, VendorPrefix) / O,
+  "object-position": ObjectPosition(Position, VendorPrefix) / O,
+
   "overflow": Overflow(Overflow),
   "overflow-x": OverflowX(OverflowKeyword),
   "overflow-y": OverflowY(OverflowKeyword),
   "text-overflow": TextOverflow(TextOverflow, VendorPrefix) / O,
+  "overscroll-behavior-x": OverscrollBehaviorX(OverscrollBehaviorKeyword),
+  "overscroll-behavior-y": OverscrollBehaviorY(OverscrollBehaviorKeyword),
+  "overscroll-behavior-inline": OverscrollBehaviorInline(OverscrollBehaviorKeyword),
+  "overscroll-behavior-block": OverscrollBehaviorBlock(OverscrollBehaviorKeyword),
+  "overscroll-behavior": OverscrollBehavior(OverscrollBehavior),
+  "line-clamp": LineClamp(LineClamp, VendorPrefix) / WebKit,
+
+  // https://drafts.csswg.org/css-contain-2/#content-visibility
+  "content-visibility": ContentVisibility(ContentVisibility),
+  // https://drafts.csswg.org/css-sizing-4/#intrinsic-size-override
+  "contain-intrinsic-width": ContainIntrinsicWidth(ContainIntrinsicLength),
+  "contain-intrinsic-height": ContainIntrinsicHeight(ContainIntrinsicLength),
+  "contain-intrinsic-size": ContainIntrinsicSize(ContainIntrinsicSize),
 
   // https://www.w3.org/TR/2020/WD-css-position-3-20200519
   "position": Position(position::Position),
@@ -851,6 +1028,12 @@ define_properties! {
   "font-family": FontFamily(Vec<FontFamily<'i>>),
   "font-style": FontStyle(FontStyle),
   "font-variant-caps": FontVariantCaps(FontVariantCaps),
+  "font-variant-ligatures": FontVariantLigatures(FontVariantLigatures),
+  "font-variant-alternates": FontVariantAlternates(FontVariantAlternates<'i>),
+  "font-variant-numeric": FontVariantNumeric(FontVariantNumeric),
+  "font-variant-east-asian": FontVariantEastAsian(FontVariantEastAsian),
+  "font-variant-position": FontVariantPosition(FontVariantPosition),
+  "font-variant": FontVariant(FontVariant<'i>),
   "line-height": LineHeight(LineHeight),
   "font": Font(Font<'i>),
   "vertical-align": VerticalAlign(VerticalAlign),
@@ -884,9 +1067,14 @@ define_properties! {
   "rotate": Rotate(Rotate),
   "scale": Scale(Scale),
 
+  // https://drafts.csswg.org/css-viewport/#zoom-property
+  "zoom": Zoom(Zoom),
+
   // https://www.w3.org/TR/2021/CRD-css-text-3-20210422
   "text-transform": TextTransform(TextTransform),
   "white-space": WhiteSpace(WhiteSpace),
+  "white-space-collapse": WhiteSpaceCollapse(WhiteSpaceCollapse),
+  "text-wrap": TextWrap(TextWrap),
   "tab-size": TabSize(LengthOrNumber, VendorPrefix) / Moz / O,
   "word-break": WordBreak(WordBreak),
   "line-break": LineBreak(LineBreak),
@@ -921,7 +1109,17 @@ define_properties! {
   "caret": Caret(Caret),
   "user-select": UserSelect(UserSelect, VendorPrefix) / WebKit / Moz / Ms,
   "accent-color": AccentColor(ColorOrAuto),
+  "color-scheme": ColorScheme(ColorScheme<'i>),
   "appearance": Appearance(Appearance<'i>, VendorPrefix) / WebKit / Moz / Ms,
+  "scrollbar-gutter": ScrollbarGutter(ScrollbarGutter),
+  "scrollbar-width": ScrollbarWidth(ScrollbarWidth),
+  "scrollbar-color": ScrollbarColor(ScrollbarColor),
+
+  // https://drafts.csswg.org/css-writing-modes-4/
+  "writing-mode": WritingMode(WritingMode, VendorPrefix) / WebKit / Ms,
+
+  // https://w3c.github.io/pointerevents/#the-touch-action-css-property
+  "touch-action": TouchAction(TouchAction, VendorPrefix) / Ms,
 
   // https://www.w3.org/TR/2020/WD-css-lists-3-20201117
   "list-style-type": ListStyleType(ListStyleType<'i>),
@@ -929,6 +1127,12 @@ define_properties! {
   "list-style-position": ListStylePosition(ListStylePosition),
   "list-style": ListStyle(ListStyle<'i>),
   "marker-side": MarkerSide(MarkerSide),
+  "counter-reset": CounterReset(CounterReset<'i>),
+  "counter-increment": CounterIncrement(CounterList<'i>),
+  "counter-set": CounterSet(CounterList<'i>),
+
+  // https://www.w3.org/TR/2019/WD-css-content-3-20190802
+  "quotes": Quotes(Quotes<'i>),
 
   // CSS modules
   "composes": Composes(Composes<'i>) if css_modules,
@@ -989,11 +1193,649 @@ define_properties! {
   "mask-box-image-outset": WebKitMaskBoxImageOutset(Rect<LengthOrNumber>, VendorPrefix) / WebKit unprefixed: false,
   "mask-box-image-repeat": WebKitMaskBoxImageRepeat(BorderImageRepeat, VendorPrefix) / WebKit unprefixed: false,
 
+  // https://www.w3.org/TR/css-shapes-1/
+  "shape-outside": ShapeOutside(ShapeOutside<'i>),
+
+  // https://drafts.fxtf.org/motion-1/
+  "offset-position": OffsetPosition(OffsetPosition),
+  "offset-path": OffsetPath(OffsetPath<'i>),
+  "offset-distance": OffsetDistance(LengthPercentage),
+  "offset-rotate": OffsetRotate(OffsetRotate),
+  "offset-anchor": OffsetAnchor(OffsetAnchor),
+  "offset": Offset(Offset<'i>),
+
   // https://drafts.fxtf.org/filter-effects-1/
   "filter": Filter(FilterList<'i>, VendorPrefix) / WebKit,
   "backdrop-filter": BackdropFilter(FilterList<'i>, VendorPrefix) / WebKit,
 }
 
+impl<'i> Property<'i> {
+  /// Returns the parsed property of a [Verbatim](Property::Verbatim) property, or the property itself otherwise.
+  pub(crate) fn unwrap_verbatim(&self) -> &Property<'i> {
+    match self {
+      Property::Verbatim(verbatim) => &verbatim.property,
+      property => property,
+    }
+  }
+
+  /// Returns whether the value is valid with the vendor prefix of the property. Some legacy values are
+  /// only valid with a prefix, e.g. `-ms-user-select: element`.
+  fn is_valid_for_prefix(&self) -> bool {
+    match self {
+      Property::UserSelect(UserSelect::Element, prefix) => *prefix == VendorPrefix::Ms,
+      _ => true,
+    }
+  }
+
+  /// Checks that the value of a property with a restricted range, such as `flex-grow`, is within that range.
+  /// Out of range values are an error in [strict](ParserOptions::strict) mode, and are otherwise clamped
+  /// if [clamp_values](ParserOptions::clamp_values) is enabled. Alpha values such as `opacity` are valid
+  /// when out of range and clamped at computed value time, so they are clamped in either mode.
+  fn check_range(&mut self, options: &ParserOptions) -> Result<(), ()> {
+    use Property::*;
+
+    if !options.strict && !options.clamp_values {
+      return Ok(());
+    }
+
+    fn check<T: PartialOrd + Copy>(value: &mut T, min: T, max: T, clamp: bool) -> Result<(), ()> {
+      if *value < min {
+        *value = min;
+      } else if *value > max {
+        *value = max;
+      } else {
+        return Ok(());
+      }
+
+      if clamp {
+        Ok(())
+      } else {
+        Err(())
+      }
+    }
+
+    let clamp = !options.strict;
+    match self {
+      // Percentages are stored as numbers, e.g. 200% is 2.
+      Opacity(AlphaValue(value)) | FillOpacity(AlphaValue(value)) | StrokeOpacity(AlphaValue(value)) => {
+        check(value, 0.0, 1.0, true)
+      }
+      FlexGrow(value, _)
+      | FlexShrink(value, _)
+      | BoxFlex(value, _)
+      | FlexPositive(value, _)
+      | FlexNegative(value, _) => check(value, 0.0, f32::INFINITY, clamp),
+      StrokeMiterlimit(value) => check(value, 1.0, f32::INFINITY, clamp),
+      BoxOrdinalGroup(value, _) | BoxFlexGroup(value, _) => check(value, 1, CSSInteger::MAX, clamp),
+      // `order` and `flex-order` accept any integer.
+      _ => Ok(()),
+    }
+  }
+
+  /// Returns the property names in a `transition-property` or `transition` value that are not
+  /// known properties, which are likely typos. `all`, `none`, and custom properties are always valid.
+  /// Vendor prefixed names are only known if the property supports that prefix.
+  fn unknown_transition_properties(&self) -> Vec<&PropertyId<'i>> {
+    let is_unknown = |property_id: &&PropertyId<'i>| match property_id {
+      PropertyId::Custom(name) => !name.starts_with("--") && !name.eq_ignore_ascii_case("none"),
+      _ => false,
+    };
+
+    match self {
+      Property::TransitionProperty(properties, _) => properties.iter().filter(is_unknown).collect(),
+      Property::Transition(transitions, _) => transitions.iter().map(|t| &t.property).filter(is_unknown).collect(),
+      _ => Vec::new(),
+    }
+  }
+
+  /// Returns the first unknown property name in a `transition-property` or `transition` value, if any.
+  pub(crate) fn unknown_transition_property(&self) -> Option<&PropertyId<'i>> {
+    self.unknown_transition_properties().into_iter().next()
+  }
+
+  /// Returns whether a `transition-property` or `transition` value contains an unknown property name
+  /// that is not vendor prefixed. These are errors in strict mode, while unknown prefixed names, which
+  /// may be supported by a browser without being known here, are only reported as warnings.
+  fn has_unknown_unprefixed_transition_property(&self) -> bool {
+    self
+      .unknown_transition_properties()
+      .iter()
+      .any(|property_id| !property_id.name().starts_with('-'))
+  }
+}
+
+impl<'i> PropertyId<'i> {
+  /// Returns the longhand properties that this property expands to, or an empty list if it is not a shorthand.
+  ///
+  /// Longhands that support vendor prefixes are returned unprefixed; the prefix of a prefixed shorthand applies
+  /// to its longhands that support it. Logical longhand properties return the physical properties they may be
+  /// compiled to, depending on the direction. The `all` shorthand is not expanded, since it sets every property
+  /// other than `direction` and `unicode-bidi`.
+  pub fn longhands(&self) -> &'static [PropertyId<'static>] {
+    use PropertyId::*;
+
+    macro_rules! longhands {
+      ($($id: expr),+ $(,)?) => {{
+        static LONGHANDS: &[PropertyId<'static>] = &[$($id),+];
+        LONGHANDS
+      }};
+    }
+
+    match self {
+      Background => longhands![
+        BackgroundColor,
+        BackgroundImage,
+        BackgroundPositionX,
+        BackgroundPositionY,
+        BackgroundSize,
+        BackgroundRepeat,
+        BackgroundAttachment,
+        BackgroundClip(VendorPrefix::None),
+        BackgroundOrigin,
+      ],
+      BackgroundPosition => longhands![BackgroundPositionX, BackgroundPositionY],
+      Overflow => longhands![OverflowX, OverflowY],
+      OverscrollBehavior => longhands![OverscrollBehaviorX, OverscrollBehaviorY],
+      OverscrollBehaviorInline | OverscrollBehaviorBlock => longhands![OverscrollBehaviorX, OverscrollBehaviorY],
+      WhiteSpace => longhands![WhiteSpaceCollapse],
+      ContainIntrinsicSize => longhands![ContainIntrinsicWidth, ContainIntrinsicHeight],
+
+      Inset => longhands![Top, Right, Bottom, Left],
+      InsetBlock => longhands![InsetBlockStart, InsetBlockEnd],
+      InsetInline => longhands![InsetInlineStart, InsetInlineEnd],
+      InsetBlockStart => longhands![Top],
+      InsetBlockEnd => longhands![Bottom],
+      InsetInlineStart | InsetInlineEnd => longhands![Left, Right],
+
+      BlockSize => longhands![Height],
+      InlineSize => longhands![Width],
+      MinBlockSize => longhands![MinHeight],
+      MinInlineSize => longhands![MinWidth],
+      MaxBlockSize => longhands![MaxHeight],
+      MaxInlineSize => longhands![MaxWidth],
+
+      Border => longhands![
+        BorderTopWidth,
+        BorderRightWidth,
+        BorderBottomWidth,
+        BorderLeftWidth,
+        BorderTopStyle,
+        BorderRightStyle,
+        BorderBottomStyle,
+        BorderLeftStyle,
+        BorderTopColor,
+        BorderRightColor,
+        BorderBottomColor,
+        BorderLeftColor,
+      ],
+      BorderColor => longhands![BorderTopColor, BorderRightColor, BorderBottomColor, BorderLeftColor],
+      BorderStyle => longhands![BorderTopStyle, BorderRightStyle, BorderBottomStyle, BorderLeftStyle],
+      BorderWidth => longhands![BorderTopWidth, BorderRightWidth, BorderBottomWidth, BorderLeftWidth],
+      BorderTop => longhands![BorderTopWidth, BorderTopStyle, BorderTopColor],
+      BorderRight => longhands![BorderRightWidth, BorderRightStyle, BorderRightColor],
+      BorderBottom => longhands![BorderBottomWidth, BorderBottomStyle, BorderBottomColor],
+      BorderLeft => longhands![BorderLeftWidth, BorderLeftStyle, BorderLeftColor],
+      BorderBlock => longhands![
+        BorderBlockStartWidth,
+        BorderBlockEndWidth,
+        BorderBlockStartStyle,
+        BorderBlockEndStyle,
+        BorderBlockStartColor,
+        BorderBlockEndColor,
+      ],
+      BorderInline => longhands![
+        BorderInlineStartWidth,
+        BorderInlineEndWidth,
+        BorderInlineStartStyle,
+        BorderInlineEndStyle,
+        BorderInlineStartColor,
+        BorderInlineEndColor,
+      ],
+      BorderBlockStart => longhands![BorderBlockStartWidth, BorderBlockStartStyle, BorderBlockStartColor],
+      BorderBlockEnd => longhands![BorderBlockEndWidth, BorderBlockEndStyle, BorderBlockEndColor],
+      BorderInlineStart => longhands![BorderInlineStartWidth, BorderInlineStartStyle, BorderInlineStartColor],
+      BorderInlineEnd => longhands![BorderInlineEndWidth, BorderInlineEndStyle, BorderInlineEndColor],
+      BorderBlockColor => longhands![BorderBlockStartColor, BorderBlockEndColor],
+      BorderBlockStyle => longhands![BorderBlockStartStyle, BorderBlockEndStyle],
+      BorderBlockWidth => longhands![BorderBlockStartWidth, BorderBlockEndWidth],
+      BorderInlineColor => longhands![BorderInlineStartColor, BorderInlineEndColor],
+      BorderInlineStyle => longhands![BorderInlineStartStyle, BorderInlineEndStyle],
+      BorderInlineWidth => longhands![BorderInlineStartWidth, BorderInlineEndWidth],
+      BorderBlockStartColor => longhands![BorderTopColor],
+      BorderBlockEndColor => longhands![BorderBottomColor],
+      BorderInlineStartColor | BorderInlineEndColor => longhands![BorderLeftColor, BorderRightColor],
+      BorderBlockStartStyle => longhands![BorderTopStyle],
+      BorderBlockEndStyle => longhands![BorderBottomStyle],
+      BorderInlineStartStyle | BorderInlineEndStyle => longhands![BorderLeftStyle, BorderRightStyle],
+      BorderBlockStartWidth => longhands![BorderTopWidth],
+      BorderBlockEndWidth => longhands![BorderBottomWidth],
+      BorderInlineStartWidth | BorderInlineEndWidth => longhands![BorderLeftWidth, BorderRightWidth],
+
+      BorderRadius(_) => longhands![
+        BorderTopLeftRadius(VendorPrefix::None),
+        BorderTopRightRadius(VendorPrefix::None),
+        BorderBottomRightRadius(VendorPrefix::None),
+        BorderBottomLeftRadius(VendorPrefix::None),
+      ],
+      BorderStartStartRadius | BorderStartEndRadius => longhands![
+        BorderTopLeftRadius(VendorPrefix::None),
+        BorderTopRightRadius(VendorPrefix::None),
+      ],
+      BorderEndStartRadius | BorderEndEndRadius => longhands![
+        BorderBottomLeftRadius(VendorPrefix::None),
+        BorderBottomRightRadius(VendorPrefix::None),
+      ],
+      BorderImage(_) => longhands![
+        BorderImageSource,
+        BorderImageSlice,
+        BorderImageWidth,
+        BorderImageOutset,
+        BorderImageRepeat,
+      ],
+
+      Outline => longhands![OutlineColor, OutlineStyle, OutlineWidth],
+
+      FlexFlow(_) => longhands![FlexDirection(VendorPrefix::None), FlexWrap(VendorPrefix::None)],
+      Flex(_) => longhands![
+        FlexGrow(VendorPrefix::None),
+        FlexShrink(VendorPrefix::None),
+        FlexBasis(VendorPrefix::None)
+      ],
+      PlaceContent => longhands![AlignContent(VendorPrefix::None), JustifyContent(VendorPrefix::None)],
+      PlaceSelf => longhands![AlignSelf(VendorPrefix::None), JustifySelf],
+      PlaceItems => longhands![AlignItems(VendorPrefix::None), JustifyItems],
+      Gap => longhands![RowGap, ColumnGap],
+
+      #[cfg(feature = "grid")]
+      GridTemplate => longhands![GridTemplateRows, GridTemplateColumns, GridTemplateAreas],
+      #[cfg(feature = "grid")]
+      Grid => longhands![
+        GridTemplateRows,
+        GridTemplateColumns,
+        GridTemplateAreas,
+        GridAutoRows,
+        GridAutoColumns,
+        GridAutoFlow,
+      ],
+      #[cfg(feature = "grid")]
+      GridRow => longhands![GridRowStart, GridRowEnd],
+      #[cfg(feature = "grid")]
+      GridColumn => longhands![GridColumnStart, GridColumnEnd],
+      #[cfg(feature = "grid")]
+      GridArea => longhands![GridRowStart, GridColumnStart, GridRowEnd, GridColumnEnd],
+
+      Margin => longhands![MarginTop, MarginRight, MarginBottom, MarginLeft],
+      MarginBlock => longhands![MarginBlockStart, MarginBlockEnd],
+      MarginInline => longhands![MarginInlineStart, MarginInlineEnd],
+      MarginBlockStart => longhands![MarginTop],
+      MarginBlockEnd => longhands![MarginBottom],
+      MarginInlineStart | MarginInlineEnd => longhands![MarginLeft, MarginRight],
+      Padding => longhands![PaddingTop, PaddingRight, PaddingBottom, PaddingLeft],
+      PaddingBlock => longhands![PaddingBlockStart, PaddingBlockEnd],
+      PaddingInline => longhands![PaddingInlineStart, PaddingInlineEnd],
+      PaddingBlockStart => longhands![PaddingTop],
+      PaddingBlockEnd => longhands![PaddingBottom],
+      PaddingInlineStart | PaddingInlineEnd => longhands![PaddingLeft, PaddingRight],
+      ScrollMargin => longhands![ScrollMarginTop, ScrollMarginRight, ScrollMarginBottom, ScrollMarginLeft],
+      ScrollMarginBlock => longhands![ScrollMarginBlockStart, ScrollMarginBlockEnd],
+      ScrollMarginInline => longhands![ScrollMarginInlineStart, ScrollMarginInlineEnd],
+      ScrollMarginBlockStart => longhands![ScrollMarginTop],
+      ScrollMarginBlockEnd => longhands![ScrollMarginBottom],
+      ScrollMarginInlineStart | ScrollMarginInlineEnd => longhands![ScrollMarginLeft, ScrollMarginRight],
+      ScrollPadding => longhands![
+        ScrollPaddingTop,
+        ScrollPaddingRight,
+        ScrollPaddingBottom,
+        ScrollPaddingLeft
+      ],
+      ScrollPaddingBlock => longhands![ScrollPaddingBlockStart, ScrollPaddingBlockEnd],
+      ScrollPaddingInline => longhands![ScrollPaddingInlineStart, ScrollPaddingInlineEnd],
+      ScrollPaddingBlockStart => longhands![ScrollPaddingTop],
+      ScrollPaddingBlockEnd => longhands![ScrollPaddingBottom],
+      ScrollPaddingInlineStart | ScrollPaddingInlineEnd => longhands![ScrollPaddingLeft, ScrollPaddingRight],
+
+      Font => longhands![
+        FontStyle,
+        FontVariantCaps,
+        FontWeight,
+        FontStretch,
+        FontSize,
+        LineHeight,
+        FontFamily,
+        FontVariantLigatures,
+        FontVariantAlternates,
+        FontVariantNumeric,
+        FontVariantEastAsian,
+        FontVariantPosition,
+      ],
+      FontVariant => longhands![
+        FontVariantLigatures,
+        FontVariantCaps,
+        FontVariantAlternates,
+        FontVariantNumeric,
+        FontVariantEastAsian,
+        FontVariantPosition,
+      ],
+      Transition(_) => longhands![
+        TransitionProperty(VendorPrefix::None),
+        TransitionDuration(VendorPrefix::None),
+        TransitionTimingFunction(VendorPrefix::None),
+        TransitionDelay(VendorPrefix::None),
+      ],
+      Animation(_) => longhands![
+        AnimationName(VendorPrefix::None),
+        AnimationDuration(VendorPrefix::None),
+        AnimationTimingFunction(VendorPrefix::None),
+        AnimationIterationCount(VendorPrefix::None),
+        AnimationDirection(VendorPrefix::None),
+        AnimationPlayState(VendorPrefix::None),
+        AnimationDelay(VendorPrefix::None),
+        AnimationFillMode(VendorPrefix::None),
+      ],
+      TextDecoration(_) => longhands![
+        TextDecorationLine(VendorPrefix::None),
+        TextDecorationStyle(VendorPrefix::None),
+        TextDecorationColor(VendorPrefix::None),
+        TextDecorationThickness,
+      ],
+      TextEmphasis(_) => longhands![
+        TextEmphasisStyle(VendorPrefix::None),
+        TextEmphasisColor(VendorPrefix::None)
+      ],
+      Caret => longhands![CaretColor, CaretShape],
+      ListStyle => longhands![ListStyleType, ListStyleImage, ListStylePosition],
+      Marker => longhands![MarkerStart, MarkerMid, MarkerEnd],
+
+      Mask(_) => longhands![
+        MaskImage(VendorPrefix::None),
+        MaskMode,
+        MaskRepeat(VendorPrefix::None),
+        MaskPositionX,
+        MaskPositionY,
+        MaskClip(VendorPrefix::None),
+        MaskOrigin(VendorPrefix::None),
+        MaskSize(VendorPrefix::None),
+        MaskComposite,
+      ],
+      MaskPosition(_) => longhands![MaskPositionX, MaskPositionY],
+      Offset => longhands![OffsetPosition, OffsetPath, OffsetDistance, OffsetRotate, OffsetAnchor],
+      MaskBorder => longhands![
+        MaskBorderSource,
+        MaskBorderSlice,
+        MaskBorderWidth,
+        MaskBorderOutset,
+        MaskBorderRepeat,
+        MaskBorderMode,
+      ],
+      WebKitMaskBoxImage(_) => longhands![
+        WebKitMaskBoxImageSource(VendorPrefix::None),
+        WebKitMaskBoxImageSlice(VendorPrefix::None),
+        WebKitMaskBoxImageWidth(VendorPrefix::None),
+        WebKitMaskBoxImageOutset(VendorPrefix::None),
+        WebKitMaskBoxImageRepeat(VendorPrefix::None),
+      ],
+      // Longhand properties.
+      All
+      | Custom(_)
+      | BackgroundColor
+      | BackgroundImage
+      | BackgroundPositionX
+      | BackgroundPositionY
+      | BackgroundSize
+      | BackgroundRepeat
+      | BackgroundAttachment
+      | BackgroundClip(_)
+      | BackgroundOrigin
+      | BoxShadow(_)
+      | Opacity
+      | Color
+      | Display
+      | Visibility
+      | Width
+      | Height
+      | MinWidth
+      | MinHeight
+      | MaxWidth
+      | MaxHeight
+      | BoxSizing(_)
+      | ObjectFit(_)
+      | ObjectPosition(_)
+      | OverflowX
+      | OverflowY
+      | TextOverflow(_)
+      | OverscrollBehaviorX
+      | OverscrollBehaviorY
+      | LineClamp(_)
+      | ContentVisibility
+      | ContainIntrinsicWidth
+      | ContainIntrinsicHeight
+      | Position
+      | Top
+      | Bottom
+      | Left
+      | Right
+      | BorderTopColor
+      | BorderBottomColor
+      | BorderLeftColor
+      | BorderRightColor
+      | BorderTopStyle
+      | BorderBottomStyle
+      | BorderLeftStyle
+      | BorderRightStyle
+      | BorderTopWidth
+      | BorderBottomWidth
+      | BorderLeftWidth
+      | BorderRightWidth
+      | BorderTopLeftRadius(_)
+      | BorderTopRightRadius(_)
+      | BorderBottomLeftRadius(_)
+      | BorderBottomRightRadius(_)
+      | BorderImageSource
+      | BorderImageOutset
+      | BorderImageRepeat
+      | BorderImageWidth
+      | BorderImageSlice
+      | OutlineColor
+      | OutlineStyle
+      | OutlineWidth
+      | FlexDirection(_)
+      | FlexWrap(_)
+      | FlexGrow(_)
+      | FlexShrink(_)
+      | FlexBasis(_)
+      | Order(_)
+      | AlignContent(_)
+      | JustifyContent(_)
+      | AlignSelf(_)
+      | JustifySelf
+      | AlignItems(_)
+      | JustifyItems
+      | RowGap
+      | ColumnGap
+      | BoxOrient(_)
+      | BoxDirection(_)
+      | BoxOrdinalGroup(_)
+      | BoxAlign(_)
+      | BoxFlex(_)
+      | BoxFlexGroup(_)
+      | BoxPack(_)
+      | BoxLines(_)
+      | FlexPack(_)
+      | FlexOrder(_)
+      | FlexAlign(_)
+      | FlexItemAlign(_)
+      | FlexLinePack(_)
+      | FlexPositive(_)
+      | FlexNegative(_)
+      | FlexPreferredSize(_)
+      | MarginTop
+      | MarginBottom
+      | MarginLeft
+      | MarginRight
+      | PaddingTop
+      | PaddingBottom
+      | PaddingLeft
+      | PaddingRight
+      | ScrollMarginTop
+      | ScrollMarginBottom
+      | ScrollMarginLeft
+      | ScrollMarginRight
+      | ScrollPaddingTop
+      | ScrollPaddingBottom
+      | ScrollPaddingLeft
+      | ScrollPaddingRight
+      | FontWeight
+      | FontSize
+      | FontStretch
+      | FontFamily
+      | FontStyle
+      | FontVariantCaps
+      | FontVariantLigatures
+      | FontVariantAlternates
+      | FontVariantNumeric
+      | FontVariantEastAsian
+      | FontVariantPosition
+      | LineHeight
+      | VerticalAlign
+      | FontPalette
+      | TransitionProperty(_)
+      | TransitionDuration(_)
+      | TransitionDelay(_)
+      | TransitionTimingFunction(_)
+      | AnimationName(_)
+      | AnimationDuration(_)
+      | AnimationTimingFunction(_)
+      | AnimationIterationCount(_)
+      | AnimationDirection(_)
+      | AnimationPlayState(_)
+      | AnimationDelay(_)
+      | AnimationFillMode(_)
+      | Transform(_)
+      | TransformStyle(_)
+      | TransformBox
+      | BackfaceVisibility(_)
+      | Perspective(_)
+      | PerspectiveOrigin(_)
+      | Translate
+      | Rotate
+      | Scale
+      | Zoom
+      | TextTransform
+      | WhiteSpaceCollapse
+      | TextWrap
+      | TabSize(_)
+      | WordBreak
+      | LineBreak
+      | Hyphens(_)
+      | OverflowWrap
+      | WordWrap
+      | TextAlign
+      | TextAlignLast(_)
+      | TextJustify
+      | WordSpacing
+      | LetterSpacing
+      | TextIndent
+      | TextDecorationLine(_)
+      | TextDecorationStyle(_)
+      | TextDecorationColor(_)
+      | TextDecorationThickness
+      | TextDecorationSkipInk(_)
+      | TextEmphasisStyle(_)
+      | TextEmphasisColor(_)
+      | TextEmphasisPosition(_)
+      | TextShadow
+      | Resize
+      | Cursor
+      | CaretColor
+      | CaretShape
+      | UserSelect(_)
+      | AccentColor
+      | ColorScheme
+      | Appearance(_)
+      | ScrollbarGutter
+      | ScrollbarWidth
+      | ScrollbarColor
+      | WritingMode(_)
+      | TouchAction(_)
+      | ListStyleType
+      | ListStyleImage
+      | ListStylePosition
+      | MarkerSide
+      | CounterReset
+      | CounterIncrement
+      | CounterSet
+      | Quotes
+      | Composes
+      | Fill
+      | FillRule
+      | FillOpacity
+      | Stroke
+      | StrokeOpacity
+      | StrokeWidth
+      | StrokeLinecap
+      | StrokeLinejoin
+      | StrokeMiterlimit
+      | StrokeDasharray
+      | StrokeDashoffset
+      | MarkerStart
+      | MarkerMid
+      | MarkerEnd
+      | ColorInterpolation
+      | ColorInterpolationFilters
+      | ColorRendering
+      | ShapeRendering
+      | TextRendering
+      | ImageRendering
+      | ClipPath(_)
+      | ClipRule
+      | MaskImage(_)
+      | MaskMode
+      | MaskRepeat(_)
+      | MaskPositionX
+      | MaskPositionY
+      | MaskClip(_)
+      | MaskOrigin(_)
+      | MaskSize(_)
+      | MaskComposite
+      | MaskType
+      | MaskBorderSource
+      | MaskBorderMode
+      | MaskBorderSlice
+      | MaskBorderWidth
+      | MaskBorderOutset
+      | MaskBorderRepeat
+      | WebKitMaskComposite
+      | WebKitMaskSourceType(_)
+      | WebKitMaskBoxImageSource(_)
+      | WebKitMaskBoxImageSlice(_)
+      | WebKitMaskBoxImageWidth(_)
+      | WebKitMaskBoxImageOutset(_)
+      | WebKitMaskBoxImageRepeat(_)
+      | ShapeOutside
+      | OffsetPosition
+      | OffsetPath
+      | OffsetDistance
+      | OffsetRotate
+      | OffsetAnchor
+      | Filter(_)
+      | BackdropFilter(_)
+      | TransformOrigin(_) => &[],
+      #[cfg(feature = "grid")]
+      GridTemplateColumns | GridTemplateRows | GridAutoColumns | GridAutoRows | GridAutoFlow
+      | GridTemplateAreas | GridRowStart | GridRowEnd | GridColumnStart | GridColumnEnd => &[],
+    }
+  }
+
+  /// Returns the feature used to determine which vendor prefixes the property needs for a set of targets.
+  /// Returns `None` for properties that have no prefixes, or are only supported with a prefix.
+  pub(crate) fn prefix_feature(&self) -> Option<Feature> {
+    match self {
+      PropertyId::Custom(_) => None,
+      _ => Feature::from_name(self.name()),
+    }
+  }
+}
+
 impl<'i, T: smallvec::Array<Item = V>, V: Parse<'i>> Parse<'i> for SmallVec<T> {
   fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     // Copied from cssparser `parse_comma_separated` but using SmallVec instead of Vec.
@@ -0,0 +1,576 @@
+//! CSS properties related to motion paths.
+
+use super::masking::GeometryBox;
+use super::{Property, PropertyId};
+use crate::compat::Feature;
+use crate::context::PropertyHandlerContext;
+use crate::declaration::DeclarationList;
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::printer::Printer;
+use crate::targets::Browsers;
+use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::values::angle::Angle;
+use crate::values::length::LengthPercentage;
+use crate::values::position::Position;
+use crate::values::shape::{BasicShape, FillRule};
+use crate::values::string::CowArcStr;
+use crate::values::url::Url;
+use cssparser::*;
+
+enum_property! {
+  /// A [`<ray-size>`](https://drafts.fxtf.org/motion-1/#typedef-ray-size) value,
+  /// used in the `ray()` function.
+  ///
+  /// See [Ray](Ray).
+  pub enum RaySize {
+    /// The distance to the closest side of the containing box.
+    "closest-side": ClosestSide,
+    /// The distance to the closest corner of the containing box.
+    "closest-corner": ClosestCorner,
+    /// The distance to the farthest side of the containing box.
+    "farthest-side": FarthestSide,
+    /// The distance to the farthest corner of the containing box.
+    "farthest-corner": FarthestCorner,
+    /// The distance to the point where the ray intersects the containing box.
+    "sides": Sides,
+  }
+}
+
+/// A [`ray()`](https://drafts.fxtf.org/motion-1/#ray-function) function, used in the `offset-path` property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ray {
+  /// The angle of the ray.
+  pub angle: Angle,
+  /// The length of the ray.
+  pub size: Option<RaySize>,
+  /// Whether the ray is shortened so that the element stays within the containing box.
+  pub contain: bool,
+}
+
+impl<'i> Parse<'i> for Ray {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("ray")?;
+    input.parse_nested_block(|input| {
+      let mut angle = None;
+      let mut size = None;
+      let mut contain = false;
+
+      loop {
+        if angle.is_none() {
+          if let Ok(value) = input.try_parse(Angle::parse) {
+            angle = Some(value);
+            continue;
+          }
+        }
+
+        if size.is_none() {
+          if let Ok(value) = input.try_parse(RaySize::parse) {
+            size = Some(value);
+            continue;
+          }
+        }
+
+        if !contain && input.try_parse(|input| input.expect_ident_matching("contain")).is_ok() {
+          contain = true;
+          continue;
+        }
+
+        break;
+      }
+
+      match angle {
+        Some(angle) => Ok(Ray { angle, size, contain }),
+        None => Err(input.new_custom_error(ParserError::InvalidValue)),
+      }
+    })
+  }
+}
+
+impl ToCss for Ray {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("ray(")?;
+    self.angle.to_css(dest)?;
+    if let Some(size) = &self.size {
+      dest.write_char(' ')?;
+      size.to_css(dest)?;
+    }
+    if self.contain {
+      dest.write_str(" contain")?;
+    }
+    dest.write_char(')')
+  }
+}
+
+/// A [`path()`](https://www.w3.org/TR/css-shapes-2/#funcdef-path) function, used in the `offset-path`
+/// and `clip-path` properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathFunction<'i> {
+  /// The fill rule used to determine the interior of the path.
+  pub fill_rule: FillRule,
+  /// The SVG path data.
+  pub path: CowArcStr<'i>,
+}
+
+impl<'i> Parse<'i> for PathFunction<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    input.expect_function_matching("path")?;
+    input.parse_nested_block(|input| {
+      let fill_rule = if let Ok(fill_rule) = input.try_parse(FillRule::parse) {
+        input.expect_comma()?;
+        fill_rule
+      } else {
+        FillRule::default()
+      };
+
+      let path = input.expect_string_cloned()?;
+      Ok(PathFunction {
+        fill_rule,
+        path: path.into(),
+      })
+    })
+  }
+}
+
+impl<'i> ToCss for PathFunction<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.write_str("path(")?;
+    if self.fill_rule != FillRule::default() {
+      self.fill_rule.to_css(dest)?;
+      dest.delim(',', false)?;
+    }
+    serialize_string(&self.path, dest)?;
+    dest.write_char(')')
+  }
+}
+
+/// A value for the [offset-path](https://drafts.fxtf.org/motion-1/#offset-path-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OffsetPath<'i> {
+  /// No offset path.
+  None,
+  /// A ray starting at the offset position.
+  Ray(Ray),
+  /// An SVG path.
+  Path(PathFunction<'i>),
+  /// A url reference to an SVG shape element.
+  Url(Url<'i>),
+  /// A basic shape, positioned according to the reference box.
+  Shape(Box<BasicShape>, GeometryBox),
+  /// A reference box.
+  Box(GeometryBox),
+}
+
+impl<'i> Parse<'i> for OffsetPath<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(OffsetPath::None);
+    }
+
+    if let Ok(ray) = input.try_parse(Ray::parse) {
+      return Ok(OffsetPath::Ray(ray));
+    }
+
+    if let Ok(path) = input.try_parse(PathFunction::parse) {
+      return Ok(OffsetPath::Path(path));
+    }
+
+    if let Ok(url) = input.try_parse(Url::parse) {
+      return Ok(OffsetPath::Url(url));
+    }
+
+    if let Ok(shape) = input.try_parse(BasicShape::parse) {
+      let b = input.try_parse(GeometryBox::parse).unwrap_or_default();
+      return Ok(OffsetPath::Shape(Box::new(shape), b));
+    }
+
+    let b = GeometryBox::parse(input)?;
+    if let Ok(shape) = input.try_parse(BasicShape::parse) {
+      return Ok(OffsetPath::Shape(Box::new(shape), b));
+    }
+    Ok(OffsetPath::Box(b))
+  }
+}
+
+impl<'i> ToCss for OffsetPath<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      OffsetPath::None => dest.write_str("none"),
+      OffsetPath::Ray(ray) => ray.to_css(dest),
+      OffsetPath::Path(path) => path.to_css(dest),
+      OffsetPath::Url(url) => url.to_css(dest),
+      OffsetPath::Shape(shape, b) => {
+        shape.to_css(dest)?;
+        if *b != GeometryBox::default() {
+          dest.write_char(' ')?;
+          b.to_css(dest)?;
+        }
+        Ok(())
+      }
+      OffsetPath::Box(b) => b.to_css(dest),
+    }
+  }
+}
+
+/// A value for the [offset-rotate](https://drafts.fxtf.org/motion-1/#offset-rotate-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetRotate {
+  /// Whether the element is rotated according to the direction of the path.
+  pub auto: bool,
+  /// Whether the element is rotated according to the opposite direction of the path.
+  pub reverse: bool,
+  /// The angle of rotation, added to the path direction if `auto` or `reverse` is specified.
+  pub angle: Angle,
+}
+
+impl Default for OffsetRotate {
+  fn default() -> OffsetRotate {
+    OffsetRotate {
+      auto: true,
+      reverse: false,
+      angle: Angle::Deg(0.0),
+    }
+  }
+}
+
+impl<'i> Parse<'i> for OffsetRotate {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let mut angle = input.try_parse(Angle::parse).ok();
+    let (auto, reverse) = if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      (true, false)
+    } else if input.try_parse(|input| input.expect_ident_matching("reverse")).is_ok() {
+      (false, true)
+    } else {
+      (false, false)
+    };
+
+    if angle.is_none() && (auto || reverse) {
+      angle = input.try_parse(Angle::parse).ok();
+    }
+
+    match angle {
+      Some(angle) => Ok(OffsetRotate { auto, reverse, angle }),
+      None if auto || reverse => Ok(OffsetRotate {
+        auto,
+        reverse,
+        angle: Angle::Deg(0.0),
+      }),
+      None => Err(input.new_custom_error(ParserError::InvalidValue)),
+    }
+  }
+}
+
+impl ToCss for OffsetRotate {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.auto {
+      dest.write_str("auto")?;
+    } else if self.reverse {
+      dest.write_str("reverse")?;
+    }
+
+    if !(self.auto || self.reverse) || !self.angle.is_zero() {
+      if self.auto || self.reverse {
+        dest.write_char(' ')?;
+      }
+      self.angle.to_css(dest)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A value for the [offset-anchor](https://drafts.fxtf.org/motion-1/#offset-anchor-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OffsetAnchor {
+  /// The anchor is the same as the `transform-origin`, or the offset position if the path is `none`.
+  Auto,
+  /// An explicit anchor point.
+  Position(Position),
+}
+
+impl<'i> Parse<'i> for OffsetAnchor {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(OffsetAnchor::Auto);
+    }
+
+    Ok(OffsetAnchor::Position(Position::parse(input)?))
+  }
+}
+
+impl ToCss for OffsetAnchor {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      OffsetAnchor::Auto => dest.write_str("auto"),
+      OffsetAnchor::Position(position) => position.to_css(dest),
+    }
+  }
+}
+
+/// A value for the [offset-position](https://drafts.fxtf.org/motion-1/#offset-position-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OffsetPosition {
+  /// The element has no offset starting position, and paths start at the center of the containing block.
+  Normal,
+  /// The offset starting position is the top left corner of the element's box.
+  Auto,
+  /// An explicit offset starting position.
+  Position(Position),
+}
+
+impl<'i> Parse<'i> for OffsetPosition {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(OffsetPosition::Normal);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(OffsetPosition::Auto);
+    }
+
+    Ok(OffsetPosition::Position(Position::parse(input)?))
+  }
+}
+
+impl ToCss for OffsetPosition {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      OffsetPosition::Normal => dest.write_str("normal"),
+      OffsetPosition::Auto => dest.write_str("auto"),
+      OffsetPosition::Position(position) => position.to_css(dest),
+    }
+  }
+}
+
+/// A value for the [offset](https://drafts.fxtf.org/motion-1/#offset-shorthand) shorthand property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offset<'i> {
+  /// The offset starting position.
+  pub position: OffsetPosition,
+  /// The offset path.
+  pub path: OffsetPath<'i>,
+  /// The distance along the offset path.
+  pub distance: LengthPercentage,
+  /// The rotation of the element along the offset path.
+  pub rotate: OffsetRotate,
+  /// The anchor point of the element.
+  pub anchor: OffsetAnchor,
+}
+
+impl<'i> Parse<'i> for Offset<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let position = input.try_parse(OffsetPosition::parse).ok();
+    let path = input.try_parse(OffsetPath::parse).ok();
+    if position.is_none() && path.is_none() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    let mut distance = None;
+    let mut rotate = None;
+    if path.is_some() {
+      loop {
+        if distance.is_none() {
+          if let Ok(value) = input.try_parse(LengthPercentage::parse) {
+            distance = Some(value);
+            continue;
+          }
+        }
+
+        if rotate.is_none() {
+          if let Ok(value) = input.try_parse(OffsetRotate::parse) {
+            rotate = Some(value);
+            continue;
+          }
+        }
+
+        break;
+      }
+    }
+
+    let anchor = if input.try_parse(|input| input.expect_delim('/')).is_ok() {
+      OffsetAnchor::parse(input)?
+    } else {
+      OffsetAnchor::Auto
+    };
+
+    Ok(Offset {
+      position: position.unwrap_or(OffsetPosition::Normal),
+      path: path.unwrap_or(OffsetPath::None),
+      distance: distance.unwrap_or_else(LengthPercentage::zero),
+      rotate: rotate.unwrap_or_default(),
+      anchor,
+    })
+  }
+}
+
+impl<'i> ToCss for Offset<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    let has_position = self.position != OffsetPosition::Normal;
+    let has_distance = self.distance != LengthPercentage::zero();
+    let has_rotate = self.rotate != OffsetRotate::default();
+
+    if has_position {
+      self.position.to_css(dest)?;
+    }
+
+    // The distance and rotation can only be specified after a path.
+    if !has_position || self.path != OffsetPath::None || has_distance || has_rotate {
+      if has_position {
+        dest.write_char(' ')?;
+      }
+      self.path.to_css(dest)?;
+
+      if has_distance {
+        dest.write_char(' ')?;
+        self.distance.to_css(dest)?;
+      }
+
+      if has_rotate {
+        dest.write_char(' ')?;
+        self.rotate.to_css(dest)?;
+      }
+    }
+
+    if self.anchor != OffsetAnchor::Auto {
+      dest.delim('/', true)?;
+      self.anchor.to_css(dest)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct OffsetHandler<'i> {
+  targets: Option<Browsers>,
+  position: Option<OffsetPosition>,
+  path: Option<OffsetPath<'i>>,
+  distance: Option<LengthPercentage>,
+  rotate: Option<OffsetRotate>,
+  anchor: Option<OffsetAnchor>,
+  has_any: bool,
+}
+
+impl<'i> OffsetHandler<'i> {
+  pub fn new(targets: Option<Browsers>) -> OffsetHandler<'i> {
+    OffsetHandler {
+      targets,
+      ..OffsetHandler::default()
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for OffsetHandler<'i> {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    use Property::*;
+
+    match property {
+      OffsetPosition(val) => self.position = Some(val.clone()),
+      OffsetPath(val) => self.path = Some(val.clone()),
+      OffsetDistance(val) => self.distance = Some(val.clone()),
+      OffsetRotate(val) => self.rotate = Some(val.clone()),
+      OffsetAnchor(val) => self.anchor = Some(val.clone()),
+      Offset(val) => {
+        self.position = Some(val.position.clone());
+        self.path = Some(val.path.clone());
+        self.distance = Some(val.distance.clone());
+        self.rotate = Some(val.rotate.clone());
+        self.anchor = Some(val.anchor.clone());
+      }
+      Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::OffsetPosition
+            | PropertyId::OffsetPath
+            | PropertyId::OffsetDistance
+            | PropertyId::OffsetRotate
+            | PropertyId::OffsetAnchor
+            | PropertyId::Offset
+        ) =>
+      {
+        self.finalize(dest, context);
+        dest.push(property.clone());
+        return true;
+      }
+      _ => return false,
+    }
+
+    self.has_any = true;
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList<'i>, _: &mut PropertyHandlerContext<'i>) {
+    if !self.has_any {
+      return;
+    }
+
+    self.has_any = false;
+
+    let position = std::mem::take(&mut self.position);
+    let path = std::mem::take(&mut self.path);
+    let distance = std::mem::take(&mut self.distance);
+    let rotate = std::mem::take(&mut self.rotate);
+    let anchor = std::mem::take(&mut self.anchor);
+
+    // Only merge into the shorthand if all targets support it.
+    let shorthand_supported =
+      self.targets.is_none() || Feature::OffsetShorthand.is_compatible(self.targets.unwrap());
+    match (position, path, distance, rotate, anchor) {
+      (Some(position), Some(path), Some(distance), Some(rotate), Some(anchor)) if shorthand_supported => dest
+        .push(Property::Offset(Offset {
+          position,
+          path,
+          distance,
+          rotate,
+          anchor,
+        })),
+      (position, path, distance, rotate, anchor) => {
+        if let Some(position) = position {
+          dest.push(Property::OffsetPosition(position))
+        }
+
+        if let Some(path) = path {
+          dest.push(Property::OffsetPath(path))
+        }
+
+        if let Some(distance) = distance {
+          dest.push(Property::OffsetDistance(distance))
+        }
+
+        if let Some(rotate) = rotate {
+          dest.push(Property::OffsetRotate(rotate))
+        }
+
+        if let Some(anchor) = anchor {
+          dest.push(Property::OffsetAnchor(anchor))
+        }
+      }
+    }
+  }
+}
@@ -1,5 +1,7 @@
 //! CSS properties related to overflow.
 
+use super::display::{Display, DisplayInside, DisplayOutside, DisplayPair};
+use super::flex::BoxOrient;
 use super::{Property, PropertyId};
 use crate::compat::Feature;
 use crate::context::PropertyHandlerContext;
@@ -9,6 +11,8 @@ use crate::macros::enum_property;
 use crate::printer::Printer;
 use crate::targets::Browsers;
 use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::values::number::CSSInteger;
+use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
 
 enum_property! {
@@ -144,3 +148,278 @@ impl<'i> PropertyHandler<'i> for OverflowHandler {
     }
   }
 }
+
+enum_property! {
+  /// An [overscroll-behavior](https://drafts.csswg.org/css-overscroll-1/#overscroll-behavior-properties) keyword
+  /// as used in the `overscroll-behavior-x`, `overscroll-behavior-y`, `overscroll-behavior-inline`,
+  /// `overscroll-behavior-block`, and `overscroll-behavior` properties.
+  pub enum OverscrollBehaviorKeyword {
+    /// Scroll chaining and the default action at the scroll boundary are allowed.
+    Auto,
+    /// Scroll chaining is prevented, but the default action at the scroll boundary is allowed.
+    Contain,
+    /// Scroll chaining and the default action at the scroll boundary are prevented.
+    None,
+  }
+}
+
+/// A value for the [overscroll-behavior](https://drafts.csswg.org/css-overscroll-1/#propdef-overscroll-behavior)
+/// shorthand property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverscrollBehavior {
+  /// The overscroll behavior for the x direction.
+  pub x: OverscrollBehaviorKeyword,
+  /// The overscroll behavior for the y direction.
+  pub y: OverscrollBehaviorKeyword,
+}
+
+impl<'i> Parse<'i> for OverscrollBehavior {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let x = OverscrollBehaviorKeyword::parse(input)?;
+    let y = input.try_parse(OverscrollBehaviorKeyword::parse).unwrap_or(x);
+    Ok(OverscrollBehavior { x, y })
+  }
+}
+
+impl ToCss for OverscrollBehavior {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    self.x.to_css(dest)?;
+    if self.y != self.x {
+      dest.write_char(' ')?;
+      self.y.to_css(dest)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct OverscrollBehaviorHandler {
+  x: Option<OverscrollBehaviorKeyword>,
+  y: Option<OverscrollBehaviorKeyword>,
+  inline: Option<OverscrollBehaviorKeyword>,
+  block: Option<OverscrollBehaviorKeyword>,
+}
+
+impl<'i> PropertyHandler<'i> for OverscrollBehaviorHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    use Property::*;
+
+    let logical_supported = context.is_supported(Feature::LogicalOverscrollBehavior);
+
+    // Physical and logical values for the same axis may override each other, so
+    // flush the other kind before storing a value to preserve the order between them.
+    macro_rules! physical {
+      ($x: expr, $y: expr) => {{
+        if self.inline.is_some() || self.block.is_some() {
+          self.finalize(dest, context);
+        }
+        if let Some(x) = $x {
+          self.x = Some(x);
+        }
+        if let Some(y) = $y {
+          self.y = Some(y);
+        }
+      }};
+    }
+
+    // Without support for logical values, assume a horizontal writing mode,
+    // where the inline axis is x and the block axis is y.
+    macro_rules! logical {
+      ($prop: ident, $physical: ident, $val: expr) => {{
+        if logical_supported {
+          if self.x.is_some() || self.y.is_some() {
+            self.finalize(dest, context);
+          }
+          self.$prop = Some($val);
+        } else {
+          self.$physical = Some($val);
+        }
+      }};
+    }
+
+    match property {
+      OverscrollBehaviorX(val) => physical!(Some(*val), None),
+      OverscrollBehaviorY(val) => physical!(None, Some(*val)),
+      OverscrollBehavior(val) => physical!(Some(val.x), Some(val.y)),
+      OverscrollBehaviorInline(val) => logical!(inline, x, *val),
+      OverscrollBehaviorBlock(val) => logical!(block, y, *val),
+      Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::OverscrollBehaviorX
+            | PropertyId::OverscrollBehaviorY
+            | PropertyId::OverscrollBehavior
+            | PropertyId::OverscrollBehaviorInline
+            | PropertyId::OverscrollBehaviorBlock
+        ) =>
+      {
+        self.finalize(dest, context);
+        dest.push(match &val.property_id {
+          PropertyId::OverscrollBehaviorInline if !logical_supported => {
+            Property::Unparsed(val.with_property_id(PropertyId::OverscrollBehaviorX))
+          }
+          PropertyId::OverscrollBehaviorBlock if !logical_supported => {
+            Property::Unparsed(val.with_property_id(PropertyId::OverscrollBehaviorY))
+          }
+          _ => property.clone(),
+        });
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    let x = std::mem::take(&mut self.x);
+    let y = std::mem::take(&mut self.y);
+    let inline = std::mem::take(&mut self.inline);
+    let block = std::mem::take(&mut self.block);
+
+    match (x, y) {
+      (Some(x), Some(y)) => dest.push(Property::OverscrollBehavior(OverscrollBehavior { x, y })),
+      (x, y) => {
+        if let Some(x) = x {
+          dest.push(Property::OverscrollBehaviorX(x))
+        }
+
+        if let Some(y) = y {
+          dest.push(Property::OverscrollBehaviorY(y))
+        }
+      }
+    }
+
+    if let Some(inline) = inline {
+      dest.push(Property::OverscrollBehaviorInline(inline))
+    }
+
+    if let Some(block) = block {
+      dest.push(Property::OverscrollBehaviorBlock(block))
+    }
+  }
+}
+
+/// A value for the [line-clamp](https://drafts.csswg.org/css-overflow-4/#line-clamp) property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineClamp {
+  /// The content is not clamped.
+  None,
+  /// The content is clamped after the given number of lines.
+  Lines(CSSInteger),
+}
+
+impl<'i> Parse<'i> for LineClamp {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(LineClamp::None);
+    }
+
+    let location = input.current_source_location();
+    let lines = CSSInteger::parse(input)?;
+    if lines < 1 {
+      return Err(location.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(LineClamp::Lines(lines))
+  }
+}
+
+impl ToCss for LineClamp {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      LineClamp::None => dest.write_str("none"),
+      LineClamp::Lines(lines) => lines.to_css(dest),
+    }
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct LineClampHandler {
+  targets: Option<Browsers>,
+  value: Option<(LineClamp, VendorPrefix)>,
+}
+
+impl LineClampHandler {
+  pub fn new(targets: Option<Browsers>) -> LineClampHandler {
+    LineClampHandler {
+      targets,
+      ..LineClampHandler::default()
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for LineClampHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::LineClamp(val, prefix) => {
+        if let Some((cur, prefixes)) = &mut self.value {
+          // If the value is the same, add the prefix.
+          // If the prefix is the same, then update the value.
+          if val == cur || prefixes.contains(*prefix) {
+            *cur = *val;
+            *prefixes |= *prefix;
+            return true;
+          }
+        }
+
+        self.finalize(dest, context);
+        self.value = Some((*val, *prefix));
+      }
+      Property::Unparsed(val) if matches!(val.property_id, PropertyId::LineClamp(_)) => {
+        self.finalize(dest, context);
+        dest.push(property.clone());
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    let (value, mut prefix) = match std::mem::take(&mut self.value) {
+      Some(value) => value,
+      None => return,
+    };
+
+    // Unprefixed line-clamp is not supported anywhere yet. The prefixed version only
+    // works in combination with a vertical -webkit-box, so add those unless already set.
+    if prefix.contains(VendorPrefix::None) && !prefix.contains(VendorPrefix::WebKit) {
+      if let Some(targets) = self.targets {
+        if !Feature::LineClamp.is_compatible(targets) {
+          prefix |= VendorPrefix::WebKit;
+          if value != LineClamp::None {
+            if !dest.iter().any(|p| matches!(p, Property::Display(_))) {
+              dest.push(Property::Display(Display::Pair(DisplayPair {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::Box(VendorPrefix::WebKit),
+                is_list_item: false,
+              })));
+            }
+
+            if !dest.iter().any(|p| matches!(p, Property::BoxOrient(..))) {
+              dest.push(Property::BoxOrient(BoxOrient::Vertical, VendorPrefix::WebKit));
+            }
+          }
+        }
+      }
+    }
+
+    dest.push(Property::LineClamp(value, prefix));
+  }
+}
@@ -4,6 +4,7 @@ use crate::context::{DeclarationContext, PropertyHandlerContext};
 use crate::declaration::DeclarationList;
 use crate::prefixes::Feature;
 use crate::properties::custom::CustomProperty;
+use crate::stylesheet::Optimization;
 use crate::targets::Browsers;
 use crate::traits::{FallbackValues, PropertyHandler};
 use crate::vendor_prefix::VendorPrefix;
@@ -93,9 +94,11 @@ define_prefixes! {
   TextAlignLast,
   TextDecorationSkipInk,
   TextOverflow,
-  UserSelect,
   Appearance,
   ClipPath,
+  ObjectFit,
+  ObjectPosition,
+  TouchAction,
 }
 
 macro_rules! define_fallbacks {
@@ -128,7 +131,11 @@ macro_rules! define_fallbacks {
                   }
                 )?
 
-                let fallbacks = val.get_fallbacks(targets);
+                let original = val.clone();
+                let mut fallbacks = val.get_fallbacks(targets);
+                if val != original && !context.apply_optimization(Optimization::ColorFold) {
+                  fallbacks.push(std::mem::replace(&mut val, original));
+                }
                 #[allow(unused_variables)]
                 let has_fallbacks = !fallbacks.is_empty();
                 for fallback in fallbacks {
@@ -211,4 +218,6 @@ define_fallbacks! {
   Stroke,
   CaretColor,
   Caret,
+  AccentColor,
+  ScrollbarColor,
 }
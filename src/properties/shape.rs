@@ -0,0 +1,85 @@
+//! CSS properties related to shapes around floats.
+
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::printer::Printer;
+use crate::traits::{Parse, ToCss};
+use crate::values::{image::Image, shape::BasicShape};
+use cssparser::*;
+
+enum_property! {
+  /// A [`<shape-box>`](https://www.w3.org/TR/css-shapes-1/#typedef-shape-box) value
+  /// as used in the `shape-outside` property.
+  pub enum ShapeBox {
+    /// The shape is defined by the margin box.
+    "margin-box": MarginBox,
+    /// The shape is defined by the border box.
+    "border-box": BorderBox,
+    /// The shape is defined by the padding box.
+    "padding-box": PaddingBox,
+    /// The shape is defined by the content box.
+    "content-box": ContentBox,
+  }
+}
+
+impl Default for ShapeBox {
+  fn default() -> ShapeBox {
+    ShapeBox::MarginBox
+  }
+}
+
+/// A value for the [shape-outside](https://www.w3.org/TR/css-shapes-1/#shape-outside-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeOutside<'i> {
+  /// The float area is unaffected.
+  None,
+  /// A basic shape, positioned according to the reference box.
+  Shape(Box<BasicShape>, ShapeBox),
+  /// A reference box.
+  Box(ShapeBox),
+  /// The shape is extracted from the alpha channel of an image.
+  Image(Image<'i>),
+}
+
+impl<'i> Parse<'i> for ShapeOutside<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(ShapeOutside::None);
+    }
+
+    if let Ok(shape) = input.try_parse(BasicShape::parse) {
+      let b = input.try_parse(ShapeBox::parse).unwrap_or_default();
+      return Ok(ShapeOutside::Shape(Box::new(shape), b));
+    }
+
+    if let Ok(b) = input.try_parse(ShapeBox::parse) {
+      if let Ok(shape) = input.try_parse(BasicShape::parse) {
+        return Ok(ShapeOutside::Shape(Box::new(shape), b));
+      }
+      return Ok(ShapeOutside::Box(b));
+    }
+
+    Ok(ShapeOutside::Image(Image::parse(input)?))
+  }
+}
+
+impl<'i> ToCss for ShapeOutside<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ShapeOutside::None => dest.write_str("none"),
+      ShapeOutside::Shape(shape, b) => {
+        shape.to_css(dest)?;
+        if *b != ShapeBox::default() {
+          dest.write_char(' ')?;
+          b.to_css(dest)?;
+        }
+        Ok(())
+      }
+      ShapeOutside::Box(b) => b.to_css(dest),
+      ShapeOutside::Image(image) => image.to_css(dest),
+    }
+  }
+}
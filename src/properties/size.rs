@@ -5,10 +5,13 @@ use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
 use crate::error::{ParserError, PrinterError};
 use crate::macros::enum_property;
+use crate::prefixes::Feature as PrefixFeature;
 use crate::printer::Printer;
 use crate::properties::{Property, PropertyId};
-use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::targets::Browsers;
+use crate::traits::{FallbackValues, Parse, PropertyHandler, ToCss};
 use crate::values::length::LengthPercentage;
+use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
 
 // https://drafts.csswg.org/css-sizing-3/#specifying-sizes
@@ -22,57 +25,15 @@ pub enum Size {
   /// An explicit length or percentage.
   LengthPercentage(LengthPercentage),
   /// The `min-content` keyword.
-  MinContent,
+  MinContent(VendorPrefix),
   /// The `max-content` keyword.
-  MaxContent,
+  MaxContent(VendorPrefix),
+  /// The `fit-content` keyword.
+  FitContent(VendorPrefix),
   /// The `fit-content()` function.
-  FitContent(LengthPercentage),
-}
-
-impl<'i> Parse<'i> for Size {
-  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
-      return Ok(Size::Auto);
-    }
-
-    if input.try_parse(|i| i.expect_ident_matching("min-content")).is_ok() {
-      return Ok(Size::MinContent);
-    }
-
-    if input.try_parse(|i| i.expect_ident_matching("max-content")).is_ok() {
-      return Ok(Size::MaxContent);
-    }
-
-    if let Ok(l) = input.try_parse(|input| LengthPercentage::parse(input)) {
-      return Ok(Size::LengthPercentage(l));
-    }
-
-    if let Ok(l) = parse_fit_content(input) {
-      return Ok(Size::FitContent(l));
-    }
-
-    Err(input.new_error_for_next_token())
-  }
-}
-
-impl ToCss for Size {
-  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
-  where
-    W: std::fmt::Write,
-  {
-    use Size::*;
-    match self {
-      Auto => dest.write_str("auto"),
-      MinContent => dest.write_str("min-content"),
-      MaxContent => dest.write_str("max-content"),
-      FitContent(l) => {
-        dest.write_str("fit-content(")?;
-        l.to_css(dest)?;
-        dest.write_str(")")
-      }
-      LengthPercentage(l) => l.to_css(dest),
-    }
-  }
+  FitContentFunction(LengthPercentage),
+  /// The `stretch` keyword, or the `-webkit-fill-available` or `-moz-available` prefixed keywords.
+  Stretch(VendorPrefix),
 }
 
 /// A value for the [minimum](https://drafts.csswg.org/css-sizing-3/#min-size-properties)
@@ -85,59 +46,147 @@ pub enum MinMaxSize {
   /// An explicit length or percentage.
   LengthPercentage(LengthPercentage),
   /// The `min-content` keyword.
-  MinContent,
+  MinContent(VendorPrefix),
   /// The `max-content` keyword.
-  MaxContent,
+  MaxContent(VendorPrefix),
+  /// The `fit-content` keyword.
+  FitContent(VendorPrefix),
   /// The `fit-content()` function.
-  FitContent(LengthPercentage),
+  FitContentFunction(LengthPercentage),
+  /// The `stretch` keyword, or the `-webkit-fill-available` or `-moz-available` prefixed keywords.
+  Stretch(VendorPrefix),
 }
 
-impl<'i> Parse<'i> for MinMaxSize {
-  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
-      return Ok(MinMaxSize::None);
-    }
+macro_rules! impl_size {
+  ($name: ident, $keyword: literal, $variant: ident) => {
+    impl<'i> Parse<'i> for $name {
+      fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching($keyword)).is_ok() {
+          return Ok($name::$variant);
+        }
 
-    if input.try_parse(|i| i.expect_ident_matching("min-content")).is_ok() {
-      return Ok(MinMaxSize::MinContent);
-    }
+        let res = input.try_parse(|input| {
+          let location = input.current_source_location();
+          let ident = input.expect_ident()?;
+          Ok(match_ignore_ascii_case! { &*ident,
+            "min-content" => $name::MinContent(VendorPrefix::None),
+            "-webkit-min-content" => $name::MinContent(VendorPrefix::WebKit),
+            "-moz-min-content" => $name::MinContent(VendorPrefix::Moz),
+            "max-content" => $name::MaxContent(VendorPrefix::None),
+            "-webkit-max-content" => $name::MaxContent(VendorPrefix::WebKit),
+            "-moz-max-content" => $name::MaxContent(VendorPrefix::Moz),
+            "fit-content" => $name::FitContent(VendorPrefix::None),
+            "-webkit-fit-content" => $name::FitContent(VendorPrefix::WebKit),
+            "-moz-fit-content" => $name::FitContent(VendorPrefix::Moz),
+            "stretch" => $name::Stretch(VendorPrefix::None),
+            "-webkit-fill-available" => $name::Stretch(VendorPrefix::WebKit),
+            "-moz-available" => $name::Stretch(VendorPrefix::Moz),
+            _ => return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
+          })
+        });
+
+        if res.is_ok() {
+          return res;
+        }
+
+        if let Ok(l) = input.try_parse(|input| LengthPercentage::parse(input)) {
+          return Ok($name::LengthPercentage(l));
+        }
 
-    if input.try_parse(|i| i.expect_ident_matching("max-content")).is_ok() {
-      return Ok(MinMaxSize::MaxContent);
+        if let Ok(l) = input.try_parse(parse_fit_content) {
+          return Ok($name::FitContentFunction(l));
+        }
+
+        Err(input.new_error_for_next_token())
+      }
     }
 
-    if let Ok(percent) = input.try_parse(|input| LengthPercentage::parse(input)) {
-      return Ok(MinMaxSize::LengthPercentage(percent));
+    impl ToCss for $name {
+      fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+      where
+        W: std::fmt::Write,
+      {
+        use $name::*;
+        match self {
+          $variant => dest.write_str($keyword),
+          MinContent(vp) => {
+            vp.to_css(dest)?;
+            dest.write_str("min-content")
+          }
+          MaxContent(vp) => {
+            vp.to_css(dest)?;
+            dest.write_str("max-content")
+          }
+          FitContent(vp) => {
+            vp.to_css(dest)?;
+            dest.write_str("fit-content")
+          }
+          Stretch(vp) => match *vp {
+            VendorPrefix::WebKit => dest.write_str("-webkit-fill-available"),
+            VendorPrefix::Moz => dest.write_str("-moz-available"),
+            _ => dest.write_str("stretch"),
+          },
+          FitContentFunction(l) => {
+            dest.write_str("fit-content(")?;
+            l.to_css(dest)?;
+            dest.write_str(")")
+          }
+          LengthPercentage(l) => l.to_css(dest),
+        }
+      }
     }
 
-    if let Ok(l) = parse_fit_content(input) {
-      return Ok(MinMaxSize::FitContent(l));
+    impl $name {
+      /// Returns the same intrinsic sizing keyword with the given vendor prefix,
+      /// along with the prefix feature it corresponds to.
+      fn with_prefix(&self, prefix: VendorPrefix) -> Option<(PrefixFeature, $name)> {
+        match self {
+          $name::MinContent(_) => Some((PrefixFeature::MinContent, $name::MinContent(prefix))),
+          $name::MaxContent(_) => Some((PrefixFeature::MaxContent, $name::MaxContent(prefix))),
+          $name::FitContent(_) => Some((PrefixFeature::FitContent, $name::FitContent(prefix))),
+          $name::Stretch(_) => Some((PrefixFeature::Stretch, $name::Stretch(prefix))),
+          _ => Option::None,
+        }
+      }
+
+      /// Returns whether this is a prefixed version of the given unprefixed keyword.
+      fn is_prefixed_version_of(&self, other: &$name) -> bool {
+        use $name::*;
+        match (self, other) {
+          (MinContent(a), MinContent(VendorPrefix::None))
+          | (MaxContent(a), MaxContent(VendorPrefix::None))
+          | (FitContent(a), FitContent(VendorPrefix::None))
+          | (Stretch(a), Stretch(VendorPrefix::None)) => *a != VendorPrefix::None,
+          _ => false,
+        }
+      }
     }
 
-    Err(input.new_error_for_next_token())
-  }
-}
+    impl FallbackValues for $name {
+      fn get_fallbacks(&mut self, targets: Browsers) -> Vec<Self> {
+        let mut res = Vec::new();
+        let prefixes = match self.with_prefix(VendorPrefix::None) {
+          Some((feature, unprefixed)) if unprefixed == *self => feature.prefixes_for(targets),
+          _ => return res,
+        };
 
-impl ToCss for MinMaxSize {
-  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
-  where
-    W: std::fmt::Write,
-  {
-    use MinMaxSize::*;
-    match self {
-      None => dest.write_str("none"),
-      MinContent => dest.write_str("min-content"),
-      MaxContent => dest.write_str("max-content"),
-      FitContent(l) => {
-        dest.write_str("fit-content(")?;
-        l.to_css(dest)?;
-        dest.write_str(")")
+        for prefix in [VendorPrefix::WebKit, VendorPrefix::Moz] {
+          if prefixes.contains(prefix) {
+            if let Some((_, fallback)) = self.with_prefix(prefix) {
+              res.push(fallback);
+            }
+          }
+        }
+
+        res
       }
-      LengthPercentage(l) => l.to_css(dest),
     }
-  }
+  };
 }
 
+impl_size!(Size, "auto", Auto);
+impl_size!(MinMaxSize, "none", None);
+
 fn parse_fit_content<'i, 't>(
   input: &mut Parser<'i, 't>,
 ) -> Result<LengthPercentage, ParseError<'i, ParserError<'i>>> {
@@ -155,6 +204,22 @@ enum_property! {
   }
 }
 
+enum_property! {
+  /// A value for the [object-fit](https://drafts.csswg.org/css-images-3/#the-object-fit) property.
+  pub enum ObjectFit {
+    /// The replaced content is sized to fill the element's content box, ignoring its aspect ratio.
+    "fill": Fill,
+    /// The replaced content is scaled to fit within the content box, preserving its aspect ratio.
+    "contain": Contain,
+    /// The replaced content is scaled to cover the content box, preserving its aspect ratio.
+    "cover": Cover,
+    /// The replaced content is not resized.
+    "none": None,
+    /// The replaced content is sized as if `none` or `contain` were specified, whichever is smaller.
+    "scale-down": ScaleDown,
+  }
+}
+
 #[derive(Default)]
 pub(crate) struct SizeHandler;
 
@@ -167,25 +232,40 @@ impl<'i> PropertyHandler<'i> for SizeHandler {
   ) -> bool {
     let logical_supported = context.is_supported(Feature::LogicalSize);
 
+    macro_rules! property {
+      ($prop: ident, $val: ident) => {{
+        if let Some(targets) = context.targets {
+          let fallbacks = $val.clone().get_fallbacks(targets);
+          if !fallbacks.is_empty() {
+            // Replace any prefixed declarations that preceded the unprefixed one.
+            dest.retain(|decl| !matches!(decl, Property::$prop(v) if v.is_prefixed_version_of($val)));
+            for fallback in fallbacks {
+              dest.push(Property::$prop(fallback));
+            }
+          }
+        }
+
+        dest.push(Property::$prop($val.clone()));
+      }};
+    }
+
     macro_rules! logical {
       ($prop: ident, $val: ident, $physical: ident) => {
         if logical_supported {
-          dest.push(Property::$prop($val.clone()));
+          property!($prop, $val);
         } else {
-          dest.push(Property::$physical($val.clone()));
+          property!($physical, $val);
         }
       };
     }
 
     match property {
-      Property::Width(_)
-      | Property::Height(_)
-      | Property::MinWidth(_)
-      | Property::MaxWidth(_)
-      | Property::MinHeight(_)
-      | Property::MaxHeight(_) => {
-        dest.push(property.clone());
-      }
+      Property::Width(v) => property!(Width, v),
+      Property::Height(v) => property!(Height, v),
+      Property::MinWidth(v) => property!(MinWidth, v),
+      Property::MaxWidth(v) => property!(MaxWidth, v),
+      Property::MinHeight(v) => property!(MinHeight, v),
+      Property::MaxHeight(v) => property!(MaxHeight, v),
       Property::BlockSize(size) => logical!(BlockSize, size, Height),
       Property::MinBlockSize(size) => logical!(MinBlockSize, size, MinHeight),
       Property::MaxBlockSize(size) => logical!(MaxBlockSize, size, MaxHeight),
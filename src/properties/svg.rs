@@ -69,7 +69,7 @@ impl<'i> ToCss for SVGPaint<'i> {
     match self {
       SVGPaint::None => dest.write_str("none"),
       SVGPaint::Url(url, fallback) => {
-        url.to_css(dest)?;
+        url.to_css_reference(dest)?;
         if let Some(fallback) = fallback {
           dest.write_char(' ')?;
           fallback.to_css(dest)?;
@@ -235,7 +235,7 @@ impl<'i> ToCss for Marker<'i> {
   {
     match self {
       Marker::None => dest.write_str("none"),
-      Marker::Url(url) => url.to_css(dest),
+      Marker::Url(url) => url.to_css_reference(dest),
     }
   }
 }
@@ -293,13 +293,25 @@ enum_property! {
 }
 
 enum_property! {
-  /// A value for the [image-rendering](https://www.w3.org/TR/SVG2/painting.html#ImageRendering) property.
+  /// A value for the [image-rendering](https://drafts.csswg.org/css-images-3/#the-image-rendering) property.
   pub enum ImageRendering {
     /// The UA can choose a tradeoff between speed and quality.
-    Auto,
-    /// The UA shall optimize speed over quality.
-    OptimizeSpeed,
-    /// The UA shall optimize quality over speed.
-    OptimizeQuality,
+    "auto": Auto,
+    /// The UA shall optimize speed over quality. Deprecated SVG keyword.
+    "optimizespeed": OptimizeSpeed,
+    /// The UA shall optimize quality over speed. Deprecated SVG keyword.
+    "optimizequality": OptimizeQuality,
+    /// The image is scaled with an algorithm that smooths colors.
+    "smooth": Smooth,
+    /// Like `smooth`, but with a preference for higher-quality scaling.
+    "high-quality": HighQuality,
+    /// The image is scaled with an algorithm that preserves contrast and edges.
+    "crisp-edges": CrispEdges,
+    /// The image is scaled with nearest neighbor when enlarged.
+    "pixelated": Pixelated,
+    /// The legacy WebKit equivalent of `crisp-edges`.
+    "-webkit-optimize-contrast": WebKitOptimizeContrast,
+    /// The legacy Firefox equivalent of `crisp-edges`.
+    "-moz-crisp-edges": MozCrispEdges,
   }
 }
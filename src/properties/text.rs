@@ -10,6 +10,7 @@ use crate::error::{ParserError, PrinterError};
 use crate::macros::enum_property;
 use crate::prefixes::Feature;
 use crate::printer::Printer;
+use crate::stylesheet::Optimization;
 use crate::targets::Browsers;
 use crate::traits::{FallbackValues, Parse, PropertyHandler, ToCss};
 use crate::values::calc::{Calc, MathFunction};
@@ -171,6 +172,67 @@ enum_property! {
   }
 }
 
+impl WhiteSpace {
+  /// Returns the values of the `white-space-collapse` and `text-wrap` longhands the keyword expands to.
+  pub fn to_longhands(&self) -> (WhiteSpaceCollapse, TextWrap) {
+    match self {
+      WhiteSpace::Normal => (WhiteSpaceCollapse::Collapse, TextWrap::Wrap),
+      WhiteSpace::Pre => (WhiteSpaceCollapse::Preserve, TextWrap::NoWrap),
+      WhiteSpace::NoWrap => (WhiteSpaceCollapse::Collapse, TextWrap::NoWrap),
+      WhiteSpace::PreWrap => (WhiteSpaceCollapse::Preserve, TextWrap::Wrap),
+      WhiteSpace::BreakSpaces => (WhiteSpaceCollapse::BreakSpaces, TextWrap::Wrap),
+      WhiteSpace::PreLine => (WhiteSpaceCollapse::PreserveBreaks, TextWrap::Wrap),
+    }
+  }
+
+  /// Returns the `white-space` keyword equivalent to the given longhand values, if any.
+  pub fn from_longhands(collapse: WhiteSpaceCollapse, wrap: TextWrap) -> Option<WhiteSpace> {
+    Some(match (collapse, wrap) {
+      (WhiteSpaceCollapse::Collapse, TextWrap::Wrap) => WhiteSpace::Normal,
+      (WhiteSpaceCollapse::Preserve, TextWrap::NoWrap) => WhiteSpace::Pre,
+      (WhiteSpaceCollapse::Collapse, TextWrap::NoWrap) => WhiteSpace::NoWrap,
+      (WhiteSpaceCollapse::Preserve, TextWrap::Wrap) => WhiteSpace::PreWrap,
+      (WhiteSpaceCollapse::BreakSpaces, TextWrap::Wrap) => WhiteSpace::BreakSpaces,
+      (WhiteSpaceCollapse::PreserveBreaks, TextWrap::Wrap) => WhiteSpace::PreLine,
+      _ => return None,
+    })
+  }
+}
+
+enum_property! {
+  /// A value for the [white-space-collapse](https://drafts.csswg.org/css-text-4/#white-space-collapsing) property.
+  pub enum WhiteSpaceCollapse {
+    /// Sequences of white space are collapsed into a single character.
+    "collapse": Collapse,
+    /// White space is removed.
+    "discard": Discard,
+    /// White space and segment breaks are preserved.
+    "preserve": Preserve,
+    /// Segment breaks are preserved, but other white space is collapsed.
+    "preserve-breaks": PreserveBreaks,
+    /// White space is preserved, but segment breaks are collapsed.
+    "preserve-spaces": PreserveSpaces,
+    /// Like `preserve`, but preserved white space always takes up space and allows wrapping.
+    "break-spaces": BreakSpaces,
+  }
+}
+
+enum_property! {
+  /// A value for the [text-wrap](https://drafts.csswg.org/css-text-4/#text-wrap) property.
+  pub enum TextWrap {
+    /// Lines may break at allowed soft wrap opportunities.
+    "wrap": Wrap,
+    /// Lines do not break.
+    "nowrap": NoWrap,
+    /// Lines are wrapped with their lengths balanced.
+    "balance": Balance,
+    /// Lines are wrapped with a slower algorithm that favors better layout.
+    "pretty": Pretty,
+    /// Lines are wrapped so that editing content does not change earlier lines.
+    "stable": Stable,
+  }
+}
+
 enum_property! {
   /// A value for the [word-break](https://www.w3.org/TR/2021/CRD-css-text-3-20210422/#word-break-property) property.
   pub enum WordBreak {
@@ -1259,11 +1321,11 @@ impl ToCss for TextShadow {
     dest.write_char(' ')?;
     self.y_offset.to_css(dest)?;
 
-    if self.blur != Length::zero() || self.spread != Length::zero() {
+    if !self.blur.is_zero() || !self.spread.is_zero() {
       dest.write_char(' ')?;
       self.blur.to_css(dest)?;
 
-      if self.spread != Length::zero() {
+      if !self.spread.is_zero() {
         dest.write_char(' ')?;
         self.spread.to_css(dest)?;
       }
@@ -1340,3 +1402,201 @@ impl FallbackValues for SmallVec<[TextShadow; 1]> {
     res
   }
 }
+
+#[derive(Default)]
+pub(crate) struct WhiteSpaceHandler {
+  targets: Option<Browsers>,
+  white_space: Option<WhiteSpace>,
+  collapse: Option<WhiteSpaceCollapse>,
+  wrap: Option<TextWrap>,
+}
+
+impl WhiteSpaceHandler {
+  pub fn new(targets: Option<Browsers>) -> WhiteSpaceHandler {
+    WhiteSpaceHandler {
+      targets,
+      ..WhiteSpaceHandler::default()
+    }
+  }
+
+  fn is_supported(&self, feature: compat::Feature) -> bool {
+    self.targets.map_or(true, |targets| feature.is_compatible(targets))
+  }
+}
+
+impl<'i> PropertyHandler<'i> for WhiteSpaceHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::WhiteSpace(val) => {
+        // The shorthand only overrides the longhands that precede it in browsers that support them.
+        if self.collapse.is_some() || self.wrap.is_some() {
+          self.finalize(dest, context);
+        }
+        self.white_space = Some(*val);
+      }
+      Property::WhiteSpaceCollapse(val) => self.collapse = Some(*val),
+      Property::TextWrap(val) => self.wrap = Some(*val),
+      Property::Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::WhiteSpace | PropertyId::WhiteSpaceCollapse | PropertyId::TextWrap
+        ) =>
+      {
+        self.finalize(dest, context);
+        dest.push(property.clone());
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    let white_space = std::mem::take(&mut self.white_space);
+    let collapse = std::mem::take(&mut self.collapse);
+    let wrap = std::mem::take(&mut self.wrap);
+
+    // The longhands can only be combined into an equivalent `white-space` keyword if all targets support
+    // them. Otherwise, browsers that ignore the longhands would see a different value.
+    let is_supported = (collapse.is_none() || self.is_supported(compat::Feature::WhiteSpaceCollapse))
+      && (wrap.is_none() || self.is_supported(compat::Feature::TextWrap));
+    if is_supported {
+      let (merged_collapse, merged_wrap) = match white_space {
+        Some(white_space) => {
+          let (shorthand_collapse, shorthand_wrap) = white_space.to_longhands();
+          (collapse.or(Some(shorthand_collapse)), wrap.or(Some(shorthand_wrap)))
+        }
+        None => (collapse, wrap),
+      };
+
+      if let (Some(collapse), Some(wrap)) = (merged_collapse, merged_wrap) {
+        if let Some(white_space) = WhiteSpace::from_longhands(collapse, wrap) {
+          dest.push(Property::WhiteSpace(white_space));
+          return;
+        }
+      }
+    }
+
+    if let Some(white_space) = white_space {
+      dest.push(Property::WhiteSpace(white_space));
+    }
+
+    if let Some(collapse) = collapse {
+      dest.push(Property::WhiteSpaceCollapse(collapse));
+    }
+
+    if let Some(wrap) = wrap {
+      dest.push(Property::TextWrap(wrap));
+    }
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct OverflowWrapHandler {
+  targets: Option<Browsers>,
+  overflow_wrap: Option<OverflowWrap>,
+  has_overflow_wrap: bool,
+  has_word_wrap: bool,
+  word_break: Option<WordBreak>,
+}
+
+impl OverflowWrapHandler {
+  pub fn new(targets: Option<Browsers>) -> OverflowWrapHandler {
+    OverflowWrapHandler {
+      targets,
+      ..OverflowWrapHandler::default()
+    }
+  }
+
+  fn flush_overflow_wrap<'i>(&mut self, dest: &mut DeclarationList<'i>) {
+    let has_overflow_wrap = std::mem::take(&mut self.has_overflow_wrap);
+    let has_word_wrap = std::mem::take(&mut self.has_word_wrap);
+    if let Some(val) = std::mem::take(&mut self.overflow_wrap) {
+      // With targets, output the standard name, along with the legacy alias if it is not supported.
+      let (overflow_wrap, word_wrap) = match self.targets {
+        Some(targets) => (true, !compat::Feature::OverflowWrap.is_compatible(targets)),
+        None => (has_overflow_wrap, has_word_wrap),
+      };
+
+      if word_wrap {
+        dest.push(Property::WordWrap(val));
+      }
+
+      if overflow_wrap {
+        dest.push(Property::OverflowWrap(val));
+      }
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for OverflowWrapHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::OverflowWrap(val) | Property::WordWrap(val) => {
+        // word-wrap is a legacy alias of overflow-wrap, so the last value wins. Different values
+        // declared with the other name are kept as fallbacks, e.g. for browsers without `anywhere`.
+        let is_word_wrap = matches!(property, Property::WordWrap(..));
+        if let Some(cur) = self.overflow_wrap {
+          let has_name = if is_word_wrap {
+            self.has_word_wrap
+          } else {
+            self.has_overflow_wrap
+          };
+          if cur != *val && !has_name {
+            self.flush_overflow_wrap(dest);
+          }
+        }
+
+        self.overflow_wrap = Some(*val);
+        if is_word_wrap {
+          self.has_word_wrap = true;
+        } else {
+          self.has_overflow_wrap = true;
+        }
+      }
+      Property::WordBreak(val) => self.word_break = Some(*val),
+      Property::Unparsed(val)
+        if matches!(
+          val.property_id,
+          PropertyId::OverflowWrap | PropertyId::WordWrap | PropertyId::WordBreak
+        ) =>
+      {
+        self.finalize(dest, context);
+        dest.push(property.clone());
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, context: &mut PropertyHandlerContext<'i>) {
+    if let Some(word_break) = std::mem::take(&mut self.word_break) {
+      // `word-break: break-word` is equivalent to `word-break: normal` with `overflow-wrap: anywhere`
+      // regardless of the value of overflow-wrap, which is used for targets that do not support it.
+      if word_break == WordBreak::BreakWord
+        && matches!(self.targets, Some(targets) if !compat::Feature::WordBreakBreakWord.is_compatible(targets)
+          && compat::Feature::OverflowWrapAnywhere.is_compatible(targets))
+        && context.apply_optimization(Optimization::WordBreakRewrite)
+      {
+        dest.push(Property::WordBreak(WordBreak::Normal));
+        self.overflow_wrap = Some(OverflowWrap::Anywhere);
+        self.has_overflow_wrap = true;
+      } else {
+        dest.push(Property::WordBreak(word_break));
+      }
+    }
+
+    self.flush_overflow_wrap(dest);
+  }
+}
@@ -1,6 +1,7 @@
 //! CSS properties related to 2D and 3D transforms.
 
 use super::{Property, PropertyId};
+use crate::compat;
 use crate::context::PropertyHandlerContext;
 use crate::declaration::DeclarationList;
 use crate::error::{ParserError, PrinterError};
@@ -14,6 +15,7 @@ use crate::values::{
   angle::Angle,
   length::{Length, LengthPercentage},
   percentage::NumberOrPercentage,
+  position::{HorizontalPosition, Position, VerticalPosition},
 };
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
@@ -52,7 +54,8 @@ impl ToCss for TransformList {
       return Ok(());
     }
 
-    if dest.minify {
+    // Matrices are always in pixels, so skip combining transforms when converting px to rem.
+    if dest.minify && !dest.convert_px {
       // Combine transforms into a single matrix.
       if let Some(matrix) = self.to_matrix() {
         // Generate based on the original transforms.
@@ -1460,7 +1463,16 @@ impl ToCss for Translate {
 impl Translate {
   /// Converts the translation to a transform function.
   pub fn to_transform(&self) -> Transform {
-    Transform::Translate3d(self.x.clone(), self.y.clone(), self.z.clone())
+    if self.z == 0.0 {
+      Transform::Translate(self.x.clone(), self.y.clone())
+    } else {
+      Transform::Translate3d(self.x.clone(), self.y.clone(), self.z.clone())
+    }
+  }
+
+  /// Returns whether the translation has no effect.
+  pub fn is_identity(&self) -> bool {
+    self.x == 0.0 && self.y == 0.0 && self.z == 0.0
   }
 }
 
@@ -1518,16 +1530,17 @@ impl ToCss for Rotate {
   where
     W: std::fmt::Write,
   {
-    if self.x == 0.0 && self.y == 0.0 && self.z == 1.0 && self.angle == 0.0 {
+    if self.is_identity() {
       dest.write_str("none")?;
       return Ok(());
     }
 
-    if self.x == 1.0 && self.y == 0.0 && self.z == 0.0 {
+    // Only the direction of the axis is significant, so e.g. `0 0 2` is equivalent to `z`.
+    if self.x > 0.0 && self.y == 0.0 && self.z == 0.0 {
       dest.write_str("x ")?;
-    } else if self.x == 0.0 && self.y == 1.0 && self.z == 0.0 {
+    } else if self.x == 0.0 && self.y > 0.0 && self.z == 0.0 {
       dest.write_str("y ")?;
-    } else if !(self.x == 0.0 && self.y == 0.0 && self.z == 1.0) {
+    } else if !(self.x == 0.0 && self.y == 0.0 && self.z > 0.0) {
       self.x.to_css(dest)?;
       dest.write_char(' ')?;
       self.y.to_css(dest)?;
@@ -1543,7 +1556,20 @@ impl ToCss for Rotate {
 impl Rotate {
   /// Converts the rotation to a transform function.
   pub fn to_transform(&self) -> Transform {
-    Transform::Rotate3d(self.x, self.y, self.z, self.angle.clone())
+    if self.x == 0.0 && self.y == 0.0 && self.z > 0.0 {
+      Transform::Rotate(self.angle.clone())
+    } else if self.x > 0.0 && self.y == 0.0 && self.z == 0.0 {
+      Transform::RotateX(self.angle.clone())
+    } else if self.x == 0.0 && self.y > 0.0 && self.z == 0.0 {
+      Transform::RotateY(self.angle.clone())
+    } else {
+      Transform::Rotate3d(self.x, self.y, self.z, self.angle.clone())
+    }
+  }
+
+  /// Returns whether the rotation has no effect.
+  pub fn is_identity(&self) -> bool {
+    self.angle == 0.0
   }
 }
 
@@ -1589,13 +1615,17 @@ impl ToCss for Scale {
   where
     W: std::fmt::Write,
   {
-    self.x.to_css(dest)?;
-    if self.y != self.x || self.z != 1.0 {
+    // Percentages are serialized as numbers, which are never longer.
+    let x: f32 = (&self.x).into();
+    let y: f32 = (&self.y).into();
+    let z: f32 = (&self.z).into();
+    x.to_css(dest)?;
+    if y != x || z != 1.0 {
       dest.write_char(' ')?;
-      self.y.to_css(dest)?;
-      if self.z != 1.0 {
+      y.to_css(dest)?;
+      if z != 1.0 {
         dest.write_char(' ')?;
-        self.z.to_css(dest)?;
+        z.to_css(dest)?;
       }
     }
 
@@ -1606,7 +1636,90 @@ impl ToCss for Scale {
 impl Scale {
   /// Converts the scale to a transform function.
   pub fn to_transform(&self) -> Transform {
-    Transform::Scale3d(self.x.clone(), self.y.clone(), self.z.clone())
+    if self.z == 1.0 {
+      Transform::Scale(self.x.clone(), self.y.clone())
+    } else {
+      Transform::Scale3d(self.x.clone(), self.y.clone(), self.z.clone())
+    }
+  }
+
+  /// Returns whether the scale has no effect.
+  pub fn is_identity(&self) -> bool {
+    self.x == 1.0 && self.y == 1.0 && self.z == 1.0
+  }
+}
+
+/// A value for the non-standard [zoom](https://drafts.csswg.org/css-viewport/#zoom-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Zoom {
+  /// The `normal` keyword.
+  Normal,
+  /// The `reset` keyword.
+  Reset,
+  /// A zoom factor.
+  Amount(NumberOrPercentage),
+}
+
+impl<'i> Parse<'i> for Zoom {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(Zoom::Normal);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("reset")).is_ok() {
+      return Ok(Zoom::Reset);
+    }
+
+    let location = input.current_source_location();
+    let amount = NumberOrPercentage::parse(input)?;
+    let value: f32 = (&amount).into();
+    if value < 0.0 {
+      return Err(location.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(Zoom::Amount(amount))
+  }
+}
+
+impl ToCss for Zoom {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      Zoom::Normal => dest.write_str("normal"),
+      Zoom::Reset => dest.write_str("reset"),
+      Zoom::Amount(amount) => amount.to_css(dest),
+    }
+  }
+}
+
+impl Zoom {
+  /// Converts the zoom factor to an equivalent `scale()` transform function.
+  /// Returns `None` for the `normal` and `reset` keywords.
+  pub fn to_transform(&self) -> Option<Transform> {
+    match self {
+      Zoom::Amount(amount) => {
+        let value: f32 = amount.into();
+        let scale = NumberOrPercentage::Number(value);
+        Some(Transform::Scale(scale.clone(), scale))
+      }
+      _ => None,
+    }
+  }
+
+  /// Returns `transform` and `transform-origin` declarations with the given prefix that scale an element
+  /// from its top left corner like the zoom factor. Returns `None` for the `normal` and `reset` keywords.
+  pub(crate) fn to_transform_properties<'i>(&self, prefix: VendorPrefix) -> Option<[Property<'i>; 2]> {
+    let transform = self.to_transform()?;
+    let origin = Position {
+      x: HorizontalPosition::Length(LengthPercentage::zero()),
+      y: VerticalPosition::Length(LengthPercentage::zero()),
+    };
+    Some([
+      Property::Transform(TransformList(vec![transform]), prefix),
+      Property::TransformOrigin(origin, prefix),
+    ])
   }
 }
 
@@ -1618,6 +1731,8 @@ pub(crate) struct TransformHandler {
   rotate: Option<Rotate>,
   scale: Option<Scale>,
   has_any: bool,
+  zoom: Option<Zoom>,
+  has_transform: bool,
 }
 
 impl TransformHandler {
@@ -1634,19 +1749,28 @@ impl<'i> PropertyHandler<'i> for TransformHandler {
     &mut self,
     property: &Property<'i>,
     dest: &mut DeclarationList<'i>,
-    _: &mut PropertyHandlerContext<'i>,
+    context: &mut PropertyHandlerContext<'i>,
   ) -> bool {
     use Property::*;
 
     macro_rules! individual_property {
-      ($prop: ident, $val: ident) => {
-        if let Some((transform, _)) = &mut self.transform {
-          transform.0.push($val.to_transform())
-        } else {
-          self.$prop = Some($val.clone());
-          self.has_any = true;
-        }
-      };
+      ($prop: ident, $val: ident) => {{
+        self.$prop = Some($val.clone());
+        self.has_any = true;
+      }};
+    }
+
+    if context.convert_zoom
+      && matches!(
+        property.property_id(),
+        PropertyId::Transform(_)
+          | PropertyId::TransformOrigin(_)
+          | PropertyId::Translate
+          | PropertyId::Rotate
+          | PropertyId::Scale
+      )
+    {
+      self.has_transform = true;
     }
 
     match property {
@@ -1655,7 +1779,7 @@ impl<'i> PropertyHandler<'i> for TransformHandler {
         // values, we need to flush what we have immediately to preserve order.
         if let Some((cur, prefixes)) = &self.transform {
           if cur != val && !prefixes.contains(*vp) {
-            self.flush(dest);
+            self.flush(dest, context);
           }
         }
 
@@ -1667,10 +1791,6 @@ impl<'i> PropertyHandler<'i> for TransformHandler {
           self.transform = Some((val.clone(), *vp));
           self.has_any = true;
         }
-
-        self.translate = None;
-        self.rotate = None;
-        self.scale = None;
       }
       Translate(val) => individual_property!(translate, val),
       Rotate(val) => individual_property!(rotate, val),
@@ -1681,7 +1801,7 @@ impl<'i> PropertyHandler<'i> for TransformHandler {
           PropertyId::Transform(_) | PropertyId::Translate | PropertyId::Rotate | PropertyId::Scale
         ) =>
       {
-        self.flush(dest);
+        self.flush(dest, context);
         let prop = if matches!(val.property_id, PropertyId::Transform(_)) {
           Property::Unparsed(val.get_prefixed(self.targets, Feature::Transform))
         } else {
@@ -1689,19 +1809,33 @@ impl<'i> PropertyHandler<'i> for TransformHandler {
         };
         dest.push(prop)
       }
+      Zoom(val) => self.zoom = Some(val.clone()),
       _ => return false,
     }
 
     true
   }
 
-  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
-    self.flush(dest);
+  fn finalize(&mut self, dest: &mut DeclarationList, context: &mut PropertyHandlerContext<'i>) {
+    self.flush(dest, context);
+
+    let has_transform = std::mem::take(&mut self.has_transform);
+    if let Some(zoom) = std::mem::take(&mut self.zoom) {
+      // Only convert if the rule doesn't already declare a transform, which the scale would override.
+      if context.convert_zoom && !has_transform && !context.is_supported(compat::Feature::Zoom) {
+        if let Some(properties) = zoom.to_transform_properties(self.transform_prefix(VendorPrefix::None)) {
+          dest.extend(properties);
+          return;
+        }
+      }
+
+      dest.push(Property::Zoom(zoom));
+    }
   }
 }
 
 impl TransformHandler {
-  fn flush(&mut self, dest: &mut DeclarationList) {
+  fn flush(&mut self, dest: &mut DeclarationList, context: &PropertyHandlerContext) {
     if !self.has_any {
       return;
     }
@@ -1713,14 +1847,42 @@ impl TransformHandler {
     let rotate = std::mem::take(&mut self.rotate);
     let scale = std::mem::take(&mut self.scale);
 
-    if let Some((transform, prefix)) = transform {
-      let mut prefix = prefix;
-      if prefix.contains(VendorPrefix::None) {
-        if let Some(targets) = self.targets {
-          prefix = Feature::Transform.prefixes_for(targets)
-        }
+    // When the individual transform properties are not supported, they are converted to functions of an
+    // unprefixed transform. They are applied before the transform property, in the order translate, rotate,
+    // scale. Identity values have no effect.
+    if (translate.is_some() || rotate.is_some() || scale.is_some())
+      && !context.is_supported(compat::Feature::IndividualTransforms)
+      && transform
+        .as_ref()
+        .map_or(true, |(_, prefix)| prefix.contains(VendorPrefix::None))
+    {
+      let mut functions = Vec::new();
+      if let Some(translate) = translate.as_ref().filter(|t| !t.is_identity()) {
+        functions.push(translate.to_transform());
+      }
+      if let Some(rotate) = rotate.as_ref().filter(|r| !r.is_identity()) {
+        functions.push(rotate.to_transform());
+      }
+      if let Some(scale) = scale.as_ref().filter(|s| !s.is_identity()) {
+        functions.push(scale.to_transform());
       }
-      dest.push(Property::Transform(transform, prefix))
+
+      let prefix = match transform {
+        Some((transform, prefix)) => {
+          functions.extend(transform.0);
+          prefix
+        }
+        None => VendorPrefix::None,
+      };
+      dest.push(Property::Transform(
+        TransformList(functions),
+        self.transform_prefix(prefix),
+      ));
+      return;
+    }
+
+    if let Some((transform, prefix)) = transform {
+      dest.push(Property::Transform(transform, self.transform_prefix(prefix)));
     }
 
     if let Some(translate) = translate {
@@ -1735,4 +1897,12 @@ impl TransformHandler {
       dest.push(Property::Scale(scale))
     }
   }
+
+  /// Returns the prefixes to output a transform with. Unprefixed transforms are prefixed for the targets.
+  fn transform_prefix(&self, prefix: VendorPrefix) -> VendorPrefix {
+    match self.targets {
+      Some(targets) if prefix.contains(VendorPrefix::None) => Feature::Transform.prefixes_for(targets),
+      _ => prefix,
+    }
+  }
 }
@@ -8,6 +8,7 @@ use crate::error::{ParserError, PrinterError};
 use crate::prefixes::Feature;
 use crate::printer::Printer;
 use crate::properties::masking::get_webkit_mask_property;
+use crate::stylesheet::Optimization;
 use crate::targets::Browsers;
 use crate::traits::{Parse, PropertyHandler, ToCss};
 use crate::values::{easing::EasingFunction, time::Time};
@@ -274,6 +275,7 @@ impl<'i> TransitionHandler<'i> {
                 b >>= 1;
               }
             }
+            remove_subsumed_transitions(&mut transitions, context);
             transitions
           }};
         }
@@ -339,6 +341,49 @@ impl<'i> TransitionHandler<'i> {
   }
 }
 
+/// Removes transitions that are subsumed by a transition of `all` with the same timing.
+/// Transitions with a different timing than `all` are kept, even if they are overridden.
+fn remove_subsumed_transitions(transitions: &mut SmallVec<[Transition; 1]>, context: &mut PropertyHandlerContext) {
+  let last_all = match transitions.iter().rposition(|t| t.property == PropertyId::All) {
+    Some(last_all) => last_all,
+    None => return,
+  };
+
+  let same_timing = |a: &Transition, b: &Transition| {
+    a.duration == b.duration && a.delay == b.delay && a.timing_function == b.timing_function
+  };
+
+  let keep: Vec<bool> = transitions
+    .iter()
+    .enumerate()
+    .map(|(i, transition)| {
+      if i < last_all {
+        // Everything before the last `all` transition is overridden by it.
+        return !same_timing(transition, &transitions[last_all]);
+      }
+
+      if i == last_all {
+        return true;
+      }
+
+      // The last occurrence of a property determines its timing, so properties
+      // that are listed again after `all` must be kept.
+      let is_repeated = transitions[last_all + 1..]
+        .iter()
+        .enumerate()
+        .any(|(j, t)| last_all + 1 + j != i && t.property == transition.property);
+      is_repeated || !same_timing(transition, &transitions[last_all])
+    })
+    .collect();
+
+  if keep.iter().all(|k| *k) || !context.apply_optimization(Optimization::TransitionAllMerge) {
+    return;
+  }
+
+  let mut keep = keep.into_iter();
+  transitions.retain(|_| keep.next().unwrap_or(true));
+}
+
 #[inline]
 fn is_transition_property(property_id: &PropertyId) -> bool {
   match property_id {
@@ -1,13 +1,19 @@
 //! CSS properties related to user interface.
 
-use crate::error::{ParserError, PrinterError};
+use super::{Property, PropertyId};
+use crate::compat;
+use crate::context::PropertyHandlerContext;
+use crate::declaration::DeclarationList;
+use crate::error::{MinifyWarningKind, ParserError, PrinterError};
 use crate::macros::{enum_property, shorthand_property};
+use crate::prefixes::Feature;
 use crate::printer::Printer;
 use crate::targets::Browsers;
-use crate::traits::{FallbackValues, Parse, ToCss};
-use crate::values::color::CssColor;
+use crate::traits::{FallbackValues, Parse, PropertyHandler, ToCss};
+use crate::values::color::{ColorFallbackKind, CssColor};
 use crate::values::string::CowArcStr;
 use crate::values::url::Url;
+use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
 use smallvec::SmallVec;
 
@@ -265,9 +271,276 @@ enum_property! {
     Contain,
     /// Only the entire element is selectable.
     All,
+    /// The legacy `-ms-user-select` equivalent of `contain`.
+    Element,
   }
 }
 
+#[derive(Default)]
+pub(crate) struct UserSelectHandler {
+  targets: Option<Browsers>,
+  user_select: Option<(UserSelect, VendorPrefix)>,
+}
+
+impl UserSelectHandler {
+  pub fn new(targets: Option<Browsers>) -> UserSelectHandler {
+    UserSelectHandler {
+      targets,
+      ..UserSelectHandler::default()
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for UserSelectHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::UserSelect(val, prefix) => {
+        // Old IE uses `element` rather than `contain`. Normalize so the two can be merged.
+        let val = match val {
+          UserSelect::Element if *prefix == VendorPrefix::Ms => UserSelect::Contain,
+          val => val.clone(),
+        };
+
+        if let Some((cur, prefixes)) = &mut self.user_select {
+          if *cur != val && !prefixes.contains(*prefix) {
+            self.finalize(dest, context);
+            self.user_select = Some((val, *prefix));
+          } else {
+            *cur = val;
+            *prefixes |= *prefix;
+          }
+        } else {
+          self.user_select = Some((val, *prefix));
+        }
+      }
+      Property::Unparsed(unparsed) if matches!(unparsed.property_id, PropertyId::UserSelect(_)) => {
+        self.finalize(dest, context);
+        dest.push(property.clone())
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    if let Some((val, mut prefixes)) = std::mem::take(&mut self.user_select) {
+      if prefixes.contains(VendorPrefix::None) {
+        if let Some(targets) = self.targets {
+          prefixes = Feature::UserSelect.prefixes_for(targets);
+
+          // `contain` has no equivalent in older WebKit and Gecko versions.
+          if val == UserSelect::Contain {
+            prefixes.remove(VendorPrefix::WebKit | VendorPrefix::Moz);
+          }
+        }
+      }
+
+      if val == UserSelect::Contain && prefixes.contains(VendorPrefix::Ms) {
+        dest.push(Property::UserSelect(UserSelect::Element, VendorPrefix::Ms));
+        prefixes.remove(VendorPrefix::Ms);
+      }
+
+      if !prefixes.is_empty() {
+        dest.push(Property::UserSelect(val, prefixes))
+      }
+    }
+  }
+}
+
+/// A color scheme name, as used in the [color-scheme](ColorScheme) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorSchemeName<'i> {
+  /// The light color scheme.
+  Light,
+  /// The dark color scheme.
+  Dark,
+  /// An unknown color scheme, which is ignored by browsers.
+  Custom(CowArcStr<'i>),
+}
+
+impl<'i> Parse<'i> for ColorSchemeName<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    let location = input.current_source_location();
+    let ident = input.expect_ident()?;
+    match_ignore_ascii_case! { &*ident,
+      "light" => Ok(ColorSchemeName::Light),
+      "dark" => Ok(ColorSchemeName::Dark),
+      "normal" | "only" | "initial" | "inherit" | "unset" | "default" | "revert" | "revert-layer" => {
+        Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
+      },
+      _ => Ok(ColorSchemeName::Custom(ident.into()))
+    }
+  }
+}
+
+impl<'i> ToCss for ColorSchemeName<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ColorSchemeName::Light => dest.write_str("light"),
+      ColorSchemeName::Dark => dest.write_str("dark"),
+      ColorSchemeName::Custom(name) => {
+        serialize_identifier(name, dest)?;
+        Ok(())
+      }
+    }
+  }
+}
+
+/// A value for the [color-scheme](https://drafts.csswg.org/css-color-adjust/#color-scheme-prop) property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScheme<'i> {
+  /// The color schemes the element can be rendered with, in order of preference.
+  /// Empty for the `normal` keyword.
+  pub schemes: Vec<ColorSchemeName<'i>>,
+  /// Whether the user agent is forbidden from overriding the color scheme, i.e. the `only` keyword.
+  pub only: bool,
+}
+
+impl<'i> Parse<'i> for ColorScheme<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+      return Ok(ColorScheme {
+        schemes: Vec::new(),
+        only: false,
+      });
+    }
+
+    // The `only` keyword may appear either before or after the list of schemes.
+    let mut only = input.try_parse(|input| input.expect_ident_matching("only")).is_ok();
+    let mut schemes = Vec::new();
+    while let Ok(scheme) = input.try_parse(ColorSchemeName::parse) {
+      schemes.push(scheme);
+    }
+
+    if schemes.is_empty() {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    if !only {
+      only = input.try_parse(|input| input.expect_ident_matching("only")).is_ok();
+    }
+
+    Ok(ColorScheme { schemes, only })
+  }
+}
+
+impl<'i> ToCss for ColorScheme<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if self.schemes.is_empty() {
+      return dest.write_str("normal");
+    }
+
+    let mut first = true;
+    for scheme in &self.schemes {
+      if first {
+        first = false;
+      } else {
+        dest.write_char(' ')?;
+      }
+      scheme.to_css(dest)?;
+    }
+
+    if self.only {
+      dest.write_str(" only")?;
+    }
+    Ok(())
+  }
+}
+
+/// Emits a warning for a property that cannot be polyfilled when the feature is not supported by the targets.
+fn warn_if_unsupported<'i>(
+  property: &Property<'i>,
+  feature: compat::Feature,
+  targets: Option<Browsers>,
+  context: &mut PropertyHandlerContext<'i>,
+) {
+  if let Some(targets) = targets {
+    if !feature.is_compatible(targets) {
+      context.warnings.push(MinifyWarningKind::UnsupportedProperty {
+        property: property.name().into(),
+      });
+    }
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct ColorSchemeHandler {
+  targets: Option<Browsers>,
+}
+
+impl ColorSchemeHandler {
+  pub fn new(targets: Option<Browsers>) -> ColorSchemeHandler {
+    ColorSchemeHandler { targets }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for ColorSchemeHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::ColorScheme(_) => {}
+      Property::Unparsed(unparsed) if unparsed.property_id == PropertyId::ColorScheme => {}
+      _ => return false,
+    }
+
+    // There is no way to polyfill color-scheme, so warn when it is not supported.
+    warn_if_unsupported(property, compat::Feature::ColorScheme, self.targets, context);
+    dest.push(property.clone());
+    true
+  }
+
+  fn finalize(&mut self, _: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {}
+}
+
+#[derive(Default)]
+pub(crate) struct AccentColorHandler {
+  targets: Option<Browsers>,
+}
+
+impl AccentColorHandler {
+  pub fn new(targets: Option<Browsers>) -> AccentColorHandler {
+    AccentColorHandler { targets }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for AccentColorHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    _: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::AccentColor(_) => {}
+      Property::Unparsed(unparsed) if unparsed.property_id == PropertyId::AccentColor => {}
+      _ => return false,
+    }
+
+    // Browsers without accent-color support use their default control colors, so warn rather than fail.
+    // The property is left to the FallbackHandler, which adds the color fallbacks.
+    warn_if_unsupported(property, compat::Feature::AccentColor, self.targets, context);
+    false
+  }
+
+  fn finalize(&mut self, _: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {}
+}
+
 /// A value for the [appearance](https://www.w3.org/TR/2021/WD-css-ui-4-20210316/#appearance-switching) property.
 #[derive(Debug, Clone, PartialEq)]
 #[allow(missing_docs)]
@@ -342,3 +615,271 @@ impl<'i> ToCss for Appearance<'i> {
     }
   }
 }
+
+/// A value for the [scrollbar-gutter](https://drafts.csswg.org/css-overflow-3/#scrollbar-gutter-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollbarGutter {
+  /// Space is reserved for the scrollbar only when the box overflows.
+  Auto,
+  /// Space is always reserved for the scrollbar.
+  Stable,
+  /// Space is always reserved for the scrollbar on both edges of the box.
+  StableBothEdges,
+}
+
+impl<'i> Parse<'i> for ScrollbarGutter {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(ScrollbarGutter::Auto);
+    }
+
+    // The keywords may appear in either order.
+    let both_edges = input.try_parse(|input| input.expect_ident_matching("both-edges")).is_ok();
+    input.expect_ident_matching("stable")?;
+    if both_edges || input.try_parse(|input| input.expect_ident_matching("both-edges")).is_ok() {
+      Ok(ScrollbarGutter::StableBothEdges)
+    } else {
+      Ok(ScrollbarGutter::Stable)
+    }
+  }
+}
+
+impl ToCss for ScrollbarGutter {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ScrollbarGutter::Auto => dest.write_str("auto"),
+      ScrollbarGutter::Stable => dest.write_str("stable"),
+      ScrollbarGutter::StableBothEdges => dest.write_str("stable both-edges"),
+    }
+  }
+}
+
+enum_property! {
+  /// A value for the [scrollbar-width](https://drafts.csswg.org/css-scrollbars-1/#scrollbar-width) property.
+  pub enum ScrollbarWidth {
+    /// The default platform scrollbar width.
+    Auto,
+    /// A thin scrollbar variant, if provided by the platform.
+    Thin,
+    /// No scrollbar is shown, but the element is still scrollable.
+    None,
+  }
+}
+
+/// A value for the [scrollbar-color](https://drafts.csswg.org/css-scrollbars-1/#scrollbar-color) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollbarColor {
+  /// The default platform scrollbar colors.
+  Auto,
+  /// Explicit scrollbar colors.
+  Colors {
+    /// The color of the scrollbar thumb.
+    thumb: CssColor,
+    /// The color of the scrollbar track.
+    track: CssColor,
+  },
+}
+
+impl<'i> Parse<'i> for ScrollbarColor {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(ScrollbarColor::Auto);
+    }
+
+    let thumb = CssColor::parse(input)?;
+    let track = CssColor::parse(input)?;
+    Ok(ScrollbarColor::Colors { thumb, track })
+  }
+}
+
+impl ToCss for ScrollbarColor {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      ScrollbarColor::Auto => dest.write_str("auto"),
+      ScrollbarColor::Colors { thumb, track } => {
+        thumb.to_css(dest)?;
+        dest.write_char(' ')?;
+        track.to_css(dest)
+      }
+    }
+  }
+}
+
+impl FallbackValues for ScrollbarColor {
+  fn get_fallbacks(&mut self, targets: Browsers) -> Vec<Self> {
+    match self {
+      ScrollbarColor::Colors { thumb, track } => {
+        let fallbacks = thumb.get_necessary_fallbacks(targets) | track.get_necessary_fallbacks(targets);
+
+        let mut res = Vec::new();
+        if fallbacks.contains(ColorFallbackKind::RGB) {
+          res.push(ScrollbarColor::Colors {
+            thumb: thumb.to_rgb(),
+            track: track.to_rgb(),
+          });
+        }
+
+        if fallbacks.contains(ColorFallbackKind::P3) {
+          res.push(ScrollbarColor::Colors {
+            thumb: thumb.to_p3(),
+            track: track.to_p3(),
+          });
+        }
+
+        if fallbacks.contains(ColorFallbackKind::LAB) {
+          *thumb = thumb.to_lab();
+          *track = track.to_lab();
+        }
+
+        res
+      }
+      ScrollbarColor::Auto => Vec::new(),
+    }
+  }
+}
+
+enum_property! {
+  /// A horizontal panning gesture allowed by the [touch-action](TouchAction) property.
+  pub enum TouchActionPanX {
+    /// Horizontal panning in either direction.
+    "pan-x": PanX,
+    /// Panning towards the left, i.e. scrolling towards the right.
+    "pan-left": PanLeft,
+    /// Panning towards the right, i.e. scrolling towards the left.
+    "pan-right": PanRight,
+  }
+}
+
+enum_property! {
+  /// A vertical panning gesture allowed by the [touch-action](TouchAction) property.
+  pub enum TouchActionPanY {
+    /// Vertical panning in either direction.
+    "pan-y": PanY,
+    /// Panning upwards, i.e. scrolling downwards.
+    "pan-up": PanUp,
+    /// Panning downwards, i.e. scrolling upwards.
+    "pan-down": PanDown,
+  }
+}
+
+/// A value for the [touch-action](https://w3c.github.io/pointerevents/#the-touch-action-css-property) property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchAction {
+  /// All touch gestures are handled by the browser.
+  Auto,
+  /// No touch gestures are handled by the browser.
+  None,
+  /// Only panning and zooming gestures are handled by the browser.
+  Manipulation,
+  /// A combination of panning and zooming gestures handled by the browser.
+  /// At least one of the gestures is present.
+  Gestures {
+    /// The allowed horizontal panning gesture.
+    pan_x: Option<TouchActionPanX>,
+    /// The allowed vertical panning gesture.
+    pan_y: Option<TouchActionPanY>,
+    /// Whether pinch zooming is allowed.
+    pinch_zoom: bool,
+  },
+}
+
+impl<'i> Parse<'i> for TouchAction {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+      return Ok(TouchAction::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+      return Ok(TouchAction::None);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("manipulation")).is_ok() {
+      return Ok(TouchAction::Manipulation);
+    }
+
+    // Each gesture may appear at most once, in any order.
+    let mut pan_x = None;
+    let mut pan_y = None;
+    let mut pinch_zoom = false;
+    loop {
+      if pan_x.is_none() {
+        if let Ok(val) = input.try_parse(TouchActionPanX::parse) {
+          pan_x = Some(val);
+          continue;
+        }
+      }
+
+      if pan_y.is_none() {
+        if let Ok(val) = input.try_parse(TouchActionPanY::parse) {
+          pan_y = Some(val);
+          continue;
+        }
+      }
+
+      if !pinch_zoom && input.try_parse(|input| input.expect_ident_matching("pinch-zoom")).is_ok() {
+        pinch_zoom = true;
+        continue;
+      }
+
+      break;
+    }
+
+    if pan_x.is_none() && pan_y.is_none() && !pinch_zoom {
+      return Err(input.new_custom_error(ParserError::InvalidValue));
+    }
+
+    Ok(TouchAction::Gestures {
+      pan_x,
+      pan_y,
+      pinch_zoom,
+    })
+  }
+}
+
+impl ToCss for TouchAction {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    match self {
+      TouchAction::Auto => dest.write_str("auto"),
+      TouchAction::None => dest.write_str("none"),
+      TouchAction::Manipulation => dest.write_str("manipulation"),
+      TouchAction::Gestures {
+        pan_x,
+        pan_y,
+        pinch_zoom,
+      } => {
+        // Gestures are serialized in canonical order.
+        let mut needs_space = false;
+        if let Some(pan_x) = pan_x {
+          pan_x.to_css(dest)?;
+          needs_space = true;
+        }
+
+        if let Some(pan_y) = pan_y {
+          if needs_space {
+            dest.write_char(' ')?;
+          }
+          pan_y.to_css(dest)?;
+          needs_space = true;
+        }
+
+        if *pinch_zoom {
+          if needs_space {
+            dest.write_char(' ')?;
+          }
+          dest.write_str("pinch-zoom")?;
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
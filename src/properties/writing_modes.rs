@@ -0,0 +1,146 @@
+//! CSS properties related to writing modes.
+
+use super::{Property, PropertyId};
+use crate::context::PropertyHandlerContext;
+use crate::declaration::DeclarationList;
+use crate::error::{ParserError, PrinterError};
+use crate::macros::enum_property;
+use crate::prefixes::Feature;
+use crate::printer::Printer;
+use crate::targets::Browsers;
+use crate::traits::{Parse, PropertyHandler, ToCss};
+use crate::vendor_prefix::VendorPrefix;
+use cssparser::*;
+
+enum_property! {
+  /// A value for the [writing-mode](https://drafts.csswg.org/css-writing-modes-4/#block-flow) property.
+  pub enum WritingMode {
+    /// Top-to-bottom block flow direction, with horizontal lines.
+    "horizontal-tb": HorizontalTb,
+    /// Right-to-left block flow direction, with vertical lines.
+    "vertical-rl": VerticalRl,
+    /// Left-to-right block flow direction, with vertical lines.
+    "vertical-lr": VerticalLr,
+    /// Right-to-left block flow direction, with vertical lines and all glyphs set sideways.
+    "sideways-rl": SidewaysRl,
+    /// Left-to-right block flow direction, with vertical lines and all glyphs set sideways,
+    /// toward the bottom.
+    "sideways-lr": SidewaysLr,
+    /// The legacy `-ms-writing-mode` equivalent of `horizontal-tb`.
+    "lr-tb": LrTb,
+    /// The legacy `-ms-writing-mode` equivalent of `vertical-rl`.
+    "tb-rl": TbRl,
+    /// The legacy `-ms-writing-mode` equivalent of `vertical-lr`.
+    "tb-lr": TbLr,
+  }
+}
+
+impl WritingMode {
+  /// Converts a legacy SVG 1.1 keyword to the equivalent standard keyword.
+  fn to_standard(&self) -> WritingMode {
+    match self {
+      WritingMode::LrTb => WritingMode::HorizontalTb,
+      WritingMode::TbRl => WritingMode::VerticalRl,
+      WritingMode::TbLr => WritingMode::VerticalLr,
+      val => *val,
+    }
+  }
+
+  /// Converts a standard keyword to the equivalent legacy SVG 1.1 keyword, as used by `-ms-writing-mode`.
+  /// Sideways writing modes have no legacy equivalent.
+  fn to_legacy(&self) -> Option<WritingMode> {
+    match self {
+      WritingMode::HorizontalTb | WritingMode::LrTb => Some(WritingMode::LrTb),
+      WritingMode::VerticalRl | WritingMode::TbRl => Some(WritingMode::TbRl),
+      WritingMode::VerticalLr | WritingMode::TbLr => Some(WritingMode::TbLr),
+      WritingMode::SidewaysRl | WritingMode::SidewaysLr => None,
+    }
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct WritingModeHandler {
+  targets: Option<Browsers>,
+  writing_mode: Option<(WritingMode, VendorPrefix)>,
+}
+
+impl WritingModeHandler {
+  pub fn new(targets: Option<Browsers>) -> WritingModeHandler {
+    WritingModeHandler {
+      targets,
+      ..WritingModeHandler::default()
+    }
+  }
+}
+
+impl<'i> PropertyHandler<'i> for WritingModeHandler {
+  fn handle_property(
+    &mut self,
+    property: &Property<'i>,
+    dest: &mut DeclarationList<'i>,
+    context: &mut PropertyHandlerContext<'i>,
+  ) -> bool {
+    match property {
+      Property::WritingMode(val, prefix) => {
+        // Old IE uses the SVG 1.1 keywords. Normalize so the two can be merged.
+        let val = if *prefix == VendorPrefix::Ms {
+          val.to_standard()
+        } else {
+          *val
+        };
+
+        if let Some((cur, prefixes)) = &mut self.writing_mode {
+          // Sideways writing modes are not prefixed, so a previous value is kept as a fallback for
+          // targets that need the prefixed legacy implementations.
+          let needs_fallback = val.to_legacy().is_none()
+            && cur.to_legacy().is_some()
+            && self.targets.map_or(false, |targets| {
+              Feature::WritingMode.prefixes_for(targets) != VendorPrefix::None
+            });
+          if *cur != val && (!prefixes.contains(*prefix) || needs_fallback) {
+            self.finalize(dest, context);
+            self.writing_mode = Some((val, *prefix));
+          } else {
+            *cur = val;
+            *prefixes |= *prefix;
+          }
+        } else {
+          self.writing_mode = Some((val, *prefix));
+        }
+      }
+      Property::Unparsed(unparsed) if matches!(unparsed.property_id, PropertyId::WritingMode(_)) => {
+        self.finalize(dest, context);
+        dest.push(property.clone())
+      }
+      _ => return false,
+    }
+
+    true
+  }
+
+  fn finalize(&mut self, dest: &mut DeclarationList, _: &mut PropertyHandlerContext<'i>) {
+    if let Some((val, mut prefixes)) = std::mem::take(&mut self.writing_mode) {
+      if prefixes.contains(VendorPrefix::None) {
+        if let Some(targets) = self.targets {
+          prefixes = Feature::WritingMode.prefixes_for(targets);
+
+          // Sideways writing modes have no equivalent in the legacy implementations.
+          if val.to_legacy().is_none() {
+            prefixes = VendorPrefix::None;
+          }
+        }
+      }
+
+      if prefixes.contains(VendorPrefix::Ms) {
+        if let Some(legacy) = val.to_legacy() {
+          dest.push(Property::WritingMode(legacy, VendorPrefix::Ms));
+        }
+        prefixes.remove(VendorPrefix::Ms);
+      }
+
+      if !prefixes.is_empty() {
+        dest.push(Property::WritingMode(val, prefixes))
+      }
+    }
+  }
+}
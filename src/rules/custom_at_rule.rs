@@ -0,0 +1,100 @@
+use super::Location;
+use crate::error::{ParserError, PrinterError};
+use crate::printer::Printer;
+use crate::properties::custom::TokenList;
+use crate::traits::ToCss;
+use crate::values::string::CowArcStr;
+use cssparser::*;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A handler for a custom at-rule, such as `@apply` or `@tailwind`.
+///
+/// Handlers are registered by name in [ParserOptions](crate::stylesheet::ParserOptions).
+/// At-rules with no registered handler are skipped.
+pub trait AtRuleHandler: Debug + Send + Sync {
+  /// Parses the prelude of the at-rule, i.e. everything between the name and the block or semicolon.
+  /// By default, the prelude is stored as raw tokens.
+  fn parse_prelude<'i, 't>(
+    &self,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<TokenList<'i>, ParseError<'i, ParserError<'i>>> {
+    TokenList::parse_raw(input)
+  }
+
+  /// Parses the contents of the block of the at-rule, if any.
+  /// By default, the block is stored as raw tokens.
+  fn parse_block<'i, 't>(
+    &self,
+    input: &mut Parser<'i, 't>,
+  ) -> Result<TokenList<'i>, ParseError<'i, ParserError<'i>>> {
+    TokenList::parse_raw(input)
+  }
+
+  /// Serializes the at-rule, returning `false` to use the default serialization of the raw tokens instead.
+  /// The printer tracks the source map and indentation, and whether the output is minified.
+  fn to_css<'a>(
+    &self,
+    _rule: &CustomAtRule,
+    _dest: &mut Printer<'a, dyn std::fmt::Write + 'a>,
+  ) -> Result<bool, PrinterError> {
+    Ok(false)
+  }
+}
+
+/// The handler for unknown at-rules when [preserve_unknown_at_rules](crate::stylesheet::ParserOptions::preserve_unknown_at_rules)
+/// is enabled, which stores and serializes the raw tokens.
+#[derive(Debug)]
+pub(crate) struct UnknownAtRuleHandler;
+
+impl AtRuleHandler for UnknownAtRuleHandler {}
+
+/// An at-rule parsed by a user-provided [AtRuleHandler](AtRuleHandler), or an unknown at-rule
+/// preserved as is.
+#[derive(Debug, Clone)]
+pub struct CustomAtRule<'i> {
+  pub name: CowArcStr<'i>,
+  pub prelude: TokenList<'i>,
+  pub block: Option<TokenList<'i>>,
+  pub loc: Location,
+  pub(crate) handler: Arc<dyn AtRuleHandler>,
+}
+
+impl<'i> PartialEq for CustomAtRule<'i> {
+  fn eq(&self, other: &CustomAtRule<'i>) -> bool {
+    self.name == other.name && self.prelude == other.prelude && self.block == other.block && self.loc == other.loc
+  }
+}
+
+impl<'i> ToCss for CustomAtRule<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    dest.add_mapping(self.loc);
+    if dest.with_dyn(|dest| self.handler.to_css(self, dest))? {
+      return Ok(());
+    }
+
+    dest.write_char('@')?;
+    dest.write_str(&self.name)?;
+    if !self.prelude.0.is_empty() {
+      dest.write_char(' ')?;
+      self.prelude.to_css(dest, false)?;
+    }
+
+    match &self.block {
+      Some(block) => {
+        dest.whitespace()?;
+        dest.write_char('{')?;
+        dest.indent();
+        dest.newline()?;
+        block.to_css(dest, false)?;
+        dest.dedent();
+        dest.newline()?;
+        dest.write_char('}')
+      }
+      None => dest.write_char(';'),
+    }
+  }
+}
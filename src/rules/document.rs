@@ -1,12 +1,96 @@
 use super::Location;
 use super::{CssRuleList, MinifyContext};
-use crate::error::{MinifyError, PrinterError};
+use crate::error::{MinifyError, ParserError, PrinterError};
 use crate::printer::Printer;
-use crate::traits::ToCss;
+use crate::traits::{Parse, ToCss};
+use crate::values::string::CowArcStr;
+use crate::vendor_prefix::VendorPrefix;
+use cssparser::*;
 
+/// A function in the prelude of a [@document](MozDocumentRule) rule, which matches the url of the document.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DocumentCondition<'i> {
+  /// `url()`, which matches the exact url.
+  Url(CowArcStr<'i>),
+  /// `url-prefix()`, which matches urls starting with the given string. An empty prefix matches every url.
+  UrlPrefix(CowArcStr<'i>),
+  /// `domain()`, which matches urls on the given domain or one of its subdomains.
+  Domain(CowArcStr<'i>),
+  /// `regexp()`, which matches urls that entirely match the given regular expression.
+  Regexp(CowArcStr<'i>),
+}
+
+impl<'i> Parse<'i> for DocumentCondition<'i> {
+  fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
+    if let Ok(url) = input.try_parse(|input| input.expect_url()) {
+      return Ok(DocumentCondition::Url(url.into()));
+    }
+
+    let location = input.current_source_location();
+    let name = input.expect_function()?.clone();
+    input.parse_nested_block(|input| {
+      match_ignore_ascii_case! { &name,
+        // A quoted url is a function token rather than a url token, e.g. `url("https://example.com/")`.
+        "url" => Ok(DocumentCondition::Url(input.expect_string_cloned()?.into())),
+        "url-prefix" => Ok(DocumentCondition::UrlPrefix(parse_string_or_raw(input)?)),
+        "domain" => Ok(DocumentCondition::Domain(parse_string_or_raw(input)?)),
+        "regexp" => Ok(DocumentCondition::Regexp(input.expect_string_cloned()?.into())),
+        _ => Err(location.new_unexpected_token_error(Token::Function(name.clone())))
+      }
+    })
+  }
+}
+
+/// Parses a quoted string, or the raw source text of the remaining tokens as Gecko does,
+/// e.g. `url-prefix(https://)`.
+fn parse_string_or_raw<'i, 't>(
+  input: &mut Parser<'i, 't>,
+) -> Result<CowArcStr<'i>, ParseError<'i, ParserError<'i>>> {
+  if let Ok(s) = input.try_parse(|input| -> Result<CowRcStr<'i>, ParseError<'i, ParserError<'i>>> {
+    let s = input.expect_string_cloned()?;
+    input.expect_exhausted()?;
+    Ok(s)
+  }) {
+    return Ok(s.into());
+  }
+
+  let start = input.position();
+  while input.next().is_ok() {}
+  Ok(input.slice_from(start).trim().into())
+}
+
+impl<'i> ToCss for DocumentCondition<'i> {
+  fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    let (name, value) = match self {
+      DocumentCondition::Url(url) => ("url", url),
+      DocumentCondition::UrlPrefix(prefix) => ("url-prefix", prefix),
+      DocumentCondition::Domain(domain) => ("domain", domain),
+      DocumentCondition::Regexp(regexp) => ("regexp", regexp),
+    };
+
+    dest.write_str(name)?;
+    dest.write_char('(')?;
+    if !value.is_empty() {
+      serialize_string(value, dest)?;
+    }
+    dest.write_char(')')
+  }
+}
+
+/// A [@document](https://www.w3.org/TR/2012/WD-css3-conditional-20120911/#at-document) rule.
+///
+/// Only `@-moz-document url-prefix()`, a legacy hack to target Firefox, is parsed by default.
+/// Other matching functions and the unprefixed `@document` rule are parsed when
+/// [document_rules](crate::stylesheet::ParserOptions::document_rules) is enabled.
 #[derive(Debug, PartialEq, Clone)]
 pub struct MozDocumentRule<'i> {
+  /// The matching functions. The rule applies if any of them match.
+  pub conditions: Vec<DocumentCondition<'i>>,
   pub rules: CssRuleList<'i>,
+  pub vendor_prefix: VendorPrefix,
   pub loc: Location,
 }
 
@@ -22,7 +106,18 @@ impl<'i> ToCss for MozDocumentRule<'i> {
     W: std::fmt::Write,
   {
     dest.add_mapping(self.loc);
-    dest.write_str("@-moz-document url-prefix()")?;
+    dest.write_char('@')?;
+    self.vendor_prefix.to_css(dest)?;
+    dest.write_str("document ")?;
+    let mut first = true;
+    for condition in &self.conditions {
+      if first {
+        first = false;
+      } else {
+        dest.delim(',', false)?;
+      }
+      condition.to_css(dest)?;
+    }
     dest.whitespace()?;
     dest.write_char('{')?;
     dest.indent();
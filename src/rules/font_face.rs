@@ -374,6 +374,32 @@ impl<'i> AtRuleParser<'i> for FontFaceDeclarationParser {
   type Error = ParserError<'i>;
 }
 
+impl<'i> FontFaceRule<'i> {
+  /// Returns whether the two rules define the same font face, i.e. they have the same
+  /// descriptors regardless of order. When a descriptor is repeated, only the last one is used.
+  /// Urls in `src` are compared by value, so duplicates at different locations are equivalent.
+  pub(crate) fn is_equivalent(&self, other: &FontFaceRule<'i>) -> bool {
+    let a = self.descriptors();
+    let b = other.descriptors();
+    a.len() == b.len() && a.iter().all(|property| b.contains(property))
+  }
+
+  fn descriptors(&self) -> Vec<&FontFaceProperty<'i>> {
+    let mut descriptors: Vec<&FontFaceProperty<'i>> = Vec::new();
+    for property in self.properties.iter().rev() {
+      let is_same = |other: &&FontFaceProperty<'i>| match (property, *other) {
+        (FontFaceProperty::Custom(a), FontFaceProperty::Custom(b)) => a.name == b.name,
+        _ => std::mem::discriminant(property) == std::mem::discriminant(*other),
+      };
+
+      if !descriptors.iter().any(is_same) {
+        descriptors.push(property);
+      }
+    }
+    descriptors
+  }
+}
+
 impl<'i> ToCss for FontFaceRule<'i> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
@@ -4,6 +4,7 @@ use crate::error::{ParserError, PrinterError};
 use crate::printer::Printer;
 use crate::properties::custom::CustomProperty;
 use crate::properties::font::FontFamily;
+use crate::stylesheet::Optimization;
 use crate::targets::Browsers;
 use crate::traits::{Parse, ToCss};
 use crate::values::color::{ColorFallbackKind, CssColor};
@@ -206,7 +207,13 @@ impl<'i> FontPaletteValuesRule<'i> {
             }
 
             let override_colors = if fallbacks.contains(ColorFallbackKind::LAB) {
-              override_colors.iter().map(|o| o.get_fallback(ColorFallbackKind::P3)).collect()
+              let folded = override_colors.iter().map(|o| o.get_fallback(ColorFallbackKind::P3)).collect();
+              if context.apply_optimization(Optimization::ColorFold, self.loc) {
+                folded
+              } else {
+                properties.push(FontPaletteValuesProperty::OverrideColors(folded));
+                override_colors.clone()
+              }
             } else {
               override_colors.clone()
             };
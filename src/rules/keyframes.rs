@@ -7,11 +7,14 @@ use crate::error::{ParserError, PrinterError};
 use crate::parser::ParserOptions;
 use crate::printer::Printer;
 use crate::properties::custom::{CustomProperty, UnparsedProperty};
-use crate::properties::Property;
+use crate::properties::size::Size;
+use crate::properties::{Property, PropertyId};
 use crate::targets::Browsers;
 use crate::traits::{Parse, ToCss};
-use crate::values::color::ColorFallbackKind;
+use crate::values::color::{ColorFallbackKind, CssColor};
+use crate::values::easing::EasingFunction;
 use crate::values::ident::CustomIdent;
+use crate::values::length::LengthPercentage;
 use crate::values::percentage::Percentage;
 use crate::vendor_prefix::VendorPrefix;
 use cssparser::*;
@@ -34,7 +37,63 @@ impl<'i> KeyframesRule<'i> {
         .minify(context.handler, context.important_handler, context.handler_context)
     }
 
+    if context.remove_interpolated_keyframes {
+      self.remove_interpolated_keyframes();
+    }
+
     context.handler_context.context = DeclarationContext::None;
+    context.forward_handler_warnings(self.loc);
+  }
+
+  /// Removes keyframes whose declarations are equal to the linear interpolation of the previous
+  /// and next keyframes, e.g. `50% { opacity: .5 }` between `from { opacity: 0 }` and `to { opacity: 1 }`.
+  fn remove_interpolated_keyframes(&mut self) {
+    // Only keyframes with a single selector in ascending order are supported. Each keyframe
+    // must set the same properties, and timing functions other than `linear` are unsafe.
+    let mut offsets = Vec::with_capacity(self.keyframes.len());
+    for keyframe in &self.keyframes {
+      let offset = match keyframe.selectors.as_slice() {
+        [KeyframeSelector::From] => 0.0,
+        [KeyframeSelector::To] => 1.0,
+        [KeyframeSelector::Percentage(Percentage(p))] => *p,
+        _ => return,
+      };
+
+      if matches!(offsets.last(), Some(last) if *last >= offset)
+        || !keyframe.declarations.important_declarations.is_empty()
+        || !keyframe.declarations.apply.is_empty()
+        || keyframe.declarations.declarations.iter().any(|property| match property {
+          Property::AnimationTimingFunction(easing, _) => easing.iter().any(|e| *e != EasingFunction::Linear),
+          Property::Unparsed(unparsed) => matches!(unparsed.property_id, PropertyId::AnimationTimingFunction(_)),
+          _ => false,
+        })
+      {
+        return;
+      }
+
+      offsets.push(offset);
+    }
+
+    let mut prev = 0;
+    let mut i = 1;
+    let mut remove = vec![false; self.keyframes.len()];
+    while i + 1 < self.keyframes.len() {
+      let t = (offsets[i] - offsets[prev]) / (offsets[i + 1] - offsets[prev]);
+      if is_interpolated_block(
+        &self.keyframes[prev].declarations.declarations,
+        &self.keyframes[i].declarations.declarations,
+        &self.keyframes[i + 1].declarations.declarations,
+        t,
+      ) {
+        remove[i] = true;
+      } else {
+        prev = i;
+      }
+      i += 1;
+    }
+
+    let mut remove = remove.into_iter();
+    self.keyframes.retain(|_| !remove.next().unwrap());
   }
 
   pub(crate) fn get_fallbacks(&mut self, targets: Browsers) -> Vec<CssRule<'i>> {
@@ -105,6 +164,7 @@ impl<'i> KeyframesRule<'i> {
               _ => property.clone(),
             })
             .collect(),
+          apply: keyframe.declarations.apply.clone(),
         },
       })
       .collect();
@@ -173,6 +233,96 @@ impl<'i> ToCss for KeyframesRule<'i> {
   }
 }
 
+/// Returns whether each declaration in `mid` is equal to the interpolation of the same
+/// property in `prev` and `next` at progress `t`.
+fn is_interpolated_block(prev: &[Property], mid: &[Property], next: &[Property], t: f32) -> bool {
+  if prev.len() != mid.len() || next.len() != mid.len() {
+    return false;
+  }
+
+  mid.iter().all(|property| {
+    let id = property.property_id();
+    match (
+      prev.iter().find(|p| p.property_id() == id),
+      next.iter().find(|p| p.property_id() == id),
+    ) {
+      (Some(prev), Some(next)) => is_interpolated(prev, property, next, t),
+      _ => false,
+    }
+  })
+}
+
+fn is_interpolated(prev: &Property, mid: &Property, next: &Property, t: f32) -> bool {
+  if mid == prev && mid == next {
+    return true;
+  }
+
+  match (prev, mid, next) {
+    (Property::Opacity(a), Property::Opacity(b), Property::Opacity(c)) => approx_eq(lerp(a.0, c.0, t), b.0),
+    (Property::Color(a), Property::Color(b), Property::Color(c))
+    | (Property::BackgroundColor(a), Property::BackgroundColor(b), Property::BackgroundColor(c)) => {
+      is_interpolated_color(a, b, c, t)
+    }
+    (
+      Property::Width(Size::LengthPercentage(a)),
+      Property::Width(Size::LengthPercentage(b)),
+      Property::Width(Size::LengthPercentage(c)),
+    )
+    | (
+      Property::Height(Size::LengthPercentage(a)),
+      Property::Height(Size::LengthPercentage(b)),
+      Property::Height(Size::LengthPercentage(c)),
+    ) => is_interpolated_length(a, b, c, t),
+    _ => false,
+  }
+}
+
+fn is_interpolated_length(a: &LengthPercentage, b: &LengthPercentage, c: &LengthPercentage, t: f32) -> bool {
+  match (a, b, c) {
+    (LengthPercentage::Dimension(a), LengthPercentage::Dimension(b), LengthPercentage::Dimension(c)) => {
+      let ((a, a_unit), (b, b_unit), (c, c_unit)) = (a.to_unit_value(), b.to_unit_value(), c.to_unit_value());
+      a_unit == b_unit && b_unit == c_unit && approx_eq(lerp(a, c, t), b)
+    }
+    (LengthPercentage::Percentage(a), LengthPercentage::Percentage(b), LengthPercentage::Percentage(c)) => {
+      approx_eq(lerp(a.0, c.0, t), b.0)
+    }
+    _ => false,
+  }
+}
+
+/// Colors are interpolated in sRGB with premultiplied alpha, as for transitions and animations.
+/// Other color spaces are not supported.
+fn is_interpolated_color(a: &CssColor, b: &CssColor, c: &CssColor, t: f32) -> bool {
+  match (a, b, c) {
+    (CssColor::RGBA(a), CssColor::RGBA(b), CssColor::RGBA(c)) => {
+      let alpha = lerp(a.alpha as f32, c.alpha as f32, t);
+      if (alpha - b.alpha as f32).abs() > 0.5 {
+        return false;
+      }
+
+      let channel = |x: u8, y: u8, z: u8| {
+        let value = if alpha == 0.0 {
+          0.0
+        } else {
+          lerp(x as f32 * a.alpha as f32, z as f32 * c.alpha as f32, t) / alpha
+        };
+        b.alpha == 0 || (value - y as f32).abs() <= 0.5
+      };
+
+      channel(a.red, b.red, c.red) && channel(a.green, b.green, c.green) && channel(a.blue, b.blue, c.blue)
+    }
+    _ => false,
+  }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+  a + (b - a) * t
+}
+
+fn approx_eq(a: f32, b: f32) -> bool {
+  (a - b).abs() <= 1e-5 * a.abs().max(b.abs()).max(1.0)
+}
+
 /// https://drafts.csswg.org/css-animations/#typedef-keyframe-selector
 #[derive(Debug, PartialEq, Clone)]
 pub enum KeyframeSelector {
@@ -1,5 +1,5 @@
-use super::{CssRuleList, Location};
-use crate::error::{ParserError, PrinterError};
+use super::{CssRuleList, Location, MinifyContext};
+use crate::error::{MinifyError, ParserError, PrinterError};
 use crate::printer::Printer;
 use crate::traits::{Parse, ToCss};
 use crate::values::string::CowArcStr;
@@ -96,6 +96,19 @@ pub struct LayerBlockRule<'i> {
   pub loc: Location,
 }
 
+impl<'i> LayerBlockRule<'i> {
+  pub(crate) fn minify(
+    &mut self,
+    context: &mut MinifyContext<'_, 'i>,
+    parent_is_unused: bool,
+  ) -> Result<bool, MinifyError> {
+    self.rules.minify(context, parent_is_unused)?;
+
+    // Named layers establish the layer order even when empty, so only anonymous layers can be removed.
+    Ok(self.rules.0.is_empty() && self.name.is_none())
+  }
+}
+
 impl<'i> ToCss for LayerBlockRule<'i> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
@@ -19,12 +19,25 @@ impl<'i> MediaRule<'i> {
     context: &mut MinifyContext<'_, 'i>,
     parent_is_unused: bool,
   ) -> Result<bool, MinifyError> {
-    self.rules.minify(context, parent_is_unused)?;
+    // Rules that are already conditional on prefers-reduced-motion don't need another guard.
+    let in_reduced_motion_query = context.in_reduced_motion_query;
+    if self.query.has_feature("prefers-reduced-motion") {
+      context.in_reduced_motion_query = true;
+    }
+    let res = self.rules.minify(context, parent_is_unused);
+    context.in_reduced_motion_query = in_reduced_motion_query;
+    res?;
 
     if let Some(custom_media) = &context.custom_media {
       self.query.transform_custom_media(self.loc, custom_media)?;
     }
 
+    self.query.simplify(*context.targets);
+
+    if let Some(targets) = context.targets {
+      self.query.transform_resolution(*targets);
+    }
+
     Ok(self.rules.0.is_empty() || self.query.never_matches())
   }
 }
@@ -1,4 +1,5 @@
 pub mod counter_style;
+pub mod custom_at_rule;
 pub mod custom_media;
 pub mod document;
 pub mod font_face;
@@ -19,20 +20,23 @@ use self::font_palette_values::FontPaletteValuesRule;
 use self::layer::{LayerBlockRule, LayerStatementRule};
 use self::property::PropertyRule;
 use crate::context::PropertyHandlerContext;
-use crate::declaration::DeclarationHandler;
+use crate::declaration::{DeclarationBlock, DeclarationHandler};
 use crate::dependencies::{Dependency, ImportDependency};
-use crate::error::{MinifyError, ParserError, PrinterError};
-use crate::parser::TopLevelRuleParser;
+use crate::error::{MinifyError, MinifyWarning, MinifyWarningKind, ParserError, PrinterError};
+use crate::media_query::MediaContext;
+use crate::parser::{with_max_nesting_depth, TopLevelRuleParser};
 use crate::prefixes::Feature;
-use crate::printer::Printer;
-use crate::selector::{downlevel_selectors, get_prefix, is_equivalent};
-use crate::stylesheet::ParserOptions;
+use crate::printer::{Printer, PrinterOptions};
+use crate::properties::{Property, PropertyId};
+use crate::selector::{downlevel_selectors, get_prefix, is_equivalent, Selectors};
+use crate::stylesheet::{Optimization, ParserOptions, ReducedMotion, UnsupportedHasBehavior};
 use crate::targets::Browsers;
 use crate::traits::ToCss;
 use crate::values::string::CowArcStr;
 use crate::vendor_prefix::VendorPrefix;
 use counter_style::CounterStyleRule;
 use cssparser::{parse_one_rule, ParseError, Parser, ParserInput};
+use custom_at_rule::CustomAtRule;
 use custom_media::CustomMediaRule;
 use document::MozDocumentRule;
 use font_face::FontFaceRule;
@@ -42,6 +46,8 @@ use media::MediaRule;
 use namespace::NamespaceRule;
 use nesting::NestingRule;
 use page::PageRule;
+use parcel_selectors::parser::{Combinator, Component, Selector};
+use parcel_selectors::SelectorList;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use style::StyleRule;
@@ -72,6 +78,21 @@ pub struct Location {
   /// The column number within a line, starting at 1 for first the character of the line.
   /// Column numbers are counted in UTF-16 code units.
   pub column: u32,
+  /// Whether the rule was generated during minification, e.g. as a fallback, rather than
+  /// written by the author. The line and column then refer to the original rule it was
+  /// generated from, and source maps leave the rule unmapped.
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  pub generated: bool,
+}
+
+impl Location {
+  /// Returns a copy of the location, marked as generated.
+  pub fn as_generated(&self) -> Location {
+    Location {
+      generated: true,
+      ..*self
+    }
+  }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -93,6 +114,7 @@ pub enum CssRule<'i> {
   LayerStatement(LayerStatementRule<'i>),
   LayerBlock(LayerBlockRule<'i>),
   Property(PropertyRule<'i>),
+  Custom(CustomAtRule<'i>),
   Ignored,
 }
 
@@ -123,6 +145,7 @@ impl<'a, 'i> ToCssWithContext<'a, 'i> for CssRule<'i> {
       CssRule::LayerStatement(layer) => layer.to_css(dest),
       CssRule::LayerBlock(layer) => layer.to_css(dest),
       CssRule::Property(property) => property.to_css(dest),
+      CssRule::Custom(rule) => rule.to_css(dest),
       CssRule::Ignored => Ok(()),
     }
   }
@@ -134,7 +157,9 @@ impl<'i> CssRule<'i> {
     input: &mut Parser<'i, 't>,
     options: &ParserOptions,
   ) -> Result<Self, ParseError<'i, ParserError<'i>>> {
-    let (_, rule) = parse_one_rule(input, &mut TopLevelRuleParser::new(&options))?;
+    let (_, rule) = with_max_nesting_depth(options.max_nesting_depth, || {
+      parse_one_rule(input, &mut TopLevelRuleParser::new(&options))
+    })?;
     Ok(rule)
   }
 
@@ -144,6 +169,68 @@ impl<'i> CssRule<'i> {
     let mut parser = Parser::new(&mut input);
     Self::parse(&mut parser, &options)
   }
+
+  /// Returns the source location of the rule, or `None` for ignored rules.
+  pub fn loc(&self) -> Option<Location> {
+    match self {
+      CssRule::Media(rule) => Some(rule.loc),
+      CssRule::Import(rule) => Some(rule.loc),
+      CssRule::Style(rule) => Some(rule.loc),
+      CssRule::Keyframes(rule) => Some(rule.loc),
+      CssRule::FontFace(rule) => Some(rule.loc),
+      CssRule::FontPaletteValues(rule) => Some(rule.loc),
+      CssRule::Page(rule) => Some(rule.loc),
+      CssRule::Supports(rule) => Some(rule.loc),
+      CssRule::CounterStyle(rule) => Some(rule.loc),
+      CssRule::Namespace(rule) => Some(rule.loc),
+      CssRule::MozDocument(rule) => Some(rule.loc),
+      CssRule::Nesting(rule) => Some(rule.loc),
+      CssRule::Viewport(rule) => Some(rule.loc),
+      CssRule::CustomMedia(rule) => Some(rule.loc),
+      CssRule::LayerStatement(rule) => Some(rule.loc),
+      CssRule::LayerBlock(rule) => Some(rule.loc),
+      CssRule::Property(rule) => Some(rule.loc),
+      CssRule::Custom(rule) => Some(rule.loc),
+      CssRule::Ignored => None,
+    }
+  }
+
+  pub(crate) fn set_loc(&mut self, loc: Location) {
+    match self {
+      CssRule::Media(rule) => rule.loc = loc,
+      CssRule::Import(rule) => rule.loc = loc,
+      CssRule::Style(rule) => rule.loc = loc,
+      CssRule::Keyframes(rule) => rule.loc = loc,
+      CssRule::FontFace(rule) => rule.loc = loc,
+      CssRule::FontPaletteValues(rule) => rule.loc = loc,
+      CssRule::Page(rule) => rule.loc = loc,
+      CssRule::Supports(rule) => rule.loc = loc,
+      CssRule::CounterStyle(rule) => rule.loc = loc,
+      CssRule::Namespace(rule) => rule.loc = loc,
+      CssRule::MozDocument(rule) => rule.loc = loc,
+      CssRule::Nesting(rule) => rule.loc = loc,
+      CssRule::Viewport(rule) => rule.loc = loc,
+      CssRule::CustomMedia(rule) => rule.loc = loc,
+      CssRule::LayerStatement(rule) => rule.loc = loc,
+      CssRule::LayerBlock(rule) => rule.loc = loc,
+      CssRule::Property(rule) => rule.loc = loc,
+      CssRule::Custom(rule) => rule.loc = loc,
+      CssRule::Ignored => {}
+    }
+  }
+
+  /// Returns whether the rule, or any rule nested within it, declares one of the given custom properties.
+  pub(crate) fn declares_custom_property(&self, names: &[CowArcStr<'i>]) -> bool {
+    match self {
+      CssRule::Style(style) => style.declares_custom_property(names),
+      CssRule::Nesting(nesting) => nesting.style.declares_custom_property(names),
+      CssRule::Media(media) => media.rules.declares_custom_property(names),
+      CssRule::Supports(supports) => supports.rules.declares_custom_property(names),
+      CssRule::LayerBlock(layer) => layer.rules.declares_custom_property(names),
+      CssRule::MozDocument(document) => document.rules.declares_custom_property(names),
+      _ => false,
+    }
+  }
 }
 
 impl<'i> ToCss for CssRule<'i> {
@@ -164,18 +251,281 @@ pub(crate) struct MinifyContext<'a, 'i> {
   pub important_handler: &'a mut DeclarationHandler<'i>,
   pub handler_context: &'a mut PropertyHandlerContext<'i>,
   pub unused_symbols: &'a HashSet<String>,
-  pub custom_media: Option<HashMap<CowArcStr<'i>, CustomMediaRule<'i>>>,
+  pub custom_media: Option<&'a HashMap<CowArcStr<'i>, CustomMediaRule<'i>>>,
+  pub reduced_motion: Option<ReducedMotion>,
+  pub in_reduced_motion_query: bool,
+  pub preserve_empty_rules: bool,
+  pub remove_interpolated_keyframes: bool,
+  pub dedupe_font_faces: bool,
+  pub nest_rules: bool,
+  pub unsupported_has: UnsupportedHasBehavior,
+  pub warn_optimizations: bool,
+  pub warnings: Vec<MinifyWarning>,
+}
+
+impl<'a, 'i> MinifyContext<'a, 'i> {
+  /// Creates a context with the same options as this one that uses the given handlers,
+  /// e.g. to minify rules in parallel.
+  #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+  fn fork<'b>(
+    &'b self,
+    handler: &'b mut DeclarationHandler<'i>,
+    important_handler: &'b mut DeclarationHandler<'i>,
+    handler_context: &'b mut PropertyHandlerContext<'i>,
+  ) -> MinifyContext<'b, 'i> {
+    MinifyContext {
+      targets: self.targets,
+      handler,
+      important_handler,
+      handler_context,
+      unused_symbols: self.unused_symbols,
+      custom_media: self.custom_media,
+      reduced_motion: self.reduced_motion,
+      in_reduced_motion_query: self.in_reduced_motion_query,
+      preserve_empty_rules: self.preserve_empty_rules,
+      remove_interpolated_keyframes: self.remove_interpolated_keyframes,
+      dedupe_font_faces: self.dedupe_font_faces,
+      nest_rules: self.nest_rules,
+      unsupported_has: self.unsupported_has,
+      warn_optimizations: self.warn_optimizations,
+      warnings: Vec::new(),
+    }
+  }
+
+  /// Returns whether the optimization is enabled, and emits a warning for it at the given location
+  /// if requested. This should only be called once all other conditions for applying it are met.
+  fn apply_optimization(&mut self, optimization: Optimization, loc: Location) -> bool {
+    if self.handler_context.disabled_optimizations.contains(&optimization) {
+      return false;
+    }
+
+    if self.warn_optimizations {
+      self.warnings.push(MinifyWarning {
+        kind: MinifyWarningKind::OptimizationApplied { optimization },
+        loc,
+      });
+    }
+    true
+  }
+
+  /// Moves the warnings emitted by the declaration handlers into this context at the location of the rule
+  /// whose declarations were minified.
+  pub fn forward_handler_warnings(&mut self, loc: Location) {
+    self
+      .warnings
+      .extend(self.handler_context.warnings.drain(..).map(|kind| MinifyWarning { kind, loc }));
+  }
 }
 
 impl<'i> CssRuleList<'i> {
+  /// Moves the locations of all rules, including nested rules, from the source index `from`
+  /// to `from + offset`, and other source indices by the same amount relative to `from`.
+  pub(crate) fn update_source_index(&mut self, from: u32, offset: u32) {
+    macro_rules! update {
+      ($loc: expr) => {
+        $loc.source_index = $loc.source_index.saturating_sub(from) + offset
+      };
+    }
+
+    for rule in &mut self.0 {
+      match rule {
+        CssRule::Media(media) => {
+          update!(media.loc);
+          media.rules.update_source_index(from, offset);
+        }
+        CssRule::Import(import) => update!(import.loc),
+        CssRule::Style(style) => {
+          update!(style.loc);
+          style.rules.update_source_index(from, offset);
+        }
+        CssRule::Keyframes(keyframes) => update!(keyframes.loc),
+        CssRule::FontFace(font_face) => update!(font_face.loc),
+        CssRule::FontPaletteValues(font_palette_values) => update!(font_palette_values.loc),
+        CssRule::Page(page) => update!(page.loc),
+        CssRule::Supports(supports) => {
+          update!(supports.loc);
+          supports.rules.update_source_index(from, offset);
+        }
+        CssRule::CounterStyle(counter_style) => update!(counter_style.loc),
+        CssRule::Namespace(namespace) => update!(namespace.loc),
+        CssRule::MozDocument(document) => {
+          update!(document.loc);
+          document.rules.update_source_index(from, offset);
+        }
+        CssRule::Nesting(nesting) => {
+          update!(nesting.loc);
+          update!(nesting.style.loc);
+          nesting.style.rules.update_source_index(from, offset);
+        }
+        CssRule::Viewport(viewport) => update!(viewport.loc),
+        CssRule::CustomMedia(custom_media) => update!(custom_media.loc),
+        CssRule::LayerStatement(layer) => update!(layer.loc),
+        CssRule::LayerBlock(layer) => {
+          update!(layer.loc);
+          layer.rules.update_source_index(from, offset);
+        }
+        CssRule::Property(property) => update!(property.loc),
+        CssRule::Custom(custom) => update!(custom.loc),
+        CssRule::Ignored => {}
+      }
+    }
+  }
+
+  /// Removes `@import` rules that follow other rules, which browsers ignore. This can happen when
+  /// style sheets are combined, e.g. with [concat](crate::stylesheet::StyleSheet::concat).
+  /// Only `@layer` statements may precede `@import` rules, and they cannot be interleaved.
+  pub(crate) fn remove_misplaced_imports(&mut self, warnings: &mut Vec<MinifyWarning>) {
+    let mut allow_imports = true;
+    let mut has_imports = false;
+    self.0.retain(|rule| {
+      match rule {
+        CssRule::Import(import) => {
+          if !allow_imports {
+            warnings.push(MinifyWarning {
+              kind: MinifyWarningKind::MisplacedImportRule,
+              loc: import.loc,
+            });
+            return false;
+          }
+          has_imports = true;
+        }
+        CssRule::LayerStatement(_) if !has_imports => {}
+        CssRule::Ignored => {}
+        _ => allow_imports = false,
+      }
+      true
+    });
+  }
+
+  pub(crate) fn apply_utilities(
+    &mut self,
+    utilities: &HashMap<String, DeclarationBlock<'i>>,
+  ) -> Result<(), MinifyError> {
+    for rule in &mut self.0 {
+      match rule {
+        CssRule::Style(style) => style.apply_utilities(utilities)?,
+        CssRule::Nesting(nesting) => nesting.style.apply_utilities(utilities)?,
+        CssRule::Media(media) => media.rules.apply_utilities(utilities)?,
+        CssRule::Supports(supports) => supports.rules.apply_utilities(utilities)?,
+        CssRule::LayerBlock(layer) => layer.rules.apply_utilities(utilities)?,
+        CssRule::MozDocument(document) => document.rules.apply_utilities(utilities)?,
+        _ => {}
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Counts the style rules, including nested ones, that declare each property.
+  pub(crate) fn count_property_ids(&self, counts: &mut HashMap<PropertyId<'i>, usize>) {
+    for rule in &self.0 {
+      let style = match rule {
+        CssRule::Style(style) => style,
+        CssRule::Nesting(nesting) => &nesting.style,
+        CssRule::Media(media) => {
+          media.rules.count_property_ids(counts);
+          continue;
+        }
+        CssRule::Supports(supports) => {
+          supports.rules.count_property_ids(counts);
+          continue;
+        }
+        CssRule::LayerBlock(layer) => {
+          layer.rules.count_property_ids(counts);
+          continue;
+        }
+        CssRule::MozDocument(document) => {
+          document.rules.count_property_ids(counts);
+          continue;
+        }
+        _ => continue,
+      };
+
+      for id in style.declarations.property_ids() {
+        *counts.entry(id).or_insert(0) += 1;
+      }
+      style.rules.count_property_ids(counts);
+    }
+  }
+
   pub(crate) fn minify(
     &mut self,
     context: &mut MinifyContext<'_, 'i>,
     parent_is_unused: bool,
+  ) -> Result<(), MinifyError> {
+    self.minify_with(context, parent_is_unused, Vec::new())
+  }
+
+  /// Minifies the rules in parallel, and then merges adjacent rules serially in source order.
+  /// Only the independent work within each rule is parallelized, so the result is identical
+  /// to [minify](CssRuleList::minify).
+  #[cfg(feature = "parallel")]
+  pub(crate) fn minify_parallel(&mut self, context: &mut MinifyContext<'_, 'i>) -> Result<(), MinifyError> {
+    use rayon::prelude::*;
+
+    let template = &*context;
+    let minified = self
+      .0
+      .par_iter_mut()
+      .map(|rule| {
+        let mut handler = DeclarationHandler::new(*template.targets);
+        let mut important_handler = DeclarationHandler::new(*template.targets);
+        let mut handler_context = template.handler_context.fork();
+        let mut context = template.fork(&mut handler, &mut important_handler, &mut handler_context);
+
+        let removed = match rule {
+          CssRule::Style(style) => style.minify(&mut context, false),
+          CssRule::Media(media) => media.minify(&mut context, false),
+          CssRule::Supports(supports) => supports.minify(&mut context, false).map(|_| false),
+          CssRule::MozDocument(document) => document.minify(&mut context).map(|_| false),
+          CssRule::LayerBlock(layer) => layer.minify(&mut context, false),
+          CssRule::Nesting(nesting) => nesting.minify(&mut context, false),
+          _ => return None,
+        };
+
+        let warnings = context.warnings;
+        Some(removed.map(|removed| MinifiedRule {
+          removed,
+          handler_context,
+          warnings,
+        }))
+      })
+      .collect();
+
+    self.minify_with(context, false, minified)
+  }
+
+  /// Minifies the rules, skipping the work for those that were already minified up front,
+  /// e.g. in parallel. Each entry of `minified` corresponds to the rule at the same index.
+  fn minify_with(
+    &mut self,
+    context: &mut MinifyContext<'_, 'i>,
+    parent_is_unused: bool,
+    minified: Vec<Option<Result<MinifiedRule<'i>, MinifyError>>>,
   ) -> Result<(), MinifyError> {
     let mut keyframe_rules = HashMap::new();
+    let mut font_face_rules = Vec::new();
     let mut rules = Vec::new();
+    let mut minified = minified.into_iter();
+    let mut prev_loc = None;
     for mut rule in self.0.drain(..) {
+      // Rules that minify their declarations forward the handler warnings themselves. Any that are left
+      // over, e.g. from @font-face, @page or @viewport rules, belong to the previous rule.
+      if let Some(loc) = std::mem::replace(&mut prev_loc, rule.loc()) {
+        context.forward_handler_warnings(loc);
+      }
+
+      let minified = minified.next().flatten();
+      // Returns the result of a rule that was already minified, or otherwise minifies it now.
+      macro_rules! minify {
+        ($e: expr) => {
+          match minified {
+            Some(minified) => minified?.restore(context),
+            None => $e,
+          }
+        };
+      }
+
       match &mut rule {
         CssRule::Keyframes(keyframes) => {
           if context.unused_symbols.contains(keyframes.name.0.as_ref()) {
@@ -215,25 +565,56 @@ impl<'i> CssRuleList<'i> {
             continue;
           }
         }
+        CssRule::FontFace(font_face) if context.dedupe_font_faces => {
+          // Later font faces take precedence over earlier ones that match the same fonts, so an identical
+          // font face declared earlier in this list is removed rather than this one. It is replaced with a
+          // placeholder so that the indices of other rules stay the same.
+          if let Some(pos) = font_face_rules.iter().position(|idx| match rules.get(*idx) {
+            Some(CssRule::FontFace(existing)) => existing.is_equivalent(font_face),
+            _ => false,
+          }) {
+            if context.apply_optimization(Optimization::FontFaceDedupe, font_face.loc) {
+              rules[font_face_rules.swap_remove(pos)] = CssRule::Ignored;
+            }
+          }
+
+          font_face_rules.push(rules.len());
+        }
         CssRule::CustomMedia(_) => {
           if context.custom_media.is_some() {
             continue;
           }
         }
         CssRule::Media(media) => {
-          if media.minify(context, parent_is_unused)? {
+          if minify!(media.minify(context, parent_is_unused)?) {
             continue;
           }
         }
         CssRule::Supports(supports) => {
-          supports.minify(context, parent_is_unused)?;
+          minify!({
+            supports.minify(context, parent_is_unused)?;
+            false
+          });
           if supports.rules.0.is_empty() {
             continue;
           }
         }
-        CssRule::MozDocument(document) => document.minify(context)?,
+        CssRule::MozDocument(document) => {
+          minify!({
+            document.minify(context)?;
+            false
+          });
+          if document.rules.0.is_empty() {
+            continue;
+          }
+        }
+        CssRule::LayerBlock(layer) => {
+          if minify!(layer.minify(context, parent_is_unused)?) {
+            continue;
+          }
+        }
         CssRule::Style(style) => {
-          if parent_is_unused || style.minify(context, parent_is_unused)? {
+          if parent_is_unused || minify!(style.minify(context, parent_is_unused)?) {
             continue;
           }
 
@@ -251,15 +632,9 @@ impl<'i> CssRuleList<'i> {
               && last_style_rule.is_compatible(*context.targets)
               && style.rules.0.is_empty()
               && last_style_rule.rules.0.is_empty()
+              && context.apply_optimization(Optimization::RuleMerge, style.loc)
             {
-              last_style_rule
-                .declarations
-                .declarations
-                .extend(style.declarations.declarations.drain(..));
-              last_style_rule
-                .declarations
-                .important_declarations
-                .extend(style.declarations.important_declarations.drain(..));
+              last_style_rule.declarations.append(std::mem::take(&mut style.declarations));
               last_style_rule.declarations.minify(
                 context.handler,
                 context.important_handler,
@@ -272,7 +647,10 @@ impl<'i> CssRuleList<'i> {
               && last_style_rule.rules.0.is_empty()
             {
               // Append the selectors to the last rule if the declarations are the same, and all selectors are compatible.
-              if style.is_compatible(*context.targets) && last_style_rule.is_compatible(*context.targets) {
+              if style.is_compatible(*context.targets)
+                && last_style_rule.is_compatible(*context.targets)
+                && context.apply_optimization(Optimization::RuleMerge, style.loc)
+              {
                 last_style_rule.selectors.0.extend(style.selectors.0.drain(..));
                 continue;
               }
@@ -297,7 +675,14 @@ impl<'i> CssRuleList<'i> {
 
           let supports = context.handler_context.get_supports_rules(&style);
           let logical = context.handler_context.get_logical_rules(&style);
-          if !style.is_empty() {
+          let reduced_motion = match context.reduced_motion {
+            Some(mode) if !context.in_reduced_motion_query => {
+              context.handler_context.get_reduced_motion_rule(style, mode)
+            }
+            _ => None,
+          };
+
+          if !style.is_empty() || context.preserve_empty_rules {
             rules.push(rule);
           }
 
@@ -308,6 +693,7 @@ impl<'i> CssRuleList<'i> {
           }
 
           rules.extend(supports);
+          rules.extend(reduced_motion);
           continue;
         }
         CssRule::CounterStyle(counter_style) => {
@@ -315,8 +701,17 @@ impl<'i> CssRuleList<'i> {
             continue;
           }
         }
+        CssRule::Viewport(viewport) => {
+          if let Some(targets) = context.targets {
+            if !viewport.is_supported(*targets) {
+              continue;
+            }
+          }
+        }
         CssRule::Nesting(nesting) => {
-          if nesting.minify(context, parent_is_unused)? {
+          if minify!(nesting.minify(context, parent_is_unused)?)
+            || (nesting.style.is_empty() && !context.preserve_empty_rules)
+          {
             continue;
           }
         }
@@ -336,9 +731,359 @@ impl<'i> CssRuleList<'i> {
       rules.push(rule)
     }
 
+    if let Some(loc) = prev_loc {
+      context.forward_handler_warnings(loc);
+    }
+
+    if !font_face_rules.is_empty() {
+      rules.retain(|rule| !matches!(rule, CssRule::Ignored));
+    }
+
+    merge_root_variables(&mut rules, context);
+    // Nesting is only supported by recent browsers, so rules are only nested for targets that support it.
+    if context.nest_rules
+      && context.targets.map_or(false, |targets| {
+        crate::compat::Feature::CssNesting.is_compatible(targets)
+      })
+    {
+      nest_rules(&mut rules);
+    }
     self.0 = rules;
     Ok(())
   }
+
+  /// Returns whether any of the rules declares one of the given custom properties.
+  pub(crate) fn declares_custom_property(&self, names: &[CowArcStr<'i>]) -> bool {
+    self.0.iter().any(|rule| rule.declares_custom_property(names))
+  }
+
+  /// Appends each selector of the style rules, including nested rules, to the list along with the
+  /// location of its rule. Nested selectors are resolved against their parent rule.
+  pub(crate) fn collect_selectors<'a>(
+    &'a self,
+    selectors: &mut Vec<(String, Location)>,
+    context: Option<&StyleContext<'a, 'i>>,
+  ) {
+    for rule in &self.0 {
+      let style = match rule {
+        CssRule::Style(style) => style,
+        CssRule::Nesting(nesting) => &nesting.style,
+        CssRule::Media(media) => {
+          media.rules.collect_selectors(selectors, context);
+          continue;
+        }
+        CssRule::Supports(supports) => {
+          supports.rules.collect_selectors(selectors, context);
+          continue;
+        }
+        CssRule::LayerBlock(layer) => {
+          layer.rules.collect_selectors(selectors, context);
+          continue;
+        }
+        CssRule::MozDocument(document) => {
+          document.rules.collect_selectors(selectors, context);
+          continue;
+        }
+        _ => continue,
+      };
+
+      for selector in &style.selectors.0 {
+        let mut s = String::new();
+        let mut printer = Printer::new(&mut s, PrinterOptions::default());
+        if selector.to_css_with_context(&mut printer, context).is_ok() {
+          selectors.push((s, style.loc));
+        }
+      }
+
+      style.rules.collect_selectors(
+        selectors,
+        Some(&StyleContext {
+          rule: style,
+          parent: context,
+        }),
+      );
+    }
+  }
+
+  /// Splits the rules into those that apply in every environment described by the given context,
+  /// and the rest. See [StyleSheet::split_by_media](crate::stylesheet::StyleSheet::split_by_media).
+  pub(crate) fn split_by_media(&self, context: &MediaContext) -> (CssRuleList<'i>, CssRuleList<'i>) {
+    let mut matching = Vec::new();
+    let mut rest = Vec::new();
+
+    for rule in &self.0 {
+      match rule {
+        CssRule::Media(media) => {
+          if media.query.evaluate(context) == Some(true) {
+            // The media query is redundant in the matching style sheet, so the rules are unwrapped.
+            let (m, r) = media.rules.split_by_media(context);
+            matching.extend(m.0);
+            if !r.0.is_empty() {
+              rest.push(CssRule::Media(MediaRule {
+                query: media.query.clone(),
+                rules: r,
+                loc: media.loc,
+              }));
+            }
+          } else {
+            rest.push(rule.clone());
+          }
+        }
+        CssRule::Supports(supports) => {
+          split_wrapper_rule(&supports.rules, context, &mut matching, &mut rest, |rules| {
+            CssRule::Supports(SupportsRule {
+              condition: supports.condition.clone(),
+              rules,
+              loc: supports.loc,
+            })
+          })
+        }
+        CssRule::LayerBlock(layer) => {
+          split_wrapper_rule(&layer.rules, context, &mut matching, &mut rest, |rules| {
+            CssRule::LayerBlock(LayerBlockRule {
+              name: layer.name.clone(),
+              rules,
+              loc: layer.loc,
+            })
+          })
+        }
+        CssRule::MozDocument(document) => {
+          split_wrapper_rule(&document.rules, context, &mut matching, &mut rest, |rules| {
+            CssRule::MozDocument(MozDocumentRule {
+              conditions: document.conditions.clone(),
+              rules,
+              vendor_prefix: document.vendor_prefix,
+              loc: document.loc,
+            })
+          })
+        }
+        CssRule::Import(import) => {
+          if import.media.evaluate(context) == Some(true) {
+            matching.push(rule.clone());
+          } else {
+            rest.push(rule.clone());
+          }
+        }
+        // These affect the meaning of other rules, or define names and page styles that rules in
+        // either style sheet may depend on, so both style sheets need them.
+        CssRule::Namespace(..)
+        | CssRule::LayerStatement(..)
+        | CssRule::CustomMedia(..)
+        | CssRule::Keyframes(..)
+        | CssRule::FontFace(..)
+        | CssRule::FontPaletteValues(..)
+        | CssRule::Property(..)
+        | CssRule::CounterStyle(..)
+        | CssRule::Page(..) => {
+          matching.push(rule.clone());
+          rest.push(rule.clone());
+        }
+        CssRule::Ignored => {}
+        _ => matching.push(rule.clone()),
+      }
+    }
+
+    (CssRuleList(matching), CssRuleList(rest))
+  }
+}
+
+/// Splits the children of a rule such as `@supports`, preserving the rule in each partition that
+/// includes any of its children.
+fn split_wrapper_rule<'i, F: Fn(CssRuleList<'i>) -> CssRule<'i>>(
+  rules: &CssRuleList<'i>,
+  context: &MediaContext,
+  matching: &mut Vec<CssRule<'i>>,
+  rest: &mut Vec<CssRule<'i>>,
+  wrap: F,
+) {
+  let (m, r) = rules.split_by_media(context);
+  if !m.0.is_empty() {
+    matching.push(wrap(m));
+  }
+  if !r.0.is_empty() {
+    rest.push(wrap(r));
+  }
+}
+
+/// Nests style rules into the preceding style rule when all of their selectors start with its selector
+/// followed by a combinator, e.g. `.a{} .a .b{}` => `.a{& .b{}}`. Nested rules are written after the
+/// declarations of their parent, so only adjacent rules are nested to keep the rules in the same order.
+/// The parent must have a single selector, so that `&` has the same specificity as the prefix it replaces.
+fn nest_rules(rules: &mut Vec<CssRule>) {
+  let mut nested: Vec<CssRule> = Vec::with_capacity(rules.len());
+  for mut rule in rules.drain(..) {
+    let selectors = match (&rule, nested.last()) {
+      (CssRule::Style(style), Some(CssRule::Style(parent))) => nest_selectors(parent, style),
+      _ => None,
+    };
+
+    if let (Some(selectors), Some(CssRule::Style(parent))) = (selectors, nested.last_mut()) {
+      if let CssRule::Style(style) = &mut rule {
+        style.selectors = selectors;
+      }
+      parent.rules.0.push(rule);
+      continue;
+    }
+
+    nested.push(rule);
+  }
+
+  *rules = nested;
+}
+
+/// Returns the selectors of the style rule relative to the parent rule, or `None` if it cannot be nested.
+fn nest_selectors<'i>(parent: &StyleRule<'i>, style: &StyleRule<'i>) -> Option<SelectorList<'i, Selectors>> {
+  let is_unprefixed = |prefix: VendorPrefix| prefix.is_empty() || prefix == VendorPrefix::None;
+  if parent.selectors.0.len() != 1
+    || parent.selectors.0[0].has_pseudo_element()
+    || !is_unprefixed(parent.vendor_prefix)
+    || !is_unprefixed(style.vendor_prefix)
+  {
+    return None;
+  }
+
+  style
+    .selectors
+    .0
+    .iter()
+    .map(|selector| nest_selector(&parent.selectors.0[0], selector))
+    .collect::<Option<_>>()
+    .map(SelectorList)
+}
+
+/// Replaces the parent selector at the start of the selector with `&`, e.g. `.a .b` => `& .b`.
+/// Returns `None` if the selector does not start with the entire parent selector followed by a combinator.
+fn nest_selector<'i>(
+  parent: &Selector<'i, Selectors>,
+  selector: &Selector<'i, Selectors>,
+) -> Option<Selector<'i, Selectors>> {
+  // Components are stored in match order, so the parent selector is at the end.
+  let components = selector.iter_raw_match_order().as_slice();
+  if components.len() <= parent.len() + 1 {
+    return None;
+  }
+
+  let (rest, prefix) = components.split_at(components.len() - parent.len() - 1);
+  let combinator = match prefix[0] {
+    Component::Combinator(combinator)
+      if matches!(
+        combinator,
+        Combinator::Descendant | Combinator::Child | Combinator::NextSibling | Combinator::LaterSibling
+      ) =>
+    {
+      combinator
+    }
+    _ => return None,
+  };
+
+  if !prefix[1..].iter().eq(parent.iter_raw_match_order()) {
+    return None;
+  }
+
+  let mut nested = vec![Component::Nesting, Component::Combinator(combinator)];
+  let mut combinators = rest.iter().rev().filter_map(|c| c.as_combinator());
+  for compound in rest.split(|c| c.is_combinator()).rev() {
+    nested.extend(compound.iter().cloned());
+    if let Some(combinator) = combinators.next() {
+      nested.push(Component::Combinator(combinator));
+    }
+  }
+
+  Some(Selector::from_vec2(nested))
+}
+
+/// Merges `:root` rules that only contain custom properties into the first such rule. Later declarations
+/// replace earlier ones with the same name. A rule is not merged if any rule in between declares one of
+/// its custom properties, since moving it earlier would change which value wins.
+fn merge_root_variables(rules: &mut Vec<CssRule>, context: &mut MinifyContext) {
+  let mut target = None;
+  let mut merged = false;
+  for i in 0..rules.len() {
+    let names = match &rules[i] {
+      CssRule::Style(style) if style.is_root_variables() => style
+        .declarations
+        .declarations
+        .iter()
+        .chain(style.declarations.important_declarations.iter())
+        .filter_map(|property| match property {
+          Property::Custom(custom) => Some(custom.name.clone()),
+          _ => None,
+        })
+        .collect::<Vec<_>>(),
+      _ => continue,
+    };
+
+    let t = match target {
+      Some(t) => t,
+      None => {
+        target = Some(i);
+        continue;
+      }
+    };
+
+    if rules[t + 1..i].iter().any(|rule| rule.declares_custom_property(&names)) {
+      continue;
+    }
+
+    match rules[i].loc() {
+      Some(loc) if context.apply_optimization(Optimization::RootVariableMerge, loc) => {}
+      _ => continue,
+    }
+
+    let (before, after) = rules.split_at_mut(i);
+    if let (CssRule::Style(target), CssRule::Style(style)) = (&mut before[t], &mut after[0]) {
+      let declarations = &mut target.declarations;
+      for property in style.declarations.declarations.drain(..) {
+        // Normal declarations never apply over an !important declaration of the same property.
+        if declarations
+          .important_declarations
+          .iter()
+          .any(|p| same_custom_property(p, &property))
+        {
+          continue;
+        }
+        declarations.declarations.retain(|p| !same_custom_property(p, &property));
+        declarations.declarations.push(property);
+      }
+      for property in style.declarations.important_declarations.drain(..) {
+        declarations.declarations.retain(|p| !same_custom_property(p, &property));
+        declarations
+          .important_declarations
+          .retain(|p| !same_custom_property(p, &property));
+        declarations.important_declarations.push(property);
+      }
+    }
+
+    rules[i] = CssRule::Ignored;
+    merged = true;
+  }
+
+  if merged {
+    rules.retain(|rule| !matches!(rule, CssRule::Ignored));
+  }
+}
+
+fn same_custom_property(a: &Property, b: &Property) -> bool {
+  matches!((a, b), (Property::Custom(a), Property::Custom(b)) if a.name == b.name)
+}
+
+/// The result of minifying a rule ahead of merging it with its siblings.
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+struct MinifiedRule<'i> {
+  /// Whether the rule should be removed.
+  removed: bool,
+  /// The state left over from minifying the rule's declarations, e.g. logical properties and
+  /// `@supports` fallbacks, which is used when merging the rule.
+  handler_context: PropertyHandlerContext<'i>,
+  warnings: Vec<MinifyWarning>,
+}
+
+impl<'i> MinifiedRule<'i> {
+  fn restore(self, context: &mut MinifyContext<'_, 'i>) -> bool {
+    *context.handler_context = self.handler_context;
+    context.warnings.extend(self.warnings);
+    self.removed
+  }
 }
 
 impl<'i> ToCss for CssRuleList<'i> {
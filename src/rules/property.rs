@@ -13,11 +13,11 @@ use cssparser::*;
 /// https://drafts.css-houdini.org/css-properties-values-api/#at-property-rule
 #[derive(Debug, PartialEq, Clone)]
 pub struct PropertyRule<'i> {
-  name: DashedIdent<'i>,
-  syntax: SyntaxString,
+  pub(crate) name: DashedIdent<'i>,
+  pub(crate) syntax: SyntaxString,
   inherits: bool,
   initial_value: Option<ParsedComponent<'i>>,
-  loc: Location,
+  pub(crate) loc: Location,
 }
 
 impl<'i> PropertyRule<'i> {
@@ -46,23 +46,10 @@ impl<'i> PropertyRule<'i> {
     let inherits = parser.inherits.ok_or(input.new_custom_error(ParserError::AtRuleBodyInvalid))?;
 
     // `initial-value` is required unless the syntax is a universal definition.
-    let initial_value = match syntax {
-      SyntaxString::Universal => match parser.initial_value {
-        None => None,
-        Some(val) => {
-          let mut input = ParserInput::new(val);
-          let mut parser = Parser::new(&mut input);
-          Some(syntax.parse_value(&mut parser)?)
-        }
-      },
-      _ => {
-        let val = parser
-          .initial_value
-          .ok_or(input.new_custom_error(ParserError::AtRuleBodyInvalid))?;
-        let mut input = ParserInput::new(val);
-        let mut parser = Parser::new(&mut input);
-        Some(syntax.parse_value(&mut parser)?)
-      }
+    let initial_value = match (&syntax, parser.initial_value) {
+      (SyntaxString::Universal, None) => None,
+      (_, None) => return Err(input.new_custom_error(ParserError::AtRuleBodyInvalid)),
+      (_, Some((val, location))) => Some(parse_initial_value(&syntax, val, location)?),
     };
 
     return Ok(PropertyRule {
@@ -75,6 +62,39 @@ impl<'i> PropertyRule<'i> {
   }
 }
 
+/// Parses the buffered `initial-value` descriptor according to the syntax. The entire value must match
+/// the syntax, and it must be [computationally independent](https://drafts.css-houdini.org/css-properties-values-api/#computationally-independent).
+/// Errors are reported at their location in the source, rather than within the buffered value.
+fn parse_initial_value<'i>(
+  syntax: &SyntaxString,
+  value: &'i str,
+  location: SourceLocation,
+) -> Result<ParsedComponent<'i>, ParseError<'i, ParserError<'i>>> {
+  let mut input = ParserInput::new(value);
+  let mut parser = Parser::new(&mut input);
+  let start = parser.current_source_location();
+  let result = syntax.parse_value(&mut parser).and_then(|value| {
+    // The universal syntax only stores the first token.
+    if *syntax != SyntaxString::Universal {
+      parser.expect_exhausted()?;
+    }
+
+    if !value.is_computationally_independent() {
+      return Err(start.new_custom_error(ParserError::ComputationallyDependentInitialValue));
+    }
+
+    Ok(value)
+  });
+
+  result.map_err(|mut err| {
+    if err.location.line == 0 {
+      err.location.column += location.column - 1;
+    }
+    err.location.line += location.line;
+    err
+  })
+}
+
 impl<'i> ToCss for PropertyRule<'i> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
@@ -122,7 +142,7 @@ impl<'i> ToCss for PropertyRule<'i> {
 pub(crate) struct PropertyRuleDeclarationParser<'i> {
   syntax: Option<SyntaxString>,
   inherits: Option<bool>,
-  initial_value: Option<&'i str>,
+  initial_value: Option<(&'i str, SourceLocation)>,
 }
 
 impl<'i> cssparser::DeclarationParser<'i> for PropertyRuleDeclarationParser<'i> {
@@ -153,10 +173,12 @@ impl<'i> cssparser::DeclarationParser<'i> for PropertyRuleDeclarationParser<'i>
       },
       "initial-value" => {
         // Buffer the value into a string. We will parse it later.
+        input.skip_whitespace();
+        let location = input.current_source_location();
         let start = input.position();
         while input.next().is_ok() {}
         let initial_value = input.slice_from(start);
-        self.initial_value = Some(initial_value);
+        self.initial_value = Some((initial_value, location));
       },
       _ => return Err(input.new_custom_error(ParserError::InvalidDeclaration))
     }
@@ -2,15 +2,20 @@ use super::Location;
 use super::MinifyContext;
 use crate::compat::Feature;
 use crate::context::DeclarationContext;
-use crate::declaration::DeclarationBlock;
-use crate::error::{MinifyError, PrinterError, PrinterErrorKind};
-use crate::printer::Printer;
+use crate::declaration::{DeclarationBlock, DeclarationItem};
+use crate::error::{MinifyError, MinifyWarning, MinifyWarningKind, PrinterError, PrinterErrorKind};
+use crate::printer::{Printer, PrinterOptions};
+use crate::properties::Property;
 use crate::rules::{CssRuleList, StyleContext, ToCssWithContext};
-use crate::selector::{is_compatible, is_unused, Selectors};
+use crate::selector::{has_has, has_nth_child_of, is_compatible, is_unused, remove_has, Selectors};
+use crate::stylesheet::UnsupportedHasBehavior;
 use crate::targets::Browsers;
 use crate::traits::ToCss;
+use crate::values::string::CowArcStr;
 use crate::vendor_prefix::VendorPrefix;
+use parcel_selectors::parser::Component;
 use parcel_selectors::SelectorList;
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct StyleRule<'i> {
@@ -22,6 +27,58 @@ pub struct StyleRule<'i> {
 }
 
 impl<'i> StyleRule<'i> {
+  /// Returns the class name if the selector is a single class, e.g. `.btn`.
+  pub(crate) fn utility_name(&self) -> Option<&str> {
+    if self.selectors.0.len() != 1 {
+      return None;
+    }
+
+    let mut iter = self.selectors.0[0].iter_raw_match_order();
+    match (iter.next(), iter.next()) {
+      (Some(Component::Class(name)), None) => Some(name.0.as_ref()),
+      _ => None,
+    }
+  }
+
+  /// Returns whether the selector is `:root` and the rule only contains custom properties.
+  pub(crate) fn is_root_variables(&self) -> bool {
+    if self.selectors.0.len() != 1
+      || !self.rules.0.is_empty()
+      || self.declarations.is_empty()
+      || !self.declarations.apply.is_empty()
+    {
+      return false;
+    }
+
+    let mut iter = self.selectors.0[0].iter_raw_match_order();
+    matches!((iter.next(), iter.next()), (Some(Component::Root), None))
+      && self
+        .declarations
+        .declarations
+        .iter()
+        .chain(self.declarations.important_declarations.iter())
+        .all(|property| matches!(property, Property::Custom(..)))
+  }
+
+  /// Returns whether the rule or any of its nested rules declares one of the given custom properties.
+  pub(crate) fn declares_custom_property(&self, names: &[CowArcStr<'i>]) -> bool {
+    self
+      .declarations
+      .declarations
+      .iter()
+      .chain(self.declarations.important_declarations.iter())
+      .any(|property| matches!(property, Property::Custom(custom) if names.contains(&custom.name)))
+      || self.rules.declares_custom_property(names)
+  }
+
+  pub(crate) fn apply_utilities(
+    &mut self,
+    utilities: &HashMap<String, DeclarationBlock<'i>>,
+  ) -> Result<(), MinifyError> {
+    self.declarations.apply_utilities(utilities, self.loc)?;
+    self.rules.apply_utilities(utilities)
+  }
+
   pub(crate) fn minify(
     &mut self,
     context: &mut MinifyContext<'_, 'i>,
@@ -36,15 +93,42 @@ impl<'i> StyleRule<'i> {
 
         self.declarations.declarations.clear();
         self.declarations.important_declarations.clear();
+        self.declarations.apply.clear();
         unused = true;
       }
     }
 
+    if let Some(targets) = context.targets {
+      if !Feature::CssHas.is_compatible(*targets) && has_has(&self.selectors.0) {
+        match context.unsupported_has {
+          UnsupportedHasBehavior::Keep => {}
+          UnsupportedHasBehavior::Warn => self.warn_unsupported_selector(context),
+          // Selectors are only dropped when no target supports :has(), since they still match in those that do.
+          UnsupportedHasBehavior::Drop if Feature::CssHas.is_partially_compatible(*targets) => {
+            self.warn_unsupported_selector(context)
+          }
+          UnsupportedHasBehavior::Drop => {
+            // Only the selectors using :has() are removed, so the rest of the list still matches.
+            // Within :is() and :where(), only the arguments using it are removed.
+            self.selectors.0.retain(|selector| remove_has(selector));
+            if self.selectors.0.is_empty() {
+              return Ok(true);
+            }
+          }
+        }
+      }
+
+      if !Feature::CssNthChildOf.is_compatible(*targets) && has_nth_child_of(&self.selectors.0) {
+        self.warn_unsupported_selector(context);
+      }
+    }
+
     context.handler_context.context = DeclarationContext::StyleRule;
     self
       .declarations
       .minify(context.handler, context.important_handler, context.handler_context);
     context.handler_context.context = DeclarationContext::None;
+    context.forward_handler_warnings(self.loc);
 
     if !self.rules.0.is_empty() {
       self.rules.minify(context, unused)?;
@@ -56,6 +140,19 @@ impl<'i> StyleRule<'i> {
     Ok(false)
   }
 
+  fn warn_unsupported_selector(&self, context: &mut MinifyContext<'_, 'i>) {
+    let mut selector = String::new();
+    let res = self
+      .selectors
+      .to_css_with_context(&mut Printer::new(&mut selector, PrinterOptions::default()), None);
+    if res.is_ok() {
+      context.warnings.push(MinifyWarning {
+        kind: MinifyWarningKind::UnsupportedSelector { selector },
+        loc: self.loc,
+      });
+    }
+  }
+
   pub fn is_empty(&self) -> bool {
     self.declarations.is_empty() && self.rules.0.is_empty()
   }
@@ -121,7 +218,8 @@ impl<'a, 'i> StyleRule<'i> {
     let supports_nesting = self.rules.0.is_empty()
       || dest.targets.is_none()
       || Feature::CssNesting.is_compatible(dest.targets.unwrap());
-    let len = self.declarations.declarations.len() + self.declarations.important_declarations.len();
+    let items = self.declarations.items();
+    let len = items.len();
     let has_declarations = supports_nesting || len > 0 || self.rules.0.is_empty();
 
     if has_declarations {
@@ -132,37 +230,38 @@ impl<'a, 'i> StyleRule<'i> {
       dest.indent();
 
       let mut i = 0;
-      macro_rules! write {
-        ($decls: ident, $important: literal) => {
-          for decl in &self.declarations.$decls {
-            // The CSS modules `composes` property is handled specially, and omitted during printing.
-            // We need to add the classes it references to the list for the selectors in this rule.
-            if let crate::properties::Property::Composes(composes) = &decl {
-              if dest.is_nested() && dest.css_module.is_some() {
-                return Err(dest.error(PrinterErrorKind::InvalidComposesNesting, composes.loc));
-              }
+      for item in &items {
+        // The CSS modules `composes` property is handled specially, and omitted during printing.
+        // We need to add the classes it references to the list for the selectors in this rule.
+        let property = match item {
+          DeclarationItem::Declaration(crate::properties::Property::Verbatim(verbatim), _) => {
+            Some(&*verbatim.property)
+          }
+          DeclarationItem::Declaration(decl, _) => Some(*decl),
+          DeclarationItem::Apply(_) => None,
+        };
+        if let Some(crate::properties::Property::Composes(composes)) = property {
+          if dest.is_nested() && dest.css_module.is_some() {
+            return Err(dest.error(PrinterErrorKind::InvalidComposesNesting, composes.loc));
+          }
 
-              if let Some(css_module) = &mut dest.css_module {
-                css_module
-                  .handle_composes(&self.selectors, &composes)
-                  .map_err(|e| dest.error(e, composes.loc))?;
-                continue;
-              }
-            }
+          if let Some(css_module) = &mut dest.css_module {
+            css_module
+              .handle_composes(&self.selectors, &composes)
+              .map_err(|e| dest.error(e, composes.loc))?;
+            continue;
+          }
+        }
 
-            dest.newline()?;
-            decl.to_css(dest, $important)?;
-            if i != len - 1 || !dest.minify {
-              dest.write_char(';')?;
-            }
+        dest.newline()?;
+        item.to_css(dest)?;
+        // The last semicolon can only be omitted if there are no nested rules after it.
+        if i != len - 1 || !dest.minify || (supports_nesting && !self.rules.0.is_empty()) {
+          dest.write_char(';')?;
+        }
 
-            i += 1;
-          }
-        };
+        i += 1;
       }
-
-      write!(declarations, false);
-      write!(important_declarations, true);
     }
 
     macro_rules! newline {
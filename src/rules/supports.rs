@@ -1,6 +1,7 @@
 use super::Location;
 use super::{CssRuleList, MinifyContext};
 use crate::error::{MinifyError, ParserError, PrinterError};
+use crate::parser::{expect_no_error_token, is_nesting_depth_error, parse_nested_block};
 use crate::printer::Printer;
 use crate::rules::{StyleContext, ToCssWithContext};
 use crate::traits::{Parse, ToCss};
@@ -54,7 +55,10 @@ pub enum SupportsCondition<'i> {
   Or(Vec<SupportsCondition<'i>>),
   Declaration(CowArcStr<'i>),
   Selector(CowArcStr<'i>),
-  // FontTechnology()
+  /// A `font-tech()` condition, e.g. `font-tech(color-COLRv1)`.
+  FontTechnology(CowArcStr<'i>),
+  /// A `font-format()` condition, e.g. `font-format(woff2)`.
+  FontFormat(CowArcStr<'i>),
   Parens(Box<SupportsCondition<'i>>),
   Unknown(CowArcStr<'i>),
 }
@@ -143,9 +147,9 @@ impl<'i> SupportsCondition<'i> {
         match_ignore_ascii_case! { &*f,
           "selector" => {
             let res = input.try_parse(|input| {
-              input.parse_nested_block(|input| {
+              parse_nested_block(input, |input| {
                 let pos = input.position();
-                input.expect_no_error_token()?;
+                expect_no_error_token(input)?;
                 Ok(SupportsCondition::Selector(input.slice_from(pos).into()))
               })
             });
@@ -153,27 +157,50 @@ impl<'i> SupportsCondition<'i> {
               return res
             }
           },
+          "font-tech" => {
+            let res = input.try_parse(|input| {
+              input.parse_nested_block(|input| {
+                Ok(SupportsCondition::FontTechnology(input.expect_ident_cloned()?.into()))
+              })
+            });
+            if res.is_ok() {
+              return res
+            }
+          },
+          "font-format" => {
+            let res = input.try_parse(|input| {
+              input.parse_nested_block(|input| {
+                Ok(SupportsCondition::FontFormat(input.expect_ident_cloned()?.into()))
+              })
+            });
+            if res.is_ok() {
+              return res
+            }
+          },
           _ => {}
         }
       }
       Token::ParenthesisBlock => {
         let res = input.try_parse(|input| {
-          input.parse_nested_block(|input| {
-            if let Ok(condition) = input.try_parse(SupportsCondition::parse) {
-              return Ok(SupportsCondition::Parens(Box::new(condition)));
+          parse_nested_block(input, |input| {
+            match input.try_parse(SupportsCondition::parse) {
+              Ok(condition) => return Ok(SupportsCondition::Parens(Box::new(condition))),
+              Err(err) if is_nesting_depth_error(&err) => return Err(err),
+              Err(_) => {}
             }
 
             Self::parse_declaration(input)
           })
         });
-        if res.is_ok() {
-          return res;
+        match res {
+          Err(err) if !is_nesting_depth_error(&err) => {}
+          res => return res,
         }
       }
       t => return Err(location.new_unexpected_token_error(t.clone())),
     };
 
-    input.parse_nested_block(|input| input.expect_no_error_token().map_err(|err| err.into()))?;
+    parse_nested_block(input, expect_no_error_token)?;
     Ok(SupportsCondition::Unknown(input.slice_from(pos).into()))
   }
 
@@ -181,7 +208,7 @@ impl<'i> SupportsCondition<'i> {
     let pos = input.position();
     input.expect_ident()?;
     input.expect_colon()?;
-    input.expect_no_error_token()?;
+    expect_no_error_token(input)?;
     Ok(SupportsCondition::Declaration(input.slice_from(pos).into()))
   }
 }
@@ -235,6 +262,16 @@ impl<'i> ToCss for SupportsCondition<'i> {
         dest.write_str(sel)?;
         dest.write_char(')')
       }
+      SupportsCondition::FontTechnology(tech) => {
+        dest.write_str("font-tech(")?;
+        serialize_identifier(tech, dest)?;
+        dest.write_char(')')
+      }
+      SupportsCondition::FontFormat(format) => {
+        dest.write_str("font-format(")?;
+        serialize_identifier(format, dest)?;
+        dest.write_char(')')
+      }
       SupportsCondition::Unknown(unknown) => dest.write_str(&unknown),
     }
   }
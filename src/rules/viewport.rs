@@ -1,7 +1,9 @@
 use super::Location;
 use crate::declaration::DeclarationBlock;
 use crate::error::PrinterError;
+use crate::prefixes::Feature;
 use crate::printer::Printer;
+use crate::targets::Browsers;
 use crate::traits::ToCss;
 use crate::vendor_prefix::VendorPrefix;
 
@@ -12,6 +14,20 @@ pub struct ViewportRule<'i> {
   pub loc: Location,
 }
 
+impl<'i> ViewportRule<'i> {
+  /// Returns whether any of the targets ever supported the rule. `@viewport` is deprecated,
+  /// and was only implemented with the `-ms-` prefix in IE and legacy Edge, and `-o-` in Opera.
+  /// The unprefixed rule is kept as long as any target supported a prefixed form.
+  pub(crate) fn is_supported(&self, targets: Browsers) -> bool {
+    let prefixes = Feature::AtViewport.prefixes_for(targets);
+    if self.vendor_prefix == VendorPrefix::None {
+      prefixes.intersects(VendorPrefix::Ms | VendorPrefix::O)
+    } else {
+      prefixes.contains(self.vendor_prefix)
+    }
+  }
+}
+
 impl<'i> ToCss for ViewportRule<'i> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
@@ -1,5 +1,6 @@
 use crate::compat::Feature;
 use crate::error::{ParserError, PrinterError};
+use crate::parser::{is_nesting_depth_error, parse_nested_block};
 use crate::printer::Printer;
 use crate::rules::{StyleContext, ToCssWithContext};
 use crate::stylesheet::PrinterOptions;
@@ -13,6 +14,7 @@ use parcel_selectors::{
   parser::{Combinator, Component, Selector, SelectorImpl},
   SelectorList,
 };
+use retain_mut::RetainMut;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
@@ -211,7 +213,7 @@ impl<'a, 'i> parcel_selectors::parser::Parser<'i> for SelectorParser<'a, 'i> {
       "dir" => Dir(Direction::parse(parser)?),
       "local" if self.css_modules => Local(Box::new(parcel_selectors::parser::Selector::parse(self, parser)?)),
       "global" if self.css_modules => Global(Box::new(parcel_selectors::parser::Selector::parse(self, parser)?)),
-      _ => return Err(parser.new_custom_error(parcel_selectors::parser::SelectorParseErrorKind::UnexpectedIdent(name.clone()))),
+      _ => CustomFunction(name.into(), parse_raw_arguments(parser)),
     };
 
     Ok(pseudo_class)
@@ -263,6 +265,14 @@ impl<'a, 'i> parcel_selectors::parser::Parser<'i> for SelectorParser<'a, 'i> {
     Ok(pseudo_element)
   }
 
+  fn parse_functional_pseudo_element<'t>(
+    &self,
+    name: CowRcStr<'i>,
+    parser: &mut cssparser::Parser<'i, 't>,
+  ) -> Result<PseudoElement<'i>, ParseError<'i, Self::Error>> {
+    Ok(PseudoElement::CustomFunction(name.into(), parse_raw_arguments(parser)))
+  }
+
   #[inline]
   fn parse_slotted(&self) -> bool {
     true
@@ -295,6 +305,29 @@ impl<'a, 'i> parcel_selectors::parser::Parser<'i> for SelectorParser<'a, 'i> {
   fn is_nesting_allowed(&self) -> bool {
     self.is_nesting_allowed
   }
+
+  fn parse_nested_block<'t, R, F>(
+    &self,
+    input: &mut cssparser::Parser<'i, 't>,
+    parse: F,
+  ) -> Result<R, ParseError<'i, Self::Error>>
+  where
+    F: for<'tt> FnOnce(&mut cssparser::Parser<'i, 'tt>) -> Result<R, ParseError<'i, Self::Error>>,
+  {
+    parse_nested_block(input, parse)
+  }
+
+  fn is_recoverable_error(&self, error: &ParseError<'i, Self::Error>) -> bool {
+    !is_nesting_depth_error(error)
+  }
+}
+
+/// Returns the source text of the arguments of an unknown functional pseudo class or element,
+/// which are preserved as is.
+fn parse_raw_arguments<'i, 't>(parser: &mut cssparser::Parser<'i, 't>) -> CowArcStr<'i> {
+  let start = parser.position();
+  while parser.next_including_whitespace_and_comments().is_ok() {}
+  parser.slice_from(start).trim().into()
 }
 
 enum_property! {
@@ -305,6 +338,22 @@ enum_property! {
   }
 }
 
+/// Serializes a language range as an identifier, or as a string if it would need to be escaped,
+/// e.g. for wildcard ranges such as `"*-CH"`.
+fn serialize_lang<W>(lang: &str, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: fmt::Write,
+{
+  let mut id = String::new();
+  serialize_identifier(lang, &mut id)?;
+  if !lang.is_empty() && id == lang {
+    dest.write_str(lang)
+  } else {
+    serialize_string(lang, dest)?;
+    Ok(())
+  }
+}
+
 /// https://drafts.csswg.org/selectors-4/#structural-pseudos
 #[derive(Clone, Eq, PartialEq)]
 pub enum PseudoClass<'i> {
@@ -377,6 +426,8 @@ pub enum PseudoClass<'i> {
   WebKitScrollbar(WebKitScrollbarPseudoClass),
 
   Custom(CowArcStr<'i>),
+  /// An unknown functional pseudo class, with its raw arguments.
+  CustomFunction(CowArcStr<'i>, CowArcStr<'i>),
 }
 
 /// https://webkit.org/blog/363/styling-scrollbars/
@@ -459,7 +510,7 @@ impl<'a, 'i> ToCssWithContext<'a, 'i> for PseudoClass<'i> {
           } else {
             dest.delim(',', false)?;
           }
-          serialize_identifier(lang, dest)?;
+          serialize_lang(lang, dest)?;
         }
         return dest.write_str(")");
       }
@@ -604,6 +655,13 @@ impl<'a, 'i> ToCssWithContext<'a, 'i> for PseudoClass<'i> {
         dest.write_char(':')?;
         return dest.write_str(&val);
       }
+      CustomFunction(name, arguments) => {
+        dest.write_char(':')?;
+        dest.write_str(name)?;
+        dest.write_char('(')?;
+        dest.write_str(arguments)?;
+        dest.write_char(')')
+      }
     }
   }
 }
@@ -660,6 +718,8 @@ pub enum PseudoElement<'i> {
   FileSelectorButton(VendorPrefix),
   WebKitScrollbar(WebKitScrollbarPseudoElement),
   Custom(CowArcStr<'i>),
+  /// An unknown functional pseudo element, e.g. `::highlight(name)`, with its raw arguments.
+  CustomFunction(CowArcStr<'i>, CowArcStr<'i>),
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -762,6 +822,13 @@ impl<'i> ToCss for PseudoElement<'i> {
         dest.write_str("::")?;
         return dest.write_str(val);
       }
+      CustomFunction(name, arguments) => {
+        dest.write_str("::")?;
+        dest.write_str(name)?;
+        dest.write_char('(')?;
+        dest.write_str(arguments)?;
+        dest.write_char(')')
+      }
     }
   }
 }
@@ -1117,6 +1184,28 @@ impl<'a, 'i> ToCssWithContext<'a, 'i> for Component<'i, Selectors> {
         serialize_selector_list(list.iter(), dest, context, true)?;
         dest.write_str(")")
       }
+      NthChild(a, b) | NthLastChild(a, b) | NthOfType(a, b) | NthLastOfType(a, b) => {
+        match *self {
+          NthChild(..) => dest.write_str(":nth-child(")?,
+          NthLastChild(..) => dest.write_str(":nth-last-child(")?,
+          NthOfType(..) => dest.write_str(":nth-of-type(")?,
+          NthLastOfType(..) => dest.write_str(":nth-last-of-type(")?,
+          _ => unreachable!(),
+        }
+        serialize_nth(*a, *b, dest)?;
+        dest.write_char(')')
+      }
+      NthChildOf(a, b, ref list) | NthLastChildOf(a, b, ref list) => {
+        match *self {
+          NthChildOf(..) => dest.write_str(":nth-child(")?,
+          NthLastChildOf(..) => dest.write_str(":nth-last-child(")?,
+          _ => unreachable!(),
+        }
+        serialize_nth(*a, *b, dest)?;
+        dest.write_str(" of ")?;
+        serialize_selector_list(list.iter(), dest, context, false)?;
+        dest.write_char(')')
+      }
       NonTSPseudoClass(pseudo) => pseudo.to_css_with_context(dest, context),
       PseudoElement(pseudo) => pseudo.to_css(dest),
       Nesting => serialize_nesting(dest, context, false),
@@ -1136,6 +1225,29 @@ impl<'a, 'i> ToCssWithContext<'a, 'i> for Component<'i, Selectors> {
   }
 }
 
+fn serialize_nth<W>(a: i32, b: i32, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: fmt::Write,
+{
+  // When the step is positive and the offset is not, only the offset modulo the step
+  // matters, since indices less than 1 never match. e.g. `2n-1` is equivalent to `2n+1`.
+  let b = if a > 0 && b <= 0 { b.rem_euclid(a) } else { b };
+
+  // `odd` is shorter than `2n+1`. `even` is longer than `2n`, so it is never used.
+  match (a, b) {
+    (0, 0) => dest.write_char('0')?,
+    (2, 1) => dest.write_str("odd")?,
+    (1, 0) => dest.write_char('n')?,
+    (-1, 0) => dest.write_str("-n")?,
+    (_, 0) => write!(dest, "{}n", a)?,
+    (0, _) => write!(dest, "{}", b)?,
+    (1, _) => write!(dest, "n{:+}", b)?,
+    (-1, _) => write!(dest, "-n{:+}", b)?,
+    (_, _) => write!(dest, "{}n{:+}", a, b)?,
+  }
+  Ok(())
+}
+
 fn serialize_nesting<W>(
   dest: &mut Printer<W>,
   context: Option<&StyleContext>,
@@ -1320,6 +1432,8 @@ pub fn is_compatible(selectors: &SelectorList<Selectors>, targets: Option<Browse
         | Component::OnlyOfType
         | Component::Root => Feature::CssSel3,
 
+        Component::NthChildOf(..) | Component::NthLastChildOf(..) => Feature::CssNthChildOf,
+
         Component::Is(_) | Component::Nesting => Feature::CssMatchesPseudo,
         Component::Any(..) => Feature::AnyPseudo,
         Component::Has(_) => Feature::CssHas,
@@ -1560,6 +1674,58 @@ fn downlevel_dir<'i>(dir: Direction, targets: Browsers) -> Component<'i, Selecto
   }
 }
 
+/// Returns whether any of the selectors contain an `:nth-child()` or `:nth-last-child()`
+/// pseudo-class with an `of <selector-list>` clause.
+pub(crate) fn has_nth_child_of(selectors: &[Selector<Selectors>]) -> bool {
+  selectors.iter().any(|selector| {
+    selector.iter_raw_match_order().any(|component| match component {
+      Component::NthChildOf(..) | Component::NthLastChildOf(..) => true,
+      Component::Is(list)
+      | Component::Where(list)
+      | Component::Negation(list)
+      | Component::Has(list)
+      | Component::Any(_, list) => has_nth_child_of(list),
+      _ => false,
+    })
+  })
+}
+
+/// Determines whether any of the selectors use `:has()`, including within other pseudo classes.
+pub(crate) fn has_has(selectors: &[Selector<Selectors>]) -> bool {
+  selectors.iter().any(|selector| {
+    selector.iter_raw_match_order().any(|component| match component {
+      Component::Has(_) => true,
+      Component::Is(list)
+      | Component::Where(list)
+      | Component::Negation(list)
+      | Component::Any(_, list)
+      | Component::NthChildOf(_, _, list)
+      | Component::NthLastChildOf(_, _, list) => has_has(list),
+      _ => false,
+    })
+  })
+}
+
+/// Removes the arguments of `:is()` and `:where()` that use `:has()`. These pseudo classes ignore invalid
+/// arguments, so this is what browsers that don't support `:has()` do as well. Returns whether the selector
+/// can still match, i.e. it does not use `:has()` elsewhere and some arguments remain.
+pub(crate) fn remove_has(selector: &mut Selector<Selectors>) -> bool {
+  selector.iter_mut_raw_match_order().all(|component| match component {
+    Component::Has(_) => false,
+    Component::Is(list) | Component::Where(list) => {
+      let mut selectors = std::mem::take(list).into_vec();
+      RetainMut::retain_mut(&mut selectors, remove_has);
+      *list = selectors.into_boxed_slice();
+      !list.is_empty()
+    }
+    Component::Negation(list)
+    | Component::Any(_, list)
+    | Component::NthChildOf(_, _, list)
+    | Component::NthLastChildOf(_, _, list) => !has_has(list),
+    _ => true,
+  })
+}
+
 /// Determines whether a selector list contains only unused selectors.
 /// A selector is considered unused if it contains a class or id component that exists in the set of unused symbols.
 pub fn is_unused(
@@ -1579,7 +1745,11 @@ pub fn is_unused(
             return true;
           }
         }
-        Component::Is(is) | Component::Where(is) | Component::Any(_, is) => {
+        Component::Is(is)
+        | Component::Where(is)
+        | Component::Any(_, is)
+        | Component::NthChildOf(_, _, is)
+        | Component::NthLastChildOf(_, _, is) => {
           if is_unused(&mut is.iter(), unused_symbols, parent_is_unused) {
             return true;
           }
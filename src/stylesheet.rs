@@ -3,44 +3,351 @@ use crate::context::{DeclarationContext, PropertyHandlerContext};
 use crate::css_modules::{hash, CssModule, CssModuleExports};
 use crate::declaration::{DeclarationBlock, DeclarationHandler};
 use crate::dependencies::Dependency;
-use crate::error::{Error, ErrorLocation, MinifyErrorKind, ParserError, PrinterError, PrinterErrorKind};
-use crate::parser::TopLevelRuleParser;
+use crate::diff::{diff_rules, Change};
+use crate::error::{
+  Error, ErrorLocation, MinifyErrorKind, MinifyWarningKind, ParserError, PrinterError, PrinterErrorKind,
+};
+use crate::media_query::MediaContext;
+use crate::parser::{with_max_nesting_depth, TopLevelRuleParser};
 use crate::printer::Printer;
-use crate::rules::{CssRule, CssRuleList, MinifyContext};
+use crate::rules::{CssRule, CssRuleList, Location, MinifyContext};
 use crate::targets::Browsers;
 use crate::traits::ToCss;
-use cssparser::{Parser, ParserInput, RuleListParser};
+use crate::values::string::Interner;
+use crate::values::syntax::SyntaxString;
+use cssparser::{BasicParseErrorKind, ParseError, ParseErrorKind, Parser, ParserInput, RuleListParser};
+#[cfg(feature = "std")]
+use parcel_sourcemap::SourceMap;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 
 pub use crate::parser::ParserOptions;
+pub use crate::parser::ParserOptionsBuilder;
 pub use crate::printer::PrinterOptions;
+pub use crate::printer::PrinterOptionsBuilder;
 pub use crate::printer::PseudoClasses;
+pub use crate::printer::PxToRemOptions;
+pub use crate::printer::UrlQuotes;
 
 #[derive(Debug)]
 pub struct StyleSheet<'i> {
   pub rules: CssRuleList<'i>,
   pub sources: Vec<String>,
+  /// The source text of each source, which is embedded in source maps when
+  /// [include_sources_content](PrinterOptions::include_sources_content) is enabled.
+  pub(crate) sources_content: Vec<&'i str>,
   options: ParserOptions,
+  /// Whether CSS module names are hashed based on the source each rule came from,
+  /// rather than the first source. This is the case for style sheets combined with
+  /// [concat](StyleSheet::concat), which were separate modules.
+  hash_per_source: bool,
 }
 
 #[derive(Default)]
 pub struct MinifyOptions {
   pub targets: Option<Browsers>,
   pub unused_symbols: HashSet<String>,
+  /// If provided, animations and transitions declared by style rules are made to respect the user's
+  /// `prefers-reduced-motion` preference. Rules already inside a media query testing this feature are left as is.
+  pub reduced_motion: Option<ReducedMotion>,
+  /// Whether to keep style rules with no declarations, e.g. when their selectors are
+  /// consumed externally. At-rules that contain no rules are still removed.
+  pub preserve_empty_rules: bool,
+  /// Whether to remove keyframes whose declarations are equal to the linear interpolation of the
+  /// surrounding keyframes. This is only safe if the animations using the keyframes have a `linear`
+  /// timing function, so it must be enabled explicitly. Keyframes that set a non-linear
+  /// `animation-timing-function` are never removed.
+  pub remove_interpolated_keyframes: bool,
+  /// Whether to remove `@font-face` rules that are identical to a later rule in the same
+  /// rule list, e.g. when multiple files declaring the same fonts are bundled together.
+  /// The last occurrence is kept, since later font faces take precedence. Rules are compared
+  /// by their descriptors regardless of order, so faces that differ in any descriptor, such
+  /// as `unicode-range` or `font-weight`, are kept.
+  pub dedupe_font_faces: bool,
+  /// Whether to convert the non-standard `zoom` property to an equivalent `transform: scale()`
+  /// when it is not supported by the targets. Rules that already declare a transform are left as is.
+  pub convert_zoom: bool,
+  /// Whether to remove declarations that set a property to its initial value, e.g. `opacity: 1`.
+  /// Only `opacity`, `transform`, `filter`, `perspective` and `clip-path` declarations that are not set by an
+  /// earlier declaration in the same block, or declared by any other rule in the style sheet, are removed. This still changes the cascade if a rule in another style
+  /// sheet sets the property on the same elements, so it must be enabled explicitly.
+  pub remove_initial_values: bool,
+  /// Whether to remove vendor prefixed declarations whose prefix is not needed by any of the targets,
+  /// e.g. `-webkit-border-radius`, even if the unprefixed property is not also declared. By default,
+  /// prefixed declarations are only removed when they are redundant with an unprefixed declaration.
+  /// Properties that are only supported with a prefix, such as `-webkit-line-clamp`, are kept.
+  /// This has no effect without targets.
+  pub remove_unneeded_prefixes: bool,
+  /// Whether to move declarations using features that are supported by some targets but not all, and cannot be
+  /// converted to a supported equivalent, into an `@supports` rule that tests for the declaration. Fallbacks
+  /// are added in an `@supports not` rule where possible, e.g. `zoom: 2` falls back to `transform: scale(2)`
+  /// when the rule does not declare a transform. Declarations using `var()` cannot be tested, so they are
+  /// kept and a warning is emitted instead. This has no effect without targets.
+  pub wrap_unsupported_features: bool,
+  /// Whether to nest style rules into the preceding style rule when all of their selectors start with its
+  /// selector followed by a combinator, e.g. `.card{} .card .title{}` becomes `.card{& .title{}}`. Only
+  /// adjacent rules are nested, and only into rules with a single selector, so the cascade is unchanged.
+  /// This has no effect without targets, or if any of the targets do not support CSS nesting.
+  pub nest_rules: bool,
+  /// The syntax of custom properties registered outside the style sheet, keyed by name including the
+  /// leading dashes. `@property` rules at the top level of the style sheet are registered automatically.
+  /// Values of registered custom properties are minified according to their syntax, e.g. `0px` for a
+  /// `<length>` is minified to `0`. Properties with the universal syntax `*` are left as is, and so are all
+  /// values if any of the targets do not support `@property`.
+  pub registered_properties: HashMap<String, SyntaxString>,
+  /// How selectors using `:has()` are handled when it is not supported by the targets.
+  pub unsupported_has: UnsupportedHasBehavior,
+  /// How logical properties such as `margin-inline-start` are handled when they are not supported by the targets.
+  pub logical_fallback: LogicalFallback,
+  /// If provided, warnings generated during minification are appended to this list.
+  pub warnings: Option<Arc<RwLock<Vec<Error<MinifyWarningKind>>>>>,
+  /// Whether to emit an [OptimizationApplied](MinifyWarningKind::OptimizationApplied) warning each time an
+  /// [Optimization] that may change behavior in edge cases is applied, so that they can be audited.
+  pub warn_optimizations: bool,
+  /// Optimizations that are not applied.
+  pub disabled_optimizations: HashSet<Optimization>,
+  /// Whether to minify independent top-level rules in parallel. The output is the same as when minifying serially.
+  #[cfg(feature = "parallel")]
+  pub parallel: bool,
+}
+
+/// An optimization applied during minification that may change the behavior of a style sheet in edge cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Optimization {
+  /// Adjacent style rules with the same selectors are merged into one rule, and adjacent style rules
+  /// with the same declarations are merged into one rule with both selector lists. Selectors are assumed
+  /// to be supported by browsers other than the targets, since an unsupported selector invalidates
+  /// the entire merged rule.
+  RuleMerge,
+  /// `:root` rules that only declare custom properties are merged into the first such rule, moving
+  /// their declarations before any rules in between.
+  RootVariableMerge,
+  /// Trailing identity functions in `filter` and `backdrop-filter` values, such as `brightness(1)`, are removed.
+  /// This assumes that browsers pad shorter filter lists with identity functions when interpolating them,
+  /// as the specification requires.
+  IdentityFilterRemoval,
+  /// `@font-face` rules that are identical to a later rule are removed when
+  /// [dedupe_font_faces](MinifyOptions::dedupe_font_faces) is enabled. The remaining rule also takes precedence
+  /// over faces matching the same font that were declared between the duplicates.
+  FontFaceDedupe,
+  /// Transitions of a property in a `transition` list that also contains `all` with the same timing are removed.
+  /// This assumes that the property is animatable, since `all` only covers animatable properties.
+  TransitionAllMerge,
+  /// Declarations that set a property to its initial value are removed when
+  /// [remove_initial_values](MinifyOptions::remove_initial_values) is enabled.
+  InitialValueRemoval,
+  /// `word-break: break-word` is replaced with `word-break: normal` and `overflow-wrap: anywhere` for targets
+  /// that do not support it. These are equivalent, but override an `overflow-wrap` declared by another rule.
+  WordBreakRewrite,
+  /// Earlier declarations of a custom property with the same importance in the same rule are removed. Custom
+  /// properties accept any value, so these only apply in browsers that fail to parse the later declaration.
+  CustomPropertyDedupe,
+  /// Normal declarations that are overridden by an `!important` declaration of the same property in the same
+  /// rule are removed. These only apply in browsers that fail to parse the important declaration.
+  DeclarationDrop,
+  /// Colors are converted to a color space supported by all targets, such as `oklab()` to `lab()`, replacing
+  /// the authored value rather than only adding a fallback. Browsers outside the targets that only support
+  /// the authored color space ignore the converted value.
+  ColorFold,
+}
+
+impl std::fmt::Display for Optimization {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    match self {
+      Optimization::RuleMerge => write!(f, "Adjacent style rules were merged"),
+      Optimization::RootVariableMerge => write!(f, "`:root` rules declaring custom properties were merged"),
+      Optimization::IdentityFilterRemoval => write!(f, "Identity filter functions were removed"),
+      Optimization::FontFaceDedupe => write!(f, "Duplicate `@font-face` rules were removed"),
+      Optimization::TransitionAllMerge => write!(f, "Transitions covered by `all` were removed"),
+      Optimization::InitialValueRemoval => write!(f, "A declaration setting the initial value was removed"),
+      Optimization::WordBreakRewrite => write!(f, "`word-break: break-word` was rewritten"),
+      Optimization::CustomPropertyDedupe => write!(f, "Duplicate custom property declarations were removed"),
+      Optimization::DeclarationDrop => write!(f, "Declarations overridden by `!important` were removed"),
+      Optimization::ColorFold => write!(f, "A color was converted to a supported color space"),
+    }
+  }
+}
+
+/// How selectors using `:has()` are handled during minification when `:has()` is not supported
+/// by the targets. It cannot be polyfilled, so these selectors never match in those browsers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnsupportedHasBehavior {
+  /// The selectors are preserved as is.
+  Keep,
+  /// The selectors are preserved, and a warning is emitted.
+  Warn,
+  /// The selectors using `:has()` are removed from the selector list, and the rule is removed
+  /// if no selectors remain. Within `:is()` and `:where()`, only the arguments using it are removed.
+  /// If some of the targets support `:has()`, the selectors are preserved and a warning is emitted instead.
+  Drop,
+}
+
+impl Default for UnsupportedHasBehavior {
+  fn default() -> UnsupportedHasBehavior {
+    UnsupportedHasBehavior::Keep
+  }
+}
+
+/// How animations and transitions are made to respect the user's `prefers-reduced-motion` preference.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReducedMotion {
+  /// A companion `@media (prefers-reduced-motion: reduce)` rule is generated after each style rule
+  /// that declares animations or transitions, resetting them.
+  Reset,
+  /// The `animation` and `transition` declarations of each style rule are moved into a
+  /// `@media (prefers-reduced-motion: no-preference)` rule following it.
+  Guard,
+}
+
+/// How logical properties are handled during minification when they are not supported by the targets.
+/// This currently applies to the `margin`, `padding`, and `inset` properties.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogicalFallback {
+  /// The logical properties are converted to physical properties. Properties that depend on the
+  /// direction are set via `:dir()` rules.
+  Convert,
+  /// The physical properties are emitted, followed by the logical properties inside an `@supports` rule,
+  /// e.g. `@supports (margin-inline-start: 0)`.
+  PhysicalWithSupports,
+  /// The logical properties are preserved, and the physical properties are emitted inside an `@supports not` rule,
+  /// e.g. `@supports not (margin-inline-start: 0)`. Properties that depend on the direction are still set via
+  /// `:dir()` rules, which are equivalent to the logical properties in browsers that support them.
+  LogicalWithSupports,
+}
+
+impl Default for LogicalFallback {
+  fn default() -> LogicalFallback {
+    LogicalFallback::Convert
+  }
+}
+
+/// Options for [transform](crate::transform), which parses, minifies, and prints a style sheet in one step.
+#[derive(Default)]
+pub struct TransformOptions<'a> {
+  /// The filename of the style sheet, used in error locations.
+  pub filename: String,
+  /// Options for parsing the style sheet.
+  pub parser: ParserOptions,
+  /// Options for minifying the style sheet.
+  pub minify: MinifyOptions,
+  /// Options for printing the style sheet. If no targets are set, the minify targets are used.
+  pub printer: PrinterOptions<'a>,
 }
 
 pub struct ToCssResult {
   pub code: String,
   pub exports: Option<CssModuleExports>,
   pub dependencies: Option<Vec<Dependency>>,
+  /// The generated source map, if a [source_map](PrinterOptions::source_map) was provided and
+  /// [encode_source_map](PrinterOptions::encode_source_map) is enabled.
+  pub source_map: Option<SourceMapJson>,
+}
+
+/// A [version 3](https://sourcemaps.info/spec.html) source map, as produced while printing a style sheet.
+/// It serializes to the standard JSON representation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMapJson {
+  /// The version of the source map format. Always 3.
+  pub version: u8,
+  /// The root that source paths are resolved against.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub source_root: Option<String>,
+  /// The VLQ encoded mappings.
+  pub mappings: String,
+  /// The paths of the original sources.
+  pub sources: Vec<String>,
+  /// The contents of each original source, or `None` if contents are not embedded.
+  /// Entries are `None` for sources whose contents were not added to the source map.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sources_content: Option<Vec<Option<String>>>,
+  /// The symbol names referenced by the mappings.
+  pub names: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl SourceMapJson {
+  /// Encodes the mappings and sources of the given source map, optionally including the contents of the sources.
+  pub fn from_source_map(
+    source_map: &mut SourceMap,
+    include_sources_content: bool,
+    source_root: Option<String>,
+  ) -> Result<SourceMapJson, PrinterError> {
+    let mut mappings = Vec::new();
+    source_map.write_vlq(&mut mappings).map_err(|_| PrinterError {
+      kind: PrinterErrorKind::FmtError,
+      loc: None,
+    })?;
+
+    let sources = source_map.get_sources().clone();
+    let contents = source_map.get_sources_content();
+    let sources_content = if include_sources_content {
+      Some(
+        (0..sources.len())
+          .map(|i| contents.get(i).filter(|content| !content.is_empty()).cloned())
+          .collect(),
+      )
+    } else {
+      None
+    };
+
+    Ok(SourceMapJson {
+      version: 3,
+      source_root,
+      // VLQ mappings only consist of base64 characters and separators.
+      mappings: String::from_utf8(mappings).unwrap(),
+      sources,
+      sources_content,
+      names: source_map.get_names().clone(),
+    })
+  }
 }
 
 impl<'i> StyleSheet<'i> {
   pub fn new(sources: Vec<String>, rules: CssRuleList, options: ParserOptions) -> StyleSheet {
     StyleSheet {
       sources,
+      sources_content: Vec::new(),
       rules,
       options,
+      hash_per_source: false,
+    }
+  }
+
+  /// Combines multiple style sheets into one, with the rules of each style sheet in order.
+  ///
+  /// The sources of each style sheet are appended to the combined list of sources, and the
+  /// locations of their rules are updated to match, so that error locations and source maps
+  /// refer to the original files. The parser options of the first style sheet are used.
+  /// When CSS modules are enabled, names are hashed based on the original file they came from,
+  /// so the same class name in two files results in two different names. The export for that name is the
+  /// name from the first file, and composes the names from the others.
+  pub fn concat(sheets: Vec<StyleSheet<'i>>) -> StyleSheet<'i> {
+    let mut options = None;
+    let mut sources = Vec::new();
+    let mut sources_content = Vec::new();
+    let mut rules = Vec::new();
+    for mut sheet in sheets {
+      // Parsed style sheets use the source index from their parser options for all rules.
+      let offset = sources.len() as u32;
+      sheet.rules.update_source_index(sheet.options.source_index, offset);
+      // Keep the contents aligned with the sources, even if some are missing.
+      sheet.sources_content.resize(sheet.sources.len(), "");
+      sources.extend(sheet.sources);
+      sources_content.extend(sheet.sources_content);
+      rules.extend(sheet.rules.0);
+      if options.is_none() {
+        options = Some(sheet.options);
+      }
+    }
+
+    StyleSheet {
+      sources,
+      sources_content,
+      rules: CssRuleList(rules),
+      options: options.unwrap_or_default(),
+      hash_per_source: true,
     }
   }
 
@@ -49,33 +356,141 @@ impl<'i> StyleSheet<'i> {
     code: &'i str,
     options: ParserOptions,
   ) -> Result<StyleSheet<'i>, Error<ParserError<'i>>> {
+    let _interner = Interner::new();
     let mut input = ParserInput::new(&code);
     let mut parser = Parser::new(&mut input);
     let rule_list_parser = RuleListParser::new_for_stylesheet(&mut parser, TopLevelRuleParser::new(&options));
 
     let mut rules = vec![];
-    for rule in rule_list_parser {
-      let rule = match rule {
-        Ok((_, CssRule::Ignored)) => continue,
-        Ok((_, rule)) => rule,
-        Err((e, _)) => return Err(Error::from(e, filename)),
-      };
+    with_max_nesting_depth(options.max_nesting_depth, || {
+      for rule in rule_list_parser {
+        let rule = match rule {
+          Ok((_, CssRule::Ignored)) => continue,
+          Ok((_, rule)) => rule,
+          // When custom at-rules are handled, unknown at-rules without a registered handler are skipped.
+          Err((
+            ParseError {
+              kind: ParseErrorKind::Basic(BasicParseErrorKind::AtRuleInvalid(_)),
+              ..
+            },
+            _,
+          )) if !options.at_rule_handlers.is_empty() || options.preserve_unknown_at_rules => continue,
+          Err((e, _)) => return Err(Error::from(e, filename.clone())),
+        };
 
-      rules.push(rule)
-    }
+        rules.push(rule)
+      }
+      Ok(())
+    })?;
 
     Ok(StyleSheet {
       sources: vec![filename],
+      sources_content: vec![code],
       rules: CssRuleList(rules),
       options,
+      hash_per_source: false,
+    })
+  }
+
+  /// Returns the declarations of each top-level style rule whose selector is a single class,
+  /// e.g. `.btn { ... }`, keyed by class name. Rules with the same class are combined in order.
+  /// The result can be passed to [apply_utilities](StyleSheet::apply_utilities).
+  pub fn utilities(&self) -> HashMap<String, DeclarationBlock<'i>> {
+    let mut utilities: HashMap<String, DeclarationBlock<'i>> = HashMap::new();
+    for rule in &self.rules.0 {
+      if let CssRule::Style(style) = rule {
+        if let Some(name) = style.utility_name() {
+          match utilities.get_mut(name) {
+            Some(utility) => utility.append(style.declarations.clone()),
+            None => {
+              utilities.insert(name.to_owned(), style.declarations.clone());
+            }
+          }
+        }
+      }
+    }
+    utilities
+  }
+
+  /// Replaces `@apply` rules within style rules with the declarations of the referenced utilities.
+  ///
+  /// Applied declarations are inserted in place of the `@apply` rule, so declarations that follow it
+  /// in the same rule override them. If the `@apply` rule is marked `!important`, all applied declarations
+  /// become important. Important declarations from utilities are placed before the rule's own important
+  /// declarations. Referencing a utility that is not defined is an error.
+  pub fn apply_utilities(
+    &mut self,
+    utilities: &HashMap<String, DeclarationBlock<'i>>,
+  ) -> Result<(), Error<MinifyErrorKind>> {
+    self.rules.apply_utilities(utilities).map_err(|e| Error {
+      kind: e.kind,
+      loc: Some(ErrorLocation::from(
+        e.loc,
+        self.sources[e.loc.source_index as usize].clone(),
+      )),
     })
   }
 
+  /// Returns every selector in the style sheet along with the location of its style rule, e.g. to
+  /// find selectors that match no elements in a set of HTML documents. Selector lists are flattened
+  /// into individual selectors, and rules within at-rules or nested within other style rules are
+  /// included. Nested selectors are resolved against their parent, e.g. `.foo { & > .bar {} }`
+  /// results in `.foo` and `.foo > .bar`.
+  pub fn selectors(&self) -> Vec<(String, Location)> {
+    let mut selectors = Vec::new();
+    self.rules.collect_selectors(&mut selectors, None);
+    selectors
+  }
+
+  /// Splits the style sheet into the rules that apply in every environment described by the given context,
+  /// and the rest, e.g. to extract critical CSS for a viewport size. `@media` rules that always match in the
+  /// context are unwrapped in the first style sheet. Other wrapper rules such as `@supports` and `@layer`
+  /// are preserved in each style sheet that contains any of their children. `@namespace` and `@layer`
+  /// statements, and rules that define names or page styles such as `@keyframes`, `@font-face`, `@property`,
+  /// `@counter-style` and `@page`, are included in both.
+  pub fn split_by_media(&self, ctx: MediaContext) -> (StyleSheet<'i>, StyleSheet<'i>) {
+    let (matching, rest) = self.rules.split_by_media(&ctx);
+    let split = |rules| StyleSheet {
+      rules,
+      sources: self.sources.clone(),
+      sources_content: self.sources_content.clone(),
+      options: self.options.clone(),
+      hash_per_source: self.hash_per_source,
+    };
+    (split(matching), split(rest))
+  }
+
+  /// Compares this style sheet with another at the level of rules and declarations, ignoring formatting
+  /// and source locations. Rules that appear in both style sheets but in a different order are reported
+  /// as moves. See [Change](crate::diff::Change) for details.
+  pub fn diff<'a>(&'a self, other: &'a StyleSheet<'i>) -> Vec<Change<'a, 'i>> {
+    let mut changes = Vec::new();
+    diff_rules(&self.rules, &other.rules, &mut changes);
+    changes
+  }
+
   pub fn minify(&mut self, options: MinifyOptions) -> Result<(), Error<MinifyErrorKind>> {
-    let mut context = PropertyHandlerContext::new(options.targets);
+    let mut context = PropertyHandlerContext::from_options(&options);
     let mut handler = DeclarationHandler::new(options.targets);
     let mut important_handler = DeclarationHandler::new(options.targets);
 
+    // @property rules may be defined after the custom properties they register are used,
+    // so collect the syntax of each top-level rule before minifying any declarations.
+    let registered_properties = Arc::make_mut(&mut context.registered_properties);
+    for rule in &self.rules.0 {
+      if let CssRule::Property(rule) = rule {
+        registered_properties.insert(rule.name.0.to_string(), rule.syntax.clone());
+      }
+    }
+
+    // Initial values may only be removed when no other rule declares the property, since that rule
+    // could match the same elements, so collect the properties of every rule first.
+    if options.remove_initial_values {
+      let mut declared_properties = HashMap::new();
+      self.rules.count_property_ids(&mut declared_properties);
+      context.declared_properties = Arc::new(declared_properties);
+    }
+
     // @custom-media rules may be defined after they are referenced, but may only be defined at the top level
     // of a stylesheet. Do a pre-scan here and create a lookup table by name.
     let custom_media = if self.options.custom_media
@@ -99,10 +514,44 @@ impl<'i> StyleSheet<'i> {
       important_handler: &mut important_handler,
       handler_context: &mut context,
       unused_symbols: &options.unused_symbols,
-      custom_media,
+      custom_media: custom_media.as_ref(),
+      reduced_motion: options.reduced_motion,
+      in_reduced_motion_query: false,
+      preserve_empty_rules: options.preserve_empty_rules,
+      remove_interpolated_keyframes: options.remove_interpolated_keyframes,
+      dedupe_font_faces: options.dedupe_font_faces,
+      nest_rules: options.nest_rules,
+      unsupported_has: options.unsupported_has,
+      warn_optimizations: options.warn_optimizations,
+      warnings: Vec::new(),
     };
 
-    self.rules.minify(&mut ctx, false).map_err(|e| Error {
+    self.rules.remove_misplaced_imports(&mut ctx.warnings);
+
+    #[cfg(feature = "parallel")]
+    let res = if options.parallel {
+      self.rules.minify_parallel(&mut ctx)
+    } else {
+      self.rules.minify(&mut ctx, false)
+    };
+    #[cfg(not(feature = "parallel"))]
+    let res = self.rules.minify(&mut ctx, false);
+
+    if let Some(warnings) = &options.warnings {
+      if let Ok(mut warnings) = warnings.write() {
+        for warning in ctx.warnings.drain(..) {
+          warnings.push(Error {
+            kind: warning.kind,
+            loc: Some(ErrorLocation::from(
+              warning.loc,
+              self.sources[warning.loc.source_index as usize].clone(),
+            )),
+          })
+        }
+      }
+    }
+
+    res.map_err(|e| Error {
       kind: e.kind,
       loc: Some(ErrorLocation::from(
         e.loc,
@@ -114,6 +563,14 @@ impl<'i> StyleSheet<'i> {
   }
 
   pub fn to_css(&self, options: PrinterOptions) -> Result<ToCssResult, Error<PrinterErrorKind>> {
+    if let Some(px_to_rem) = &options.px_to_rem {
+      px_to_rem.validate()?;
+    }
+
+    let encode_source_map = options.encode_source_map;
+    let include_sources_content = options.include_sources_content;
+    let source_root = options.source_root.clone();
+
     // Make sure we always have capacity > 0: https://github.com/napi-rs/napi-rs/issues/1124.
     let mut dest = String::with_capacity(1);
     let mut printer = Printer::new(&mut dest, options);
@@ -122,30 +579,91 @@ impl<'i> StyleSheet<'i> {
 
     if self.options.css_modules {
       let h = hash(printer.filename());
+      let source_hashes = if self.hash_per_source {
+        Some(self.sources.iter().map(|source| hash(source)).collect::<Vec<_>>())
+      } else {
+        None
+      };
       let mut exports = HashMap::new();
       printer.css_module = Some(CssModule {
         hash: &h,
+        source_hashes: source_hashes.as_deref(),
         exports: &mut exports,
       });
 
-      self.rules.to_css(&mut printer)?;
+      self.rules.to_css(&mut printer).map_err(|e| printer.map_output_size_error(e))?;
       printer.newline()?;
+      let source_map = if encode_source_map {
+        self.source_map_json(&printer, include_sources_content, source_root.clone())?
+      } else {
+        None
+      };
 
       Ok(ToCssResult {
         dependencies: printer.dependencies,
         code: dest,
         exports: Some(exports),
+        source_map,
       })
     } else {
-      self.rules.to_css(&mut printer)?;
+      self.rules.to_css(&mut printer).map_err(|e| printer.map_output_size_error(e))?;
       printer.newline()?;
+      let source_map = if encode_source_map {
+        self.source_map_json(&printer, include_sources_content, source_root.clone())?
+      } else {
+        None
+      };
       Ok(ToCssResult {
         dependencies: printer.dependencies,
         code: dest,
         exports: None,
+        source_map,
       })
     }
   }
+
+  /// Encodes the source map generated while printing the style sheet, if any. This works on a copy, so that
+  /// the caller's source map is only updated with the mappings.
+  #[cfg(feature = "std")]
+  fn source_map_json<W: std::fmt::Write>(
+    &self,
+    printer: &Printer<W>,
+    include_sources_content: bool,
+    source_root: Option<String>,
+  ) -> Result<Option<SourceMapJson>, PrinterError> {
+    let source_map = match printer.source_map.as_deref() {
+      Some(source_map) => source_map,
+      None => return Ok(None),
+    };
+
+    let mut source_map = source_map.clone();
+
+    // Mappings refer to sources by index, so make sure the source map knows about all of them.
+    for source in self.sources.iter().skip(source_map.get_sources().len()) {
+      source_map.add_source(source);
+    }
+
+    if include_sources_content {
+      for (index, content) in self.sources_content.iter().enumerate() {
+        if !content.is_empty() {
+          let _ = source_map.set_source_content(index, content);
+        }
+      }
+    }
+
+    SourceMapJson::from_source_map(&mut source_map, include_sources_content, source_root).map(Some)
+  }
+
+  /// Source maps require the `std` feature, so none is generated without it.
+  #[cfg(not(feature = "std"))]
+  fn source_map_json<W: std::fmt::Write>(
+    &self,
+    _printer: &Printer<W>,
+    _include_sources_content: bool,
+    _source_root: Option<String>,
+  ) -> Result<Option<SourceMapJson>, PrinterError> {
+    Ok(None)
+  }
 }
 
 pub struct StyleAttribute<'i> {
@@ -154,55 +672,63 @@ pub struct StyleAttribute<'i> {
 
 impl<'i> StyleAttribute<'i> {
   pub fn parse(code: &'i str) -> Result<StyleAttribute, Error<ParserError<'i>>> {
+    let options = ParserOptions::default();
     let mut input = ParserInput::new(&code);
     let mut parser = Parser::new(&mut input);
-    let options = ParserOptions::default();
     Ok(StyleAttribute {
       declarations: DeclarationBlock::parse(&mut parser, &options).map_err(|e| Error::from(e, "".into()))?,
     })
   }
 
   pub fn minify(&mut self, options: MinifyOptions) {
-    let mut context = PropertyHandlerContext::new(options.targets);
+    let mut context = PropertyHandlerContext::from_options(&options);
     let mut handler = DeclarationHandler::new(options.targets);
     let mut important_handler = DeclarationHandler::new(options.targets);
     context.context = DeclarationContext::StyleAttribute;
+    context.declared_properties =
+      Arc::new(self.declarations.property_ids().into_iter().map(|id| (id, 1)).collect());
     self.declarations.minify(&mut handler, &mut important_handler, &mut context);
+
+    // Declarations in a style attribute have no source locations.
+    if let Some(warnings) = &options.warnings {
+      if let Ok(mut warnings) = warnings.write() {
+        for kind in context.warnings.drain(..) {
+          warnings.push(Error { kind, loc: None })
+        }
+      }
+    }
   }
 
   pub fn to_css(&self, options: PrinterOptions) -> Result<ToCssResult, PrinterError> {
+    #[cfg(feature = "std")]
     assert!(
       options.source_map.is_none(),
       "Source maps are not supported for style attributes"
     );
 
+    if let Some(px_to_rem) = &options.px_to_rem {
+      px_to_rem.validate()?;
+    }
+
     // Make sure we always have capacity > 0: https://github.com/napi-rs/napi-rs/issues/1124.
     let mut dest = String::with_capacity(1);
     let mut printer = Printer::new(&mut dest, options);
 
-    let len = self.declarations.declarations.len() + self.declarations.important_declarations.len();
-    let mut i = 0;
-
-    macro_rules! write {
-      ($decls: expr, $important: literal) => {
-        for decl in &$decls {
-          decl.to_css(&mut printer, $important)?;
-          if i != len - 1 {
-            printer.write_char(';')?;
-            printer.whitespace()?;
-          }
-          i += 1;
-        }
-      };
+    let items = self.declarations.items();
+    let len = items.len();
+    for (i, item) in items.iter().enumerate() {
+      item.to_css(&mut printer).map_err(|e| printer.map_output_size_error(e))?;
+      if i != len - 1 {
+        printer.write_char(';')?;
+        printer.whitespace()?;
+      }
     }
 
-    write!(self.declarations.declarations, false);
-    write!(self.declarations.important_declarations, true);
-
     Ok(ToCssResult {
       dependencies: printer.dependencies,
       code: dest,
       exports: None,
+      source_map: None,
     })
   }
 }
@@ -2,6 +2,7 @@
 
 use crate::compat::Feature;
 use crate::error::{ParserError, PrinterError};
+use crate::parser::parse_nested_block;
 use crate::printer::Printer;
 use crate::traits::{Parse, ToCss};
 use cssparser::*;
@@ -125,14 +126,14 @@ impl<
     let f = input.expect_function()?;
     match_ignore_ascii_case! { &f,
       "calc" => {
-        let calc = input.parse_nested_block(Calc::parse_sum)?;
+        let calc = parse_nested_block(input, Calc::parse_sum)?;
         match calc {
           Calc::Value(_) | Calc::Number(_) => Ok(calc),
           _ => Ok(Calc::Function(Box::new(MathFunction::Calc(calc))))
         }
       },
       "min" => {
-        let mut args = input.parse_nested_block(|input| input.parse_comma_separated(Calc::parse_sum))?;
+        let mut args = parse_nested_block(input, |input| input.parse_comma_separated(Calc::parse_sum))?;
         let mut reduced = Calc::reduce_args(&mut args, std::cmp::Ordering::Less);
         if reduced.len() == 1 {
           return Ok(reduced.remove(0))
@@ -140,7 +141,7 @@ impl<
         Ok(Calc::Function(Box::new(MathFunction::Min(reduced))))
       },
       "max" => {
-        let mut args = input.parse_nested_block(|input| input.parse_comma_separated(Calc::parse_sum))?;
+        let mut args = parse_nested_block(input, |input| input.parse_comma_separated(Calc::parse_sum))?;
         let mut reduced = Calc::reduce_args(&mut args, std::cmp::Ordering::Greater);
         if reduced.len() == 1 {
           return Ok(reduced.remove(0))
@@ -148,7 +149,7 @@ impl<
         Ok(Calc::Function(Box::new(MathFunction::Max(reduced))))
       },
       "clamp" => {
-        let (mut min, mut center, mut max) = input.parse_nested_block(|input| {
+        let (mut min, mut center, mut max) = parse_nested_block(input, |input| {
           let min = Some(Calc::parse_sum(input)?);
           input.expect_comma()?;
           let center: Calc<V> = Calc::parse_sum(input)?;
@@ -303,7 +304,7 @@ impl<
     }
 
     if input.try_parse(|input| input.expect_parenthesis_block()).is_ok() {
-      return input.parse_nested_block(Calc::parse_sum);
+      return parse_nested_block(input, Calc::parse_sum);
     }
 
     if let Ok(num) = input.try_parse(|input| input.expect_number()) {
@@ -321,7 +322,7 @@ impl<
     // Reduces the arguments of a min() or max() expression, combining compatible values.
     // e.g. min(1px, 1em, 2px, 3in) => min(1px, 1em)
     let mut reduced: Vec<Calc<V>> = vec![];
-    for arg in args.drain(..) {
+    for arg in Calc::flatten_args(args, cmp) {
       let mut found = None;
       match &arg {
         Calc::Value(val) => {
@@ -341,7 +342,13 @@ impl<
             }
           }
         }
-        _ => {}
+        _ => {
+          // Other expressions can't be compared, but identical ones are redundant.
+          // e.g. max(1em + 1px, 1em + 1px) => 1em + 1px
+          if reduced.contains(&arg) {
+            found = Some(None);
+          }
+        }
       }
       if let Some(r) = found {
         if let Some(r) = r {
@@ -353,6 +360,23 @@ impl<
     }
     reduced
   }
+
+  fn flatten_args(args: &mut Vec<Calc<V>>, cmp: std::cmp::Ordering) -> Vec<Calc<V>> {
+    // Nested min() or max() functions of the same kind are equivalent to their arguments.
+    // e.g. max(1px, max(1em, 2vw)) => max(1px, 1em, 2vw)
+    let mut flattened = Vec::with_capacity(args.len());
+    for arg in args.drain(..) {
+      match arg {
+        Calc::Function(f) => match (*f, cmp) {
+          (MathFunction::Min(nested), std::cmp::Ordering::Less)
+          | (MathFunction::Max(nested), std::cmp::Ordering::Greater) => flattened.extend(nested),
+          (f, _) => flattened.push(Calc::Function(Box::new(f))),
+        },
+        arg => flattened.push(arg),
+      }
+    }
+    flattened
+  }
 }
 
 impl<V: std::ops::Mul<f32, Output = V>> std::ops::Mul<f32> for Calc<V> {
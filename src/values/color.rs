@@ -4,6 +4,7 @@ use super::percentage::Percentage;
 use crate::compat::Feature;
 use crate::error::{ParserError, PrinterError};
 use crate::macros::enum_property;
+use crate::parser::parse_nested_block;
 use crate::printer::Printer;
 use crate::rules::supports::SupportsCondition;
 use crate::targets::Browsers;
@@ -488,7 +489,7 @@ fn parse_color_function<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CssColor,
       Ok(CssColor::Float(Box::new(FloatColor::RGB(SRGB { r, g, b, alpha: a }))))
     },
     "color-mix" => {
-      input.parse_nested_block(parse_color_mix)
+      parse_nested_block(input, parse_color_mix)
     },
     _ => Err(location.new_unexpected_token_error(
       cssparser::Token::Ident(function.clone())
@@ -568,6 +569,8 @@ fn parse_predefined<'i, 't>(
 /// Parses the hsl() and hwb() functions.
 /// Only the modern syntax with no commas is handled here, cssparser handles the legacy syntax.
 /// The results of this function are stored as floating point if there are any `none` components.
+/// In the modern syntax, the saturation and lightness (or whiteness and blackness) may also be
+/// numbers, which are equivalent to percentages, e.g. `hsl(120 50 25%)`.
 #[inline]
 fn parse_hsl_hwb<'i, 't>(
   input: &mut Parser<'i, 't>,
@@ -575,8 +578,8 @@ fn parse_hsl_hwb<'i, 't>(
   // https://drafts.csswg.org/css-color-4/#the-hsl-notation
   let res = input.parse_nested_block(|input| {
     let h = parse_angle_or_number(input)?;
-    let a = parse_percentage(input)?.clamp(0.0, 1.0);
-    let b = parse_percentage(input)?.clamp(0.0, 1.0);
+    let a = parse_percentage_or_number(input)?.clamp(0.0, 1.0);
+    let b = parse_percentage_or_number(input)?.clamp(0.0, 1.0);
     let alpha = parse_alpha(input)?;
 
     Ok((h, a, b, alpha))
@@ -589,36 +592,22 @@ fn parse_hsl_hwb<'i, 't>(
 fn parse_rgb<'i, 't>(input: &mut Parser<'i, 't>) -> Result<(f32, f32, f32, f32), ParseError<'i, ParserError<'i>>> {
   // https://drafts.csswg.org/css-color-4/#rgb-functions
   let res = input.parse_nested_block(|input| {
-    // percentages and numbers cannot be mixed, but we might not know
-    // what kind of components to expect until later if there are `none` values.
-    #[derive(PartialEq)]
-    enum Kind {
-      Unknown,
-      Number,
-      Percentage,
-    }
-
+    // The modern syntax allows numbers and percentages to be mixed, e.g. `rgb(255 50% 0)`.
+    // The legacy comma separated syntax is handled by cssparser, which does not.
     #[inline]
-    fn parse_component<'i, 't>(
-      input: &mut Parser<'i, 't>,
-      kind: Kind,
-    ) -> Result<(f32, Kind), ParseError<'i, ParserError<'i>>> {
+    fn parse_component<'i, 't>(input: &mut Parser<'i, 't>) -> Result<f32, ParseError<'i, ParserError<'i>>> {
       let location = input.current_source_location();
       Ok(match *input.next()? {
-        Token::Number { value, .. } if kind != Kind::Percentage => {
-          (value.round().clamp(0.0, 255.0) / 255.0, Kind::Number)
-        }
-        Token::Percentage { unit_value, .. } if kind != Kind::Number => {
-          (unit_value.clamp(0.0, 1.0), Kind::Percentage)
-        }
-        Token::Ident(ref ident) if ident.eq_ignore_ascii_case("none") => (f32::NAN, Kind::Unknown),
+        Token::Number { value, .. } => value.round().clamp(0.0, 255.0) / 255.0,
+        Token::Percentage { unit_value, .. } => unit_value.clamp(0.0, 1.0),
+        Token::Ident(ref ident) if ident.eq_ignore_ascii_case("none") => f32::NAN,
         ref t => return Err(location.new_unexpected_token_error(t.clone())),
       })
     }
 
-    let (r, is_number) = parse_component(input, Kind::Unknown)?;
-    let (g, is_number) = parse_component(input, is_number)?;
-    let (b, _) = parse_component(input, is_number)?;
+    let r = parse_component(input)?;
+    let g = parse_component(input)?;
+    let b = parse_component(input)?;
     let alpha = parse_alpha(input)?;
     Ok((r, g, b, alpha))
   })?;
@@ -653,6 +642,18 @@ fn parse_percentage<'i, 't>(input: &mut Parser<'i, 't>) -> Result<f32, ParseErro
   })
 }
 
+/// Parses a percentage, or a number that is equivalent to a percentage, e.g. `50` for `50%`.
+#[inline]
+fn parse_percentage_or_number<'i, 't>(input: &mut Parser<'i, 't>) -> Result<f32, ParseError<'i, ParserError<'i>>> {
+  let location = input.current_source_location();
+  Ok(match *input.next()? {
+    Token::Percentage { unit_value, .. } => unit_value,
+    Token::Number { value, .. } => value / 100.0,
+    Token::Ident(ref ident) if ident.eq_ignore_ascii_case("none") => f32::NAN,
+    ref t => return Err(location.new_unexpected_token_error(t.clone())),
+  })
+}
+
 #[inline]
 fn parse_number<'i, 't>(input: &mut Parser<'i, 't>) -> Result<f32, ParseError<'i, ParserError<'i>>> {
   let location = input.current_source_location();
@@ -5,6 +5,7 @@ use super::gradient::*;
 use super::resolution::Resolution;
 use crate::dependencies::{Dependency, UrlDependency};
 use crate::error::{ParserError, PrinterError};
+use crate::parser::parse_nested_block;
 use crate::prefixes::{is_webkit_gradient, Feature};
 use crate::printer::Printer;
 use crate::targets::Browsers;
@@ -352,7 +353,7 @@ impl<'i> Parse<'i> for ImageSet<'i> {
       ))
     };
 
-    let options = input.parse_nested_block(|input| input.parse_comma_separated(ImageSetOption::parse))?;
+    let options = parse_nested_block(input, |input| input.parse_comma_separated(ImageSetOption::parse))?;
     Ok(ImageSet { options, vendor_prefix })
   }
 }
@@ -315,6 +315,14 @@ impl ToCss for LengthValue {
   where
     W: std::fmt::Write,
   {
+    if let LengthValue::Px(px) = self {
+      if dest.convert_px {
+        if let Some(rem) = dest.px_to_rem.as_ref().and_then(|options| options.convert(*px)) {
+          return LengthValue::Rem(rem).to_css(dest);
+        }
+      }
+    }
+
     let (value, unit) = self.to_unit_value();
 
     // The unit can be omitted if the value is zero, except inside calc()
@@ -344,6 +352,7 @@ where
   W: std::fmt::Write,
 {
   use cssparser::ToCss;
+  let value = dest.round(value);
   let int_value = if value.fract() == 0.0 { Some(value as i32) } else { None };
   let token = Token::Dimension {
     has_sign: value < 0.0,
@@ -446,6 +455,11 @@ impl Length {
     Length::Value(LengthValue::Px(0.0))
   }
 
+  /// Returns whether the length is zero, in any unit.
+  pub fn is_zero(&self) -> bool {
+    matches!(self, Length::Value(value) if value.to_unit_value().0 == 0.0)
+  }
+
   /// Constructs a length with the given pixel value.
   pub fn px(px: CSSNumber) -> Length {
     Length::Value(LengthValue::Px(px))
@@ -33,7 +33,7 @@ impl ToCss for CSSNumber {
     W: std::fmt::Write,
   {
     use cssparser::ToCss;
-    let number = *self;
+    let number = dest.round(*self);
     let int_value = if number.fract() == 0.0 {
       Some(number as i32)
     } else {
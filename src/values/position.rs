@@ -137,21 +137,21 @@ impl ToCss for Position {
   {
     match (&self.x, &self.y) {
       (x_pos @ &HorizontalPosition::Side(side, Some(_)), &VerticalPosition::Length(ref y_lp))
-        if side != HorizontalPositionKeyword::Left =>
+        if side != HorizontalPositionKeyword::Left && x_pos.to_length_percentage().is_none() =>
       {
         x_pos.to_css(dest)?;
         dest.write_str(" top ")?;
         y_lp.to_css(dest)
       }
       (x_pos @ &HorizontalPosition::Side(side, Some(_)), y)
-        if side != HorizontalPositionKeyword::Left && y.is_center() =>
+        if side != HorizontalPositionKeyword::Left && y.is_center() && x_pos.to_length_percentage().is_none() =>
       {
         // If there is a side keyword with an offset, "center" must be a keyword not a percentage.
         x_pos.to_css(dest)?;
         dest.write_str(" center")
       }
       (&HorizontalPosition::Length(ref x_lp), y_pos @ &VerticalPosition::Side(side, Some(_)))
-        if side != VerticalPositionKeyword::Top =>
+        if side != VerticalPositionKeyword::Top && y_pos.to_length_percentage().is_none() =>
       {
         dest.write_str("left ")?;
         x_lp.to_css(dest)?;
@@ -179,52 +179,21 @@ impl ToCss for Position {
         y.to_css(dest)
       }
       (x_pos, y_pos) => {
-        let zero = LengthPercentage::zero();
-        let fifty = LengthPercentage::Percentage(Percentage(0.5));
-        let x_len = match &x_pos {
-          HorizontalPosition::Side(HorizontalPositionKeyword::Left, len) => {
-            if let Some(len) = len {
-              if *len == 0.0 {
-                Some(&zero)
-              } else {
-                Some(len)
-              }
-            } else {
-              Some(&zero)
-            }
-          }
-          HorizontalPosition::Length(len) if *len == 0.0 => Some(&zero),
-          HorizontalPosition::Length(len) => Some(len),
-          HorizontalPosition::Center => Some(&fifty),
-          _ => None,
-        };
-
-        let y_len = match &y_pos {
-          VerticalPosition::Side(VerticalPositionKeyword::Top, len) => {
-            if let Some(len) = len {
-              if *len == 0.0 {
-                Some(&zero)
-              } else {
-                Some(len)
-              }
-            } else {
-              Some(&zero)
-            }
-          }
-          VerticalPosition::Length(len) if *len == 0.0 => Some(&zero),
-          VerticalPosition::Length(len) => Some(len),
-          VerticalPosition::Center => Some(&fifty),
-          _ => None,
-        };
-
-        if let (Some(x), Some(y)) = (x_len, y_len) {
+        let x_len = x_pos.to_length_percentage();
+        let y_len = y_pos.to_length_percentage();
+        if let (Some(x), Some(y)) = (&x_len, &y_len) {
           x.to_css(dest)?;
-          dest.write_str(" ")?;
-          y.to_css(dest)
+          // `center` is assumed for the vertical position if omitted.
+          if !matches!(y, LengthPercentage::Percentage(Percentage(p)) if *p == 0.5) {
+            dest.write_str(" ")?;
+            y.to_css(dest)?;
+          }
+          Ok(())
         } else {
-          x_pos.to_css(dest)?;
+          // When a side offset is present, `center` must be written as a keyword rather than a percentage.
+          x_pos.to_css_keyword(dest)?;
           dest.write_str(" ")?;
-          y_pos.to_css(dest)
+          y_pos.to_css_keyword(dest)
         }
       }
     }
@@ -257,6 +226,49 @@ impl<S> PositionComponent<S> {
   fn is_zero(&self) -> bool {
     matches!(self, PositionComponent::Length(len) if *len == 0.0)
   }
+
+  /// Converts the component to an equivalent length or percentage from the top-left corner of the box,
+  /// if possible. Offsets from the bottom or right sides can only be converted when they are percentages.
+  fn to_length_percentage(&self) -> Option<LengthPercentage>
+  where
+    S: Copy + Into<LengthPercentage> + PartialEq,
+  {
+    match self {
+      PositionComponent::Center => Some(LengthPercentage::Percentage(Percentage(0.5))),
+      PositionComponent::Length(len) if *len == 0.0 => Some(LengthPercentage::zero()),
+      PositionComponent::Length(len) => Some(len.clone()),
+      PositionComponent::Side(side, None) => Some((*side).into()),
+      PositionComponent::Side(side, Some(len)) => {
+        let start: LengthPercentage = (*side).into();
+        if start == 0.0 {
+          // A left or top side with an offset.
+          if *len == 0.0 {
+            Some(LengthPercentage::zero())
+          } else {
+            Some(len.clone())
+          }
+        } else if let LengthPercentage::Percentage(Percentage(p)) = len {
+          // A right or bottom side with a percentage offset, e.g. `right 10%` => `90%`.
+          Some(LengthPercentage::Percentage(Percentage(1.0 - p)))
+        } else if *len == 0.0 {
+          Some(start)
+        } else {
+          None
+        }
+      }
+    }
+  }
+
+  fn to_css_keyword<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    S: ToCss,
+    W: std::fmt::Write,
+  {
+    match self {
+      PositionComponent::Center => dest.write_str("center"),
+      _ => self.to_css(dest),
+    }
+  }
 }
 
 impl<'i, S: Parse<'i>> Parse<'i> for PositionComponent<S> {
@@ -3,7 +3,9 @@
 use cssparser::CowRcStr;
 use serde::{Serialize, Serializer};
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::cmp;
+use std::collections::HashSet;
 use std::fmt;
 use std::hash;
 use std::marker::PhantomData;
@@ -53,7 +55,7 @@ impl<'a> From<&CowRcStr<'a>> for CowArcStr<'a> {
       // CowRcStr are very rare in practice though, since most strings are
       // borrowed directly from the input.
       let ptr = local.ptr as *const () as *mut String;
-      CowArcStr::from(unsafe { (*ptr).clone() })
+      CowArcStr::from_arc(intern(unsafe { &*ptr }))
     } else {
       let s = unsafe {
         str::from_utf8_unchecked(slice::from_raw_parts(
@@ -67,6 +69,65 @@ impl<'a> From<&CowRcStr<'a>> for CowArcStr<'a> {
   }
 }
 
+thread_local! {
+  static INTERNER: RefCell<Option<HashSet<InternedString>>> = RefCell::new(None);
+}
+
+/// Owned strings created while parsing, e.g. identifiers containing escapes, are often repeated
+/// many times in a style sheet (think `.md\:flex`). While an interner is alive, identical owned
+/// strings created on the current thread share a single allocation. Since the strings are reference
+/// counted, they remain valid for as long as the values referencing them, even after the interner is dropped.
+pub(crate) struct Interner {
+  owner: bool,
+}
+
+impl Interner {
+  /// Starts interning strings on the current thread, unless an interner is already active.
+  pub fn new() -> Interner {
+    INTERNER.with(|interner| {
+      let mut interner = interner.borrow_mut();
+      if interner.is_some() {
+        return Interner { owner: false };
+      }
+
+      *interner = Some(HashSet::new());
+      Interner { owner: true }
+    })
+  }
+}
+
+impl Drop for Interner {
+  fn drop(&mut self) {
+    if self.owner {
+      INTERNER.with(|interner| *interner.borrow_mut() = None);
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct InternedString(Arc<String>);
+
+impl Borrow<str> for InternedString {
+  fn borrow(&self) -> &str {
+    &self.0
+  }
+}
+
+fn intern(s: &str) -> Arc<String> {
+  INTERNER.with(|interner| match &mut *interner.borrow_mut() {
+    Some(interner) => {
+      if let Some(interned) = interner.get(s) {
+        return interned.0.clone();
+      }
+
+      let arc = Arc::new(s.to_owned());
+      interner.insert(InternedString(arc.clone()));
+      arc
+    }
+    None => Arc::new(s.to_owned()),
+  })
+}
+
 // The below implementation is copied and modified from cssparser.
 
 impl<'a> From<&'a str> for CowArcStr<'a> {
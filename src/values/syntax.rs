@@ -1,5 +1,6 @@
 //! CSS syntax strings
 
+use super::calc::{Calc, MathFunction};
 use super::number::{CSSInteger, CSSNumber};
 use super::string::CowArcStr;
 use crate::error::{ParserError, PrinterError};
@@ -345,7 +346,12 @@ impl ToCss for SyntaxString {
       SyntaxString::Universal => dest.write_char('*')?,
       SyntaxString::Components(components) => {
         let mut first = true;
-        for component in components {
+        for (i, component) in components.iter().enumerate() {
+          // Components are matched in order, so a later duplicate can never match.
+          if components[..i].contains(component) {
+            continue;
+          }
+
           if first {
             first = false;
           } else {
@@ -402,6 +408,57 @@ impl ToCss for SyntaxComponentKind {
   }
 }
 
+impl<'i> ParsedComponent<'i> {
+  /// Returns whether the value is [computationally independent](https://drafts.css-houdini.org/css-properties-values-api/#computationally-independent),
+  /// i.e. it can be computed without the values of other properties. For example, `5px` is computationally
+  /// independent, but `3em` is not because it depends on `font-size`.
+  pub fn is_computationally_independent(&self) -> bool {
+    use values::length::{Length, LengthPercentage};
+
+    fn is_absolute_length(length: &Length) -> bool {
+      match length {
+        Length::Value(value) => value.to_px().is_some(),
+        Length::Calc(calc) => is_independent_calc(calc, &is_absolute_length),
+      }
+    }
+
+    fn is_absolute_length_percentage(length: &LengthPercentage) -> bool {
+      match length {
+        LengthPercentage::Dimension(value) => value.to_px().is_some(),
+        LengthPercentage::Percentage(_) => true,
+        LengthPercentage::Calc(calc) => is_independent_calc(calc, &is_absolute_length_percentage),
+      }
+    }
+
+    match self {
+      ParsedComponent::Length(length) => is_absolute_length(length),
+      ParsedComponent::LengthPercentage(length) => is_absolute_length_percentage(length),
+      ParsedComponent::Repeated(components, _) => components.iter().all(|c| c.is_computationally_independent()),
+      _ => true,
+    }
+  }
+}
+
+fn is_independent_calc<V>(calc: &Calc<V>, is_independent: &dyn Fn(&V) -> bool) -> bool {
+  match calc {
+    Calc::Value(value) => is_independent(value),
+    Calc::Number(_) => true,
+    Calc::Sum(a, b) => is_independent_calc(a, is_independent) && is_independent_calc(b, is_independent),
+    Calc::Product(_, calc) => is_independent_calc(calc, is_independent),
+    Calc::Function(function) => match &**function {
+      MathFunction::Calc(calc) => is_independent_calc(calc, is_independent),
+      MathFunction::Min(values) | MathFunction::Max(values) => {
+        values.iter().all(|calc| is_independent_calc(calc, is_independent))
+      }
+      MathFunction::Clamp(a, b, c) => {
+        is_independent_calc(a, is_independent)
+          && is_independent_calc(b, is_independent)
+          && is_independent_calc(c, is_independent)
+      }
+    },
+  }
+}
+
 impl<'i> ToCss for ParsedComponent<'i> {
   fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
   where
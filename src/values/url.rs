@@ -2,13 +2,15 @@
 
 use crate::dependencies::{Dependency, UrlDependency};
 use crate::error::{ParserError, PrinterError};
-use crate::printer::Printer;
+use crate::printer::{Printer, UrlQuotes};
 use crate::traits::{Parse, ToCss};
 use crate::values::string::CowArcStr;
 use cssparser::*;
 
 /// A CSS [url()](https://www.w3.org/TR/css-values-4/#urls) value and its source location.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Urls are compared by value only, so the same url at different locations is considered equal.
+#[derive(Debug, Clone)]
 pub struct Url<'i> {
   /// The url string.
   pub url: CowArcStr<'i>,
@@ -16,6 +18,12 @@ pub struct Url<'i> {
   pub loc: SourceLocation,
 }
 
+impl<'i> PartialEq for Url<'i> {
+  fn eq(&self, other: &Self) -> bool {
+    self.url == other.url
+  }
+}
+
 impl<'i> Parse<'i> for Url<'i> {
   fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, ParserError<'i>>> {
     let loc = input.current_source_location();
@@ -50,6 +58,24 @@ impl<'i> ToCss for Url<'i> {
     }
 
     use cssparser::ToCss;
+    match dest.url_quotes {
+      UrlQuotes::Always => return write_quoted_url(&self.url, dest),
+      UrlQuotes::Never => {
+        if self
+          .url
+          .chars()
+          .any(|c| matches!(c, '"' | '\'' | '(' | ')' | '\\') || c.is_ascii_whitespace() || c.is_control())
+        {
+          return write_quoted_url(&self.url, dest);
+        }
+
+        dest.write_str("url(")?;
+        dest.write_str(&self.url)?;
+        return dest.write_char(')');
+      }
+      UrlQuotes::Auto => {}
+    }
+
     if dest.minify {
       let mut buf = String::new();
       Token::UnquotedUrl(CowRcStr::from(self.url.as_ref())).to_css(&mut buf)?;
@@ -75,7 +101,39 @@ impl<'i> ToCss for Url<'i> {
   }
 }
 
+fn write_quoted_url<W>(url: &str, dest: &mut Printer<W>) -> Result<(), PrinterError>
+where
+  W: std::fmt::Write,
+{
+  dest.write_str("url(")?;
+  serialize_string(url, dest)?;
+  dest.write_char(')')
+}
+
 impl<'i> Url<'i> {
+  /// Returns whether the URL only consists of a fragment, e.g. `#gradient`, which refers to an
+  /// element in the current document.
+  pub fn is_fragment(&self) -> bool {
+    self.url.starts_with('#')
+  }
+
+  /// Serializes a URL that references an element, e.g. an SVG paint server in `fill: url(#gradient)`.
+  /// Fragment references refer to an element in the current document rather than another file, so they
+  /// are not dependencies.
+  pub(crate) fn to_css_reference<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
+  where
+    W: std::fmt::Write,
+  {
+    if !self.is_fragment() {
+      return self.to_css(dest);
+    }
+
+    let dependencies = dest.dependencies.take();
+    let res = self.to_css(dest);
+    dest.dependencies = dependencies;
+    res
+  }
+
   /// Returns whether the URL is absolute, and not relative.
   pub fn is_absolute(&self) -> bool {
     let url = self.url.as_ref();